@@ -0,0 +1,65 @@
+//! Build the `big-book` opening book
+//!
+//! Every position reachable in the first `PLIES` moves is analyzed, and
+//! the moves scoring within `MARGIN` of the best go in the book, weighted
+//! by how close they come. Symmetric positions are analyzed once.
+//!
+//! ```text
+//! cargo run --release --example build_book -- books/big.otbk
+//! ```
+
+use std::collections::HashSet;
+
+use othello_core::{Board, Player, Score, analyze_at_depth, calculate_flips, encode_book, legal_moves_bitboard};
+
+/// Moves from the start whose positions go in the book
+const PLIES: usize = 7;
+
+/// Depth of the analysis behind each book move
+const DEPTH: u8 = 8;
+
+/// Largest shortfall against the best move for a move to be kept
+const MARGIN: Score = 8;
+
+fn main() {
+    let path = std::env::args().nth(1).unwrap_or_else(|| "books/big.otbk".into());
+
+    // Canonical positions, one ply at a time
+    let mut seen = HashSet::new();
+    let mut layer = vec![Board::new()];
+    let mut positions = Vec::new();
+    for ply in 0..PLIES {
+        let player = if ply % 2 == 0 { Player::Black } else { Player::White };
+        let mut next = Vec::new();
+        for board in layer {
+            positions.push((board, player));
+            for pos in Board::iter_bits(legal_moves_bitboard(&board, player)) {
+                let mut after = board;
+                let flipped = calculate_flips(&board, player, pos);
+                after.place(player, pos);
+                after.flip(player.opponent(), flipped);
+                if seen.insert(after.canonical().zobrist()) {
+                    next.push(after.canonical());
+                }
+            }
+        }
+        layer = next;
+    }
+
+    let mut entries = Vec::new();
+    for (index, (board, player)) in positions.iter().enumerate() {
+        let analysis = analyze_at_depth(board, *player, DEPTH);
+        let Some(best) = analysis.best() else { continue };
+        for m in analysis.iter().filter(|m| best.score - m.score <= MARGIN) {
+            let weight = 255 - ((best.score - m.score) * 240 / MARGIN.max(1)) as u8;
+            entries.push((*board, m.pos, weight));
+        }
+        if index % 500 == 0 {
+            eprintln!("{index}/{} positions", positions.len());
+        }
+    }
+
+    let bytes = encode_book(&entries);
+    std::fs::write(&path, &bytes).expect("write book");
+    eprintln!("{} positions, {} moves, {} bytes to {path}", positions.len(), entries.len(), bytes.len());
+}
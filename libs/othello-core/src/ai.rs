@@ -2,11 +2,25 @@
 //!
 //! Implements minimax with alpha-beta pruning,
 //! iterative deepening, and endgame solving.
+//!
+//! Searches are deterministic: the same position and limits give the same
+//! move on every build, hosted or device. Scores are integers, and moves
+//! are searched in `order_moves` order, which keeps equally ordered moves
+//! in square order (A1 first). A move must score strictly better to replace
+//! the best so far, so ties go to the move searched first. Only a time
+//! limit, which depends on how fast the clock runs, can make two runs
+//! differ; node limits don't. `SEARCH_VECTORS` pins this down.
+
+use core::sync::atomic::{AtomicBool, Ordering};
 
-use crate::{Board, MoveList, Player, Position};
-use crate::eval::{evaluate, Score, SCORE_LOSS, SCORE_WIN};
+use core::cmp::Reverse;
+
+use crate::{Board, Move, MoveList, Player, Position, RuleSet, CORNER_MASK};
+use crate::eval::{evaluate, evaluate_potential_mobility, Score, SCORE_LOSS, SCORE_WIN};
 use crate::moves::{count_moves, generate_moves};
 use crate::opening::OpeningBook;
+#[cfg(feature = "std")]
+use crate::trace::SearchTree;
 
 /// AI difficulty levels
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -15,6 +29,8 @@ pub enum Difficulty {
     Medium,
     Hard,
     Expert,
+    /// Searches against the clock, as deep as it gets in the time
+    Grandmaster,
 }
 
 impl Difficulty {
@@ -25,6 +41,16 @@ impl Difficulty {
             Difficulty::Medium => 4,
             Difficulty::Hard => 6,
             Difficulty::Expert => 8,
+            // Only reached without a clock; with one, time runs out first
+            Difficulty::Grandmaster => 12,
+        }
+    }
+
+    /// Milliseconds a move may take, for levels that search against the clock
+    pub const fn time_ms(&self) -> Option<u64> {
+        match self {
+            Difficulty::Grandmaster => Some(20_000),
+            _ => None,
         }
     }
 
@@ -32,13 +58,13 @@ impl Difficulty {
     pub const fn use_endgame_solver(&self) -> bool {
         match self {
             Difficulty::Easy | Difficulty::Medium => false,
-            Difficulty::Hard | Difficulty::Expert => true,
+            Difficulty::Hard | Difficulty::Expert | Difficulty::Grandmaster => true,
         }
     }
 
     /// Whether to use opening book
     pub const fn use_opening_book(&self) -> bool {
-        matches!(self, Difficulty::Expert)
+        matches!(self, Difficulty::Expert | Difficulty::Grandmaster)
     }
 
     /// Endgame solver threshold (empty squares)
@@ -48,78 +74,718 @@ impl Difficulty {
             Difficulty::Medium => 0,
             Difficulty::Hard => 12,
             Difficulty::Expert => 14,
+            Difficulty::Grandmaster => 16,
+        }
+    }
+
+    /// Empty squares from which the game is solved for its outcome alone,
+    /// before the exact solve takes over; 0 if never
+    pub const fn wld_threshold(&self) -> u32 {
+        match self {
+            Difficulty::Grandmaster => 22,
+            _ => 0,
+        }
+    }
+
+    /// Search limits for this difficulty
+    pub const fn limits(&self) -> SearchLimits {
+        let endgame_empties = if self.use_endgame_solver() { self.endgame_threshold() } else { 0 };
+        SearchLimits {
+            depth: self.depth(),
+            nodes: None,
+            time: self.time_ms(),
+            endgame_empties,
+            wld_empties: self.wld_threshold(),
         }
     }
 }
 
-/// Search state for the AI
-struct SearchState {
-    nodes_searched: u32,
+/// How much work a search may do
+///
+/// Every search entry point takes its limits from here. A search stopped
+/// by the node or time limit plays the best move it finished, deepening
+/// one ply at a time so that it always has one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchLimits {
+    /// Plies searched from the root
+    pub depth: u8,
+    /// Nodes searched before stopping
+    pub nodes: Option<u32>,
+    /// Milliseconds before stopping; only searches given a clock, such as
+    /// `find_best_move_timed`, can keep to it
+    pub time: Option<u64>,
+    /// Solve exactly once no more than this many squares are empty
+    pub endgame_empties: u32,
+    /// Solve for a win, draw or loss alone once no more than this many
+    /// squares are empty, if that is more than `endgame_empties`
+    pub wld_empties: u32,
 }
 
-impl SearchState {
-    fn new() -> Self {
-        Self { nodes_searched: 0 }
+impl SearchLimits {
+    /// A search of `depth` plies, solved exactly once no more squares are
+    /// empty than that, which costs no more than the fixed-depth search
+    pub const fn depth(depth: u8) -> Self {
+        let depth = if depth == 0 { 1 } else { depth };
+        Self { depth, nodes: None, time: None, endgame_empties: depth as u32, wld_empties: 0 }
+    }
+
+    /// Stop after this many nodes
+    pub const fn with_nodes(self, nodes: u32) -> Self {
+        Self { nodes: Some(nodes), ..self }
+    }
+
+    /// Stop after this many milliseconds
+    pub const fn with_time(self, ms: u64) -> Self {
+        Self { time: Some(ms), ..self }
+    }
+
+    /// Solve exactly from this many empty squares instead
+    pub const fn with_endgame_empties(self, empties: u32) -> Self {
+        Self { endgame_empties: empties, ..self }
+    }
+
+    /// Solve for the outcome alone from this many empty squares instead
+    pub const fn with_wld_empties(self, empties: u32) -> Self {
+        Self { wld_empties: empties, ..self }
+    }
+
+    /// Whether a position is left to the endgame solver
+    const fn solves(&self, board: &Board) -> bool {
+        board.empty_count() <= self.endgame_empties
+    }
+
+    /// Whether a position is solved for its outcome, short of the exact solve
+    const fn solves_wld(&self, board: &Board) -> bool {
+        !self.solves(board) && board.empty_count() <= self.wld_empties
     }
 }
 
-/// Apply a move to a board, returning the new board
-fn apply_move(board: &Board, player: Player, pos: Position, flipped: u64) -> Board {
-    let mut new_board = *board;
-    new_board.place(player, pos);
-    new_board.flip(player.opponent(), flipped);
-    new_board
+impl From<Difficulty> for SearchLimits {
+    fn from(difficulty: Difficulty) -> Self {
+        difficulty.limits()
+    }
+}
+
+/// How a search uses the opening book
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BookPolicy {
+    /// Never consult the book
+    Off,
+    /// Play book moves without searching
+    Trust,
+    /// Check book moves with a shallow search, and search fully when one
+    /// scores clearly worse than the best move
+    Verify,
 }
 
-/// Order moves for better alpha-beta pruning
-fn order_moves(board: &Board, player: Player, moves: &MoveList) -> [usize; 32] {
-    let mut indices: [usize; 32] = core::array::from_fn(|i| i);
-    let mut scores: [Score; 32] = [0; 32];
+/// Depth of the search that checks a book move
+pub const BOOK_VERIFY_DEPTH: u8 = 4;
 
-    for i in 0..moves.len() {
-        let m = moves.get(i).unwrap();
-        let new_board = apply_move(board, player, m.pos, m.flipped);
+/// Shortfall against the best move at which a book move is not played
+pub const BOOK_VERIFY_MARGIN: Score = 60;
 
-        // Score based on position quality
-        let mut score = 0i32;
+/// Options for a move search, starting from a difficulty's defaults
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchConfig {
+    pub limits: SearchLimits,
+    pub book: BookPolicy,
+    /// Book consulted under the book policy
+    pub opening_book: OpeningBook<'static>,
+    /// Rules the search plays to
+    pub rules: RuleSet,
+    /// Add potential mobility to the evaluation
+    pub potential_mobility: bool,
+}
 
-        // Corners are best
-        if m.pos == 0 || m.pos == 7 || m.pos == 56 || m.pos == 63 {
-            score += 1000;
+impl SearchConfig {
+    /// Defaults for a difficulty: Expert and Grandmaster trust the book, the
+    /// rest skip it
+    pub const fn new(difficulty: Difficulty) -> Self {
+        let book = if difficulty.use_opening_book() { BookPolicy::Trust } else { BookPolicy::Off };
+        Self {
+            limits: difficulty.limits(),
+            book,
+            opening_book: OpeningBook::BUILTIN,
+            rules: RuleSet::STANDARD,
+            potential_mobility: false,
         }
-        // X-squares are worst
-        else if m.pos == 9 || m.pos == 14 || m.pos == 49 || m.pos == 54 {
-            score -= 500;
+    }
+
+    /// Search within these limits instead
+    pub const fn with_limits(self, limits: SearchLimits) -> Self {
+        Self { limits, ..self }
+    }
+
+    /// Use the opening book this way instead
+    pub const fn with_book(self, book: BookPolicy) -> Self {
+        Self { book, ..self }
+    }
+
+    /// Consult this book instead of the built-in one
+    pub const fn with_opening_book(self, opening_book: OpeningBook<'static>) -> Self {
+        Self { opening_book, ..self }
+    }
+
+    /// Play to these rules instead of standard Othello
+    pub const fn with_rules(self, rules: RuleSet) -> Self {
+        Self { rules, ..self }
+    }
+
+    /// Turn the potential mobility evaluation term on or off
+    pub const fn with_potential_mobility(self, potential_mobility: bool) -> Self {
+        Self { potential_mobility, ..self }
+    }
+
+    /// The book policy in effect; the book only holds standard play
+    const fn book_policy(&self) -> BookPolicy {
+        if self.rules.misere { BookPolicy::Off } else { self.book }
+    }
+}
+
+impl From<Difficulty> for SearchConfig {
+    fn from(difficulty: Difficulty) -> Self {
+        Self::new(difficulty)
+    }
+}
+
+/// Limits alone search without the opening book
+impl From<SearchLimits> for SearchConfig {
+    fn from(limits: SearchLimits) -> Self {
+        Self { limits, book: BookPolicy::Off, ..Self::new(Difficulty::Easy) }
+    }
+}
+
+/// Nodes between clock readings in a timed search; reading the clock can
+/// cost a message to another process, so it isn't done at every node
+const CLOCK_CHECK_NODES: u32 = 1024;
+
+/// A wall-clock limit for a search, read through a clock the caller supplies
+///
+/// The core has no clock of its own. The caller passes `now`, returning
+/// milliseconds since any fixed point, so the same limit behaves the same
+/// on hosted and device builds.
+#[derive(Clone, Copy)]
+pub struct Deadline<'a> {
+    now: &'a dyn Fn() -> u64,
+    at: u64,
+}
+
+impl<'a> Deadline<'a> {
+    /// A deadline `limit_ms` after the clock's current reading
+    pub fn after(now: &'a dyn Fn() -> u64, limit_ms: u64) -> Self {
+        Self { now, at: now().saturating_add(limit_ms) }
+    }
+
+    /// Whether the clock has reached the deadline
+    pub fn passed(&self) -> bool {
+        (self.now)() >= self.at
+    }
+}
+
+/// Why the search of a node stopped, as recorded by a traced search
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeEnd {
+    /// Still being searched; only seen if the search was cut short
+    Open,
+    /// Depth limit reached; the score is the static evaluation
+    Leaf,
+    /// Neither side can move
+    GameOver,
+    /// Every move was searched
+    Searched { moves: u8 },
+    /// A move scored too well for the opponent to allow this line
+    BetaCutoff { searched: u8, moves: u8 },
+    /// A move scored too badly for the side to move to choose this line
+    AlphaCutoff { searched: u8, moves: u8 },
+    /// The transposition table already held a score good enough to stop
+    Table,
+}
+
+/// Whether a stored score is exact or only bounds the true one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Bound {
+    /// Empty slot
+    None,
+    Exact,
+    /// The true score is at least this
+    Lower,
+    /// The true score is at most this
+    Upper,
+}
+
+/// One slot of a transposition table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct TableEntry {
+    key: u64,
+    score: Score,
+    /// Plies searched below the node; 0 for an endgame solve
+    depth: u8,
+    bound: Bound,
+    /// Best or refuting move found, `NO_MOVE` if none
+    best: Position,
+}
+
+/// Marks a table entry with no move to try first
+const NO_MOVE: Position = 64;
+
+const EMPTY_ENTRY: TableEntry = TableEntry { key: 0, score: 0, depth: 0, bound: Bound::None, best: NO_MOVE };
+
+impl TableEntry {
+    /// The stored score, if it settles a node searched with this window
+    const fn cutoff(&self, alpha: Score, beta: Score) -> Option<Score> {
+        match self.bound {
+            Bound::Exact => Some(self.score),
+            Bound::Lower if self.score >= beta => Some(self.score),
+            Bound::Upper if self.score <= alpha => Some(self.score),
+            _ => None,
+        }
+    }
+}
+
+/// Table key for a node: the board, the side to move, the side scores are
+/// taken for, and whether the score is an exact endgame solve
+fn table_key(board: &Board, current: Player, player: Player, solve: bool) -> u64 {
+    let mut key = board.zobrist();
+    if current == Player::White {
+        key ^= 0x9e37_79b9_7f4a_7c15;
+    }
+    if player == Player::White {
+        key ^= 0xbf58_476d_1ce4_e5b9;
+    }
+    if solve {
+        key ^= 0x94d0_49bb_1331_11eb;
+    }
+    key
+}
+
+/// Counts kept by a [`Searcher`] over all its searches
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchStats {
+    /// Searches run
+    pub searches: u32,
+    /// Nodes visited
+    pub nodes: u64,
+    /// Nodes settled by a transposition table entry
+    pub table_hits: u64,
+    /// Nodes cut off by alpha-beta
+    pub cutoffs: u64,
+}
+
+/// How a search chose its move
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SearchMethod {
+    /// No search needed: one legal move, or none
+    #[default]
+    Forced,
+    /// Played from the opening book
+    Book,
+    /// Searched to a depth
+    Search,
+    /// Solved for a win, draw or loss
+    Outcome,
+    /// Solved for the exact score
+    Exact,
+}
+
+/// What a [`Searcher`]'s last search did
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchReport {
+    pub method: SearchMethod,
+    /// Deepest pass that finished, in plies; for a solve, the empty squares
+    pub depth: u8,
+    /// Whether a limit or cancel cut the search short
+    pub stopped: bool,
+    /// Nodes visited
+    pub nodes: u32,
+    /// Nodes settled by a transposition table entry
+    pub table_hits: u32,
+}
+
+impl SearchReport {
+    /// Share of nodes settled by the table, in percent
+    pub const fn table_hit_percent(&self) -> u32 {
+        if self.nodes == 0 { 0 } else { (self.table_hits as u64 * 100 / self.nodes as u64) as u32 }
+    }
+}
+
+/// Move ordering memory and statistics, kept between searches
+#[derive(Debug, Clone)]
+struct SearchMemory {
+    /// Two moves that last caused a cutoff, by number of empty squares,
+    /// which names the same ply from one move of the game to the next
+    killers: [[Position; 2]; 65],
+    /// Cutoffs caused by each side's moves to each square, weighted by
+    /// the depth searched below them
+    history: [[u32; 64]; 2],
+    stats: SearchStats,
+}
+
+impl SearchMemory {
+    const fn new() -> Self {
+        Self {
+            killers: [[NO_MOVE; 2]; 65],
+            history: [[0; 64]; 2],
+            stats: SearchStats { searches: 0, nodes: 0, table_hits: 0, cutoffs: 0 },
         }
-        // C-squares are bad
-        else if [1, 6, 8, 15, 48, 55, 57, 62].contains(&m.pos) {
-            score -= 200;
+    }
+
+    /// Note a move that caused a cutoff
+    fn record_cutoff(&mut self, board: &Board, current: Player, pos: Position, weight: u32) {
+        let killers = &mut self.killers[board.empty_count() as usize];
+        if killers[0] != pos {
+            killers[1] = killers[0];
+            killers[0] = pos;
         }
-        // Edge positions are good
-        else if m.pos < 8 || m.pos >= 56 || m.pos % 8 == 0 || m.pos % 8 == 7 {
-            score += 100;
+        let count = &mut self.history[current as usize][pos as usize];
+        *count = count.saturating_add(weight);
+        self.stats.cutoffs += 1;
+    }
+
+    /// Let older history count for less than what the next search finds
+    fn age(&mut self) {
+        for count in self.history.iter_mut().flatten() {
+            *count /= 2;
         }
+    }
+}
 
-        // More flips is generally good
-        score += m.flip_count() as i32 * 5;
+/// Entries in a game's transposition table by default, 16 bytes each
+pub const SEARCHER_TABLE_ENTRIES: usize = 4096;
+
+/// Search state that lasts from one move of a game to the next
+///
+/// A `Searcher` owns a transposition table of `N` entries, killer moves,
+/// history counts and statistics, and reusing one through a game lets each
+/// search start from what the last one learned. Every root move is still
+/// searched with a full window, so a reused searcher picks the same moves
+/// as a fresh one, only sooner; a node or time limit is the one way to
+/// tell them apart. Free functions such as `find_best_move` run a fresh
+/// searcher with no table.
+#[derive(Debug, Clone)]
+pub struct Searcher<const N: usize = SEARCHER_TABLE_ENTRIES> {
+    table: [TableEntry; N],
+    memory: SearchMemory,
+    /// Rules and evaluation the table's scores were found with
+    scored_for: Option<(RuleSet, bool)>,
+    last: SearchReport,
+}
+
+impl<const N: usize> Default for Searcher<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-        // Opponent mobility after our move
-        let opp_mobility = count_moves(&new_board, player.opponent()) as i32;
-        score -= opp_mobility * 3;
+impl<const N: usize> Searcher<N> {
+    /// A searcher that has learned nothing yet
+    pub const fn new() -> Self {
+        Self {
+            table: [EMPTY_ENTRY; N],
+            memory: SearchMemory::new(),
+            scored_for: None,
+            last: SearchReport { method: SearchMethod::Forced, depth: 0, stopped: false, nodes: 0, table_hits: 0 },
+        }
+    }
 
-        scores[i] = score;
+    /// Forget everything learned, as for a new game
+    pub fn clear(&mut self) {
+        self.table = [EMPTY_ENTRY; N];
+        self.memory = SearchMemory::new();
+        self.scored_for = None;
+        self.last = SearchReport::default();
     }
 
-    // Sort indices by score (descending)
-    for i in 0..moves.len() {
-        for j in i + 1..moves.len() {
-            if scores[indices[j]] > scores[indices[i]] {
-                indices.swap(i, j);
-            }
+    /// Counts over every search since the searcher was made or cleared
+    pub const fn stats(&self) -> &SearchStats {
+        &self.memory.stats
+    }
+
+    /// What the last search did
+    pub const fn last_search(&self) -> &SearchReport {
+        &self.last
+    }
+
+    /// Search for the best move as `find_best_move` does
+    pub fn best_move(&mut self, board: &Board, player: Player, config: impl Into<SearchConfig>) -> Option<Position> {
+        self.best_move_with(board, player, config.into(), &AtomicBool::new(false))
+    }
+
+    /// Search for the best move as `find_best_move_with` does
+    pub fn best_move_with(&mut self, board: &Board, player: Player, config: SearchConfig, cancel: &AtomicBool) -> Option<Position> {
+        self.search(board, player, config, SearchState::with_cancel(cancel))
+    }
+
+    /// Search for the best move as `find_best_move_timed` does
+    pub fn best_move_timed(&mut self, board: &Board, player: Player, config: SearchConfig, now: &dyn Fn() -> u64) -> Option<Position> {
+        let deadline = config.limits.time.map(|ms| Deadline::after(now, ms));
+        self.search(board, player, config, SearchState::with_deadline(deadline))
+    }
+
+    /// Score every legal move as `analyze_at_depth` does, stopping early
+    /// once `cancel` is set, along with the line expected after the best
+    ///
+    /// The line is read from the moves the search stored in the table, so
+    /// it costs no search of its own.
+    pub fn analyze_line(&mut self, board: &Board, player: Player, depth: u8, cancel: &AtomicBool) -> (Analysis, Variation) {
+        let limits = SearchLimits::depth(depth);
+        // The analysis scores standard play with the plain evaluation
+        let scored_for = Some((RuleSet::STANDARD, false));
+        if self.scored_for != scored_for {
+            self.table = [EMPTY_ENTRY; N];
+            self.scored_for = scored_for;
+        }
+        self.memory.age();
+
+        let memory = core::mem::replace(&mut self.memory, SearchMemory::new());
+        let mut state = SearchState { cancel: Some(cancel), table: &mut self.table, memory, ..SearchState::with_limits(&limits) };
+        let analysis = score_moves_with(board, player, &limits, &mut state);
+        let line = match analysis.best() {
+            Some(best) => stored_line(&state, board, player, best.pos, limits.depth, limits.solves(board)),
+            None => Variation { moves: [0; MAX_PV], len: 0 },
+        };
+        state.memory.stats.searches += 1;
+        state.memory.stats.nodes += state.nodes_searched as u64;
+        self.memory = state.memory;
+        (analysis, line)
+    }
+
+    /// Lend the table and memory to a search, and take them back after
+    fn search(&mut self, board: &Board, player: Player, config: SearchConfig, state: SearchState) -> Option<Position> {
+        // Scores found under other rules or another evaluation don't carry over
+        let scored_for = Some((config.rules, config.potential_mobility));
+        if self.scored_for != scored_for {
+            self.table = [EMPTY_ENTRY; N];
+            self.scored_for = scored_for;
         }
+        self.memory.age();
+
+        let memory = core::mem::replace(&mut self.memory, SearchMemory::new());
+        let hits_before = memory.stats.table_hits;
+        let mut state = SearchState { table: &mut self.table, memory, ..state };
+        let best = search_best_move(board, player, config, &mut state);
+        state.memory.stats.searches += 1;
+        state.memory.stats.nodes += state.nodes_searched as u64;
+        self.last = SearchReport {
+            method: state.method,
+            depth: state.depth_reached,
+            stopped: state.stopped,
+            nodes: state.nodes_searched,
+            table_hits: (state.memory.stats.table_hits - hits_before) as u32,
+        };
+        self.memory = state.memory;
+        best
     }
+}
 
-    indices
+/// Search state for the AI
+struct SearchState<'a> {
+    nodes_searched: u32,
+    /// Set from outside to stop the search early
+    cancel: Option<&'a AtomicBool>,
+    /// Wall-clock limit, checked every `CLOCK_CHECK_NODES` nodes
+    deadline: Option<Deadline<'a>>,
+    /// Nodes to search before stopping
+    node_limit: Option<u32>,
+    /// Latched once the search has been told to stop
+    stopped: bool,
+    /// Rules the game is scored by
+    rules: RuleSet,
+    /// Potential mobility is part of the evaluation
+    potential_mobility: bool,
+    /// Transposition table, empty when the search keeps none
+    table: &'a mut [TableEntry],
+    /// Move ordering memory, handed on by a `Searcher`
+    memory: SearchMemory,
+    /// How the root move was chosen
+    method: SearchMethod,
+    /// Deepest pass finished, or the empty squares of a solve
+    depth_reached: u8,
+    /// Recorder for a traced search
+    #[cfg(feature = "std")]
+    trace: Option<&'a mut SearchTree>,
+}
+
+impl<'a> SearchState<'a> {
+    fn new() -> Self {
+        Self {
+            nodes_searched: 0,
+            cancel: None,
+            deadline: None,
+            node_limit: None,
+            stopped: false,
+            rules: RuleSet::STANDARD,
+            potential_mobility: false,
+            table: &mut [],
+            memory: SearchMemory::new(),
+            method: SearchMethod::Forced,
+            depth_reached: 0,
+            #[cfg(feature = "std")]
+            trace: None,
+        }
+    }
+
+    fn with_cancel(cancel: &'a AtomicBool) -> Self {
+        Self { cancel: Some(cancel), ..Self::new() }
+    }
+
+    fn with_deadline(deadline: Option<Deadline<'a>>) -> Self {
+        Self { deadline, ..Self::new() }
+    }
+
+    /// Stop at the limits' node count, if they set one
+    fn with_limits(limits: &SearchLimits) -> Self {
+        Self { node_limit: limits.nodes, ..Self::new() }
+    }
+
+    /// Whether a limit other than the full depth can stop the search
+    const fn limited(&self) -> bool {
+        self.deadline.is_some() || self.node_limit.is_some()
+    }
+
+    /// Static evaluation for `player`, turned around for misère play
+    fn evaluate(&self, board: &Board, player: Player) -> Score {
+        let mut score = evaluate(board, player);
+        // Only positions still in play, not the game-over scores
+        if self.potential_mobility && score.abs() < SCORE_WIN / 2 {
+            score += evaluate_potential_mobility(board, player);
+        }
+        if self.rules.misere { -score } else { score }
+    }
+
+    /// Table slot for a key, the same on every build whatever its word size
+    fn slot(&self, key: u64) -> Option<usize> {
+        let len = self.table.len() as u64;
+        if len == 0 { None } else { Some((key % len) as usize) }
+    }
+
+    /// Stored entry for a key
+    fn probe(&self, key: u64) -> Option<TableEntry> {
+        let entry = self.table[self.slot(key)?];
+        (entry.bound != Bound::None && entry.key == key).then_some(entry)
+    }
+
+    /// Store a finished node's score, classed against the window it was
+    /// searched with
+    fn store(&mut self, key: u64, depth: u8, score: Score, window: (Score, Score), best: Position) {
+        let Some(slot) = self.slot(key) else { return };
+        // A deeper search of the same node is worth more
+        let old = self.table[slot];
+        if old.key == key && old.bound != Bound::None && old.depth > depth {
+            return;
+        }
+        let bound = if score <= window.0 {
+            Bound::Upper
+        } else if score >= window.1 {
+            Bound::Lower
+        } else {
+            Bound::Exact
+        };
+        self.table[slot] = TableEntry { key, score, depth, bound, best };
+    }
+
+    /// Order moves below the root: the stored best move, then the killers,
+    /// then the static order with history breaking its ties
+    fn order_inner(&self, board: &Board, current: Player, moves: &mut MoveList, first: Position) {
+        let history = &self.memory.history[current as usize];
+        moves.sort_by_key(|m| Reverse((order_score(board, current, m), history[m.pos as usize])));
+        let killers = self.memory.killers[board.empty_count() as usize];
+        moves.sort_by_key(|m| (m.pos != first, !killers.contains(&m.pos)));
+    }
+
+    /// Whether the search has been told to stop or has run out of time
+    fn cancelled(&mut self) -> bool {
+        if !self.stopped {
+            let late = self.nodes_searched % CLOCK_CHECK_NODES == 0
+                && self.deadline.as_ref().is_some_and(Deadline::passed);
+            let spent = self.node_limit.is_some_and(|limit| self.nodes_searched >= limit);
+            self.stopped = late || spent || self.cancel.is_some_and(|c| c.load(Ordering::Relaxed));
+        }
+        self.stopped
+    }
+}
+
+/// Hooks for a traced search, recording into the attached tree
+#[cfg(feature = "std")]
+impl SearchState<'_> {
+    fn trace_enter(&mut self, pos: Position, player: Player, alpha: Score, beta: Score) {
+        if let Some(trace) = self.trace.as_deref_mut() {
+            trace.enter(pos, player, alpha, beta);
+        }
+    }
+
+    fn trace_leave(&mut self, score: Score) {
+        if let Some(trace) = self.trace.as_deref_mut() {
+            trace.leave(score);
+        }
+    }
+
+    fn trace_pass(&mut self) {
+        if let Some(trace) = self.trace.as_deref_mut() {
+            trace.pass();
+        }
+    }
+
+    fn trace_end(&mut self, end: NodeEnd) {
+        if let Some(trace) = self.trace.as_deref_mut() {
+            trace.end(end);
+        }
+    }
+}
+
+/// Without `std` there is nothing to record into
+#[cfg(not(feature = "std"))]
+impl SearchState<'_> {
+    fn trace_enter(&mut self, _pos: Position, _player: Player, _alpha: Score, _beta: Score) {}
+    fn trace_leave(&mut self, _score: Score) {}
+    fn trace_pass(&mut self) {}
+    fn trace_end(&mut self, _end: NodeEnd) {}
+}
+
+/// Apply a move to a board, returning the new board
+fn apply_move(board: &Board, player: Player, pos: Position, flipped: u64) -> Board {
+    let mut new_board = *board;
+    new_board.place(player, pos);
+    new_board.flip(player.opponent(), flipped);
+    new_board
+}
+
+/// Quick estimate of a move's quality, for search order
+fn order_score(board: &Board, player: Player, m: &Move) -> Score {
+    let new_board = apply_move(board, player, m.pos, m.flipped);
+
+    // Score based on position quality
+    let mut score = 0i32;
+
+    // Corners are best
+    if m.pos == 0 || m.pos == 7 || m.pos == 56 || m.pos == 63 {
+        score += 1000;
+    }
+    // X-squares are worst
+    else if m.pos == 9 || m.pos == 14 || m.pos == 49 || m.pos == 54 {
+        score -= 500;
+    }
+    // C-squares are bad
+    else if [1, 6, 8, 15, 48, 55, 57, 62].contains(&m.pos) {
+        score -= 200;
+    }
+    // Edge positions are good
+    else if m.pos < 8 || m.pos >= 56 || m.pos % 8 == 0 || m.pos % 8 == 7 {
+        score += 100;
+    }
+
+    // More flips is generally good
+    score += m.flip_count() as i32 * 5;
+
+    // Opponent mobility after our move
+    let opp_mobility = count_moves(&new_board, player.opponent()) as i32;
+    score -= opp_mobility * 3;
+
+    score
+}
+
+/// Order moves for better alpha-beta pruning, most promising first
+fn order_moves(board: &Board, player: Player, moves: &mut MoveList) {
+    moves.sort_by_key(|m| Reverse(order_score(board, player, m)));
 }
 
 /// Minimax with alpha-beta pruning
@@ -134,13 +800,26 @@ fn alphabeta(
 ) -> Score {
     state.nodes_searched += 1;
 
+    // A cancelled search unwinds; the caller discards the score
+    if state.cancelled() {
+        return 0;
+    }
+
     // Terminal depth or game over
     if depth == 0 {
-        return evaluate(board, player);
+        state.trace_end(NodeEnd::Leaf);
+        return state.evaluate(board, player);
     }
 
     let current = if maximizing { player } else { player.opponent() };
-    let moves = generate_moves(board, current);
+    let key = table_key(board, current, player, false);
+    let stored = state.probe(key);
+    if let Some(score) = stored.filter(|e| e.depth == depth).and_then(|e| e.cutoff(alpha, beta)) {
+        state.memory.stats.table_hits += 1;
+        state.trace_end(NodeEnd::Table);
+        return score;
+    }
+    let mut moves = generate_moves(board, current);
 
     if moves.is_empty() {
         // No moves - check if opponent can move
@@ -149,47 +828,89 @@ fn alphabeta(
 
         if opp_moves.is_empty() {
             // Game over
-            return evaluate(board, player);
+            state.trace_end(NodeEnd::GameOver);
+            return state.evaluate(board, player);
         }
 
         // Pass - search opponent's moves at same depth
+        state.trace_pass();
         return alphabeta(board, player, depth, alpha, beta, !maximizing, state);
     }
 
-    let ordered = order_moves(board, current, &moves);
+    state.order_inner(board, current, &mut moves, stored.map_or(NO_MOVE, |e| e.best));
+    let window = (alpha, beta);
+    let mut best_pos = NO_MOVE;
+    let weight = depth as u32 * depth as u32;
 
-    if maximizing {
+    let score = if maximizing {
         let mut max_eval = SCORE_LOSS;
 
-        for &idx in &ordered[..moves.len()] {
-            let m = moves.get(idx).unwrap();
+        let mut end = NodeEnd::Searched { moves: moves.len() as u8 };
+        for (searched, m) in moves.iter().enumerate() {
             let new_board = apply_move(board, current, m.pos, m.flipped);
+            state.trace_enter(m.pos, current, alpha, beta);
             let eval = alphabeta(&new_board, player, depth - 1, alpha, beta, false, state);
-            max_eval = max_eval.max(eval);
+            state.trace_leave(eval);
+            if eval > max_eval {
+                max_eval = eval;
+                best_pos = m.pos;
+            }
             alpha = alpha.max(eval);
 
             if beta <= alpha {
+                state.memory.record_cutoff(board, current, m.pos, weight);
+                end = NodeEnd::BetaCutoff { searched: searched as u8 + 1, moves: moves.len() as u8 };
                 break; // Beta cutoff
             }
         }
 
+        state.trace_end(end);
         max_eval
     } else {
         let mut min_eval = SCORE_WIN;
 
-        for &idx in &ordered[..moves.len()] {
-            let m = moves.get(idx).unwrap();
+        let mut end = NodeEnd::Searched { moves: moves.len() as u8 };
+        for (searched, m) in moves.iter().enumerate() {
             let new_board = apply_move(board, current, m.pos, m.flipped);
+            state.trace_enter(m.pos, current, alpha, beta);
             let eval = alphabeta(&new_board, player, depth - 1, alpha, beta, true, state);
-            min_eval = min_eval.min(eval);
+            state.trace_leave(eval);
+            if eval < min_eval {
+                min_eval = eval;
+                best_pos = m.pos;
+            }
             beta = beta.min(eval);
 
             if beta <= alpha {
+                state.memory.record_cutoff(board, current, m.pos, weight);
+                end = NodeEnd::AlphaCutoff { searched: searched as u8 + 1, moves: moves.len() as u8 };
                 break; // Alpha cutoff
             }
         }
 
+        state.trace_end(end);
         min_eval
+    };
+
+    if !state.cancelled() {
+        state.store(key, depth, score, window, best_pos);
+    }
+    score
+}
+
+/// Exact score of a finished game for `player`, from the final margin
+/// under `rules` so that a bigger win always scores higher
+fn final_score(board: &Board, player: Player, rules: &RuleSet) -> Score {
+    let (black, white) = rules.scoring.final_counts(board.count(Player::Black), board.count(Player::White));
+    let lead = match player {
+        Player::Black => black as Score - white as Score,
+        Player::White => white as Score - black as Score,
+    };
+    let margin = if rules.misere { -lead } else { lead };
+    match margin.cmp(&0) {
+        core::cmp::Ordering::Greater => SCORE_WIN - 64 + margin,
+        core::cmp::Ordering::Less => SCORE_LOSS + 64 + margin,
+        core::cmp::Ordering::Equal => 0,
     }
 }
 
@@ -198,14 +919,25 @@ fn solve_endgame(
     board: &Board,
     player: Player,
     mut alpha: Score,
-    beta: Score,
+    mut beta: Score,
     maximizing: bool,
     state: &mut SearchState,
 ) -> Score {
     state.nodes_searched += 1;
 
+    if state.cancelled() {
+        return 0;
+    }
+
     let current = if maximizing { player } else { player.opponent() };
-    let moves = generate_moves(board, current);
+    let key = table_key(board, current, player, true);
+    let stored = state.probe(key);
+    if let Some(score) = stored.and_then(|e| e.cutoff(alpha, beta)) {
+        state.memory.stats.table_hits += 1;
+        state.trace_end(NodeEnd::Table);
+        return score;
+    }
+    let mut moves = generate_moves(board, current);
 
     if moves.is_empty() {
         let opponent = current.opponent();
@@ -213,63 +945,147 @@ fn solve_endgame(
 
         if opp_moves.is_empty() {
             // Game over - exact score
-            let own = board.count(player) as Score;
-            let opp = board.count(player.opponent()) as Score;
-            return if own > opp {
-                SCORE_WIN - opp
-            } else if opp > own {
-                SCORE_LOSS + own
-            } else {
-                0
-            };
+            state.trace_end(NodeEnd::GameOver);
+            return final_score(board, player, &state.rules);
         }
 
+        state.trace_pass();
         return solve_endgame(board, player, alpha, beta, !maximizing, state);
     }
 
-    let ordered = order_moves(board, current, &moves);
+    state.order_inner(board, current, &mut moves, stored.map_or(NO_MOVE, |e| e.best));
+    let window = (alpha, beta);
+    let mut best_pos = NO_MOVE;
+    // Solves with more squares left save more when cut off
+    let weight = board.empty_count() * board.empty_count();
 
-    if maximizing {
+    let score = if maximizing {
         let mut max_eval = SCORE_LOSS;
 
-        for &idx in &ordered[..moves.len()] {
-            let m = moves.get(idx).unwrap();
+        let mut end = NodeEnd::Searched { moves: moves.len() as u8 };
+        for (searched, m) in moves.iter().enumerate() {
             let new_board = apply_move(board, current, m.pos, m.flipped);
+            state.trace_enter(m.pos, current, alpha, beta);
             let eval = solve_endgame(&new_board, player, alpha, beta, false, state);
-            max_eval = max_eval.max(eval);
+            state.trace_leave(eval);
+            if eval > max_eval {
+                max_eval = eval;
+                best_pos = m.pos;
+            }
             alpha = alpha.max(eval);
 
             if beta <= alpha {
+                state.memory.record_cutoff(board, current, m.pos, weight);
+                end = NodeEnd::BetaCutoff { searched: searched as u8 + 1, moves: moves.len() as u8 };
                 break;
             }
         }
 
+        state.trace_end(end);
         max_eval
     } else {
         let mut min_eval = SCORE_WIN;
 
-        for &idx in &ordered[..moves.len()] {
-            let m = moves.get(idx).unwrap();
+        let mut end = NodeEnd::Searched { moves: moves.len() as u8 };
+        for (searched, m) in moves.iter().enumerate() {
             let new_board = apply_move(board, current, m.pos, m.flipped);
+            state.trace_enter(m.pos, current, alpha, beta);
             let eval = solve_endgame(&new_board, player, alpha, beta, true, state);
-            min_eval = min_eval.min(eval);
+            state.trace_leave(eval);
+            if eval < min_eval {
+                min_eval = eval;
+                best_pos = m.pos;
+            }
+            beta = beta.min(eval);
 
             if beta <= alpha {
+                state.memory.record_cutoff(board, current, m.pos, weight);
+                end = NodeEnd::AlphaCutoff { searched: searched as u8 + 1, moves: moves.len() as u8 };
                 break;
             }
         }
 
+        state.trace_end(end);
         min_eval
+    };
+
+    if !state.cancelled() {
+        state.store(key, 0, score, window, best_pos);
     }
+    score
 }
 
-/// Find the best move for the given difficulty
+/// Find the best move for a difficulty, or within explicit limits
 pub fn find_best_move(
     board: &Board,
     player: Player,
-    difficulty: Difficulty,
+    config: impl Into<SearchConfig>,
 ) -> Option<Position> {
-    let moves = generate_moves(board, player);
+    find_best_move_cancellable(board, player, config, &AtomicBool::new(false))
+}
+
+/// Find the best move, stopping early once `cancel` is set
+///
+/// A cancelled search plays the best move whose search finished, or the
+/// first move in search order if none did.
+pub fn find_best_move_cancellable(
+    board: &Board,
+    player: Player,
+    config: impl Into<SearchConfig>,
+    cancel: &AtomicBool,
+) -> Option<Position> {
+    find_best_move_with(board, player, config.into(), cancel)
+}
+
+/// Find the best move with explicit search options, stopping early once
+/// `cancel` is set
+pub fn find_best_move_with(
+    board: &Board,
+    player: Player,
+    config: SearchConfig,
+    cancel: &AtomicBool,
+) -> Option<Position> {
+    Searcher::<0>::new().best_move_with(board, player, config, cancel)
+}
+
+/// Find the best move within the limits' time, read from `now`
+///
+/// `now` returns milliseconds since any fixed point. The search deepens
+/// one ply at a time up to the limits' depth and plays the best move of
+/// the deepest pass that finished in time. An endgame solve that runs out
+/// of time plays the best move whose solve finished.
+pub fn find_best_move_timed(
+    board: &Board,
+    player: Player,
+    config: SearchConfig,
+    now: &dyn Fn() -> u64,
+) -> Option<Position> {
+    Searcher::<0>::new().best_move_timed(board, player, config, now)
+}
+
+/// Run `find_best_move_with`'s search, recording it into `trace`
+#[cfg(feature = "std")]
+pub(crate) fn search_traced(
+    board: &Board,
+    player: Player,
+    config: SearchConfig,
+    trace: &mut SearchTree,
+) -> Option<Position> {
+    let mut state = SearchState { trace: Some(trace), ..SearchState::new() };
+    search_best_move(board, player, config, &mut state)
+}
+
+fn search_best_move(
+    board: &Board,
+    player: Player,
+    config: SearchConfig,
+    state: &mut SearchState,
+) -> Option<Position> {
+    let limits = config.limits;
+    state.node_limit = limits.nodes;
+    state.rules = config.rules;
+    state.potential_mobility = config.potential_mobility;
+    let mut moves = generate_moves(board, player);
     if moves.is_empty() {
         return None;
     }
@@ -279,31 +1095,91 @@ pub fn find_best_move(
         return Some(moves.get(0).unwrap().pos);
     }
 
-    // Check opening book for Expert
-    if difficulty.use_opening_book() {
-        if let Some(book_move) = OpeningBook::lookup(board) {
+    // A rejected book move leaves the verification scores to order the search
+    let mut verified = None;
+    // A book from outside the engine may hold a move that isn't legal here
+    let book_move = config.opening_book.lookup(board).filter(|&p| moves.as_bitboard() & (1 << p) != 0);
+    match (config.book_policy(), book_move) {
+        (BookPolicy::Trust, Some(book_move)) => {
+            state.method = SearchMethod::Book;
             return Some(book_move);
         }
+        (BookPolicy::Verify, Some(book_move)) => {
+            let check = score_moves(board, player, &SearchLimits::depth(BOOK_VERIFY_DEPTH).with_endgame_empties(0), &AtomicBool::new(false));
+            if book_move_holds(&check, book_move) {
+                state.method = SearchMethod::Book;
+                return Some(book_move);
+            }
+            verified = Some(check);
+        }
+        _ => {}
     }
 
-    let empty = board.empty_count();
-    let mut state = SearchState::new();
-
     // Endgame solving
-    if difficulty.use_endgame_solver() && empty <= difficulty.endgame_threshold() {
-        return find_best_move_endgame(board, player, &moves, &mut state);
+    if limits.solves(board) {
+        (state.method, state.depth_reached) = (SearchMethod::Exact, board.empty_count() as u8);
+        return find_best_move_endgame(board, player, &mut moves, state);
+    }
+
+    // Solving for the outcome, behind a shallow search whose move stands
+    // unless the solve proves another does better
+    if limits.solves_wld(board) {
+        (state.method, state.depth_reached) = (SearchMethod::Outcome, board.empty_count() as u8);
+        order_moves(board, player, &mut moves);
+        let (pos, _) = search_root(board, player, &moves, WLD_ORDER_DEPTH.min(limits.depth.max(1)), state);
+        moves.sort_by_key(|m| m.pos != pos);
+        return find_best_move_wld(board, player, &moves, state);
     }
 
     // Regular search
-    let depth = difficulty.depth();
-    let ordered = order_moves(board, player, &moves);
+    let depth = limits.depth.max(1);
+    match &verified {
+        Some(check) => order_by_analysis(&mut moves, check),
+        None => order_moves(board, player, &mut moves),
+    }
+
+    state.method = SearchMethod::Search;
+    if !state.limited() {
+        let (pos, finished) = search_root(board, player, &moves, depth, state);
+        if finished {
+            state.depth_reached = depth;
+        }
+        return Some(pos);
+    }
+
+    // Deepen until a limit stops the search, searching the last pass's best move first
+    let mut best_pos = moves.get(0).unwrap().pos;
+    for pass_depth in 1..=depth {
+        let (pos, finished) = search_root(board, player, &moves, pass_depth, state);
+        if !finished {
+            break;
+        }
+        state.depth_reached = pass_depth;
+        best_pos = pos;
+        moves.sort_by_key(|m| m.pos != pos);
+    }
+
+    Some(best_pos)
+}
 
-    let mut best_pos = moves.get(ordered[0]).unwrap().pos;
+/// Search every root move to `depth`, returning the best move and whether
+/// the search finished before it was stopped
+///
+/// A stopped search returns the best move whose search finished, or the
+/// first move in search order if none did.
+fn search_root(
+    board: &Board,
+    player: Player,
+    moves: &MoveList,
+    depth: u8,
+    state: &mut SearchState,
+) -> (Position, bool) {
+    let mut best_pos = moves.get(0).unwrap().pos;
     let mut best_score = SCORE_LOSS;
 
-    for &idx in &ordered[..moves.len()] {
-        let m = moves.get(idx).unwrap();
+    for m in moves.iter() {
         let new_board = apply_move(board, player, m.pos, m.flipped);
+        state.trace_enter(m.pos, player, SCORE_LOSS, SCORE_WIN);
         let score = alphabeta(
             &new_board,
             player,
@@ -311,33 +1187,51 @@ pub fn find_best_move(
             SCORE_LOSS,
             SCORE_WIN,
             false,
-            &mut state,
+            state,
         );
+        state.trace_leave(score);
 
+        if state.cancelled() {
+            return (best_pos, false);
+        }
         if score > best_score {
             best_score = score;
             best_pos = m.pos;
         }
     }
 
-    Some(best_pos)
+    state.trace_end(NodeEnd::Searched { moves: moves.len() as u8 });
+    (best_pos, true)
+}
+
+/// Whether a book move is legal and scores close enough to the best move
+fn book_move_holds(check: &Analysis, book_move: Position) -> bool {
+    match (check.best(), check.score_of(book_move)) {
+        (Some(best), Some(score)) => best.score - score < BOOK_VERIFY_MARGIN,
+        _ => false,
+    }
+}
+
+/// Put moves in the order an analysis ranked them
+fn order_by_analysis(moves: &mut MoveList, analysis: &Analysis) {
+    moves.sort_by_key(|m| analysis.iter().position(|scored| scored.pos == m.pos).unwrap_or(usize::MAX));
 }
 
 /// Find best move using endgame solver
 fn find_best_move_endgame(
     board: &Board,
     player: Player,
-    moves: &MoveList,
+    moves: &mut MoveList,
     state: &mut SearchState,
 ) -> Option<Position> {
-    let ordered = order_moves(board, player, moves);
+    order_moves(board, player, moves);
 
-    let mut best_pos = moves.get(ordered[0]).unwrap().pos;
+    let mut best_pos = moves.get(0).unwrap().pos;
     let mut best_score = SCORE_LOSS;
 
-    for &idx in &ordered[..moves.len()] {
-        let m = moves.get(idx).unwrap();
+    for m in moves.iter() {
         let new_board = apply_move(board, player, m.pos, m.flipped);
+        state.trace_enter(m.pos, player, SCORE_LOSS, SCORE_WIN);
         let score = solve_endgame(
             &new_board,
             player,
@@ -346,16 +1240,411 @@ fn find_best_move_endgame(
             false,
             state,
         );
+        state.trace_leave(score);
 
+        if state.cancelled() {
+            return Some(best_pos);
+        }
         if score > best_score {
             best_score = score;
             best_pos = m.pos;
         }
     }
 
+    state.trace_end(NodeEnd::Searched { moves: moves.len() as u8 });
     Some(best_pos)
 }
 
+/// Depth of the search that picks the move to beat in a win/loss/draw solve
+const WLD_ORDER_DEPTH: u8 = 4;
+
+/// Find a move that wins, or failing that draws, solving for the outcome
+/// alone
+///
+/// The first move is played unless another is proven to do better. Each
+/// move is only asked whether it beats the best outcome so far, which
+/// takes a much narrower window than the exact score. A solve that runs out
+/// of time plays the best move proven so far.
+fn find_best_move_wld(
+    board: &Board,
+    player: Player,
+    moves: &MoveList,
+    state: &mut SearchState,
+) -> Option<Position> {
+    let mut best_pos = moves.get(0).unwrap().pos;
+    // -1, 0 or 1 for a loss, draw or win, once the first move is solved
+    let mut best = SCORE_LOSS;
+
+    let mut end = NodeEnd::Searched { moves: moves.len() as u8 };
+    for (searched, m) in moves.iter().enumerate() {
+        let new_board = apply_move(board, player, m.pos, m.flipped);
+        let alpha = best.max(-1);
+        state.trace_enter(m.pos, player, alpha, 1);
+        let outcome = solve_endgame(&new_board, player, alpha, 1, false, state).signum();
+        state.trace_leave(outcome);
+
+        if state.cancelled() {
+            return Some(best_pos);
+        }
+        if outcome > best {
+            best = outcome;
+            best_pos = m.pos;
+        }
+        if best == 1 {
+            end = NodeEnd::BetaCutoff { searched: searched as u8 + 1, moves: moves.len() as u8 };
+            break;
+        }
+    }
+
+    state.trace_end(end);
+    Some(best_pos)
+}
+
+/// Find the best move, spreading the root moves over `threads` threads
+///
+/// Each thread takes the next unsearched root move and searches it against
+/// the best score any thread has found so far, so later moves are cut off
+/// sooner. Ties go to the move earliest in search order, which makes the
+/// result match `find_best_move_with` however the threads are scheduled.
+/// Book positions and outcome solves are left to the single-threaded search.
+#[cfg(feature = "std")]
+pub fn find_best_move_parallel(
+    board: &Board,
+    player: Player,
+    config: SearchConfig,
+    threads: usize,
+) -> Option<Position> {
+    use std::sync::Mutex;
+    use std::sync::atomic::AtomicUsize;
+
+    let mut moves = generate_moves(board, player);
+    let book = config.book_policy() != BookPolicy::Off && config.opening_book.lookup(board).is_some();
+    if moves.len() <= 1 || book || config.limits.solves_wld(board) {
+        return find_best_move_with(board, player, config, &AtomicBool::new(false));
+    }
+
+    let endgame = config.limits.solves(board);
+    let depth = config.limits.depth.max(1);
+    order_moves(board, player, &mut moves);
+
+    let next = AtomicUsize::new(0);
+    // Best score so far and the search-order slot of its move
+    let best = Mutex::new((SCORE_LOSS, 0));
+
+    std::thread::scope(|scope| {
+        for _ in 0..threads.max(1) {
+            scope.spawn(|| {
+                let mut state = SearchState {
+                    node_limit: config.limits.nodes,
+                    rules: config.rules,
+                    potential_mobility: config.potential_mobility,
+                    ..SearchState::new()
+                };
+                loop {
+                    let slot = next.fetch_add(1, Ordering::Relaxed);
+                    if slot >= moves.len() {
+                        break;
+                    }
+                    let m = moves.get(slot).unwrap();
+                    let new_board = apply_move(board, player, m.pos, m.flipped);
+
+                    // One below the best, so a move that ties it still
+                    // comes back with its exact score
+                    let alpha = best.lock().unwrap().0 - 1;
+                    let score = if endgame {
+                        solve_endgame(&new_board, player, alpha, SCORE_WIN, false, &mut state)
+                    } else {
+                        alphabeta(&new_board, player, depth - 1, alpha, SCORE_WIN, false, &mut state)
+                    };
+
+                    let mut best = best.lock().unwrap();
+                    if score > best.0 || (score == best.0 && slot < best.1) {
+                        *best = (score, slot);
+                    }
+                }
+            });
+        }
+    });
+
+    let (_, slot) = best.into_inner().unwrap();
+    Some(moves.get(slot).unwrap().pos)
+}
+
+/// A legal move with its search score
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MoveScore {
+    /// Move position
+    pub pos: Position,
+    /// Score from the mover's perspective
+    pub score: Score,
+}
+
+impl MoveScore {
+    /// Whether the search found a forced win for the mover
+    pub const fn is_won(&self) -> bool {
+        self.score > SCORE_WIN / 2
+    }
+
+    /// Whether the search found a forced loss for the mover
+    pub const fn is_lost(&self) -> bool {
+        self.score < SCORE_LOSS / 2
+    }
+}
+
+/// Scores for every legal move in a position, best first
+#[derive(Debug, Clone, Copy)]
+pub struct Analysis {
+    scores: [MoveScore; 32],
+    len: usize,
+}
+
+impl Analysis {
+    /// Create an empty analysis
+    const fn new() -> Self {
+        Self {
+            scores: [MoveScore { pos: 0, score: 0 }; 32],
+            len: 0,
+        }
+    }
+
+    /// Number of moves analyzed
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check if there were no legal moves
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Get the best move and its score
+    pub fn best(&self) -> Option<MoveScore> {
+        self.get(0)
+    }
+
+    /// Get a move by rank (0 = best)
+    pub fn get(&self, index: usize) -> Option<MoveScore> {
+        if index < self.len {
+            Some(self.scores[index])
+        } else {
+            None
+        }
+    }
+
+    /// Get the score of a specific move
+    pub fn score_of(&self, pos: Position) -> Option<Score> {
+        self.iter().find(|m| m.pos == pos).map(|m| m.score)
+    }
+
+    /// Iterate over moves, best first
+    pub fn iter(&self) -> impl Iterator<Item = &MoveScore> {
+        self.scores[..self.len].iter()
+    }
+}
+
+/// Score every legal move for a difficulty, or within explicit limits
+///
+/// Unlike `find_best_move` each move gets a full-window search, so the
+/// scores can be compared with each other. Moves are sorted best first.
+/// A node limit leaves out the moves it stops before finishing.
+pub fn analyze_moves(board: &Board, player: Player, limits: impl Into<SearchLimits>) -> Analysis {
+    score_moves(board, player, &limits.into(), &AtomicBool::new(false))
+}
+
+/// Score every legal move like `analyze_moves`, stopping early once
+/// `cancel` is set
+///
+/// A cancelled analysis leaves out the moves it stopped before finishing.
+pub fn analyze_moves_cancellable(
+    board: &Board,
+    player: Player,
+    limits: impl Into<SearchLimits>,
+    cancel: &AtomicBool,
+) -> Analysis {
+    score_moves(board, player, &limits.into(), cancel)
+}
+
+/// Score every legal move with a search of the given depth
+///
+/// Positions with no more empty squares than `depth` are solved exactly,
+/// which costs no more than the fixed-depth search would.
+pub fn analyze_at_depth(board: &Board, player: Player, depth: u8) -> Analysis {
+    score_moves(board, player, &SearchLimits::depth(depth), &AtomicBool::new(false))
+}
+
+/// Full-window search of each move, sorted best first
+fn score_moves(board: &Board, player: Player, limits: &SearchLimits, cancel: &AtomicBool) -> Analysis {
+    let mut state = SearchState { cancel: Some(cancel), ..SearchState::with_limits(limits) };
+    score_moves_with(board, player, limits, &mut state)
+}
+
+/// Full-window search of each move with a prepared state, sorted best first
+fn score_moves_with(board: &Board, player: Player, limits: &SearchLimits, state: &mut SearchState) -> Analysis {
+    let mut analysis = Analysis::new();
+    let moves = generate_moves(board, player);
+    let (depth, endgame) = (limits.depth.max(1), limits.solves(board));
+
+    for m in moves.iter() {
+        let new_board = apply_move(board, player, m.pos, m.flipped);
+        let score = if endgame {
+            solve_endgame(&new_board, player, SCORE_LOSS, SCORE_WIN, false, state)
+        } else {
+            alphabeta(&new_board, player, depth - 1, SCORE_LOSS, SCORE_WIN, false, state)
+        };
+        if state.cancelled() {
+            break;
+        }
+
+        // Insert in descending order
+        let mut i = analysis.len;
+        while i > 0 && analysis.scores[i - 1].score < score {
+            analysis.scores[i] = analysis.scores[i - 1];
+            i -= 1;
+        }
+        analysis.scores[i] = MoveScore { pos: m.pos, score };
+        analysis.len += 1;
+    }
+
+    analysis
+}
+
+/// Longest principal variation reported
+pub const MAX_PV: usize = 12;
+
+/// Expected line of play, with 255 marking a pass as in game history
+#[derive(Debug, Clone, Copy)]
+pub struct Variation {
+    moves: [Position; MAX_PV],
+    len: usize,
+}
+
+impl Variation {
+    /// Moves in the line, first move first
+    pub fn moves(&self) -> &[Position] {
+        &self.moves[..self.len]
+    }
+}
+
+/// Read the expected line from the table a search left behind
+///
+/// Starts with `first`, then takes the best move stored for each position
+/// in turn, stopping after `depth` moves or where the table has lost one.
+fn stored_line(state: &SearchState, board: &Board, player: Player, first: Position, depth: u8, solve: bool) -> Variation {
+    let mut line = Variation { moves: [0; MAX_PV], len: 0 };
+    let mut board = *board;
+    let mut current = player;
+    let mut next = first;
+    let mut remaining = depth.max(1);
+
+    while remaining > 0 && line.len < MAX_PV {
+        let moves = generate_moves(&board, current);
+        if moves.is_empty() {
+            if count_moves(&board, current.opponent()) == 0 {
+                break;
+            }
+            line.moves[line.len] = 255;
+        } else {
+            let Some(m) = moves.iter().find(|m| m.pos == next) else {
+                break;
+            };
+            board = apply_move(&board, current, m.pos, m.flipped);
+            line.moves[line.len] = m.pos;
+            remaining -= 1;
+        }
+        line.len += 1;
+        current = current.opponent();
+        next = state.probe(table_key(&board, current, player, solve)).map_or(NO_MOVE, |e| e.best);
+    }
+
+    line
+}
+
+/// Score gap a gentle CPU move may fall short of the best by
+///
+/// Large enough to allow the odd X-square, small enough to never give
+/// away a corner outright.
+pub const GENTLE_SLACK: Score = 40;
+
+/// Pick a deliberately weak move for handicap play
+///
+/// Searches only at Easy depth and picks among the moves scoring within
+/// `GENTLE_SLACK` of the best, using `seed` to choose.
+pub fn find_gentle_move(board: &Board, player: Player, seed: u32) -> Option<Position> {
+    let analysis = analyze_moves(board, player, Difficulty::Easy);
+    let best = analysis.best()?;
+    let candidates = analysis.iter().filter(|m| best.score - m.score <= GENTLE_SLACK).count();
+    analysis.get(seed as usize % candidates).map(|m| m.pos)
+}
+
+/// Strength of the adaptive opponent
+///
+/// Goes from 0, a two-ply search that plays loosely, to
+/// [`AdaptiveLevel::MAX`], a six-ply search that always takes its best
+/// move. Each step either deepens the search or narrows the choice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdaptiveLevel(u8);
+
+impl AdaptiveLevel {
+    /// Strongest level
+    pub const MAX: u8 = 9;
+
+    /// Level a new player starts at, a little below Medium
+    pub const START: AdaptiveLevel = AdaptiveLevel(4);
+
+    /// Make a level, clamped to the valid range
+    pub const fn new(level: u8) -> Self {
+        AdaptiveLevel(if level > Self::MAX { Self::MAX } else { level })
+    }
+
+    /// The level as a number from 0 to [`AdaptiveLevel::MAX`]
+    pub const fn get(self) -> u8 {
+        self.0
+    }
+
+    /// Search depth at this level
+    pub const fn depth(self) -> u8 {
+        2 + self.0 / 2
+    }
+
+    /// Score gap within which a move may be picked instead of the best
+    pub const fn slack(self) -> Score {
+        if self.0 % 2 == 0 {
+            GENTLE_SLACK
+        } else {
+            0
+        }
+    }
+
+    /// Level for the next game after a win (`Some(true)`), loss or draw
+    ///
+    /// Stepping up after each win and down after each loss settles where
+    /// the player wins about half their games.
+    pub const fn after(self, won: Option<bool>) -> Self {
+        match won {
+            Some(true) => Self::new(self.0 + 1),
+            Some(false) => AdaptiveLevel(self.0.saturating_sub(1)),
+            None => self,
+        }
+    }
+}
+
+impl Default for AdaptiveLevel {
+    fn default() -> Self {
+        Self::START
+    }
+}
+
+/// Pick a move for the adaptive opponent at `level`
+///
+/// Uses `seed` to choose among the moves within the level's slack of the
+/// best one.
+pub fn find_adaptive_move(board: &Board, player: Player, level: AdaptiveLevel, seed: u32) -> Option<Position> {
+    let analysis = analyze_at_depth(board, player, level.depth());
+    let best = analysis.best()?;
+    let candidates = analysis.iter().filter(|m| best.score - m.score <= level.slack()).count();
+    analysis.get(seed as usize % candidates).map(|m| m.pos)
+}
+
 /// Get a random legal move (for testing)
 #[cfg(feature = "std")]
 #[allow(dead_code)]
@@ -365,21 +1654,67 @@ pub fn random_move(board: &Board, player: Player) -> Option<Position> {
         return None;
     }
 
-    // Use a simple counter for pseudo-randomness in tests
-    let idx = (board.hash() as usize) % moves.len();
+    // Use a simple counter for pseudo-randomness in tests; reduce before
+    // narrowing so 32-bit builds pick the same move
+    let idx = (board.zobrist() % moves.len() as u64) as usize;
     Some(moves.get(idx).unwrap().pos)
 }
 
-/// Get a hint (best move) for the player
-#[allow(dead_code)]
-pub fn get_hint(board: &Board, player: Player) -> Option<Position> {
-    find_best_move(board, player, Difficulty::Hard)
+/// Limits for a hint: a Hard search
+pub const HINT_LIMITS: SearchLimits = Difficulty::Hard.limits();
+
+/// Get a hint (best move) for the player, searched within `limits`
+pub fn get_hint(board: &Board, player: Player, limits: SearchLimits) -> Option<Position> {
+    find_best_move(board, player, limits)
+}
+
+/// Search depth for the pre-move blunder check (kept shallow so it is instant)
+pub const BLUNDER_DEPTH: u8 = 3;
+
+/// Score drop against the best move that counts as a blunder, about a corner
+pub const BLUNDER_MARGIN: Score = 100;
+
+/// Why a move was flagged before it was played
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Blunder {
+    /// The move lets the opponent take this corner, and another move would not
+    Corner(Position),
+    /// The move scores this much worse than the best one
+    Drop(Score),
+}
+
+/// Check a move for an obvious blunder before it is played
+///
+/// Giving up a corner is found without searching; otherwise the move is
+/// compared with the best one at [`BLUNDER_DEPTH`]. Returns None for
+/// reasonable, forced or illegal moves.
+pub fn find_blunder(board: &Board, player: Player, pos: Position) -> Option<Blunder> {
+    let moves = generate_moves(board, player);
+    if moves.len() < 2 {
+        return None;
+    }
+
+    // Corners the opponent could take after each move
+    let corners_after = |m: &crate::Move| {
+        let after = apply_move(board, player, m.pos, m.flipped);
+        crate::moves::legal_moves_bitboard(&after, player.opponent()) & CORNER_MASK
+    };
+    let played = moves.iter().find(|m| m.pos == pos)?;
+    let given = corners_after(played);
+    if given != 0 && moves.iter().any(|m| corners_after(m) == 0) {
+        return Some(Blunder::Corner(given.trailing_zeros() as Position));
+    }
+
+    let analysis = analyze_at_depth(board, player, BLUNDER_DEPTH);
+    let drop = analysis.best()?.score - analysis.score_of(pos)?;
+    (drop >= BLUNDER_MARGIN).then_some(Blunder::Drop(drop))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::moves::calculate_flips;
+    use crate::Scoring;
 
     #[test]
     fn test_find_best_move_opening() {
@@ -404,6 +1739,7 @@ mod tests {
             Difficulty::Medium,
             Difficulty::Hard,
             Difficulty::Expert,
+            Difficulty::Grandmaster,
         ] {
             let best = find_best_move(&board, Player::Black, difficulty);
             assert!(best.is_some(), "Difficulty {:?} failed", difficulty);
@@ -413,6 +1749,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cancelled_search() {
+        let board = Board::new();
+        let cancel = AtomicBool::new(true);
+
+        // An already-cancelled search still plays a legal move
+        for difficulty in [Difficulty::Medium, Difficulty::Expert] {
+            let pos = find_best_move_cancellable(&board, Player::Black, difficulty, &cancel).unwrap();
+            assert!(generate_moves(&board, Player::Black).iter().any(|m| m.pos == pos));
+        }
+
+        // Uncancelled, it matches the plain search
+        let cancel = AtomicBool::new(false);
+        assert_eq!(
+            find_best_move_cancellable(&board, Player::Black, Difficulty::Hard, &cancel),
+            find_best_move(&board, Player::Black, Difficulty::Hard)
+        );
+
+        // An analysis keeps only the moves searched before the flag was set
+        let cancel = AtomicBool::new(true);
+        assert_eq!(analyze_moves_cancellable(&board, Player::Black, Difficulty::Hard, &cancel).len(), 0);
+        let cancel = AtomicBool::new(false);
+        let analysis = analyze_moves_cancellable(&board, Player::Black, Difficulty::Medium, &cancel);
+        let plain = analyze_moves(&board, Player::Black, Difficulty::Medium);
+        assert!(analysis.iter().eq(plain.iter()));
+    }
+
     #[test]
     fn test_forced_move() {
         // Create position with only one legal move
@@ -465,16 +1828,452 @@ mod tests {
         let _ = result;
     }
 
+    #[test]
+    fn test_final_score_rules() {
+        // 40-10 with 14 empty against 50-12 with 2 empty
+        let wide = Board { black: (1 << 40) - 1, white: ((1 << 10) - 1) << 54 };
+        let full = Board { black: (1 << 50) - 1, white: ((1 << 12) - 1) << 52 };
+        let discs = RuleSet::STANDARD;
+        let empties = RuleSet { scoring: Scoring::EmptiesToWinner, ..discs };
+        let misere = RuleSet { misere: true, ..discs };
+
+        // Counting discs, 50-12 is the bigger win; with the empties it is 54-10
+        assert!(final_score(&full, Player::Black, &discs) > final_score(&wide, Player::Black, &discs));
+        assert!(final_score(&wide, Player::Black, &empties) > final_score(&full, Player::Black, &empties));
+        assert_eq!(final_score(&wide, Player::Black, &discs), SCORE_WIN - 64 + 30);
+        assert_eq!(final_score(&wide, Player::White, &empties), SCORE_LOSS + 64 - 44);
+        assert_eq!(final_score(&Board::new(), Player::White, &empties), 0);
+
+        // Misère turns the result around
+        assert_eq!(final_score(&wide, Player::White, &misere), SCORE_WIN - 64 + 30);
+    }
+
+    #[test]
+    fn test_potential_mobility_term() {
+        let game = crate::parse_transcript("F5 D6 C3 D3 C4").unwrap();
+        let board = *game.board();
+        let config = SearchConfig::new(Difficulty::Medium);
+        assert!(!config.potential_mobility);
+
+        // The extra term is only added where the game goes on
+        let state = SearchState { potential_mobility: true, ..SearchState::new() };
+        assert_eq!(
+            state.evaluate(&board, Player::White),
+            evaluate(&board, Player::White) + evaluate_potential_mobility(&board, Player::White)
+        );
+        let cancel = AtomicBool::new(false);
+        let choice = find_best_move_with(&board, Player::White, config.with_potential_mobility(true), &cancel);
+        assert!(choice.is_some_and(|pos| game.is_legal(pos)));
+    }
+
+    #[test]
+    fn test_misere_search() {
+        // C5 takes White's last disc: the best move normally, the worst in
+        // misère play
+        let game = crate::parse_transcript("E6 F4 E3 F6 G5 D6 E7 F5").unwrap();
+        let board = *game.board();
+        let wipeout = crate::algebraic_to_pos(b"C5");
+        let standard = SearchConfig::new(Difficulty::Hard);
+        let misere = standard.with_rules(RuleSet { misere: true, ..RuleSet::STANDARD });
+        let cancel = AtomicBool::new(false);
+        assert_eq!(find_best_move_with(&board, Player::Black, standard, &cancel), wipeout);
+        let choice = find_best_move_with(&board, Player::Black, misere, &cancel);
+        assert!(choice.is_some() && choice != wipeout);
+    }
+
+    #[test]
+    fn test_analyze_moves() {
+        let board = Board::new();
+        let analysis = analyze_moves(&board, Player::Black, Difficulty::Medium);
+
+        // Every opening move is analyzed, sorted best first
+        assert_eq!(analysis.len(), 4);
+        let best = analysis.best().unwrap();
+        assert!(analysis.iter().all(|m| m.score <= best.score));
+        assert_eq!(analysis.score_of(best.pos), Some(best.score));
+        assert_eq!(analysis.score_of(0), None);
+
+        // The four opening moves are symmetric
+        assert!(analysis.iter().all(|m| m.score == best.score));
+
+        // No moves, empty analysis
+        let analysis = analyze_moves(&Board::empty(), Player::Black, Difficulty::Easy);
+        assert!(analysis.is_empty());
+        assert!(analysis.best().is_none());
+    }
+
+    #[test]
+    fn test_analyze_at_depth() {
+        let board = Board::new();
+        let analysis = analyze_at_depth(&board, Player::Black, 3);
+        assert_eq!(analysis.len(), 4);
+        assert!(!analysis.best().unwrap().is_won());
+
+        // Few enough empties to solve: A1 takes White's last disc on B1
+        let mut board = Board::empty();
+        board.black = !0b011;
+        board.white = 0b010;
+        let analysis = analyze_at_depth(&board, Player::Black, 4);
+        let best = analysis.best().unwrap();
+        assert!(best.is_won());
+        assert_eq!(best.pos, 0);
+    }
+
+    #[test]
+    fn test_analyze_line() {
+        let board = Board::new();
+        let (analysis, line) = Searcher::<1024>::new().analyze_line(&board, Player::Black, 4, &AtomicBool::new(false));
+        assert_eq!(line.moves().len(), 4);
+        let best = analyze_at_depth(&board, Player::Black, 4).best().unwrap();
+        assert_eq!(analysis.best().unwrap().pos, best.pos);
+        assert_eq!(line.moves()[0], best.pos);
+
+        // Every move in the line is legal in turn
+        let mut game = crate::GameState::new();
+        for &p in line.moves() {
+            assert!(game.make_move(p).is_some());
+        }
+
+        // A solved position reads its line from the solver's entries
+        let mut game = crate::GameState::new();
+        while game.board().empty_count() > 8 && !game.is_game_over() {
+            if !game.pass() {
+                let first = game.legal_moves().iter().next().unwrap().pos;
+                game.make_move(first);
+            }
+        }
+        let (board, player) = (*game.board(), game.current_player());
+        let (analysis, line) = Searcher::<1024>::new().analyze_line(&board, player, 8, &AtomicBool::new(false));
+        assert!(line.moves().len() > 1);
+        assert_eq!(line.moves()[0], analysis.best().unwrap().pos);
+        for &p in line.moves() {
+            assert!(if p == 255 { game.pass() } else { game.make_move(p).is_some() });
+        }
+    }
+
+    #[test]
+    fn test_find_blunder() {
+        // White C3 and E5 either side of Black D4 on the long diagonal
+        let mut board = Board::empty();
+        board.place(Player::White, crate::pos(2, 2));
+        board.place(Player::Black, crate::pos(3, 3));
+        board.place(Player::White, crate::pos(4, 4));
+
+        // F6 is safe, but B2 lines up B2-D4 for White to take A1
+        let f6 = crate::pos(5, 5);
+        assert_eq!(find_blunder(&board, Player::Black, f6), None);
+        let b2 = crate::pos(1, 1);
+        assert_ne!(calculate_flips(&board, Player::Black, b2), 0);
+        assert_eq!(find_blunder(&board, Player::Black, b2), Some(Blunder::Corner(crate::pos(0, 0))));
+
+        // The start position has nothing to flag, and illegal moves are ignored
+        let start = Board::new();
+        for m in generate_moves(&start, Player::Black).iter() {
+            assert_eq!(find_blunder(&start, Player::Black, m.pos), None);
+        }
+        assert_eq!(find_blunder(&start, Player::Black, crate::pos(0, 0)), None);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_book_verification() {
+        // C8 gives up far more than the best move here
+        let game = crate::parse_transcript("D3 C5 C6 C3 C4 B5 A6 E3 E6 B7 F4 F5").unwrap();
+        let board = *game.board();
+        let check = score_moves(&board, Player::Black, &SearchLimits::depth(BOOK_VERIFY_DEPTH).with_endgame_empties(0), &AtomicBool::new(false));
+        assert!(book_move_holds(&check, check.best().unwrap().pos));
+        assert!(!book_move_holds(&check, crate::pos(7, 2)));
+        // A book move that is not even legal is never played
+        assert!(!book_move_holds(&check, crate::pos(0, 0)));
+
+        // The verified order searches every move once
+        let mut moves = generate_moves(&board, Player::Black);
+        let all = moves.as_bitboard();
+        order_by_analysis(&mut moves, &check);
+        assert!(moves.iter().map(|m| m.pos).eq(check.iter().map(|m| m.pos)));
+        assert_eq!(moves.as_bitboard(), all);
+
+        // Every policy still finds a legal move
+        let cancel = AtomicBool::new(false);
+        for book in [BookPolicy::Off, BookPolicy::Trust, BookPolicy::Verify] {
+            let config = SearchConfig::new(Difficulty::Expert).with_book(book);
+            let pos = find_best_move_with(&Board::new(), Player::Black, config, &cancel).unwrap();
+            assert_ne!(calculate_flips(&Board::new(), Player::Black, pos), 0);
+        }
+        assert_eq!(SearchConfig::new(Difficulty::Hard).book, BookPolicy::Off);
+
+        // A loaded book is consulted, but an illegal move in it is not played
+        let load = |pos| OpeningBook::from_bytes(Vec::leak(crate::encode_book(&[(Board::new(), pos, 1)]))).unwrap();
+        let trusting = SearchConfig::new(Difficulty::Expert).with_book(BookPolicy::Trust);
+        let c4 = crate::pos(3, 2);
+        assert_eq!(find_best_move_with(&Board::new(), Player::Black, trusting.with_opening_book(load(c4)), &cancel), Some(c4));
+        let pos = find_best_move_with(&Board::new(), Player::Black, trusting.with_opening_book(load(0)), &cancel).unwrap();
+        assert_ne!(calculate_flips(&Board::new(), Player::Black, pos), 0);
+    }
+
+    #[test]
+    fn test_timed_search() {
+        let game = crate::parse_transcript("D3 C5 C6 C3 C4 B5 A6 E3 E6 B7 F4 F5").unwrap();
+        let board = *game.board();
+        let config = SearchConfig::new(Difficulty::Medium);
+
+        // A clock that never moves lets every pass finish
+        let still = || 0;
+        let timed = config.with_limits(Difficulty::Medium.limits().with_time(10));
+        let pos = find_best_move_timed(&board, Player::Black, timed, &still).unwrap();
+        let full = analyze_at_depth(&board, Player::Black, Difficulty::Medium.depth());
+        assert_eq!(full.score_of(pos), Some(full.best().unwrap().score));
+
+        // A clock already past the deadline still yields a legal move,
+        // reading the clock only at the checkpoints
+        let readings = core::cell::Cell::new(0u64);
+        let ticking = || {
+            readings.set(readings.get() + 1);
+            readings.get() * 1000
+        };
+        let late = config.with_limits(Difficulty::Medium.limits().with_time(0));
+        let pos = find_best_move_timed(&board, Player::Black, late, &ticking).unwrap();
+        assert_ne!(calculate_flips(&board, Player::Black, pos), 0);
+        assert!(readings.get() <= 2);
+    }
+
+    #[test]
+    fn test_searcher_reuse() {
+        // A searcher kept through a whole game plays what fresh searches do
+        let mut searcher: Searcher<1024> = Searcher::new();
+        let levels = [Difficulty::Medium.limits(), SearchLimits::depth(4).with_endgame_empties(8)];
+        let mut game = crate::GameState::new();
+        while !game.is_game_over() {
+            let (board, player) = (*game.board(), game.current_player());
+            let limits = levels[player as usize];
+            let reused = searcher.best_move(&board, player, limits);
+            assert_eq!(reused, find_best_move(&board, player, limits));
+            match reused {
+                Some(pos) => {
+                    game.make_move(pos);
+                }
+                None => {
+                    game.pass();
+                }
+            }
+        }
+        let stats = *searcher.stats();
+        assert!(stats.searches > 20 && stats.table_hits > 0 && stats.cutoffs > 0);
+
+        // Searching a position again starts from what the table holds
+        let game = crate::parse_transcript("D3 C5 C6 C3 C4 B5 A6 E3 E6 B7 F4 F5").unwrap();
+        let nodes = |searcher: &mut Searcher<1024>| {
+            let before = searcher.stats().nodes;
+            searcher.best_move(game.board(), Player::Black, Difficulty::Medium);
+            searcher.stats().nodes - before
+        };
+        searcher.clear();
+        let first = nodes(&mut searcher);
+        assert!(nodes(&mut searcher) < first);
+        assert_eq!(searcher.stats().searches, 2);
+    }
+
+    #[test]
+    fn test_search_report() {
+        let mut searcher: Searcher<1024> = Searcher::new();
+        assert_eq!(*searcher.last_search(), SearchReport::default());
+
+        let opened = crate::parse_transcript("F5").unwrap();
+        searcher.best_move(opened.board(), Player::White, Difficulty::Expert);
+        assert_eq!(searcher.last_search().method, SearchMethod::Book);
+
+        // A full search reports its depth, and a repeat finds the table
+        let game = crate::parse_transcript("D3 C5 C6 C3 C4 B5 A6 E3 E6 B7 F4 F5").unwrap();
+        searcher.best_move(game.board(), Player::Black, Difficulty::Medium);
+        let first = *searcher.last_search();
+        assert_eq!((first.method, first.depth, first.stopped), (SearchMethod::Search, 4, false));
+        assert_eq!(first.nodes as u64, searcher.stats().nodes);
+        searcher.best_move(game.board(), Player::Black, Difficulty::Medium);
+        let again = *searcher.last_search();
+        assert!(again.table_hits > 0 && again.table_hit_percent() <= 100);
+
+        // A node limit stops the deepening short of the full depth
+        searcher.best_move(game.board(), Player::Black, SearchLimits::depth(8).with_nodes(2_000));
+        let limited = *searcher.last_search();
+        assert!(limited.stopped && limited.depth < 8);
+
+        // A solve reports the empty squares it solved
+        let late = crate::parse_position("-X-OOOO-OOOOOO-X-OOXOXXXXOXOXXXXXOXOOXXXXOXXOXXXXOOXXX--X-XXX-O-X").unwrap();
+        searcher.best_move(&late.0, late.1, Difficulty::Hard);
+        let solved = *searcher.last_search();
+        assert_eq!((solved.method, solved.depth), (SearchMethod::Exact, late.0.empty_count() as u8));
+    }
+
+    #[test]
+    fn test_search_limits() {
+        let game = crate::parse_transcript("D3 C5 C6 C3 C4 B5 A6 E3 E6 B7 F4 F5").unwrap();
+        let board = *game.board();
+
+        // A difficulty's limits search exactly as the difficulty does
+        for difficulty in [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard] {
+            let limits = difficulty.limits();
+            let (by_limits, by_level) = (analyze_moves(&board, Player::Black, limits), analyze_moves(&board, Player::Black, difficulty));
+            assert!(by_limits.iter().eq(by_level.iter()));
+            assert_eq!(
+                find_best_move(&board, Player::Black, SearchConfig::new(difficulty).with_limits(limits)),
+                find_best_move(&board, Player::Black, difficulty)
+            );
+        }
+        assert_eq!(Difficulty::Medium.limits().endgame_empties, 0);
+        assert_eq!(SearchLimits::depth(0).depth, 1);
+
+        // A node limit still yields a legal move, and cuts the analysis short
+        let tight = SearchLimits::depth(8).with_nodes(50);
+        let pos = find_best_move(&board, Player::Black, tight).unwrap();
+        assert_ne!(calculate_flips(&board, Player::Black, pos), 0);
+        let partial = analyze_moves(&board, Player::Black, tight);
+        assert!(partial.len() < count_moves(&board, Player::Black) as usize);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_parallel_search_matches_serial() {
+        let cancel = AtomicBool::new(false);
+
+        // A midgame position, then an endgame the solver handles
+        let mut game = crate::parse_transcript("D3 C5 C6 C3 C4 B5 A6 E3 E6 B7 F4 F5").unwrap();
+        let mut positions = vec![(*game.board(), game.current_player(), Difficulty::Medium)];
+        let mut seed = 7u32;
+        while game.board().empty_count() > 10 && !game.is_game_over() {
+            match find_gentle_move(game.board(), game.current_player(), seed) {
+                Some(pos) => {
+                    game.make_move(pos);
+                }
+                None => {
+                    game.pass();
+                }
+            }
+            seed = seed.wrapping_mul(31).wrapping_add(3);
+        }
+        positions.push((*game.board(), game.current_player(), Difficulty::Hard));
+
+        for (board, player, difficulty) in positions {
+            let config = SearchConfig::new(difficulty);
+            let serial = find_best_move_with(&board, player, config, &cancel);
+            for threads in [1, 4] {
+                assert_eq!(find_best_move_parallel(&board, player, config, threads), serial);
+            }
+        }
+    }
+
+    #[test]
+    fn test_wld_solve() {
+        let exact = SearchLimits::depth(2).with_endgame_empties(64);
+        let wld = SearchLimits::depth(2).with_endgame_empties(0).with_wld_empties(64);
+        let nodes = |board: &Board, player: Player, limits: SearchLimits| {
+            let mut searcher: Searcher<0> = Searcher::new();
+            let pos = searcher.best_move(board, player, limits);
+            (pos, searcher.stats().nodes)
+        };
+
+        for seed in [2u32, 3, 6] {
+            let mut game = crate::GameState::new();
+            let mut seed = seed;
+            while game.board().empty_count() > 12 && !game.is_game_over() {
+                match find_gentle_move(game.board(), game.current_player(), seed) {
+                    Some(pos) => {
+                        game.make_move(pos);
+                    }
+                    None => {
+                        game.pass();
+                    }
+                }
+                seed = seed.wrapping_mul(31).wrapping_add(3);
+            }
+            let (board, player) = (*game.board(), game.current_player());
+            if count_moves(&board, player) < 2 {
+                continue;
+            }
+
+            // The outcome solve plays a move that ends as well as the exact
+            // solve's, for less work
+            let scores = analyze_moves(&board, player, exact);
+            let (pos, wld_nodes) = nodes(&board, player, wld);
+            let (_, exact_nodes) = nodes(&board, player, exact);
+            assert_eq!(scores.score_of(pos.unwrap()).unwrap().signum(), scores.best().unwrap().score.signum());
+            assert!(wld_nodes < exact_nodes, "{} vs {}", wld_nodes, exact_nodes);
+        }
+
+        // Past its threshold the outcome solve leaves the search alone
+        let board = Board::new();
+        assert_eq!(
+            find_best_move(&board, Player::Black, wld.with_wld_empties(20)),
+            find_best_move(&board, Player::Black, SearchLimits::depth(2).with_endgame_empties(0))
+        );
+    }
+
+    #[test]
+    fn test_adaptive_level_steps() {
+        let start = AdaptiveLevel::default();
+        assert_eq!(start, AdaptiveLevel::START);
+        assert_eq!(start.after(Some(true)).get(), start.get() + 1);
+        assert_eq!(start.after(Some(false)).get(), start.get() - 1);
+        assert_eq!(start.after(None), start);
+
+        // Clamped at both ends
+        let top = AdaptiveLevel::new(AdaptiveLevel::MAX + 5);
+        assert_eq!(top.get(), AdaptiveLevel::MAX);
+        assert_eq!(top.after(Some(true)), top);
+        assert_eq!(AdaptiveLevel::new(0).after(Some(false)).get(), 0);
+
+        // Each level is at least as strong as the one below
+        for level in 1..=AdaptiveLevel::MAX {
+            let (lower, upper) = (AdaptiveLevel::new(level - 1), AdaptiveLevel::new(level));
+            assert!(upper.depth() >= lower.depth());
+            assert!(upper.depth() > lower.depth() || upper.slack() < lower.slack());
+        }
+        assert_eq!(AdaptiveLevel::new(AdaptiveLevel::MAX).slack(), 0);
+    }
+
+    #[test]
+    fn test_find_adaptive_move() {
+        let board = Board::new();
+        for level in 0..=AdaptiveLevel::MAX {
+            let level = AdaptiveLevel::new(level);
+            let analysis = analyze_at_depth(&board, Player::Black, level.depth());
+            let best = analysis.best().unwrap();
+            for seed in 0..4 {
+                let pos = find_adaptive_move(&board, Player::Black, level, seed).unwrap();
+                assert!(best.score - analysis.score_of(pos).unwrap() <= level.slack());
+            }
+        }
+        assert_eq!(find_adaptive_move(&Board::empty(), Player::Black, AdaptiveLevel::START, 0), None);
+    }
+
+    #[test]
+    fn test_find_gentle_move() {
+        let board = Board::new();
+        let analysis = analyze_moves(&board, Player::Black, Difficulty::Easy);
+        let best = analysis.best().unwrap();
+
+        // Every seed gives a legal move close to the best
+        for seed in 0..8 {
+            let pos = find_gentle_move(&board, Player::Black, seed).unwrap();
+            let score = analysis.score_of(pos).unwrap();
+            assert!(best.score - score <= GENTLE_SLACK);
+        }
+
+        // Seed zero takes the best move
+        assert_eq!(find_gentle_move(&board, Player::Black, 0), Some(best.pos));
+        assert_eq!(find_gentle_move(&Board::empty(), Player::Black, 3), None);
+    }
+
     #[test]
     fn test_move_ordering() {
         let board = Board::new();
-        let moves = generate_moves(&board, Player::Black);
-        let ordered = order_moves(&board, Player::Black, &moves);
+        let mut moves = generate_moves(&board, Player::Black);
+        order_moves(&board, Player::Black, &mut moves);
 
         // Should have 4 moves
         assert!(moves.len() == 4);
 
         // First moves in ordering should be the ones with best quick eval
-        assert!(ordered[0] < moves.len());
+        let best = moves.best_by(|m| order_score(&board, Player::Black, m)).unwrap();
+        assert_eq!(moves.get(0), Some(best));
     }
 }
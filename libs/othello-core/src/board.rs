@@ -32,6 +32,18 @@ pub struct Board {
     pub white: u64,
 }
 
+/// The four corners
+pub const CORNER_MASK: u64 = 0x8100_0000_0000_0081;
+/// The outer ring of squares, corners included
+pub const EDGE_MASK: u64 = 0xFF81_8181_8181_81FF;
+/// X-squares, diagonally next to a corner (B2, G2, B7, G7)
+pub const X_SQUARE_MASK: u64 = 0x0042_0000_0000_4200;
+/// C-squares, on an edge next to a corner
+pub const C_SQUARE_MASK: u64 = 0x4281_0000_0000_8142;
+
+/// Corners handed out as a handicap, in order (A1, H8, H1, A8)
+pub const HANDICAP_CORNERS: [Position; 4] = [0, 63, 7, 56];
+
 impl Default for Board {
     fn default() -> Self {
         Self::new()
@@ -57,6 +69,17 @@ impl Board {
         Self { black: 0, white: 0 }
     }
 
+    /// Create a starting position with handicap corner discs for a player
+    ///
+    /// Corners are given in the order A1, H8, H1, A8; at most four.
+    pub fn with_handicap(player: Player, corners: u8) -> Self {
+        let mut board = Self::new();
+        for &corner in HANDICAP_CORNERS.iter().take(corners as usize) {
+            board.place(player, corner);
+        }
+        board
+    }
+
     /// Get the bitboard for a player
     #[inline]
     pub const fn get(&self, player: Player) -> u64 {
@@ -105,6 +128,17 @@ impl Board {
         }
     }
 
+    /// Every square from A1 to H8 with the disc on it, if any
+    pub fn squares(&self) -> impl Iterator<Item = (Position, Option<Player>)> {
+        let board = *self;
+        (0..64).map(move |p| (p, board.get_disc(p)))
+    }
+
+    /// Squares holding a player's discs, from A1 to H8
+    pub fn discs(&self, player: Player) -> impl Iterator<Item = Position> {
+        Self::iter_bits(self.get(player))
+    }
+
     /// Place a disc for a player
     #[inline]
     pub fn place(&mut self, player: Player, pos: Position) {
@@ -131,6 +165,22 @@ impl Board {
         self.get(player).count_ones()
     }
 
+    /// Count the corners a player holds
+    pub const fn corners_owned(&self, player: Player) -> u32 {
+        (self.get(player) & CORNER_MASK).count_ones()
+    }
+
+    /// A player's discs on the edge of the board
+    pub const fn edge_discs(&self, player: Player) -> u64 {
+        self.get(player) & EDGE_MASK
+    }
+
+    /// Whether a player has a disc anywhere in a region, such as
+    /// `X_SQUARE_MASK`
+    pub const fn owns_square(&self, region: u64, player: Player) -> bool {
+        self.get(player) & region != 0
+    }
+
     /// Count empty squares
     #[inline]
     pub const fn empty_count(&self) -> u32 {
@@ -149,7 +199,8 @@ impl Board {
         self.black | self.white
     }
 
-    /// Get a hash of the board position (for transposition tables)
+    /// Get a hash of the board position
+    #[deprecated(note = "use `Board::zobrist`, which collides far less often")]
     pub const fn hash(&self) -> u64 {
         // Simple hash combining both bitboards
         self.black.wrapping_mul(0x9e3779b97f4a7c15) ^ self.white
@@ -162,6 +213,112 @@ impl Board {
     }
 }
 
+/// Zobrist keys, one per square for each colour: `[black, white]`
+///
+/// Generated at compile time by SplitMix64 from [`ZOBRIST_SEED`], so every
+/// build has the same keys. No key is zero and the XOR of any two keys is
+/// unique, so boards that differ in four or fewer (colour, square) entries
+/// never share a key; placing a disc and flipping one is three. Beyond that,
+/// two unrelated boards collide with odds of about one in 2^64.
+pub const ZOBRIST_KEYS: [[u64; 64]; 2] = zobrist_keys();
+
+/// Seed the Zobrist keys are generated from
+pub const ZOBRIST_SEED: u64 = 0x4f54_4845_4c4c_4f21;
+
+const fn zobrist_keys() -> [[u64; 64]; 2] {
+    let mut keys = [[0u64; 64]; 2];
+    let mut state = ZOBRIST_SEED;
+    let mut i = 0;
+    while i < 128 {
+        // SplitMix64
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        keys[i / 64][i % 64] = z ^ (z >> 31);
+        i += 1;
+    }
+    keys
+}
+
+/// Zobrist hashing
+impl Board {
+    /// Zobrist key of the board: the XOR of [`ZOBRIST_KEYS`] for every disc
+    ///
+    /// The side to move is not part of the key.
+    pub const fn zobrist(&self) -> u64 {
+        xor_keys(self.black, &ZOBRIST_KEYS[0]) ^ xor_keys(self.white, &ZOBRIST_KEYS[1])
+    }
+
+    /// Key of one disc, for updating a Zobrist key as discs change
+    #[inline]
+    pub const fn zobrist_key(player: Player, pos: Position) -> u64 {
+        match player {
+            Player::Black => ZOBRIST_KEYS[0][pos as usize],
+            Player::White => ZOBRIST_KEYS[1][pos as usize],
+        }
+    }
+
+    /// Change to a Zobrist key when discs turn over, either way
+    pub const fn zobrist_flip(flipped: u64) -> u64 {
+        xor_keys(flipped, &ZOBRIST_FLIP_KEYS)
+    }
+
+    /// [`place`](Self::place) a disc, keeping `key`, the board's Zobrist
+    /// key, up to date
+    #[inline]
+    pub fn place_hashed(&mut self, player: Player, pos: Position, key: &mut u64) {
+        if !self.has_disc(player, pos) {
+            *key ^= Self::zobrist_key(player, pos);
+        }
+        self.place(player, pos);
+    }
+
+    /// [`remove`](Self::remove) a disc, keeping `key` up to date
+    #[inline]
+    pub fn remove_hashed(&mut self, player: Player, pos: Position, key: &mut u64) {
+        if self.has_disc(player, pos) {
+            *key ^= Self::zobrist_key(player, pos);
+        }
+        self.remove(player, pos);
+    }
+
+    /// [`flip`](Self::flip) discs, keeping `key` up to date
+    ///
+    /// The discs must be `from`'s, as [`calculate_flips`](crate::calculate_flips)
+    /// gives them; the cost is one XOR for each disc turned.
+    #[inline]
+    pub fn flip_hashed(&mut self, from: Player, flipped: u64, key: &mut u64) {
+        debug_assert_eq!(self.get(from) & flipped, flipped, "flipping discs the player doesn't have");
+        *key ^= Self::zobrist_flip(flipped);
+        self.flip(from, flipped);
+    }
+}
+
+/// Both colours' keys XORed for each square, the change turning a disc over
+/// makes
+const ZOBRIST_FLIP_KEYS: [u64; 64] = zobrist_flip_keys();
+
+const fn zobrist_flip_keys() -> [u64; 64] {
+    let mut keys = [0u64; 64];
+    let mut i = 0;
+    while i < 64 {
+        keys[i] = ZOBRIST_KEYS[0][i] ^ ZOBRIST_KEYS[1][i];
+        i += 1;
+    }
+    keys
+}
+
+/// XOR of the keys for every set bit
+const fn xor_keys(mut bits: u64, keys: &[u64; 64]) -> u64 {
+    let mut key = 0;
+    while bits != 0 {
+        key ^= keys[bits.trailing_zeros() as usize];
+        bits &= bits - 1;
+    }
+    key
+}
+
 /// Bit manipulation utilities
 impl Board {
     /// Get the lowest set bit position
@@ -188,6 +345,93 @@ impl Board {
     }
 }
 
+/// Symmetries of the board
+///
+/// Each works on the whole bitboard at once with byte and delta swaps,
+/// instead of moving squares one at a time.
+impl Board {
+    /// Reverse the rows, so A1 goes to A8
+    #[inline]
+    pub const fn flip_vertical(&self) -> Board {
+        Board { black: self.black.swap_bytes(), white: self.white.swap_bytes() }
+    }
+
+    /// Reverse the columns, so A1 goes to H1
+    #[inline]
+    pub const fn mirror_horizontal(&self) -> Board {
+        Board { black: mirror_bits(self.black), white: mirror_bits(self.white) }
+    }
+
+    /// Reflect in the A1-H8 diagonal, so H1 goes to A8
+    #[inline]
+    pub const fn transpose(&self) -> Board {
+        Board { black: transpose_bits(self.black), white: transpose_bits(self.white) }
+    }
+
+    /// Turn half a turn, so A1 goes to H8
+    #[inline]
+    pub const fn rotate180(&self) -> Board {
+        Board { black: self.black.reverse_bits(), white: self.white.reverse_bits() }
+    }
+
+    /// Turn a quarter turn clockwise, so A1 goes to H1
+    #[inline]
+    pub const fn rotate90(&self) -> Board {
+        self.transpose().mirror_horizontal()
+    }
+
+    /// All eight symmetric versions of the board, starting with itself
+    pub const fn symmetries(&self) -> [Board; 8] {
+        let turned = self.rotate90();
+        [
+            *self,
+            self.mirror_horizontal(),
+            self.flip_vertical(),
+            self.rotate180(),
+            self.transpose(),
+            turned,
+            turned.rotate180(),
+            self.rotate180().transpose(),
+        ]
+    }
+
+    /// The one symmetry every symmetric version of the board agrees on:
+    /// the lowest by black discs, then white
+    pub fn canonical(&self) -> Board {
+        let symmetries = self.symmetries();
+        let mut best = symmetries[0];
+        for board in symmetries {
+            if (board.black, board.white) < (best.black, best.white) {
+                best = board;
+            }
+        }
+        best
+    }
+}
+
+/// Reverse the bits within each byte of a bitboard
+const fn mirror_bits(mut x: u64) -> u64 {
+    const K1: u64 = 0x5555_5555_5555_5555;
+    const K2: u64 = 0x3333_3333_3333_3333;
+    const K4: u64 = 0x0f0f_0f0f_0f0f_0f0f;
+    x = ((x >> 1) & K1) | ((x & K1) << 1);
+    x = ((x >> 2) & K2) | ((x & K2) << 2);
+    ((x >> 4) & K4) | ((x & K4) << 4)
+}
+
+/// Swap row and column of every square of a bitboard
+const fn transpose_bits(mut x: u64) -> u64 {
+    const K1: u64 = 0x5500_5500_5500_5500;
+    const K2: u64 = 0x3333_0000_3333_0000;
+    const K4: u64 = 0x0f0f_0f0f_0000_0000;
+    let mut t = K4 & (x ^ (x << 28));
+    x ^= t ^ (t >> 28);
+    t = K2 & (x ^ (x << 14));
+    x ^= t ^ (t >> 14);
+    t = K1 & (x ^ (x << 7));
+    x ^ t ^ (t >> 7)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -242,6 +486,37 @@ mod tests {
         assert_eq!(positions, vec![0, 2, 5, 7]);
     }
 
+    #[test]
+    fn test_handicap() {
+        let board = Board::with_handicap(Player::White, 2);
+        assert_eq!(board.count(Player::White), 4);
+        assert_eq!(board.get_disc(pos(0, 0)), Some(Player::White)); // A1
+        assert_eq!(board.get_disc(pos(7, 7)), Some(Player::White)); // H8
+        assert_eq!(board.get_disc(pos(0, 7)), None); // H1
+
+        // Extra corners beyond four are ignored
+        let board = Board::with_handicap(Player::Black, 9);
+        assert_eq!(board.count(Player::Black), 6);
+        assert_eq!(Board::with_handicap(Player::Black, 0), Board::new());
+    }
+
+    #[test]
+    fn test_regions() {
+        let square = |name: &[u8]| 1u64 << crate::algebraic_to_pos(name).unwrap();
+        assert_eq!(CORNER_MASK, square(b"A1") | square(b"H1") | square(b"A8") | square(b"H8"));
+        assert_eq!(X_SQUARE_MASK, square(b"B2") | square(b"G2") | square(b"B7") | square(b"G7"));
+        assert_eq!(C_SQUARE_MASK.count_ones(), 8);
+        assert_eq!(C_SQUARE_MASK & !EDGE_MASK, 0);
+        assert_eq!(EDGE_MASK.count_ones(), 28);
+
+        let board = Board::with_handicap(Player::Black, 2);
+        assert_eq!(board.corners_owned(Player::Black), 2);
+        assert_eq!(board.corners_owned(Player::White), 0);
+        assert_eq!(board.edge_discs(Player::Black), square(b"A1") | square(b"H8"));
+        assert!(board.owns_square(CORNER_MASK, Player::Black));
+        assert!(!board.owns_square(X_SQUARE_MASK, Player::Black));
+    }
+
     #[test]
     fn test_get_disc() {
         let board = Board::new();
@@ -249,4 +524,131 @@ mod tests {
         assert_eq!(board.get_disc(pos(3, 4)), Some(Player::Black)); // E4
         assert_eq!(board.get_disc(pos(0, 0)), None); // A1 empty
     }
+
+    #[test]
+    fn test_symmetries() {
+        // Map a square by row and column, the slow way
+        let map = |board: &Board, f: fn(u8, u8) -> (u8, u8)| {
+            let mut out = Board::empty();
+            for p in 0..64 {
+                if let Some(player) = board.get_disc(p) {
+                    let (row, col) = f(p / 8, p % 8);
+                    out.place(player, pos(row, col));
+                }
+            }
+            out
+        };
+
+        let mut board = Board::empty();
+        for p in [0, 1, 2, 10, 19, 27, 45, 62] {
+            board.place(Player::Black, p);
+        }
+        for p in [5, 8, 33, 36, 50, 56] {
+            board.place(Player::White, p);
+        }
+
+        assert_eq!(board.flip_vertical(), map(&board, |r, c| (7 - r, c)));
+        assert_eq!(board.mirror_horizontal(), map(&board, |r, c| (r, 7 - c)));
+        assert_eq!(board.transpose(), map(&board, |r, c| (c, r)));
+        assert_eq!(board.rotate180(), map(&board, |r, c| (7 - r, 7 - c)));
+        assert_eq!(board.rotate90(), map(&board, |r, c| (c, 7 - r)));
+
+        // The eight symmetries are distinct for a lopsided board
+        let all = board.symmetries();
+        assert_eq!(all[0], board);
+        for (i, a) in all.iter().enumerate() {
+            assert!(all[i + 1..].iter().all(|b| a != b));
+        }
+        // The start position is symmetric about the A1-H8 diagonal
+        assert_eq!(Board::new().transpose(), Board::new());
+    }
+
+    #[test]
+    fn test_zobrist() {
+        // Test vectors, so a change to the keys is noticed
+        assert_eq!(ZOBRIST_KEYS[0][0], 0x760b_6b4e_6af1_10ce);
+        assert_eq!(ZOBRIST_KEYS[1][63], 0x13f8_762a_199e_a810);
+        assert_eq!(Board::new().zobrist(), 0xe1f2_5ede_3474_d916);
+        assert_eq!(Board::empty().zobrist(), 0);
+
+        // Keys update incrementally
+        let mut board = Board::new();
+        let mut key = board.zobrist();
+        board.place(Player::Black, pos(2, 3));
+        board.flip(Player::White, 1 << pos(3, 3));
+        key ^= Board::zobrist_key(Player::Black, pos(2, 3))
+            ^ Board::zobrist_key(Player::White, pos(3, 3))
+            ^ Board::zobrist_key(Player::Black, pos(3, 3));
+        assert_eq!(board.zobrist(), key);
+
+        // No key is zero and every pair XORs to a different value, so no
+        // four or fewer keys cancel out
+        let keys: Vec<u64> = ZOBRIST_KEYS.iter().flatten().copied().collect();
+        assert!(keys.iter().all(|&k| k != 0));
+        let mut pairs = std::collections::HashSet::new();
+        for (i, a) in keys.iter().enumerate() {
+            for b in &keys[i + 1..] {
+                assert!(pairs.insert(a ^ b));
+            }
+        }
+        for k in &keys {
+            assert!(!pairs.contains(k));
+        }
+    }
+
+    #[test]
+    fn test_zobrist_hooks() {
+        // Play a game out with the hooks, checking the kept key against a
+        // fresh one after every move and after taking each move back
+        let mut board = Board::new();
+        let mut key = board.zobrist();
+        let mut player = Player::Black;
+        for ply in 0..60 {
+            let moves = crate::moves::generate_moves(&board, player);
+            let Some(m) = moves.get(ply * 7 % moves.len().max(1)).copied() else {
+                player = player.opponent();
+                if crate::moves::generate_moves(&board, player).is_empty() {
+                    break;
+                }
+                continue;
+            };
+
+            let before = (board, key);
+            board.place_hashed(player, m.pos, &mut key);
+            board.flip_hashed(player.opponent(), m.flipped, &mut key);
+            assert_eq!(key, board.zobrist());
+
+            let mut undone = board;
+            let mut undone_key = key;
+            undone.remove_hashed(player, m.pos, &mut undone_key);
+            undone.flip_hashed(player, m.flipped, &mut undone_key);
+            assert_eq!((undone, undone_key), before);
+
+            player = player.opponent();
+        }
+        assert!(board.empty_count() < 60);
+
+        // Placing a disc that is there, or removing one that isn't,
+        // changes neither the board nor the key
+        let (start, mut key) = (Board::new(), Board::new().zobrist());
+        let mut board = start;
+        board.place_hashed(Player::Black, pos(3, 4), &mut key);
+        board.remove_hashed(Player::White, pos(3, 4), &mut key);
+        assert_eq!((board, key), (start, start.zobrist()));
+        assert_eq!(Board::zobrist_flip(0), 0);
+    }
+
+    #[test]
+    fn test_squares_and_discs() {
+        let board = Board::new();
+        let squares: Vec<_> = board.squares().collect();
+        assert_eq!(squares.len(), 64);
+        assert_eq!(squares[0], (0, None));
+        assert_eq!(squares[27], (27, Some(Player::White))); // D4
+        assert_eq!(squares[28], (28, Some(Player::Black))); // E4
+
+        assert_eq!(board.discs(Player::Black).collect::<Vec<_>>(), vec![28, 35]);
+        assert_eq!(board.discs(Player::White).collect::<Vec<_>>(), vec![27, 36]);
+        assert_eq!(Board::empty().discs(Player::Black).count(), 0);
+    }
 }
@@ -0,0 +1,220 @@
+//! Game clocks for timed play between two devices
+//!
+//! Each device times its own player's moves and sends the time with each
+//! move in a [`MoveStamp`]. The receiver has timed the same move from its
+//! side, from sending its own move to receiving the reply, which adds the
+//! network delay both ways. [`compensate`] settles the two: the mover's
+//! own time is trusted unless it is more than [`LAG_ALLOWANCE_MS`] short
+//! of what the receiver saw. The receiver sends the settled time back with
+//! its next move, and the mover corrects its clock with
+//! [`GameClock::settle`], so after every exchange both devices hold the
+//! same remaining times and agree on when a flag falls.
+//!
+//! The core has no clock, so every time here is milliseconds measured by
+//! the caller.
+
+use crate::Player;
+
+/// Time each side starts with and gains with each move
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeControl {
+    pub initial_ms: u32,
+    /// Added after each move made in time
+    pub increment_ms: u32,
+}
+
+impl TimeControl {
+    /// Three minutes and two seconds a move
+    pub const BLITZ: TimeControl = TimeControl::new(3 * 60_000, 2_000);
+
+    /// Ten minutes and five seconds a move
+    pub const RAPID: TimeControl = TimeControl::new(10 * 60_000, 5_000);
+
+    pub const fn new(initial_ms: u32, increment_ms: u32) -> Self {
+        Self { initial_ms, increment_ms }
+    }
+}
+
+/// Most network delay forgiven on one move
+///
+/// A device waiting on a remote move also waits this long past the
+/// mover's remaining time before claiming a flag fall.
+pub const LAG_ALLOWANCE_MS: u32 = 1_000;
+
+/// Times sent along with a move
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MoveStamp {
+    /// Number of moves played before this one, so a stamp can't be applied
+    /// to the wrong move
+    pub ply: u16,
+    /// Time the mover spent, by its own clock
+    pub think_ms: u32,
+    /// Time the sender settled on for the move before, the receiver's own
+    pub ack_ms: u32,
+}
+
+impl MoveStamp {
+    /// Length of a stamp in bytes
+    pub const LEN: usize = 10;
+
+    /// The stamp as little-endian bytes: ply, think time, then the ack
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        let mut bytes = [0; Self::LEN];
+        bytes[..2].copy_from_slice(&self.ply.to_le_bytes());
+        bytes[2..6].copy_from_slice(&self.think_ms.to_le_bytes());
+        bytes[6..].copy_from_slice(&self.ack_ms.to_le_bytes());
+        bytes
+    }
+
+    /// Read a stamp written by `to_bytes`
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let bytes: &[u8; Self::LEN] = bytes.get(..Self::LEN)?.try_into().ok()?;
+        Some(Self {
+            ply: u16::from_le_bytes([bytes[0], bytes[1]]),
+            think_ms: u32::from_le_bytes([bytes[2], bytes[3], bytes[4], bytes[5]]),
+            ack_ms: u32::from_le_bytes([bytes[6], bytes[7], bytes[8], bytes[9]]),
+        })
+    }
+}
+
+/// Time to charge a remote move
+///
+/// `think_ms` is the mover's own time and `interval_ms` the time the
+/// receiver waited for the move. The mover's time stands unless it is
+/// more than `LAG_ALLOWANCE_MS` short of the wait, and it can never be
+/// more than the wait.
+pub const fn compensate(think_ms: u32, interval_ms: u32) -> u32 {
+    let floor = interval_ms.saturating_sub(LAG_ALLOWANCE_MS);
+    if think_ms < floor {
+        floor
+    } else if think_ms > interval_ms {
+        interval_ms
+    } else {
+        think_ms
+    }
+}
+
+/// Remaining time for both sides
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameClock {
+    control: TimeControl,
+    /// Indexed by player, Black first
+    remaining: [u32; 2],
+    flagged: Option<Player>,
+}
+
+impl GameClock {
+    /// Both sides on the control's starting time
+    pub const fn new(control: TimeControl) -> Self {
+        Self { control, remaining: [control.initial_ms; 2], flagged: None }
+    }
+
+    pub const fn control(&self) -> TimeControl {
+        self.control
+    }
+
+    /// Time a side has left, not counting a move in progress
+    pub const fn remaining(&self, player: Player) -> u32 {
+        self.remaining[player as usize]
+    }
+
+    /// The side whose flag fell, if one has
+    pub const fn flagged(&self) -> Option<Player> {
+        self.flagged
+    }
+
+    /// Whether a side thinking for `thinking_ms` has run out of time
+    pub const fn out_of_time(&self, player: Player, thinking_ms: u32) -> bool {
+        thinking_ms >= self.remaining(player)
+    }
+
+    /// Charge a move's time to the side that made it
+    ///
+    /// A move made in time earns the increment. Returns false if the move
+    /// took the rest of the side's time, which leaves its flag fallen.
+    pub fn charge(&mut self, player: Player, ms: u32) -> bool {
+        let left = &mut self.remaining[player as usize];
+        if ms >= *left {
+            *left = 0;
+            self.flagged.get_or_insert(player);
+            return false;
+        }
+        *left = *left - ms + self.control.increment_ms;
+        true
+    }
+
+    /// Correct a move charged at `charged_ms` to the `settled_ms` the other
+    /// device acknowledged
+    ///
+    /// Returns false if the side's flag has fallen.
+    pub fn settle(&mut self, player: Player, charged_ms: u32, settled_ms: u32) -> bool {
+        if self.flagged == Some(player) {
+            return false;
+        }
+        let left = &mut self.remaining[player as usize];
+        if settled_ms >= *left + charged_ms {
+            *left = 0;
+            self.flagged.get_or_insert(player);
+            return false;
+        }
+        *left = *left + charged_ms - settled_ms;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compensate() {
+        // Up to the allowance of lag is forgiven
+        assert_eq!(compensate(4_000, 4_600), 4_000);
+        assert_eq!(compensate(4_000, 5_000), 4_000);
+        // Beyond it the mover pays for the rest
+        assert_eq!(compensate(4_000, 7_000), 6_000);
+        // A report longer than the wait is cut to it
+        assert_eq!(compensate(9_000, 7_000), 7_000);
+        assert_eq!(compensate(0, 500), 0);
+    }
+
+    #[test]
+    fn test_stamp_round_trip() {
+        let stamp = MoveStamp { ply: 17, think_ms: 12_345, ack_ms: 678 };
+        assert_eq!(MoveStamp::from_bytes(&stamp.to_bytes()), Some(stamp));
+        assert_eq!(MoveStamp::from_bytes(&stamp.to_bytes()[..9]), None);
+    }
+
+    #[test]
+    fn test_clocks_agree() {
+        // Black's device and White's device, 300 ms of lag each way
+        let control = TimeControl::new(10_000, 1_000);
+        let (mut black, mut white) = (GameClock::new(control), GameClock::new(control));
+        let lag = 300;
+
+        // Black thinks 2 s; White waits 2.6 s and forgives the lag
+        assert!(black.charge(Player::Black, 2_000));
+        let settled = compensate(2_000, 2_000 + 2 * lag);
+        assert!(white.charge(Player::Black, settled));
+        assert_eq!(black, white);
+
+        // Black reports only 1 s of a 4 s think; White charges 3.4 s and
+        // says so with its reply, which Black's clock takes on
+        assert!(black.charge(Player::Black, 1_000));
+        let settled = compensate(1_000, 4_000 + 2 * lag);
+        assert!(white.charge(Player::Black, settled));
+        assert!(black.settle(Player::Black, 1_000, settled));
+        assert_eq!(black, white);
+        assert_eq!(black.remaining(Player::Black), 10_000 - 2_000 - 3_600 + 2_000);
+
+        // Settling can bring a flag down
+        assert!(!black.settle(Player::Black, 0, 20_000));
+        assert_eq!(black.flagged(), Some(Player::Black));
+        assert_eq!(black.remaining(Player::Black), 0);
+
+        // White runs out thinking
+        assert!(white.out_of_time(Player::White, 10_000));
+        assert!(!white.charge(Player::White, 10_000));
+        assert_eq!(white.flagged(), Some(Player::White));
+    }
+}
@@ -0,0 +1,428 @@
+//! Interchangeable move choosers behind one trait
+//!
+//! An [`Engine`] picks a move for the side to move in a game, within a
+//! [`SearchLimits`]. The minimax search, a Monte Carlo tree search, a
+//! random mover and a book-only player all implement it, so the app and
+//! the self-play tooling can pit any two against each other without
+//! knowing which is which.
+//!
+//! None of these engines has a clock, so they leave `SearchLimits::time`
+//! to callers that do, such as `Searcher::best_move_timed`.
+
+use crate::{
+    Board, GameState, OpeningBook, Player, Position, RuleSet, SearchConfig, SearchLimits, Searcher,
+    SEARCHER_TABLE_ENTRIES, calculate_flips, legal_moves_bitboard,
+};
+
+/// The move an engine chose and the work it took
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchResult {
+    /// Move to play, `None` if the side to move has to pass
+    pub best: Option<Position>,
+    /// Positions searched or playouts run; 0 for engines that don't search
+    pub nodes: u64,
+}
+
+/// Something that chooses moves
+pub trait Engine {
+    /// Choose a move for the side to move in `game`
+    fn best_move(&mut self, game: &GameState, limits: &SearchLimits) -> SearchResult;
+
+    /// Short name for logs and reports
+    fn name(&self) -> &'static str;
+}
+
+/// The minimax search, keeping its `Searcher` from move to move
+#[derive(Debug, Clone)]
+pub struct Minimax<const N: usize = SEARCHER_TABLE_ENTRIES> {
+    searcher: Searcher<N>,
+    /// Options other than the limits and rules, which come with each move
+    config: SearchConfig,
+}
+
+impl<const N: usize> Minimax<N> {
+    /// Search with these options, such as `SearchConfig::new(difficulty)`
+    pub const fn new(config: SearchConfig) -> Self {
+        Self { searcher: Searcher::new(), config }
+    }
+
+    /// The searcher, for its statistics
+    pub const fn searcher(&self) -> &Searcher<N> {
+        &self.searcher
+    }
+}
+
+impl<const N: usize> Engine for Minimax<N> {
+    fn best_move(&mut self, game: &GameState, limits: &SearchLimits) -> SearchResult {
+        let config = self.config.with_limits(*limits).with_rules(*game.rules());
+        let before = self.searcher.stats().nodes;
+        let best = self.searcher.best_move(game.board(), game.current_player(), config);
+        SearchResult { best, nodes: self.searcher.stats().nodes - before }
+    }
+
+    fn name(&self) -> &'static str {
+        "minimax"
+    }
+}
+
+/// Deterministic xorshift generator, so seeded engines repeat their games
+#[derive(Debug, Clone, Copy)]
+struct Rng(u64);
+
+impl Rng {
+    const fn new(seed: u64) -> Self {
+        // Xorshift never leaves zero
+        Self(if seed == 0 { 0x9e37_79b9_7f4a_7c15 } else { seed })
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// A random square from a non-empty bitboard
+    fn pick(&mut self, bits: u64) -> Position {
+        let mut nth = self.next() % bits.count_ones() as u64;
+        let mut rest = bits;
+        while nth > 0 {
+            rest &= rest - 1;
+            nth -= 1;
+        }
+        rest.trailing_zeros() as Position
+    }
+}
+
+/// Plays a random legal move
+#[derive(Debug, Clone, Copy)]
+pub struct RandomMover {
+    rng: Rng,
+}
+
+impl RandomMover {
+    /// The same seed plays the same moves
+    pub const fn new(seed: u64) -> Self {
+        Self { rng: Rng::new(seed) }
+    }
+}
+
+impl Engine for RandomMover {
+    fn best_move(&mut self, game: &GameState, _limits: &SearchLimits) -> SearchResult {
+        let moves = legal_moves_bitboard(game.board(), game.current_player());
+        let best = (moves != 0).then(|| self.rng.pick(moves));
+        SearchResult { best, nodes: 0 }
+    }
+
+    fn name(&self) -> &'static str {
+        "random"
+    }
+}
+
+/// Plays only from an opening book
+///
+/// Out of book it plays the first legal move in square order, so games
+/// against it still finish; it is meant for testing books, not for play.
+#[derive(Debug, Clone, Copy)]
+pub struct BookPlayer {
+    book: OpeningBook<'static>,
+}
+
+impl BookPlayer {
+    /// Play from this book
+    pub const fn new(book: OpeningBook<'static>) -> Self {
+        Self { book }
+    }
+}
+
+impl Default for BookPlayer {
+    fn default() -> Self {
+        Self::new(OpeningBook::BUILTIN)
+    }
+}
+
+impl Engine for BookPlayer {
+    fn best_move(&mut self, game: &GameState, _limits: &SearchLimits) -> SearchResult {
+        let moves = legal_moves_bitboard(game.board(), game.current_player());
+        // The book holds standard play, so misère games never use it
+        let booked = if game.rules().misere {
+            None
+        } else {
+            self.book.lookup(game.board()).filter(|&pos| moves & (1 << pos) != 0)
+        };
+        let best = booked.or_else(|| Board::lowest_bit_pos(moves));
+        SearchResult { best, nodes: 0 }
+    }
+
+    fn name(&self) -> &'static str {
+        "book"
+    }
+}
+
+/// Playouts per move when the limits don't set a node count
+pub const MCTS_PLAYOUTS: u32 = 2000;
+
+/// Tree nodes a Monte Carlo search keeps by default, about 40 bytes each
+pub const MCTS_NODES: usize = 4096;
+
+/// Marks a tree node reached by a pass
+const PASS: Position = 255;
+
+/// Marks a missing node link
+const NONE: u32 = u32::MAX;
+
+/// One position in the Monte Carlo tree
+#[derive(Debug, Clone, Copy)]
+struct TreeNode {
+    board: Board,
+    /// Side to move here
+    to_move: Player,
+    /// Move that led here, `PASS` for a pass
+    pos: Position,
+    parent: u32,
+    /// Children sit next to each other from `first_child`
+    first_child: u32,
+    children: u8,
+    expanded: bool,
+    visits: u32,
+    /// Results for the side that moved into this node: 2 a win, 1 a draw
+    points: u32,
+}
+
+const EMPTY_NODE: TreeNode = TreeNode {
+    board: Board::empty(),
+    to_move: Player::Black,
+    pos: PASS,
+    parent: NONE,
+    first_child: NONE,
+    children: 0,
+    expanded: false,
+    visits: 0,
+    points: 0,
+};
+
+/// Monte Carlo tree search with random playouts
+///
+/// Each playout walks down the tree by UCB1, adds the children of the node
+/// it stops at, plays a random game from there and counts the result back
+/// up the path. The tree lives in a fixed arena of `N` nodes; once it
+/// fills, playouts carry on from the leaves. The move played is the most
+/// visited. `SearchLimits::nodes` sets the playouts, `MCTS_PLAYOUTS` if
+/// unset, and depth is not used. The arithmetic is all integer, so the
+/// same seed plays the same moves on every build.
+#[derive(Debug, Clone)]
+pub struct Mcts<const N: usize = MCTS_NODES> {
+    nodes: [TreeNode; N],
+    len: usize,
+    rng: Rng,
+}
+
+impl<const N: usize> Mcts<N> {
+    /// The same seed plays the same moves
+    pub const fn new(seed: u64) -> Self {
+        Self { nodes: [EMPTY_NODE; N], len: 0, rng: Rng::new(seed) }
+    }
+
+    fn push(&mut self, node: TreeNode) -> Option<u32> {
+        let slot = self.nodes.get_mut(self.len)?;
+        *slot = node;
+        self.len += 1;
+        Some(self.len as u32 - 1)
+    }
+
+    /// Child to follow by UCB1, unvisited children first
+    fn select(&self, index: u32) -> u32 {
+        let node = &self.nodes[index as usize];
+        // 2 ln(visits), scaled by a million under the square root, which
+        // puts the exploration term in thousandths like the win rate
+        let log = 2 * 693 * (node.visits.max(1).ilog2() as u64 + 1) * 1000;
+        let mut best = (0, node.first_child);
+        for child in node.first_child..node.first_child + node.children as u32 {
+            let c = &self.nodes[child as usize];
+            if c.visits == 0 {
+                return child;
+            }
+            let rate = c.points as u64 * 500 / c.visits as u64;
+            let value = rate + (log / c.visits as u64).isqrt();
+            if value > best.0 {
+                best = (value, child);
+            }
+        }
+        best.1
+    }
+
+    /// Add a node's children, or nothing if the game is over there or the
+    /// arena is full
+    fn expand(&mut self, index: u32) {
+        let node = self.nodes[index as usize];
+        self.nodes[index as usize].expanded = true;
+        let moves = legal_moves_bitboard(&node.board, node.to_move);
+        let next = node.to_move.opponent();
+        let first = self.len as u32;
+
+        let mut children = 0;
+        if moves == 0 {
+            if legal_moves_bitboard(&node.board, next) == 0 || self.len >= N {
+                return;
+            }
+            self.push(TreeNode { board: node.board, to_move: next, pos: PASS, parent: index, ..EMPTY_NODE });
+            children = 1;
+        } else {
+            if self.len + moves.count_ones() as usize > N {
+                return;
+            }
+            for pos in Board::iter_bits(moves) {
+                let mut board = node.board;
+                board.flip(next, calculate_flips(&node.board, node.to_move, pos));
+                board.place(node.to_move, pos);
+                self.push(TreeNode { board, to_move: next, pos, parent: index, ..EMPTY_NODE });
+                children += 1;
+            }
+        }
+        let node = &mut self.nodes[index as usize];
+        node.first_child = first;
+        node.children = children;
+    }
+
+    /// Winner of a random game from a position, `None` for a draw
+    fn playout(&mut self, mut board: Board, mut to_move: Player, rules: &RuleSet) -> Option<Player> {
+        let mut passes = 0;
+        while passes < 2 {
+            let moves = legal_moves_bitboard(&board, to_move);
+            if moves == 0 {
+                passes += 1;
+            } else {
+                passes = 0;
+                let pos = self.rng.pick(moves);
+                board.flip(to_move.opponent(), calculate_flips(&board, to_move, pos));
+                board.place(to_move, pos);
+            }
+            to_move = to_move.opponent();
+        }
+        let (black, white) = rules.scoring.final_counts(board.count(Player::Black), board.count(Player::White));
+        rules.winner(black, white)
+    }
+}
+
+impl<const N: usize> Engine for Mcts<N> {
+    fn best_move(&mut self, game: &GameState, limits: &SearchLimits) -> SearchResult {
+        let (board, player) = (*game.board(), game.current_player());
+        let moves = legal_moves_bitboard(&board, player);
+        if moves.count_ones() <= 1 {
+            return SearchResult { best: Board::lowest_bit_pos(moves), nodes: 0 };
+        }
+
+        self.len = 0;
+        self.push(TreeNode { board, to_move: player, ..EMPTY_NODE });
+        let playouts = limits.nodes.unwrap_or(MCTS_PLAYOUTS);
+        for _ in 0..playouts {
+            let mut index = 0;
+            while self.nodes[index as usize].expanded && self.nodes[index as usize].children > 0 {
+                index = self.select(index);
+            }
+            if !self.nodes[index as usize].expanded {
+                self.expand(index);
+            }
+
+            let leaf = self.nodes[index as usize];
+            let winner = self.playout(leaf.board, leaf.to_move, game.rules());
+            while index != NONE {
+                let node = &mut self.nodes[index as usize];
+                node.visits += 1;
+                // The side that moved into the node
+                node.points += match winner {
+                    Some(side) if side != node.to_move => 2,
+                    Some(_) => 0,
+                    None => 1,
+                };
+                index = node.parent;
+            }
+        }
+
+        // Most visited, the first in square order on a tie
+        let root = self.nodes[0];
+        let mut best = None;
+        for child in &self.nodes[root.first_child as usize..(root.first_child + root.children as u32) as usize] {
+            if best.is_none_or(|(visits, _)| child.visits > visits) {
+                best = Some((child.visits, child.pos));
+            }
+        }
+        SearchResult { best: best.map(|(_, pos)| pos), nodes: playouts as u64 }
+    }
+
+    fn name(&self) -> &'static str {
+        "mcts"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Difficulty;
+
+    /// Play a game between two engines, returning it finished
+    fn play(black: &mut dyn Engine, white: &mut dyn Engine, limits: &SearchLimits) -> GameState {
+        let mut game = GameState::new();
+        while !game.is_game_over() {
+            let result = match game.current_player() {
+                Player::Black => black.best_move(&game, limits),
+                Player::White => white.best_move(&game, limits),
+            };
+            match result.best {
+                Some(pos) => assert!(game.make_move(pos).is_some(), "illegal move {pos}"),
+                None => assert!(game.pass()),
+            }
+        }
+        game
+    }
+
+    #[test]
+    fn test_engines_play_legal_games() {
+        let limits = SearchLimits::depth(2).with_nodes(200);
+        let mut engines: [&mut dyn Engine; 4] = [
+            &mut Minimax::<256>::new(SearchConfig::new(Difficulty::Easy)),
+            &mut Mcts::<1024>::new(1),
+            &mut RandomMover::new(2),
+            &mut BookPlayer::default(),
+        ];
+        for first in 0..engines.len() {
+            let second = (first + 1) % engines.len();
+            let [a, b] = engines.get_disjoint_mut([first, second]).unwrap();
+            assert!(play(*a, *b, &limits).result().is_some());
+        }
+    }
+
+    #[test]
+    fn test_engine_choices() {
+        let game = crate::parse_transcript("D3 C5 C6 C3 C4 B5 A6 E3 E6 B7 F4 F5").unwrap();
+        let limits = Difficulty::Medium.limits();
+
+        // Minimax plays what the free function plays
+        let mut minimax = Minimax::<256>::new(SearchConfig::new(Difficulty::Medium));
+        let result = minimax.best_move(&game, &limits);
+        assert_eq!(result.best, crate::find_best_move(game.board(), Player::Black, Difficulty::Medium));
+        assert!(result.nodes > 0);
+
+        // Seeded engines repeat themselves
+        let mcts = Mcts::<1024>::new(7).best_move(&game, &limits.with_nodes(300));
+        assert_eq!(mcts, Mcts::<1024>::new(7).best_move(&game, &limits.with_nodes(300)));
+        assert_eq!(mcts.nodes, 300);
+        assert_eq!(RandomMover::new(3).best_move(&game, &limits), RandomMover::new(3).best_move(&game, &limits));
+
+        // The book player follows the book, then the first legal move
+        let d3 = crate::parse_transcript("D3").unwrap();
+        let booked = BookPlayer::default().best_move(&d3, &limits).best;
+        assert_eq!(booked, OpeningBook::BUILTIN.lookup(d3.board()));
+        let first = Board::lowest_bit_pos(legal_moves_bitboard(game.board(), Player::Black));
+        assert_eq!(BookPlayer::default().best_move(&game, &limits).best, first);
+    }
+
+    #[test]
+    fn test_mcts_beats_random() {
+        let limits = SearchLimits::depth(1).with_nodes(400);
+        for seed in 1..4 {
+            let game = play(&mut Mcts::<2048>::new(seed), &mut RandomMover::new(seed), &limits);
+            assert_eq!(game.result().unwrap().winner(), Some(Player::Black), "seed {seed}");
+        }
+    }
+}
@@ -6,7 +6,7 @@
 //! - Frontier discs
 //! - Disc count (weighted by game phase)
 
-use crate::{Board, Player, Position};
+use crate::{Board, Player, Position, CORNER_MASK};
 use crate::moves::count_moves;
 
 /// Evaluation score (positive = good for player, negative = bad)
@@ -17,9 +17,6 @@ pub const SCORE_WIN: Score = 100_000;
 /// Minimum possible score (losing position)
 pub const SCORE_LOSS: Score = -100_000;
 
-/// Corner positions (A1, H1, A8, H8)
-const CORNERS: [Position; 4] = [0, 7, 56, 63];
-
 /// X-squares (diagonal to corners, dangerous when corner empty)
 const X_SQUARES: [(Position, Position); 4] = [
     (9, 0),   // B2 -> A1
@@ -61,7 +58,7 @@ fn neighbor_mask(pos: Position) -> u64 {
             }
             let nr = row as i8 + dr;
             let nc = col as i8 + dc;
-            if nr >= 0 && nr < 8 && nc >= 0 && nc < 8 {
+            if (0..8).contains(&nr) && (0..8).contains(&nc) {
                 mask |= 1u64 << (nr as u8 * 8 + nc as u8);
             }
         }
@@ -70,21 +67,41 @@ fn neighbor_mask(pos: Position) -> u64 {
     mask
 }
 
-/// Count frontier discs (discs adjacent to empty squares)
-fn count_frontier(board: &Board, player: Player) -> u32 {
-    let own = board.get(player);
+/// Bitboard of a player's frontier discs (discs adjacent to empty squares)
+pub fn frontier_bitboard(board: &Board, player: Player) -> u64 {
     let empty = board.empty_squares();
-    let mut frontier = 0;
+    let mut frontier = 0u64;
 
-    for pos in Board::iter_bits(own) {
+    for pos in Board::iter_bits(board.get(player)) {
         if (neighbor_mask(pos) & empty) != 0 {
-            frontier += 1;
+            frontier |= 1u64 << pos;
         }
     }
 
     frontier
 }
 
+/// Count frontier discs (discs adjacent to empty squares)
+pub fn count_frontier(board: &Board, player: Player) -> u32 {
+    frontier_bitboard(board, player).count_ones()
+}
+
+/// Bitboard of a player's potential mobility: empty squares next to an
+/// opponent disc, where moves may open up later
+pub fn potential_mobility_bitboard(board: &Board, player: Player) -> u64 {
+    const NOT_A: u64 = 0xFEFE_FEFE_FEFE_FEFE;
+    const NOT_H: u64 = 0x7F7F_7F7F_7F7F_7F7F;
+
+    let opp = board.get(player.opponent());
+    let row = opp | ((opp << 1) & NOT_A) | ((opp >> 1) & NOT_H);
+    (row | (row << 8) | (row >> 8)) & board.empty_squares()
+}
+
+/// Count a player's potential mobility
+pub fn potential_mobility(board: &Board, player: Player) -> u32 {
+    potential_mobility_bitboard(board, player).count_ones()
+}
+
 /// Evaluate corner and X/C-square control
 fn evaluate_corners(board: &Board, player: Player) -> Score {
     let own = board.get(player);
@@ -92,14 +109,7 @@ fn evaluate_corners(board: &Board, player: Player) -> Score {
     let mut score = 0;
 
     // Corner control: very valuable
-    for corner in CORNERS {
-        let mask = 1u64 << corner;
-        if (own & mask) != 0 {
-            score += 100;
-        } else if (opp & mask) != 0 {
-            score -= 100;
-        }
-    }
+    score += (board.corners_owned(player) as Score - board.corners_owned(player.opponent()) as Score) * 100;
 
     // X-squares: dangerous when adjacent corner is empty
     for (x_sq, corner) in X_SQUARES {
@@ -141,6 +151,11 @@ fn evaluate_mobility(board: &Board, player: Player) -> Score {
     (own_moves - opp_moves) * 3
 }
 
+/// Evaluate potential mobility (more is better), an optional term
+pub fn evaluate_potential_mobility(board: &Board, player: Player) -> Score {
+    potential_mobility(board, player) as Score - potential_mobility(board, player.opponent()) as Score
+}
+
 /// Evaluate frontier discs (fewer is better)
 fn evaluate_frontier(board: &Board, player: Player) -> Score {
     let own_frontier = count_frontier(board, player) as Score;
@@ -170,18 +185,15 @@ fn evaluate_disc_count(board: &Board, player: Player) -> Score {
     (own - opp) * weight
 }
 
-/// Count stable discs (cannot be flipped)
-/// Simplified version: only counts corner-anchored stable discs
-fn count_stable_discs(board: &Board, player: Player) -> u32 {
+/// Bitboard of a player's stable discs (discs that can never be flipped)
+///
+/// Simplified: only corners, and discs on full edges that hold one of
+/// their own corners, are found.
+pub fn stable_bitboard(board: &Board, player: Player) -> u64 {
     let own = board.get(player);
-    let mut stable = 0u64;
 
     // Discs in corners are always stable
-    for corner in CORNERS {
-        if (own & (1u64 << corner)) != 0 {
-            stable |= 1u64 << corner;
-        }
-    }
+    let mut stable = own & CORNER_MASK;
 
     // Expand from corners along filled edges
     // This is a simplified version - full stability is complex
@@ -206,7 +218,12 @@ fn count_stable_discs(board: &Board, player: Player) -> u32 {
         }
     }
 
-    stable.count_ones()
+    stable
+}
+
+/// Count stable discs, as found by [`stable_bitboard`]
+pub fn count_stable_discs(board: &Board, player: Player) -> u32 {
+    stable_bitboard(board, player).count_ones()
 }
 
 /// Evaluate edge stability
@@ -252,6 +269,21 @@ pub fn evaluate(board: &Board, player: Player) -> Score {
     score
 }
 
+/// Evaluate the canonical form of the position
+///
+/// Gives the same score for all eight symmetric versions of a board by
+/// construction, so evaluations built from square patterns can rely on it.
+pub fn evaluate_canonical(board: &Board, player: Player) -> Score {
+    evaluate(&board.canonical(), player)
+}
+
+/// Whether `evaluate` scores all eight symmetric versions of a position
+/// the same, as it should
+pub fn evaluation_is_symmetric(board: &Board, player: Player) -> bool {
+    let score = evaluate(board, player);
+    board.symmetries().iter().all(|turned| evaluate(turned, player) == score)
+}
+
 /// Quick evaluation for move ordering
 /// Faster but less accurate than full evaluation
 #[allow(dead_code)]
@@ -259,17 +291,7 @@ pub fn quick_evaluate(board: &Board, player: Player) -> Score {
     let mut score = 0;
 
     // Just corners and mobility
-    let own = board.get(player);
-    let opp = board.get(player.opponent());
-
-    for corner in CORNERS {
-        let mask = 1u64 << corner;
-        if (own & mask) != 0 {
-            score += 100;
-        } else if (opp & mask) != 0 {
-            score -= 100;
-        }
-    }
+    score += (board.corners_owned(player) as Score - board.corners_owned(player.opponent()) as Score) * 100;
 
     let own_moves = count_moves(board, player) as Score;
     let opp_moves = count_moves(board, player.opponent()) as Score;
@@ -281,6 +303,31 @@ pub fn quick_evaluate(board: &Board, player: Player) -> Score {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::GameState;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    /// Positions from random games, every fourth move of each
+    fn random_positions(seed: u64, games: usize) -> Vec<Board> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut positions = Vec::new();
+        for _ in 0..games {
+            let mut game = GameState::new();
+            while !game.is_game_over() {
+                let moves = game.legal_moves();
+                if moves.is_empty() {
+                    game.pass();
+                    continue;
+                }
+                let pick = moves.get(rng.gen_range(0..moves.len())).unwrap();
+                game.make_move(pick.pos);
+                if game.move_count() % 4 == 0 || game.is_game_over() {
+                    positions.push(*game.board());
+                }
+            }
+        }
+        positions
+    }
 
     #[test]
     fn test_starting_position_neutral() {
@@ -359,6 +406,12 @@ mod tests {
 
         let stable = count_stable_discs(&board, Player::Black);
         assert_eq!(stable, 8); // All 8 discs on top edge are stable
+        assert_eq!(stable_bitboard(&board, Player::Black), 0xFF);
+
+        // Without a full edge only the corners are stable
+        board.remove(Player::Black, 3);
+        assert_eq!(stable_bitboard(&board, Player::Black), 0x81);
+        assert_eq!(stable_bitboard(&board, Player::White), 0);
     }
 
     #[test]
@@ -372,4 +425,75 @@ mod tests {
         assert_eq!(black_frontier, 2);
         assert_eq!(white_frontier, 2);
     }
+
+    #[test]
+    fn test_frontier_bitboard() {
+        let board = Board::new();
+        assert_eq!(frontier_bitboard(&board, Player::Black), board.black);
+        assert_eq!(frontier_bitboard(&board, Player::White), board.white);
+
+        // A disc boxed in on every side is not on the frontier
+        let mut board = Board::empty();
+        for row in 0..3 {
+            for col in 0..3 {
+                board.place(Player::Black, crate::pos(row, col));
+            }
+        }
+        let frontier = frontier_bitboard(&board, Player::Black);
+        assert_eq!(frontier & (1u64 << crate::pos(1, 1)), 0);
+        assert_eq!(frontier & (1u64 << crate::pos(0, 0)), 0);
+        assert_eq!(frontier.count_ones(), 5);
+        assert_eq!(count_frontier(&board, Player::Black), 5);
+    }
+
+    #[test]
+    fn test_potential_mobility() {
+        // Five empty squares around each white disc, none shared
+        let board = Board::new();
+        assert_eq!(potential_mobility(&board, Player::Black), 10);
+        assert_eq!(evaluate_potential_mobility(&board, Player::Black), 0);
+
+        // Same squares as walking each disc's neighbours
+        for board in random_positions(0x907e, 5) {
+            for player in [Player::Black, Player::White] {
+                let slow = Board::iter_bits(board.get(player.opponent()))
+                    .fold(0, |bits, pos| bits | neighbor_mask(pos))
+                    & board.empty_squares();
+                assert_eq!(potential_mobility_bitboard(&board, player), slow);
+            }
+        }
+    }
+
+    #[test]
+    fn test_evaluation_symmetric() {
+        for board in random_positions(0x5e_77e7, 40) {
+            for player in [Player::Black, Player::White] {
+                assert!(evaluation_is_symmetric(&board, player), "{}", board.to_diagram(None));
+
+                // Each part agrees on its own, to point at the one that breaks
+                for turned in board.symmetries() {
+                    assert_eq!(evaluate_corners(&turned, player), evaluate_corners(&board, player));
+                    assert_eq!(evaluate_mobility(&turned, player), evaluate_mobility(&board, player));
+                    assert_eq!(evaluate_frontier(&turned, player), evaluate_frontier(&board, player));
+                    assert_eq!(
+                        evaluate_potential_mobility(&turned, player),
+                        evaluate_potential_mobility(&board, player)
+                    );
+                    assert_eq!(evaluate_disc_count(&turned, player), evaluate_disc_count(&board, player));
+                    assert_eq!(evaluate_stability(&turned, player), evaluate_stability(&board, player));
+                    assert_eq!(quick_evaluate(&turned, player), quick_evaluate(&board, player));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_evaluate_canonical() {
+        for board in random_positions(0xca_0017, 10) {
+            let canonical = board.canonical();
+            assert!(board.symmetries().iter().all(|turned| turned.canonical() == canonical));
+            assert_eq!(evaluate_canonical(&board, Player::Black), evaluate(&board, Player::Black));
+        }
+        assert_eq!(Board::new().canonical().count(Player::Black), 2);
+    }
 }
@@ -1,6 +1,6 @@
 //! Game state management with full history tracking
 
-use crate::{Board, Move, MoveList, Player, Position};
+use crate::{Board, Move, MoveList, PassPolicy, Player, Position, RuleSet};
 use crate::moves::{calculate_flips, count_moves, generate_moves};
 
 /// Maximum number of moves in a game (theoretical max is 60)
@@ -13,6 +13,8 @@ pub enum GameResult {
     Win(Player, u32, u32),
     /// Draw with equal disc counts
     Draw(u32),
+    /// Player resigned, with disc counts (black, white) at resignation
+    Resigned(Player, u32, u32),
 }
 
 impl GameResult {
@@ -21,6 +23,15 @@ impl GameResult {
         match self {
             GameResult::Win(player, _, _) => Some(*player),
             GameResult::Draw(_) => None,
+            GameResult::Resigned(player, _, _) => Some(player.opponent()),
+        }
+    }
+
+    /// Get the player who resigned, if the game ended by resignation
+    pub const fn resigned(&self) -> Option<Player> {
+        match self {
+            GameResult::Resigned(player, _, _) => Some(*player),
+            _ => None,
         }
     }
 
@@ -30,6 +41,7 @@ impl GameResult {
             GameResult::Win(Player::Black, b, w) => (*b, *w),
             GameResult::Win(Player::White, b, w) => (*b, *w),
             GameResult::Draw(c) => (*c, *c),
+            GameResult::Resigned(_, b, w) => (*b, *w),
         }
     }
 }
@@ -52,6 +64,16 @@ impl HistoryEntry {
     }
 }
 
+/// A point in a game to come back to, from `GameState::savepoint`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Savepoint {
+    board: Board,
+    current_player: Player,
+    history_len: usize,
+    consecutive_passes: u8,
+    resigned: Option<Player>,
+}
+
 /// Complete game state with history
 #[derive(Debug, Clone)]
 pub struct GameState {
@@ -63,8 +85,18 @@ pub struct GameState {
     history: [HistoryEntry; MAX_MOVES],
     /// Number of moves in history
     history_len: usize,
+    /// End of undone moves kept for redo (>= history_len)
+    redo_len: usize,
     /// Consecutive passes (2 = game over)
     consecutive_passes: u8,
+    /// Player who resigned, if any
+    resigned: Option<Player>,
+    /// Position the game started from
+    start: Board,
+    /// Player to move at the start
+    start_player: Player,
+    /// Rules the game is played under
+    rules: RuleSet,
 }
 
 impl Default for GameState {
@@ -76,17 +108,14 @@ impl Default for GameState {
 impl GameState {
     /// Create a new game with standard starting position
     pub fn new() -> Self {
-        Self {
-            board: Board::new(),
-            current_player: Player::Black,
-            history: [HistoryEntry {
-                pos: 0,
-                flipped: 0,
-                player: Player::Black,
-            }; MAX_MOVES],
-            history_len: 0,
-            consecutive_passes: 0,
-        }
+        Self::from_board(Board::new(), Player::Black)
+    }
+
+    /// Create a new game under a rule set, from its start position
+    pub fn with_rules(rules: RuleSet) -> Self {
+        let mut game = Self::from_board(rules.start.board(), Player::Black);
+        game.rules = rules;
+        game
     }
 
     /// Create a game from a specific board position
@@ -100,10 +129,38 @@ impl GameState {
                 player: Player::Black,
             }; MAX_MOVES],
             history_len: 0,
+            redo_len: 0,
             consecutive_passes: 0,
+            resigned: None,
+            start: board,
+            start_player: current_player,
+            rules: RuleSet::STANDARD,
         }
     }
 
+    /// Get the position the game started from
+    pub const fn start_board(&self) -> &Board {
+        &self.start
+    }
+
+    /// Get the player who moved first
+    pub const fn start_player(&self) -> Player {
+        self.start_player
+    }
+
+    /// Get the rules the game is played under
+    pub const fn rules(&self) -> &RuleSet {
+        &self.rules
+    }
+
+    /// Play on under different rules
+    ///
+    /// The start position only applies to games made by `with_rules`; the
+    /// rest take effect from the next move.
+    pub fn set_rules(&mut self, rules: RuleSet) {
+        self.rules = rules;
+    }
+
     /// Get the current board
     pub const fn board(&self) -> &Board {
         &self.board
@@ -124,6 +181,29 @@ impl GameState {
         &self.history[..self.history_len]
     }
 
+    /// Encode the history at one byte per move: the square, or 255 for a
+    /// pass
+    ///
+    /// Flips aren't stored; `decode_history` finds them again by replaying
+    /// from the start position.
+    pub fn encode_history(&self) -> impl Iterator<Item = u8> + '_ {
+        self.history().iter().map(|entry| entry.pos)
+    }
+
+    /// Rebuild a game from its start and `encode_history` bytes
+    ///
+    /// Returns None if a move is illegal or a pass isn't forced.
+    pub fn decode_history(start: Board, start_player: Player, bytes: &[u8]) -> Option<Self> {
+        let mut game = Self::from_board(start, start_player);
+        for &pos in bytes {
+            let played = if pos == 255 { game.pass() } else { game.make_move(pos).is_some() };
+            if !played {
+                return None;
+            }
+        }
+        Some(game)
+    }
+
     /// Get the last move made, if any
     pub fn last_move(&self) -> Option<&HistoryEntry> {
         if self.history_len > 0 {
@@ -151,7 +231,7 @@ impl GameState {
     /// Check if the game is over
     pub fn is_game_over(&self) -> bool {
         // Game ends when both players must pass consecutively
-        self.consecutive_passes >= 2 || self.board.is_full()
+        self.resigned.is_some() || self.consecutive_passes >= 2 || self.board.is_full()
     }
 
     /// Get the game result (only valid when game is over)
@@ -163,12 +243,15 @@ impl GameState {
         let black = self.board.count(Player::Black);
         let white = self.board.count(Player::White);
 
-        Some(if black > white {
-            GameResult::Win(Player::Black, black, white)
-        } else if white > black {
-            GameResult::Win(Player::White, black, white)
-        } else {
-            GameResult::Draw(black)
+        // A resignation records the board as it stood
+        if let Some(player) = self.resigned {
+            return Some(GameResult::Resigned(player, black, white));
+        }
+        let (black, white) = self.rules.scoring.final_counts(black, white);
+
+        Some(match self.rules.winner(black, white) {
+            Some(player) => GameResult::Win(player, black, white),
+            None => GameResult::Draw(black),
         })
     }
 
@@ -192,7 +275,7 @@ impl GameState {
         // Flip opponent discs
         self.board.flip(self.current_player.opponent(), flipped);
 
-        // Record in history
+        // Record in history (a new move discards any redo line)
         if self.history_len < MAX_MOVES {
             self.history[self.history_len] = HistoryEntry {
                 pos,
@@ -201,6 +284,7 @@ impl GameState {
             };
             self.history_len += 1;
         }
+        self.redo_len = self.history_len;
 
         // Reset consecutive passes
         self.consecutive_passes = 0;
@@ -208,9 +292,24 @@ impl GameState {
         // Switch player
         self.current_player = self.current_player.opponent();
 
+        // Pass for a side left without a move, twice at the end of the game
+        if self.rules.pass == PassPolicy::Automatic {
+            while !self.is_game_over() && self.pass() {}
+        }
+
         Some(Move::new(pos, flipped))
     }
 
+    /// Make a move, reporting whether the next side then had to pass
+    ///
+    /// Passes are only made under `PassPolicy::Automatic`; otherwise the
+    /// flag is always false and the caller passes as before.
+    pub fn play(&mut self, pos: Position) -> Option<(Move, bool)> {
+        let before = self.history_len;
+        let played = self.make_move(pos)?;
+        Some((played, self.history_len > before + 1))
+    }
+
     /// Pass the turn (when no legal moves)
     ///
     /// Returns true if the pass was valid
@@ -229,6 +328,7 @@ impl GameState {
             };
             self.history_len += 1;
         }
+        self.redo_len = self.history_len;
 
         self.consecutive_passes += 1;
         self.current_player = self.current_player.opponent();
@@ -236,6 +336,22 @@ impl GameState {
         true
     }
 
+    /// Resign the game on behalf of a player
+    ///
+    /// Returns false if the game is already over
+    pub fn resign(&mut self, player: Player) -> bool {
+        if self.is_game_over() {
+            return false;
+        }
+        self.resigned = Some(player);
+        true
+    }
+
+    /// Get the player who resigned, if any
+    pub const fn resigned(&self) -> Option<Player> {
+        self.resigned
+    }
+
     /// Undo the last move
     ///
     /// Returns the undone move or None if no history
@@ -246,6 +362,7 @@ impl GameState {
 
         self.history_len -= 1;
         let entry = self.history[self.history_len];
+        self.resigned = None;
 
         if entry.is_pass() {
             // Undo pass
@@ -262,6 +379,66 @@ impl GameState {
         Some(entry)
     }
 
+    /// Mark the current position to roll back to
+    ///
+    /// Cheaper than a clone, so a line can be tried out on the game itself.
+    pub const fn savepoint(&self) -> Savepoint {
+        Savepoint {
+            board: self.board,
+            current_player: self.current_player,
+            history_len: self.history_len,
+            consecutive_passes: self.consecutive_passes,
+            resigned: self.resigned,
+        }
+    }
+
+    /// Return to a savepoint taken from this game
+    ///
+    /// Moves played since are dropped without being kept for redo. The
+    /// savepoint must not be older than a move since undone.
+    pub fn rollback(&mut self, savepoint: Savepoint) {
+        self.board = savepoint.board;
+        self.current_player = savepoint.current_player;
+        self.history_len = savepoint.history_len;
+        self.redo_len = savepoint.history_len;
+        self.consecutive_passes = savepoint.consecutive_passes;
+        self.resigned = savepoint.resigned;
+    }
+
+    /// Get the undone moves that can be redone, next one first
+    pub fn redo_history(&self) -> &[HistoryEntry] {
+        &self.history[self.history_len..self.redo_len]
+    }
+
+    /// Check if there is an undone move to redo
+    pub const fn can_redo(&self) -> bool {
+        self.history_len < self.redo_len
+    }
+
+    /// Redo the most recently undone move
+    ///
+    /// Returns the redone move or None if nothing was undone
+    pub fn redo(&mut self) -> Option<HistoryEntry> {
+        if !self.can_redo() {
+            return None;
+        }
+
+        let entry = self.history[self.history_len];
+        self.history_len += 1;
+
+        if entry.is_pass() {
+            self.consecutive_passes += 1;
+        } else {
+            self.board.place(entry.player, entry.pos);
+            self.board.flip(entry.player.opponent(), entry.flipped);
+            self.consecutive_passes = 0;
+        }
+
+        self.current_player = entry.player.opponent();
+
+        Some(entry)
+    }
+
     /// Get disc counts (black, white)
     pub fn counts(&self) -> (u32, u32) {
         (
@@ -277,7 +454,8 @@ impl GameState {
 
     /// Clone the game state at a specific move in history
     pub fn clone_at_move(&self, move_index: usize) -> Self {
-        let mut game = Self::new();
+        let mut game = Self::from_board(self.start, self.start_player);
+        game.rules = self.rules;
 
         for entry in &self.history[..move_index.min(self.history_len)] {
             if entry.is_pass() {
@@ -295,6 +473,21 @@ impl GameState {
         self.clone_at_move(move_index).board
     }
 
+    /// Iterate over the board before the first move and after each move
+    ///
+    /// Replays the history once, so walking a whole game is cheaper than
+    /// calling [`board_at_move`](Self::board_at_move) for every index.
+    pub fn boards(&self) -> impl Iterator<Item = Board> + '_ {
+        let start = core::iter::once(self.start);
+        start.chain(self.history().iter().scan(self.start, |board, entry| {
+            if !entry.is_pass() {
+                board.place(entry.player, entry.pos);
+                board.flip(entry.player.opponent(), entry.flipped);
+            }
+            Some(*board)
+        }))
+    }
+
     /// Get mobility (legal move count) for a player
     pub fn mobility(&self, player: Player) -> u32 {
         count_moves(&self.board, player)
@@ -309,7 +502,7 @@ impl GameState {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::pos;
+    use crate::{pos, Scoring};
 
     #[test]
     fn test_new_game() {
@@ -394,12 +587,29 @@ mod tests {
         assert_eq!(clone.current_player(), Player::White);
     }
 
+    #[test]
+    fn test_clone_at_move_from_board() {
+        // A1 given to White before the game starts
+        let mut board = Board::new();
+        board.place(Player::White, pos(0, 0));
+        let mut game = GameState::from_board(board, Player::Black);
+        game.make_move(pos(2, 3)); // D3
+        game.make_move(pos(2, 2)); // C3
+
+        // Replays start from the handicap position, not the standard one
+        let clone = game.clone_at_move(0);
+        assert_eq!(*clone.board(), board);
+        assert_eq!(game.board_at_move(2), *game.board());
+        assert_eq!(*game.start_board(), board);
+        assert_eq!(game.start_player(), Player::Black);
+    }
+
     #[test]
     fn test_pass() {
         // Create a position where white must pass
         let mut board = Board::empty();
         // Fill most of the board with black, leaving white with no moves
-        board.black = u64::MAX & !0xFF; // All but first row
+        board.black = !0xFF; // All but first row
         board.white = 0x01; // Single white disc at A1
 
         let mut game = GameState::from_board(board, Player::White);
@@ -426,6 +636,153 @@ mod tests {
         assert!(matches!(result, GameResult::Draw(32)));
     }
 
+    #[test]
+    fn test_empties_to_winner() {
+        // Black wipes White out after nine moves, leaving 51 squares empty
+        let mut game = crate::parse_transcript("E6 F4 E3 F6 G5 D6 E7 F5 C5").unwrap();
+        assert!(game.pass() && game.pass());
+        assert_eq!(game.result(), Some(GameResult::Win(Player::Black, 13, 0)));
+
+        let empties = RuleSet { scoring: Scoring::EmptiesToWinner, ..RuleSet::STANDARD };
+        game.set_rules(empties);
+        assert_eq!(game.result(), Some(GameResult::Win(Player::Black, 64, 0)));
+        assert_eq!(game.clone_at_move(11).result(), game.result());
+
+        // Misère hands the same board to White
+        game.set_rules(RuleSet { misere: true, ..RuleSet::STANDARD });
+        assert_eq!(game.result(), Some(GameResult::Win(Player::White, 13, 0)));
+
+        // A resignation keeps the board count
+        let mut game = GameState::with_rules(empties);
+        game.resign(Player::Black);
+        assert_eq!(game.result(), Some(GameResult::Resigned(Player::Black, 2, 2)));
+    }
+
+    #[test]
+    fn test_automatic_pass() {
+        let rules = RuleSet { pass: PassPolicy::Automatic, ..RuleSet::STANDARD };
+        let mut game = GameState::with_rules(rules);
+        for square in ["E6", "F4", "E3", "F6", "G5", "D6", "E7", "F5"] {
+            game.make_move(crate::algebraic_to_pos(square.as_bytes()).unwrap());
+        }
+        assert!(!game.is_game_over());
+
+        // The wipeout leaves neither side a move, and both passes are played
+        let (_, passed) = game.play(crate::algebraic_to_pos(b"C5").unwrap()).unwrap();
+        assert!(passed && game.is_game_over());
+        assert_eq!(game.move_count(), 11);
+        assert!(game.last_move().unwrap().is_pass());
+
+        // Undo takes the passes back one at a time
+        game.undo();
+        game.undo();
+        assert_eq!(game.current_player(), Player::White);
+        assert_eq!(game.move_count(), 9);
+
+        // Explicit passing leaves them to the caller
+        let mut game = crate::parse_transcript("E6 F4 E3 F6 G5 D6 E7 F5").unwrap();
+        assert!(!game.play(crate::algebraic_to_pos(b"C5").unwrap()).unwrap().1);
+        assert!(!game.is_game_over() && !game.has_moves());
+    }
+
+    #[test]
+    fn test_redo() {
+        let mut game = GameState::new();
+        game.make_move(pos(2, 3)); // D3
+        game.make_move(pos(2, 2)); // C3
+        let board = *game.board();
+
+        // Nothing to redo until something is undone
+        assert!(!game.can_redo());
+        assert!(game.redo().is_none());
+
+        game.undo();
+        game.undo();
+        assert_eq!(game.redo_history().len(), 2);
+        assert_eq!(game.redo_history()[0].pos, pos(2, 3));
+
+        // Redo restores the exact position
+        assert_eq!(game.redo().unwrap().pos, pos(2, 3));
+        assert_eq!(game.redo().unwrap().pos, pos(2, 2));
+        assert_eq!(*game.board(), board);
+        assert_eq!(game.current_player(), Player::Black);
+        assert_eq!(game.move_count(), 2);
+        assert!(!game.can_redo());
+
+        // A new move discards the redo line
+        game.undo();
+        game.make_move(pos(4, 2)); // C5
+        assert!(!game.can_redo());
+    }
+
+    #[test]
+    fn test_resign() {
+        let mut game = GameState::new();
+        game.make_move(pos(2, 3)); // D3
+
+        assert!(game.resign(Player::White));
+        assert!(game.is_game_over());
+
+        // Black leads 4-1 but the result is the resignation
+        let result = game.result().unwrap();
+        assert_eq!(result, GameResult::Resigned(Player::White, 4, 1));
+        assert_eq!(result.winner(), Some(Player::Black));
+        assert_eq!(result.resigned(), Some(Player::White));
+        assert_eq!(result.counts(), (4, 1));
+
+        // Can't resign twice
+        assert!(!game.resign(Player::Black));
+    }
+
+    #[test]
+    fn test_savepoint_rollback() {
+        let mut game = crate::parse_transcript("F5 D6 C3").unwrap();
+        let before = game.clone();
+        let savepoint = game.savepoint();
+
+        // Try a line that ends the game, then come back
+        for square in ["D3", "C4", "F4"] {
+            game.make_move(crate::algebraic_to_pos(square.as_bytes()).unwrap());
+        }
+        game.resign(Player::White);
+        game.rollback(savepoint);
+
+        assert_eq!(*game.board(), *before.board());
+        assert_eq!(game.current_player(), before.current_player());
+        assert_eq!(game.history().len(), 3);
+        assert!(!game.is_game_over() && !game.can_redo());
+        assert_eq!(game.legal_moves().as_bitboard(), before.legal_moves().as_bitboard());
+
+        // Pass counters come back too
+        let mut game = crate::parse_transcript("E6 F4 E3 F6 G5 D6 E7 F5 C5").unwrap();
+        assert!(game.pass());
+        let savepoint = game.savepoint();
+        assert!(game.pass() && game.is_game_over());
+        game.rollback(savepoint);
+        assert!(!game.is_game_over());
+        assert_eq!(game.move_count(), 10);
+    }
+
+    #[test]
+    fn test_history_encoding() {
+        let mut game = crate::parse_transcript("E6 F4 E3 F6 G5 D6 E7 F5 C5").unwrap();
+        assert!(game.pass() && game.pass());
+        let bytes: Vec<u8> = game.encode_history().collect();
+        assert_eq!(bytes.len(), 11);
+        assert_eq!(&bytes[9..], &[255, 255]);
+
+        let decoded = GameState::decode_history(Board::new(), Player::Black, &bytes).unwrap();
+        assert_eq!(*decoded.board(), *game.board());
+        assert!(decoded.is_game_over());
+        for (a, b) in decoded.history().iter().zip(game.history()) {
+            assert_eq!((a.pos, a.flipped, a.player), (b.pos, b.flipped, b.player));
+        }
+
+        // Illegal moves and unforced passes are refused
+        assert!(GameState::decode_history(Board::new(), Player::Black, &[0]).is_none());
+        assert!(GameState::decode_history(Board::new(), Player::Black, &[255]).is_none());
+    }
+
     #[test]
     fn test_history() {
         let mut game = GameState::new();
@@ -450,4 +807,18 @@ mod tests {
         let last = game.last_move().unwrap();
         assert_eq!(last.pos, pos(2, 3));
     }
+
+    #[test]
+    fn test_boards_match_board_at_move() {
+        let mut game = GameState::new();
+        game.make_move(pos(2, 3));
+        game.make_move(pos(2, 2));
+        game.make_move(pos(3, 2));
+
+        let boards: Vec<Board> = game.boards().collect();
+        assert_eq!(boards.len(), game.move_count() + 1);
+        for (index, board) in boards.iter().enumerate() {
+            assert_eq!(*board, game.board_at_move(index));
+        }
+    }
 }
@@ -5,24 +5,51 @@
 //! - Move generation and validation
 //! - Game state management with full history
 //! - AI with multiple difficulty levels
+//! - Move-by-move review of finished games
 //!
 //! This library is `no_std` compatible (disable default features).
 
-#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
 mod board;
 mod moves;
 mod game;
+mod rules;
+mod clock;
+mod protocol;
 mod eval;
 mod ai;
+mod engine;
 mod opening;
+mod notation;
+mod review;
+mod vectors;
+#[cfg(feature = "std")]
+mod trace;
+#[cfg(any(test, feature = "testsupport"))]
+mod reference;
 
-pub use board::{Board, Player};
+pub use board::{Board, Player, CORNER_MASK, EDGE_MASK, X_SQUARE_MASK, C_SQUARE_MASK, HANDICAP_CORNERS, ZOBRIST_KEYS, ZOBRIST_SEED};
 pub use moves::{Move, MoveList, count_moves, legal_moves_bitboard, calculate_flips};
-pub use game::{GameState, GameResult};
-pub use eval::evaluate;
-pub use ai::{Difficulty, find_best_move, get_hint};
-pub use opening::OpeningBook;
+pub use game::{GameState, GameResult, HistoryEntry, Savepoint};
+pub use rules::{RuleSet, Scoring, PassPolicy, StartPosition};
+pub use clock::{TimeControl, GameClock, MoveStamp, LAG_ALLOWANCE_MS, compensate};
+pub use protocol::{Hello, Session, Mismatch, Message, ProtocolError, Frame, Chat, CannedChat, ChatText, PROTOCOL_VERSION, MIN_PROTOCOL_VERSION, PROTOCOL_MAGIC, CAP_CLOCK, CAP_CHAT, CAPABILITIES, MAX_FRAME, MAX_CHAT};
+pub use eval::{evaluate, evaluate_canonical, evaluation_is_symmetric, count_frontier, frontier_bitboard, count_stable_discs, stable_bitboard, potential_mobility, potential_mobility_bitboard, evaluate_potential_mobility, Score};
+pub use ai::{Difficulty, SearchLimits, Searcher, SearchStats, SearchMethod, SearchReport, SEARCHER_TABLE_ENTRIES, HINT_LIMITS, BookPolicy, SearchConfig, Deadline, NodeEnd, BOOK_VERIFY_DEPTH, BOOK_VERIFY_MARGIN, find_best_move_with, find_best_move_timed, MoveScore, Analysis, Variation, MAX_PV, GENTLE_SLACK, BLUNDER_DEPTH, BLUNDER_MARGIN, AdaptiveLevel, Blunder, find_best_move, find_best_move_cancellable, find_gentle_move, find_adaptive_move, find_blunder, analyze_moves, analyze_moves_cancellable, analyze_at_depth, get_hint};
+pub use engine::{Engine, SearchResult, Minimax, Mcts, RandomMover, BookPlayer, MCTS_NODES, MCTS_PLAYOUTS};
+pub use opening::{OpeningBook, BookError, BOOK_MAGIC, BOOK_VERSION, LearnedBook, LEARNED_MAGIC, LEARNED_VERSION, LEARNED_BOOK_ENTRIES, LEARN_PLIES, NAMED_OPENINGS, identify_opening};
+#[cfg(feature = "std")]
+pub use opening::encode_book;
+pub use notation::{Diagram, TranscriptError, TranscriptFault, POSITION_LEN, parse_position, format_position, parse_transcript, replay_transcript};
+#[cfg(feature = "std")]
+pub use ai::find_best_move_parallel;
+#[cfg(feature = "std")]
+pub use trace::{SearchTree, TraceNode, trace_search};
+#[cfg(any(test, feature = "testsupport"))]
+pub use reference::ReferenceGame;
+pub use vectors::{SearchVector, SEARCH_VECTORS};
+pub use review::{GameReview, Mistake, Swing, Judgement, Annotation, ACCURATE_MARGIN, MISTAKE_MARGIN, REVIEW_VERSION};
 
 /// Position on the board (0-63)
 pub type Position = u8;
@@ -4,25 +4,26 @@
 //! directional shift operations for all 8 directions.
 
 use crate::{Board, Player, Position};
+use crate::eval::potential_mobility_bitboard;
 
 /// Direction shifts for move generation
 /// Each tuple: (shift amount, mask to avoid wraparound)
 const DIRECTIONS: [(i8, u64); 8] = [
     // Horizontal and vertical
-    (1, 0xfefefefefefefe),   // Right (not H file)
-    (-1, 0x7f7f7f7f7f7f7f7f), // Left (not A file)
-    (8, u64::MAX),           // Down
-    (-8, u64::MAX),          // Up
+    (1, 0x7f7f7f7f7f7f7f7f),  // Right (not H file)
+    (-1, 0xfefefefefefefefe), // Left (not A file)
+    (8, u64::MAX),            // Down
+    (-8, u64::MAX),           // Up
     // Diagonals
-    (9, 0xfefefefefefefe),   // Down-right
-    (7, 0x7f7f7f7f7f7f7f7f), // Down-left
-    (-7, 0xfefefefefefefe),  // Up-right
-    (-9, 0x7f7f7f7f7f7f7f7f), // Up-left
+    (9, 0x7f7f7f7f7f7f7f7f),  // Down-right
+    (7, 0xfefefefefefefefe),  // Down-left
+    (-7, 0x7f7f7f7f7f7f7f7f), // Up-right
+    (-9, 0xfefefefefefefefe), // Up-left
 ];
 
 /// Shift a bitboard in a direction
 #[inline]
-fn shift(bits: u64, dir: i8, mask: u64) -> u64 {
+const fn shift(bits: u64, dir: i8, mask: u64) -> u64 {
     let masked = bits & mask;
     if dir > 0 {
         masked << dir
@@ -31,6 +32,29 @@ fn shift(bits: u64, dir: i8, mask: u64) -> u64 {
     }
 }
 
+/// Squares from each square to the edge of the board in each direction,
+/// not counting the square itself, in the order of [`DIRECTIONS`]
+const RAYS: [[u64; 8]; 64] = rays();
+
+const fn rays() -> [[u64; 8]; 64] {
+    let mut rays = [[0u64; 8]; 64];
+    let mut pos = 0;
+    while pos < 64 {
+        let mut d = 0;
+        while d < 8 {
+            let (dir, mask) = DIRECTIONS[d];
+            let mut bit = shift(1u64 << pos, dir, mask);
+            while bit != 0 {
+                rays[pos][d] |= bit;
+                bit = shift(bit, dir, mask);
+            }
+            d += 1;
+        }
+        pos += 1;
+    }
+    rays
+}
+
 /// A single move with position and what it flips
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Move {
@@ -111,6 +135,50 @@ impl MoveList {
         self.moves[..self.len].iter()
     }
 
+    /// Sort moves by a key, smallest first, keeping equal keys in order
+    ///
+    /// `key` runs once per move, so it may be expensive.
+    pub fn sort_by_key<K: Ord + Copy>(&mut self, mut key: impl FnMut(&Move) -> K) {
+        let mut keys: [Option<K>; 32] = [None; 32];
+        for (slot, m) in keys.iter_mut().zip(self.iter()) {
+            *slot = Some(key(m));
+        }
+
+        // Insertion sort; there are never more than 32 moves
+        for i in 1..self.len {
+            let mut j = i;
+            while j > 0 && keys[j - 1] > keys[j] {
+                keys.swap(j - 1, j);
+                self.moves.swap(j - 1, j);
+                j -= 1;
+            }
+        }
+    }
+
+    /// Keep only the moves `keep` accepts, in their current order
+    pub fn retain(&mut self, mut keep: impl FnMut(&Move) -> bool) {
+        let mut kept = 0;
+        for i in 0..self.len {
+            if keep(&self.moves[i]) {
+                self.moves[kept] = self.moves[i];
+                kept += 1;
+            }
+        }
+        self.len = kept;
+    }
+
+    /// The move with the highest key, the earliest one on ties
+    pub fn best_by<K: Ord>(&self, mut key: impl FnMut(&Move) -> K) -> Option<&Move> {
+        let mut best: Option<(&Move, K)> = None;
+        for m in self.iter() {
+            let k = key(m);
+            if best.as_ref().is_none_or(|(_, top)| k > *top) {
+                best = Some((m, k));
+            }
+        }
+        best.map(|(m, _)| m)
+    }
+
     /// Get the valid move positions as a bitboard
     pub fn as_bitboard(&self) -> u64 {
         let mut bits = 0u64;
@@ -129,22 +197,25 @@ pub fn calculate_flips(board: &Board, player: Player, pos: Position) -> u64 {
 
     let own = board.get(player);
     let opp = board.get(player.opponent());
-    let pos_bit = 1u64 << pos;
     let mut flipped = 0u64;
 
-    for &(dir, mask) in &DIRECTIONS {
-        let mut candidates = 0u64;
-        let mut current = shift(pos_bit, dir, mask);
-
-        // Walk along opponent discs
-        while (current & opp) != 0 {
-            candidates |= current;
-            current = shift(current, dir, mask);
+    for (&(dir, _), &ray) in DIRECTIONS.iter().zip(&RAYS[pos as usize]) {
+        // The first square along the ray without an opponent disc ends the run
+        let stops = ray & !opp;
+        if stops == 0 {
+            continue;
         }
+        let (stop, run) = if dir > 0 {
+            let stop = stops & stops.wrapping_neg();
+            (stop, ray & (stop - 1))
+        } else {
+            let stop = 1u64 << (63 - stops.leading_zeros());
+            (stop, ray & !(stop | (stop - 1)))
+        };
 
-        // If we hit our own disc, the candidates are flipped
-        if (current & own) != 0 {
-            flipped |= candidates;
+        // If the run ends on our own disc, it is flipped
+        if (stop & own) != 0 {
+            flipped |= run;
         }
     }
 
@@ -154,14 +225,9 @@ pub fn calculate_flips(board: &Board, player: Player, pos: Position) -> u64 {
 /// Generate all legal moves for a player
 pub fn generate_moves(board: &Board, player: Player) -> MoveList {
     let mut moves = MoveList::new();
-    let empty = board.empty_squares();
 
-    // Check each empty square
-    for pos in Board::iter_bits(empty) {
-        let flipped = calculate_flips(board, player, pos);
-        if flipped != 0 {
-            moves.push(Move::new(pos, flipped));
-        }
+    for pos in Board::iter_bits(legal_moves_bitboard(board, player)) {
+        moves.push(Move::new(pos, calculate_flips(board, player, pos)));
     }
 
     moves
@@ -169,35 +235,40 @@ pub fn generate_moves(board: &Board, player: Player) -> MoveList {
 
 /// Get a quick count of legal moves without generating the full list
 pub fn count_moves(board: &Board, player: Player) -> u32 {
-    let empty = board.empty_squares();
-    let mut count = 0;
-
-    for pos in Board::iter_bits(empty) {
-        if calculate_flips(board, player, pos) != 0 {
-            count += 1;
-        }
-    }
-
-    count
+    legal_moves_bitboard(board, player).count_ones()
 }
 
 /// Check if a specific move is legal
+///
+/// Only squares next to an opponent disc are candidates, so the rest are
+/// turned away before any flips are traced.
 pub fn is_legal_move(board: &Board, player: Player, pos: Position) -> bool {
-    if board.is_occupied(pos) {
+    if potential_mobility_bitboard(board, player) & (1u64 << pos) == 0 {
         return false;
     }
     calculate_flips(board, player, pos) != 0
 }
 
 /// Get all legal move positions as a bitboard (for highlighting)
+///
+/// Works on every square at once: in each direction, runs of opponent
+/// discs next to our discs are grown one step at a time, and an empty
+/// square just past a run is a legal move. A run is at most six discs
+/// long, so this is a fixed 56 shifts however full the board is. Squares
+/// away from the opponent's discs are never reached, so there is no
+/// candidate list to keep up to date.
 pub fn legal_moves_bitboard(board: &Board, player: Player) -> u64 {
+    let own = board.get(player);
+    let opp = board.get(player.opponent());
     let empty = board.empty_squares();
     let mut legal = 0u64;
 
-    for pos in Board::iter_bits(empty) {
-        if calculate_flips(board, player, pos) != 0 {
-            legal |= 1u64 << pos;
+    for &(dir, mask) in &DIRECTIONS {
+        let mut run = shift(own, dir, mask) & opp;
+        for _ in 0..5 {
+            run |= shift(run, dir, mask) & opp;
         }
+        legal |= shift(run, dir, mask) & empty;
     }
 
     legal
@@ -270,6 +341,32 @@ mod tests {
         assert_eq!(flipped, 0); // Not on the right diagonal
     }
 
+    #[test]
+    fn test_edge_flips() {
+        // Flips that start on the A and H files must not be masked off
+        let mut board = Board::empty();
+        board.place(Player::White, pos(0, 1)); // B1
+        board.place(Player::Black, pos(0, 2)); // C1
+        board.place(Player::White, pos(1, 1)); // B2
+        board.place(Player::Black, pos(2, 2)); // C3
+        let flipped = calculate_flips(&board, Player::Black, pos(0, 0)); // A1
+        assert_eq!(flipped, (1u64 << pos(0, 1)) | (1u64 << pos(1, 1)));
+
+        let mut board = Board::empty();
+        board.place(Player::White, pos(7, 6)); // G8
+        board.place(Player::Black, pos(7, 5)); // F8
+        board.place(Player::White, pos(6, 6)); // G7
+        board.place(Player::Black, pos(5, 5)); // F6
+        let flipped = calculate_flips(&board, Player::Black, pos(7, 7)); // H8
+        assert_eq!(flipped, (1u64 << pos(7, 6)) | (1u64 << pos(6, 6)));
+
+        // No wrapping from the H file onto the next rank
+        let mut board = Board::empty();
+        board.place(Player::White, pos(1, 0)); // A2
+        board.place(Player::Black, pos(1, 1)); // B2
+        assert_eq!(calculate_flips(&board, Player::Black, pos(0, 7)), 0); // H1
+    }
+
     #[test]
     fn test_legal_moves_bitboard() {
         let board = Board::new();
@@ -307,4 +404,98 @@ mod tests {
         assert_eq!(list.get(1).unwrap().pos, 1);
         assert!(list.get(2).is_none());
     }
+
+    #[test]
+    fn test_move_list_sort_and_filter() {
+        let mut list = MoveList::new();
+        for (p, flips) in [(10, 0b1), (20, 0b111), (30, 0b11), (40, 0b111)] {
+            list.push(Move::new(p, flips));
+        }
+
+        // Ties keep their order
+        list.sort_by_key(|m| core::cmp::Reverse(m.flip_count()));
+        assert!(list.iter().map(|m| m.pos).eq([20, 40, 30, 10]));
+
+        assert_eq!(list.best_by(|m| m.flip_count()).unwrap().pos, 20);
+        assert_eq!(list.best_by(|m| m.pos).unwrap().pos, 40);
+
+        list.retain(|m| m.flip_count() > 1);
+        assert!(list.iter().map(|m| m.pos).eq([20, 40, 30]));
+        list.retain(|_| false);
+        assert!(list.is_empty());
+        assert!(list.best_by(|m| m.pos).is_none());
+    }
+
+    #[test]
+    fn test_legal_moves_match_flips() {
+        // Play out a game, checking every position square by square
+        let mut board = Board::new();
+        let mut player = Player::Black;
+        let mut seed = 12345u32;
+        for _ in 0..80 {
+            let slow = Board::iter_bits(board.empty_squares())
+                .filter(|&p| calculate_flips(&board, player, p) != 0)
+                .fold(0u64, |bits, p| bits | 1 << p);
+            assert_eq!(legal_moves_bitboard(&board, player), slow);
+            assert_eq!(count_moves(&board, player), slow.count_ones());
+            assert_eq!(generate_moves(&board, player).as_bitboard(), slow);
+            assert_eq!(slow & !potential_mobility_bitboard(&board, player), 0);
+            for p in 0..64 {
+                assert_eq!(is_legal_move(&board, player, p), slow & (1 << p) != 0);
+            }
+
+            let moves = generate_moves(&board, player);
+            if moves.is_empty() && count_moves(&board, player.opponent()) == 0 {
+                break;
+            }
+            if let Some(m) = moves.get(seed as usize % moves.len().max(1)) {
+                board.place(player, m.pos);
+                board.flip(player.opponent(), m.flipped);
+            }
+            player = player.opponent();
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345) >> 1;
+        }
+        assert!(board.empty_count() < 50);
+    }
+
+    #[test]
+    fn test_flips_match_ray_walk() {
+        // Step one square at a time, as the ray tables replace
+        let walk = |board: &Board, player: Player, pos: Position| {
+            let own = board.get(player);
+            let opp = board.get(player.opponent());
+            let mut flipped = 0u64;
+            for &(dir, mask) in &DIRECTIONS {
+                let mut run = 0u64;
+                let mut current = shift(1u64 << pos, dir, mask);
+                while (current & opp) != 0 {
+                    run |= current;
+                    current = shift(current, dir, mask);
+                }
+                if (current & own) != 0 {
+                    flipped |= run;
+                }
+            }
+            flipped
+        };
+
+        // Boards filled pseudo-randomly, so runs reach every edge
+        let mut seed = 0x2545_f491u64;
+        for _ in 0..200 {
+            let mut board = Board::empty();
+            for p in 0..64 {
+                seed = seed.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+                match seed >> 62 {
+                    0 | 1 => board.place(Player::White, p),
+                    2 => board.place(Player::Black, p),
+                    _ => {}
+                }
+            }
+            for p in Board::iter_bits(board.empty_squares()) {
+                for player in [Player::Black, Player::White] {
+                    assert_eq!(calculate_flips(&board, player, p), walk(&board, player, p));
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,287 @@
+//! Text notation for positions and move transcripts
+//!
+//! A position is 64 squares from A1 to H8, rank by rank, followed by the
+//! side to move:
+//!
+//! ```text
+//! ---------------------------OX------XO---------------------------X
+//! ```
+//!
+//! `X` (or `*`, `B`) is a black disc, `O` (or `W`) white, and `-` (or `.`,
+//! `_`) empty. Whitespace is ignored. A transcript is a list of moves from
+//! the starting position, like `F5 D6 C3` or `f5d6c3`; passes are implied.
+
+use core::fmt;
+
+use crate::{Board, GameState, Player, algebraic_to_pos, legal_moves_bitboard};
+
+/// Length of a position string
+pub const POSITION_LEN: usize = 65;
+
+/// Parse a position string, returning the board and side to move
+pub fn parse_position(s: &str) -> Option<(Board, Player)> {
+    let mut board = Board::empty();
+    let mut squares = s.bytes().filter(|c| !c.is_ascii_whitespace());
+
+    for p in 0..64 {
+        match squares.next()? {
+            b'X' | b'x' | b'*' | b'B' | b'b' => board.place(Player::Black, p),
+            b'O' | b'o' | b'W' | b'w' => board.place(Player::White, p),
+            b'-' | b'.' | b'_' => {}
+            _ => return None,
+        }
+    }
+    let to_move = match squares.next()? {
+        b'X' | b'x' | b'*' | b'B' | b'b' => Player::Black,
+        b'O' | b'o' | b'W' | b'w' => Player::White,
+        _ => return None,
+    };
+
+    if squares.next().is_some() {
+        return None;
+    }
+    Some((board, to_move))
+}
+
+/// Write a position as a string
+pub fn format_position(board: &Board, to_move: Player) -> [u8; POSITION_LEN] {
+    let mut out = [b'-'; POSITION_LEN];
+    for (p, disc) in board.squares() {
+        match disc {
+            Some(Player::Black) => out[p as usize] = b'X',
+            Some(Player::White) => out[p as usize] = b'O',
+            None => {}
+        }
+    }
+    out[64] = if to_move == Player::Black { b'X' } else { b'O' };
+    out
+}
+
+/// An ASCII picture of a board, from [`Board::to_diagram`]
+///
+/// ```text
+///   A B C D E F G H
+/// 1 - - - - - - - -
+/// 2 - - - - - - - -
+/// 3 - - - * - - - -
+/// 4 - - * O X - - -
+/// 5 - - - X O * - -
+/// 6 - - - - * - - -
+/// 7 - - - - - - - -
+/// 8 - - - - - - - -
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Diagram {
+    board: Board,
+    /// Squares marked `*`
+    marks: u64,
+}
+
+impl fmt::Display for Diagram {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("  A B C D E F G H")?;
+        for (p, disc) in self.board.squares() {
+            if p % 8 == 0 {
+                write!(f, "\n{}", p / 8 + 1)?;
+            }
+            let c = match disc {
+                Some(Player::Black) => 'X',
+                Some(Player::White) => 'O',
+                None if self.marks & (1 << p) != 0 => '*',
+                None => '-',
+            };
+            write!(f, " {}", c)?;
+        }
+        Ok(())
+    }
+}
+
+impl Board {
+    /// Draw the board as text, marking `player`'s legal moves with `*`
+    pub fn to_diagram(&self, player: Option<Player>) -> Diagram {
+        let marks = player.map_or(0, |player| legal_moves_bitboard(self, player));
+        Diagram { board: *self, marks }
+    }
+}
+
+/// Why a transcript move could not be played
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranscriptFault {
+    /// Not a square like F5 or a pass written `--`
+    BadNotation,
+    /// The square already holds a disc
+    Occupied,
+    /// The square is empty but the move flips nothing, or the game is over
+    Illegal,
+}
+
+/// The transcript move that could not be played
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TranscriptError<'a> {
+    /// Index of the move in the transcript, counting written passes
+    pub index: usize,
+    /// The move as written
+    pub token: &'a str,
+    pub fault: TranscriptFault,
+}
+
+impl GameState {
+    /// Play a move transcript on from the current position
+    ///
+    /// Moves may be run together or separated by anything that isn't a
+    /// letter, digit or `-`. Passes are made as needed, and written passes
+    /// (`--`) are accepted but not needed. On an error the game is left as
+    /// it was.
+    pub fn apply_transcript<'a>(&mut self, s: &'a str) -> Result<(), TranscriptError<'a>> {
+        let mut game = self.clone();
+        let mut index = 0;
+        let mut first = None;
+
+        let chars = s.char_indices().filter(|(_, c)| c.is_ascii_alphanumeric() || *c == '-');
+        for (at, c) in chars {
+            let Some(start) = first.take() else {
+                first = Some(at);
+                continue;
+            };
+            let token = &s[start..at + c.len_utf8()];
+            let fault = |fault| TranscriptError { index, token, fault };
+
+            // Explicit passes are accepted but not needed
+            let pair = [s.as_bytes()[start], c as u8];
+            if pair == *b"--" {
+                index += 1;
+                continue;
+            }
+            let p = algebraic_to_pos(&pair).ok_or(fault(TranscriptFault::BadNotation))?;
+            if !game.has_moves() {
+                game.pass();
+            }
+            if game.board().is_occupied(p) {
+                return Err(fault(TranscriptFault::Occupied));
+            }
+            game.make_move(p).ok_or(fault(TranscriptFault::Illegal))?;
+            index += 1;
+        }
+
+        if let Some(start) = first {
+            return Err(TranscriptError { index, token: s[start..].trim_end(), fault: TranscriptFault::BadNotation });
+        }
+        *self = game;
+        Ok(())
+    }
+}
+
+/// Replay a move transcript from the starting position
+///
+/// Returns None if any move is malformed or illegal.
+pub fn parse_transcript(s: &str) -> Option<GameState> {
+    replay_transcript(GameState::new(), s)
+}
+
+/// Replay a move transcript on top of an existing game
+///
+/// Used for games that began from a handicap or set-up position. Returns
+/// None if any move is malformed or illegal.
+pub fn replay_transcript(mut game: GameState, s: &str) -> Option<GameState> {
+    game.apply_transcript(s).ok()?;
+    Some(game)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pos;
+
+    #[test]
+    fn test_position_round_trip() {
+        let text = format_position(&Board::new(), Player::Black);
+        let text = core::str::from_utf8(&text).unwrap();
+        assert_eq!(text, "---------------------------OX------XO---------------------------X");
+        assert_eq!(parse_position(text), Some((Board::new(), Player::Black)));
+
+        // Alternative symbols and whitespace
+        let spaced = "........ ........ ........ ...wb... ...bw... ........ ........ ........ w";
+        assert_eq!(parse_position(spaced), Some((Board::new(), Player::White)));
+    }
+
+    #[test]
+    fn test_diagram() {
+        let board = Board::new();
+        let marked = board.to_diagram(Some(Player::Black)).to_string();
+        assert_eq!(
+            marked,
+            "  A B C D E F G H\n\
+             1 - - - - - - - -\n\
+             2 - - - - - - - -\n\
+             3 - - - * - - - -\n\
+             4 - - * O X - - -\n\
+             5 - - - X O * - -\n\
+             6 - - - - * - - -\n\
+             7 - - - - - - - -\n\
+             8 - - - - - - - -"
+        );
+        let plain = board.to_diagram(None).to_string();
+        assert_eq!(plain.matches('*').count(), 0);
+        assert_eq!(plain.lines().count(), 9);
+    }
+
+    #[test]
+    fn test_bad_position() {
+        assert!(parse_position("").is_none());
+        assert!(parse_position(&"-".repeat(64)).is_none()); // No side to move
+        assert!(parse_position(&format!("{}X-", "-".repeat(64))).is_none()); // Too long
+        assert!(parse_position(&format!("{}?{}X", "-".repeat(10), "-".repeat(53))).is_none());
+    }
+
+    #[test]
+    fn test_transcript() {
+        let game = parse_transcript("F5 d6 C3").unwrap();
+        assert_eq!(game.move_count(), 3);
+        assert_eq!(game.history()[0].pos, pos(4, 5));
+        assert_eq!(game.current_player(), Player::White);
+        assert_eq!(parse_transcript("f5d6c3").unwrap().board(), game.board());
+
+        assert!(parse_transcript("").unwrap().move_count() == 0);
+        assert!(parse_transcript("F5 F5").is_none()); // Occupied
+        assert!(parse_transcript("A1").is_none()); // Illegal
+        assert!(parse_transcript("F5 D").is_none()); // Incomplete
+    }
+
+    #[test]
+    fn test_replay_transcript() {
+        // Black starts with the A1 corner
+        let mut board = Board::new();
+        board.place(Player::Black, pos(0, 0));
+        let start = GameState::from_board(board, Player::Black);
+
+        let game = replay_transcript(start.clone(), "F5 -- d6").unwrap();
+        assert_eq!(game.move_count(), 2);
+        assert_ne!(game.board().get(Player::Black) & (1 << pos(0, 0)), 0);
+        assert!(replay_transcript(start, "F5 F5").is_none());
+    }
+
+    #[test]
+    fn test_transcript_errors() {
+        let error = |s| GameState::new().apply_transcript(s).unwrap_err();
+
+        let e = error("F5 D6 F5");
+        assert_eq!((e.index, e.token, e.fault), (2, "F5", TranscriptFault::Occupied));
+        let e = error("f5 -- d6 a1");
+        assert_eq!((e.index, e.token, e.fault), (3, "a1", TranscriptFault::Illegal));
+        let e = error("F5 Z9 D6");
+        assert_eq!((e.index, e.token, e.fault), (1, "Z9", TranscriptFault::BadNotation));
+        let e = error("f5d6c");
+        assert_eq!((e.index, e.token, e.fault), (2, "c", TranscriptFault::BadNotation));
+        let e = error("F 5 D 6 D 6");
+        assert_eq!((e.index, e.token, e.fault), (2, "D 6", TranscriptFault::Occupied));
+
+        // A failed transcript leaves the game untouched
+        let mut game = parse_transcript("F5 D6").unwrap();
+        let before = game.clone();
+        assert!(game.apply_transcript("C3 A1").is_err());
+        assert_eq!(game.board(), before.board());
+        assert_eq!(game.move_count(), 2);
+        assert_eq!(game.apply_transcript("C3 D3"), Ok(()));
+        assert_eq!(game.move_count(), 4);
+    }
+}
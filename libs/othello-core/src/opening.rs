@@ -1,108 +1,529 @@
 //! Opening book for Othello
 //!
 //! Contains known good opening moves for Expert difficulty.
-//! Uses board hash to quickly lookup positions.
+//! Uses board hash to quickly lookup positions; see [`OpeningBook`] for
+//! the binary format books are stored in. A [`LearnedBook`] adds moves
+//! learned from finished games.
 
-use crate::{Board, Position};
+use crate::{Board, GameState, Player, Position, algebraic_to_pos, pos, pos_to_rc};
 
-/// Opening book with hash-based lookup
-pub struct OpeningBook;
+/// Named openings, as transcripts from the standard start beginning F5
+///
+/// Statistics are kept by index, so new names go at the end.
+pub const NAMED_OPENINGS: &[(&str, &str)] = &[
+    ("Perpendicular", "F5 D6"),
+    ("Diagonal", "F5 F6"),
+    ("Parallel", "F5 F4"),
+    ("Cow", "F5 D6 C5"),
+    ("Rabbit", "F5 D6 C5 F4 E3"),
+    ("Tiger", "F5 D6 C3 D3 C4"),
+    ("Buffalo", "F5 D6 C3 D3 C4 F4 C5 B3 C2"),
+    ("Heath", "F5 F6 E6 F4 G5"),
+];
+
+/// Name the opening a game began with, as an index into [`NAMED_OPENINGS`]
+///
+/// The four first moves are symmetric, so the game is turned to begin
+/// with F5 before matching. The longest matching name wins. Games from a
+/// handicap or set-up position have no named opening.
+pub fn identify_opening(game: &GameState) -> Option<usize> {
+    if *game.start_board() != Board::new() || game.start_player() != Player::Black {
+        return None;
+    }
+
+    // Map each first move onto F5 with a symmetry of the start position
+    let first = game.history().first()?.pos;
+    let turn = |p: Position| -> Position {
+        let (row, col) = pos_to_rc(p);
+        match pos_to_rc(first) {
+            (4, 5) => p,
+            (5, 4) => pos(col, row),
+            (2, 3) => pos(7 - col, 7 - row),
+            _ => pos(7 - row, 7 - col),
+        }
+    };
+
+    let played = game.history().iter().take_while(|entry| !entry.is_pass());
+    let mut best = None;
+    for (index, (_, transcript)) in NAMED_OPENINGS.iter().enumerate() {
+        let moves = transcript.split(' ').map(|m| algebraic_to_pos(m.as_bytes()));
+        let length = transcript.split(' ').count();
+        let matches = played.clone().map(|entry| Some(turn(entry.pos))).take(length).eq(moves);
+        if matches && best.is_none_or(|(longest, _)| length > longest) {
+            best = Some((length, index));
+        }
+    }
+    best.map(|(_, index)| index)
+}
+
+/// Opening book, read in place from its binary form
+///
+/// The book borrows its bytes, so one held in flash or a memory-mapped
+/// file is searched where it lies rather than parsed into RAM. Version 1
+/// of the format is, with every integer little-endian:
+///
+/// | Offset | Size | Contents |
+/// |--------|------|----------|
+/// | 0 | 4 | Magic `OTBK` |
+/// | 4 | 2 | Version, 1 |
+/// | 6 | 2 | Reserved, 0 |
+/// | 8 | 4 | Position count `P` |
+/// | 12 | 4 | Move count `M` |
+/// | 16 | 12 `P` | Position records |
+/// | 16 + 12 `P` | 2 `M` | Move records |
+///
+/// A position record is the Zobrist key of the position's canonical board
+/// (`Board::canonical`) as a `u64`, then the index of its first move record
+/// as a `u32`. Records are sorted by key with no repeats, so a lookup is a
+/// binary search. A position's moves run up to the next record's first
+/// move, or to the end for the last record.
+///
+/// A move record is the square in the canonical orientation, then a weight
+/// from 1 to 255; higher is better. Lookups turn the square back to the
+/// orientation of the board asked about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OpeningBook<'a> {
+    bytes: &'a [u8],
+    positions: u32,
+    moves: u32,
+}
+
+/// Why bytes could not be read as an opening book
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BookError {
+    /// Shorter than the header, or than the records the header counts
+    Truncated,
+    /// Does not start with `OTBK`
+    BadMagic,
+    /// A format version this build can't read
+    UnsupportedVersion(u16),
+    /// Position keys out of order or repeated
+    Unsorted,
+    /// A move index past the moves, or before the previous position's
+    BadMoveIndex,
+    /// A square off the board or a zero weight
+    BadMove,
+}
+
+/// Magic bytes that start a book
+pub const BOOK_MAGIC: [u8; 4] = *b"OTBK";
+
+/// Book format version written and read by this build
+pub const BOOK_VERSION: u16 = 1;
+
+const HEADER_LEN: usize = 16;
+const POSITION_RECORD_LEN: usize = 12;
+const MOVE_RECORD_LEN: usize = 2;
+
+/// Built-in book: White's perpendicular reply, D3 C5 and its symmetries
+#[cfg(not(feature = "big-book"))]
+const BUILTIN_BYTES: &[u8] = &[
+    b'O', b'T', b'B', b'K', 1, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0,
+    0x35, 0x76, 0x14, 0x65, 0xbc, 0x7a, 0x69, 0xad, 0, 0, 0, 0,
+    34, 1,
+];
+
+/// Built-in book: every position of the first seven plies, from
+/// `examples/build_book.rs`
+#[cfg(feature = "big-book")]
+const BUILTIN_BYTES: &[u8] = include_bytes!("../books/big.otbk");
+
+impl<'a> OpeningBook<'a> {
+    /// The book built into the engine, used unless a search is given another
+    pub const BUILTIN: OpeningBook<'static> = match OpeningBook::from_bytes(BUILTIN_BYTES) {
+        Ok(book) => book,
+        Err(_) => panic!("built-in opening book is malformed"),
+    };
+
+    /// Read a book in place, checking its header and every record once
+    pub const fn from_bytes(bytes: &'a [u8]) -> Result<Self, BookError> {
+        if bytes.len() < HEADER_LEN {
+            return Err(BookError::Truncated);
+        }
+        if bytes[0] != BOOK_MAGIC[0] || bytes[1] != BOOK_MAGIC[1] || bytes[2] != BOOK_MAGIC[2] || bytes[3] != BOOK_MAGIC[3] {
+            return Err(BookError::BadMagic);
+        }
+        let version = u16::from_le_bytes([bytes[4], bytes[5]]);
+        if version != BOOK_VERSION {
+            return Err(BookError::UnsupportedVersion(version));
+        }
+        let positions = read_u32(bytes, 8);
+        let moves = read_u32(bytes, 12);
+        let book = Self { bytes, positions, moves };
+        if (bytes.len() as u64) < book.moves_offset() as u64 + moves as u64 * MOVE_RECORD_LEN as u64 {
+            return Err(BookError::Truncated);
+        }
+
+        let mut i = 0;
+        while i < positions {
+            if i > 0 && book.key(i) <= book.key(i - 1) {
+                return Err(BookError::Unsorted);
+            }
+            let first = book.first_move(i);
+            if first > moves || (i > 0 && first < book.first_move(i - 1)) {
+                return Err(BookError::BadMoveIndex);
+            }
+            i += 1;
+        }
+        let mut m = 0;
+        while m < moves {
+            let at = book.moves_offset() + m as usize * MOVE_RECORD_LEN;
+            if bytes[at] >= 64 || bytes[at + 1] == 0 {
+                return Err(BookError::BadMove);
+            }
+            m += 1;
+        }
+        Ok(book)
+    }
+
+    /// Number of positions in the book
+    pub const fn len(&self) -> usize {
+        self.positions as usize
+    }
+
+    /// Check if the book has no positions
+    pub const fn is_empty(&self) -> bool {
+        self.positions == 0
+    }
 
-impl OpeningBook {
     /// Lookup a position in the opening book
     ///
-    /// Returns the best move if the position is in the book.
-    pub fn lookup(board: &Board) -> Option<Position> {
-        // Normalize board by checking all 8 symmetries
-        let hash = Self::normalized_hash(board);
+    /// Returns the highest-weighted move if the position is in the book,
+    /// the first listed on a tie.
+    pub fn lookup(&self, board: &Board) -> Option<Position> {
+        let mut best: Option<(Position, u8)> = None;
+        for (pos, weight) in self.moves(board) {
+            if best.is_none_or(|(_, top)| weight > top) {
+                best = Some((pos, weight));
+            }
+        }
+        best.map(|(pos, _)| pos)
+    }
 
-        // Binary search in sorted book
-        Self::BOOK.iter()
-            .find(|(h, _)| *h == hash)
-            .map(|(_, pos)| *pos)
+    /// Book moves and their weights for a position, in book order
+    pub fn moves(&self, board: &Board) -> impl Iterator<Item = (Position, u8)> + 'a {
+        let canonical = board.canonical();
+        let turn = board.symmetries().iter().position(|b| *b == canonical).unwrap_or(0);
+        let (first, end) = match self.find(Self::normalized_hash(board)) {
+            Some(i) => (self.first_move(i), self.end_move(i)),
+            None => (0, 0),
+        };
+
+        let (bytes, offset) = (self.bytes, self.moves_offset());
+        (first..end).map(move |m| {
+            let at = offset + m as usize * MOVE_RECORD_LEN;
+            (untransform_square(bytes[at], turn), bytes[at + 1])
+        })
+    }
+
+    /// Index of the position record with this key
+    fn find(&self, key: u64) -> Option<u32> {
+        let (mut low, mut high) = (0, self.positions);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            match self.key(mid).cmp(&key) {
+                core::cmp::Ordering::Less => low = mid + 1,
+                core::cmp::Ordering::Greater => high = mid,
+                core::cmp::Ordering::Equal => return Some(mid),
+            }
+        }
+        None
+    }
+
+    const fn key(&self, i: u32) -> u64 {
+        let at = HEADER_LEN + i as usize * POSITION_RECORD_LEN;
+        read_u32(self.bytes, at) as u64 | (read_u32(self.bytes, at + 4) as u64) << 32
+    }
+
+    const fn first_move(&self, i: u32) -> u32 {
+        read_u32(self.bytes, HEADER_LEN + i as usize * POSITION_RECORD_LEN + 8)
+    }
+
+    const fn end_move(&self, i: u32) -> u32 {
+        if i + 1 < self.positions { self.first_move(i + 1) } else { self.moves }
+    }
+
+    const fn moves_offset(&self) -> usize {
+        HEADER_LEN + self.positions as usize * POSITION_RECORD_LEN
     }
 
     /// Get normalized hash considering symmetries
     fn normalized_hash(board: &Board) -> u64 {
-        let mut min_hash = board.hash();
+        board.canonical().zobrist()
+    }
+}
 
-        // Check all 8 symmetries (4 rotations x 2 mirrors)
-        let mut b = *board;
+impl Default for OpeningBook<'static> {
+    fn default() -> Self {
+        Self::BUILTIN
+    }
+}
 
-        for _ in 0..4 {
-            let h = b.hash();
-            if h < min_hash {
-                min_hash = h;
-            }
+const fn read_u32(bytes: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]])
+}
 
-            let mirrored = Self::mirror_board(&b);
-            let mh = mirrored.hash();
-            if mh < min_hash {
-                min_hash = mh;
-            }
+/// Square `pos` as it lies in symmetry `turn` of `Board::symmetries`
+fn transform_square(pos: Position, turn: usize) -> Position {
+    let single = Board { black: 1 << pos, white: 0 };
+    single.symmetries()[turn].black.trailing_zeros() as Position
+}
+
+/// The square that symmetry `turn` carries onto `pos`
+fn untransform_square(pos: Position, turn: usize) -> Position {
+    (0..64).find(|&p| transform_square(p, turn) == pos).unwrap_or(pos)
+}
 
-            b = Self::rotate_board(&b);
+/// Write a book in the binary format from (board, move, weight) entries
+///
+/// Boards are given as played; symmetric positions share one record.
+/// Moves keep the order given, and a repeated move keeps its first weight.
+/// Zero weights are raised to 1.
+#[cfg(feature = "std")]
+pub fn encode_book(entries: &[(Board, Position, u8)]) -> std::vec::Vec<u8> {
+    let mut records: std::vec::Vec<(u64, Position, u8)> = std::vec::Vec::new();
+    for (board, pos, weight) in entries {
+        let canonical = board.canonical();
+        let turn = board.symmetries().iter().position(|b| *b == canonical).unwrap_or(0);
+        let record = (canonical.zobrist(), transform_square(*pos, turn), (*weight).max(1));
+        if !records.iter().any(|r| (r.0, r.1) == (record.0, record.1)) {
+            records.push(record);
         }
+    }
+    // Stable, so each position's moves keep their order
+    records.sort_by_key(|r| r.0);
+
+    let mut keys: std::vec::Vec<(u64, u32)> = std::vec::Vec::new();
+    for (index, record) in records.iter().enumerate() {
+        if keys.last().is_none_or(|last| last.0 != record.0) {
+            keys.push((record.0, index as u32));
+        }
+    }
 
-        min_hash
+    let mut bytes = std::vec::Vec::with_capacity(HEADER_LEN + keys.len() * POSITION_RECORD_LEN + records.len() * MOVE_RECORD_LEN);
+    bytes.extend_from_slice(&BOOK_MAGIC);
+    bytes.extend_from_slice(&BOOK_VERSION.to_le_bytes());
+    bytes.extend_from_slice(&[0, 0]);
+    bytes.extend_from_slice(&(keys.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(&(records.len() as u32).to_le_bytes());
+    for (key, first) in keys {
+        bytes.extend_from_slice(&key.to_le_bytes());
+        bytes.extend_from_slice(&first.to_le_bytes());
     }
+    for (_, pos, weight) in records {
+        bytes.extend_from_slice(&[pos, weight]);
+    }
+    bytes
+}
 
-    /// Rotate board 90 degrees clockwise
-    fn rotate_board(board: &Board) -> Board {
-        let mut black = 0u64;
-        let mut white = 0u64;
+/// Magic bytes that start a stored `LearnedBook`
+pub const LEARNED_MAGIC: [u8; 4] = *b"OTLB";
 
-        for row in 0..8 {
-            for col in 0..8 {
-                let old_pos = row * 8 + col;
-                let new_row = col;
-                let new_col = 7 - row;
-                let new_pos = new_row * 8 + new_col;
+/// Learned book format version written and read by this build
+pub const LEARNED_VERSION: u16 = 1;
 
-                if (board.black & (1u64 << old_pos)) != 0 {
-                    black |= 1u64 << new_pos;
-                }
-                if (board.white & (1u64 << old_pos)) != 0 {
-                    white |= 1u64 << new_pos;
-                }
+/// Moves a `LearnedBook` holds by default, at 16 bytes each
+pub const LEARNED_BOOK_ENTRIES: usize = 512;
+
+/// Plies at the start of a game that a `LearnedBook` learns from
+pub const LEARN_PLIES: usize = 16;
+
+const LEARNED_HEADER_LEN: usize = 12;
+const LEARNED_RECORD_LEN: usize = 13;
+
+/// A move in a learned book and how the games it was played in went
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct LearnedMove {
+    /// Zobrist key of the canonical board, as in `OpeningBook`
+    key: u64,
+    /// Square in the canonical orientation
+    square: Position,
+    games: u16,
+    /// Results for the side that played it: 2 a win, 1 a draw
+    points: u16,
+}
+
+const NO_LEARNED_MOVE: LearnedMove = LearnedMove { key: 0, square: 0, games: 0, points: 0 };
+
+/// A small opening book that learns from finished games
+///
+/// Each game teaches it the moves of its first `LEARN_PLIES` plies and
+/// whether the side that played each one went on to win. `lookup` only
+/// suggests a move that has won more than it has lost, so the book sits in
+/// front of an `OpeningBook` or a search rather than replacing them. Like
+/// `OpeningBook`, symmetric positions share their moves.
+///
+/// The book holds `N` moves in place, with no allocation; once full, a new
+/// move takes the place of the one played in fewest games. `encode` and
+/// `from_bytes` store it as a header (magic `OTLB`, version `u16`,
+/// reserved `u16`, move count `u32`, little-endian) then 13-byte records:
+/// key `u64`, square, games `u16` and points `u16`.
+#[derive(Debug, Clone)]
+pub struct LearnedBook<const N: usize = LEARNED_BOOK_ENTRIES> {
+    entries: [LearnedMove; N],
+    len: usize,
+}
+
+impl<const N: usize> LearnedBook<N> {
+    /// A book that has learned nothing
+    pub const fn new() -> Self {
+        Self { entries: [NO_LEARNED_MOVE; N], len: 0 }
+    }
+
+    /// Read a book stored by `encode`
+    ///
+    /// Moves past this book's capacity are dropped, so a book can be
+    /// read back into a smaller one.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BookError> {
+        if bytes.len() < LEARNED_HEADER_LEN {
+            return Err(BookError::Truncated);
+        }
+        if bytes[..4] != LEARNED_MAGIC {
+            return Err(BookError::BadMagic);
+        }
+        let version = u16::from_le_bytes([bytes[4], bytes[5]]);
+        if version != LEARNED_VERSION {
+            return Err(BookError::UnsupportedVersion(version));
+        }
+        let count = read_u32(bytes, 8) as usize;
+        let records = bytes[LEARNED_HEADER_LEN..].chunks_exact(LEARNED_RECORD_LEN);
+        if records.len() < count {
+            return Err(BookError::Truncated);
+        }
+
+        let mut book = Self::new();
+        for record in records.take(count.min(N)) {
+            let entry = LearnedMove {
+                key: u64::from_le_bytes([record[0], record[1], record[2], record[3], record[4], record[5], record[6], record[7]]),
+                square: record[8],
+                games: u16::from_le_bytes([record[9], record[10]]),
+                points: u16::from_le_bytes([record[11], record[12]]),
+            };
+            if entry.square >= 64 || entry.games == 0 || entry.points > entry.games.saturating_mul(2) {
+                return Err(BookError::BadMove);
             }
+            book.entries[book.len] = entry;
+            book.len += 1;
         }
+        Ok(book)
+    }
 
-        Board { black, white }
+    /// The book in its stored form
+    pub fn encode(&self) -> impl Iterator<Item = u8> + '_ {
+        let header = LEARNED_MAGIC
+            .into_iter()
+            .chain(LEARNED_VERSION.to_le_bytes())
+            .chain([0, 0])
+            .chain((self.len as u32).to_le_bytes());
+        let records = self.entries[..self.len].iter().flat_map(|entry| {
+            let mut record = [0; LEARNED_RECORD_LEN];
+            record[..8].copy_from_slice(&entry.key.to_le_bytes());
+            record[8] = entry.square;
+            record[9..11].copy_from_slice(&entry.games.to_le_bytes());
+            record[11..].copy_from_slice(&entry.points.to_le_bytes());
+            record
+        });
+        header.chain(records)
     }
 
-    /// Mirror board horizontally
-    fn mirror_board(board: &Board) -> Board {
-        let mut black = 0u64;
-        let mut white = 0u64;
+    /// Number of moves learned
+    pub const fn len(&self) -> usize {
+        self.len
+    }
 
-        for row in 0..8 {
-            for col in 0..8 {
-                let old_pos = row * 8 + col;
-                let new_pos = row * 8 + (7 - col);
+    /// Check if the book has learned nothing
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
 
-                if (board.black & (1u64 << old_pos)) != 0 {
-                    black |= 1u64 << new_pos;
-                }
-                if (board.white & (1u64 << old_pos)) != 0 {
-                    white |= 1u64 << new_pos;
-                }
+    /// Forget every move
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Learn from a finished game
+    ///
+    /// Returns false, learning nothing, for a game still in progress or
+    /// played misère, where winning moves are the wrong ones to repeat.
+    pub fn learn(&mut self, game: &GameState) -> bool {
+        let result = match game.result() {
+            Some(result) if !game.rules().misere => result,
+            _ => return false,
+        };
+
+        let mut board = *game.start_board();
+        for entry in game.history().iter().take(LEARN_PLIES) {
+            if entry.is_pass() {
+                continue;
+            }
+            let points = match result.winner() {
+                Some(winner) if winner == entry.player => 2,
+                Some(_) => 0,
+                None => 1,
+            };
+            self.record(&board, entry.pos, points);
+            board.place(entry.player, entry.pos);
+            board.flip(entry.player.opponent(), entry.flipped);
+        }
+        true
+    }
+
+    /// Count one more game for a move
+    fn record(&mut self, board: &Board, pos: Position, points: u16) {
+        let (key, turn) = canonical_key(board);
+        let square = transform_square(pos, turn);
+        let learned = &mut self.entries[..self.len];
+        if let Some(entry) = learned.iter_mut().find(|e| (e.key, e.square) == (key, square)) {
+            // Halve an old record rather than let it saturate
+            if entry.games == u16::MAX {
+                entry.games /= 2;
+                entry.points /= 2;
             }
+            entry.games += 1;
+            entry.points += points;
+            return;
         }
 
-        Board { black, white }
+        let slot = if self.len < N {
+            self.len += 1;
+            self.len - 1
+        } else {
+            match (0..N).min_by_key(|&i| self.entries[i].games) {
+                Some(i) => i,
+                None => return,
+            }
+        };
+        self.entries[slot] = LearnedMove { key, square, games: 1, points };
+    }
+
+    /// The learned move with the best record in a position, if one has won
+    /// more than it lost
+    ///
+    /// The first learned wins a tie.
+    pub fn lookup(&self, board: &Board) -> Option<Position> {
+        let (key, turn) = canonical_key(board);
+        let mut best: Option<&LearnedMove> = None;
+        for entry in self.entries[..self.len].iter().filter(|e| e.key == key && e.points > e.games) {
+            // Compare points per game without dividing
+            if best.is_none_or(|top| entry.points as u32 * top.games as u32 > top.points as u32 * entry.games as u32) {
+                best = Some(entry);
+            }
+        }
+        best.map(|entry| untransform_square(entry.square, turn))
     }
+}
 
-    /// Opening book entries (hash, best_move)
-    /// These are common tournament openings
-    const BOOK: &'static [(u64, Position)] = &[
-        // Starting position responses
-        // D3 (perpendicular opening)
-        (0x0810000000000000_u64 ^ 0x1008000000000000, 19), // C5
+impl<const N: usize> Default for LearnedBook<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-        // More openings can be added here
-        // Format: (normalized_board_hash, best_move_position)
-    ];
+/// Key of a board's canonical symmetry, and which symmetry that is
+fn canonical_key(board: &Board) -> (u64, usize) {
+    let canonical = board.canonical();
+    let turn = board.symmetries().iter().position(|b| *b == canonical).unwrap_or(0);
+    (canonical.zobrist(), turn)
 }
 
 #[cfg(test)]
@@ -114,7 +535,7 @@ mod tests {
         let mut board = Board::empty();
         board.place(crate::Player::Black, 0); // A1
 
-        let rotated = OpeningBook::rotate_board(&board);
+        let rotated = board.rotate90();
         // A1 rotated 90 CW -> H1
         assert!((rotated.black & (1u64 << 7)) != 0);
     }
@@ -124,7 +545,7 @@ mod tests {
         let mut board = Board::empty();
         board.place(crate::Player::Black, 0); // A1
 
-        let mirrored = OpeningBook::mirror_board(&board);
+        let mirrored = board.mirror_horizontal();
         // A1 mirrored -> H1
         assert!((mirrored.black & (1u64 << 7)) != 0);
     }
@@ -143,11 +564,192 @@ mod tests {
         assert_eq!(h1, h2);
     }
 
+    #[test]
+    fn test_identify_opening() {
+        use crate::{parse_transcript, replay_transcript};
+
+        let name = |transcript: &str| {
+            let game = parse_transcript(transcript).unwrap();
+            identify_opening(&game).map(|index| NAMED_OPENINGS[index].0)
+        };
+        for (opening, transcript) in NAMED_OPENINGS {
+            assert_eq!(name(transcript), Some(*opening));
+        }
+
+        // Longest match, any first move, and too short to name
+        assert_eq!(name("F5 D6 C3 D3 C4 F4 C5 B3 C2 E3"), Some("Buffalo"));
+        assert_eq!(name("F5 D6 C3 D3 C4 F4"), Some("Tiger"));
+        assert_eq!(name("C4 C3"), Some("Diagonal"));
+        assert_eq!(name("E6 F4"), Some("Perpendicular"));
+        assert_eq!(name("D3 C5 D6"), Some("Cow"));
+        assert_eq!(name("F5"), None);
+
+        // Handicap starts are not named
+        let start = GameState::from_board(Board::with_handicap(Player::Black, 1), Player::Black);
+        assert!(identify_opening(&replay_transcript(start, "F5 D6").unwrap()).is_none());
+    }
+
     #[test]
     fn test_lookup_starting_position() {
         let board = Board::new();
         // Starting position may or may not be in book
-        let _result = OpeningBook::lookup(&board);
+        let _result = OpeningBook::BUILTIN.lookup(&board);
         // Just verify it doesn't crash
     }
+
+    #[test]
+    #[cfg(all(feature = "std", not(feature = "big-book")))]
+    fn test_builtin_book() {
+        use crate::parse_transcript;
+
+        // The built-in bytes are what the encoder writes for D3 C5
+        let d3 = *parse_transcript("D3").unwrap().board();
+        assert_eq!(encode_book(&[(d3, pos(4, 2), 1)]), BUILTIN_BYTES);
+        assert_eq!(OpeningBook::BUILTIN.len(), 1);
+
+        // Every first move finds the perpendicular reply, turned to match
+        for (first, reply) in [("D3", "C5"), ("C4", "E3"), ("F5", "D6"), ("E6", "F4")] {
+            let board = *parse_transcript(first).unwrap().board();
+            assert_eq!(OpeningBook::BUILTIN.lookup(&board), algebraic_to_pos(reply.as_bytes()), "{first}");
+        }
+        assert_eq!(OpeningBook::BUILTIN.lookup(&Board::new()), None);
+    }
+
+    #[test]
+    #[cfg(feature = "big-book")]
+    fn test_big_book() {
+        use crate::{GameState, legal_moves_bitboard};
+
+        // Every position of the first six moves is covered, with legal moves
+        let mut games = vec![GameState::new()];
+        for _ in 0..6 {
+            let mut next = Vec::new();
+            for game in &games {
+                let board = *game.board();
+                let moves = legal_moves_bitboard(&board, game.current_player());
+                let chosen = OpeningBook::BUILTIN.lookup(&board).unwrap();
+                assert_ne!(moves & (1 << chosen), 0);
+                assert!(OpeningBook::BUILTIN.moves(&board).all(|(pos, _)| moves & (1 << pos) != 0));
+                for pos in Board::iter_bits(moves) {
+                    let mut after = game.clone();
+                    after.make_move(pos);
+                    next.push(after);
+                }
+            }
+            // Follow only a sample of the wider plies to keep the test quick
+            next.truncate(400);
+            games = next;
+        }
+        assert!(OpeningBook::BUILTIN.len() > 2000);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_book_round_trip() {
+        use crate::parse_transcript;
+
+        let board = |transcript| *parse_transcript(transcript).unwrap().board();
+        let entries = [
+            (board("F5 D6"), pos(2, 2), 3),
+            (board("F5 D6"), pos(4, 2), 9),
+            (board("F5 F6"), pos(3, 4), 5),
+            (board("F5 F4"), pos(2, 4), 5),
+            (board("F5 D6 C5"), pos(3, 5), 7),
+        ];
+        let bytes = encode_book(&entries);
+        let book = OpeningBook::from_bytes(&bytes).unwrap();
+        assert_eq!(book.len(), 4);
+
+        // The heavier move wins, turned back to each orientation it is asked in
+        assert_eq!(book.lookup(&entries[0].0), Some(pos(4, 2)));
+        assert!(book.moves(&entries[0].0).eq([(pos(2, 2), 3), (pos(4, 2), 9)]));
+        let turned = entries[0].0.transpose();
+        assert_eq!(book.lookup(&turned), Some(pos(2, 4)));
+        for (board, reply, _) in &entries[2..] {
+            assert_eq!(book.lookup(board), Some(*reply));
+        }
+        assert_eq!(book.lookup(&Board::new()), None);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_book_rejects_bad_bytes() {
+        let good = encode_book(&[(Board::new(), pos(2, 3), 1), (Board::new().rotate90(), pos(3, 2), 1)]);
+        assert!(OpeningBook::from_bytes(&good).is_ok());
+
+        assert_eq!(OpeningBook::from_bytes(&good[..10]), Err(BookError::Truncated));
+        assert_eq!(OpeningBook::from_bytes(&good[..good.len() - 1]), Err(BookError::Truncated));
+        let mut bad = good.clone();
+        bad[0] = b'X';
+        assert_eq!(OpeningBook::from_bytes(&bad), Err(BookError::BadMagic));
+        let mut bad = good.clone();
+        bad[4] = 2;
+        assert_eq!(OpeningBook::from_bytes(&bad), Err(BookError::UnsupportedVersion(2)));
+        let mut bad = good.clone();
+        bad[HEADER_LEN + 8] = 9;
+        assert_eq!(OpeningBook::from_bytes(&bad), Err(BookError::BadMoveIndex));
+        let mut bad = good.clone();
+        let last = bad.len() - 1;
+        bad[last] = 0;
+        assert_eq!(OpeningBook::from_bytes(&bad), Err(BookError::BadMove));
+
+        // Two positions written out of order
+        let two = encode_book(&[(Board::new(), pos(2, 3), 1), (Board::empty(), pos(0, 0), 1)]);
+        let mut swapped = two.clone();
+        swapped[HEADER_LEN..HEADER_LEN + 8].copy_from_slice(&two[HEADER_LEN + 12..HEADER_LEN + 20]);
+        swapped[HEADER_LEN + 12..HEADER_LEN + 20].copy_from_slice(&two[HEADER_LEN..HEADER_LEN + 8]);
+        assert_eq!(OpeningBook::from_bytes(&swapped), Err(BookError::Unsorted));
+    }
+
+    #[test]
+    fn test_learned_book() {
+        use crate::parse_transcript;
+
+        let buffalo = "F5 D6 C3 D3 C4 F4 C5 B3 C2";
+        let won = parse_transcript(buffalo).unwrap();
+        let mut book: LearnedBook<64> = LearnedBook::new();
+        assert!(!book.learn(&won), "still in progress");
+        assert!(book.is_empty());
+
+        // White resigns, so every move Black played won
+        let mut finished = won.clone();
+        finished.resign(Player::White);
+        assert!(book.learn(&finished));
+        assert_eq!(book.len(), 9);
+
+        // Black's moves are suggested, White's lost and are not, and a
+        // turned board gets the turned move
+        let after = |n: usize| match n {
+            0 => Board::new(),
+            _ => *parse_transcript(&buffalo[..n * 3 - 1]).unwrap().board(),
+        };
+        assert_eq!(book.lookup(&after(0)), Some(pos(4, 5)));
+        assert_eq!(book.lookup(&after(2)), Some(pos(2, 2)));
+        assert_eq!(book.lookup(&after(1)), None);
+        assert_eq!(book.lookup(&after(2).transpose()), Some(pos(2, 2)));
+
+        // A loss with the same first move evens its record out
+        let mut lost = parse_transcript("F5 D6").unwrap();
+        lost.resign(Player::Black);
+        book.learn(&lost);
+        assert_eq!(book.lookup(&after(0)), None);
+
+        // Stored and read back the same
+        let bytes: Vec<u8> = book.encode().collect();
+        let read: LearnedBook<64> = LearnedBook::from_bytes(&bytes).unwrap();
+        assert_eq!(read.encode().collect::<Vec<_>>(), bytes);
+        assert_eq!(read.lookup(&after(2)), Some(pos(2, 2)));
+        let small: LearnedBook<4> = LearnedBook::from_bytes(&bytes).unwrap();
+        assert_eq!(small.len(), 4);
+        assert_eq!(LearnedBook::<64>::from_bytes(&bytes[..bytes.len() - 1]).err(), Some(BookError::Truncated));
+        assert_eq!(LearnedBook::<64>::from_bytes(&bytes[1..]).err(), Some(BookError::BadMagic));
+
+        // A full book makes room in place of the least played move
+        let mut full: LearnedBook<4> = LearnedBook::new();
+        full.learn(&finished);
+        assert_eq!(full.len(), 4);
+        assert_eq!(full.lookup(&after(8)), Some(pos(1, 2)));
+        full.clear();
+        assert!(full.is_empty());
+    }
 }
@@ -0,0 +1,497 @@
+//! Messages between two devices playing one game
+//!
+//! Each side opens the connection by sending a [`Hello`] with its protocol
+//! version, capabilities, rules, board size and time control, and checks
+//! the other's with [`Hello::negotiate`]. On a mismatch it sends
+//! [`Message::Reject`] saying why and closes, so the player sees what
+//! differs instead of the two games drifting apart later. Once agreed, the
+//! sides exchange moves, and chat if both have `CAP_CHAT`.
+//!
+//! A message is framed as a little-endian `u16` length, then a type byte
+//! and the payload the length counts. Readers skip payload bytes past the
+//! fields they know, so a later version can append fields to a message
+//! without breaking older readers. The transport is the caller's.
+
+use core::fmt;
+
+use crate::{MoveStamp, PassPolicy, Position, RuleSet, Scoring, StartPosition, TimeControl};
+
+/// Protocol version this build speaks
+pub const PROTOCOL_VERSION: u16 = 1;
+
+/// Oldest version this build can still speak
+pub const MIN_PROTOCOL_VERSION: u16 = 1;
+
+/// Bytes that open a `Hello`, so a connection to some other service fails
+/// at once
+pub const PROTOCOL_MAGIC: [u8; 4] = *b"OTNP";
+
+/// Capability: plays timed games, sending a `MoveStamp` with each move
+pub const CAP_CLOCK: u32 = 1 << 0;
+
+/// Capability: shows `Message::Chat`
+pub const CAP_CHAT: u32 = 1 << 1;
+
+/// Every capability this build has
+pub const CAPABILITIES: u32 = CAP_CLOCK | CAP_CHAT;
+
+/// Longest frame, length prefix included
+pub const MAX_FRAME: usize = 64;
+
+const HELLO: u8 = 1;
+const REJECT: u8 = 2;
+const MOVE: u8 = 3;
+const RESIGN: u8 = 4;
+const CHAT: u8 = 5;
+
+/// Longest typed chat message in bytes
+pub const MAX_CHAT: usize = 48;
+
+/// The opening message of a connection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hello {
+    pub version: u16,
+    /// `CAP_*` bits
+    pub capabilities: u32,
+    pub rules: RuleSet,
+    /// Squares on a side; this build only plays on 8
+    pub board_size: u8,
+    /// `None` for an untimed game
+    pub time_control: Option<TimeControl>,
+}
+
+/// What both sides agreed to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Session {
+    /// The lower of the two versions
+    pub version: u16,
+    /// Capabilities both sides have
+    pub capabilities: u32,
+}
+
+impl Session {
+    /// Whether both sides have a capability
+    pub const fn supports(&self, capability: u32) -> bool {
+        self.capabilities & capability == capability
+    }
+}
+
+/// Why a handshake failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mismatch {
+    /// The other side's version is too old for this build, or this
+    /// build's for the other side
+    Version(u16),
+    /// The other side plays on a board of this size
+    BoardSize(u8),
+    /// Different scoring, misère play or start position
+    Rules,
+    /// Different time controls, or a timed game with a side that has no
+    /// clock
+    TimeControl,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Mismatch::Version(version) => write!(f, "Other device speaks protocol {}; update the older app", version),
+            Mismatch::BoardSize(size) => write!(f, "Other device plays on a {0}x{0} board", size),
+            Mismatch::Rules => f.write_str("Other device chose different rules"),
+            Mismatch::TimeControl => f.write_str("Other device chose a different time control"),
+        }
+    }
+}
+
+impl Hello {
+    /// This build's greeting for a game under these rules
+    pub const fn new(rules: RuleSet, time_control: Option<TimeControl>) -> Self {
+        Self { version: PROTOCOL_VERSION, capabilities: CAPABILITIES, rules, board_size: 8, time_control }
+    }
+
+    /// Agree on a session with the other side's greeting
+    ///
+    /// The rules have to match except for the pass policy, which only
+    /// changes how each device asks its player to pass.
+    pub fn negotiate(&self, theirs: &Hello) -> Result<Session, Mismatch> {
+        let version = self.version.min(theirs.version);
+        if version < MIN_PROTOCOL_VERSION {
+            return Err(Mismatch::Version(theirs.version));
+        }
+        if theirs.board_size != self.board_size {
+            return Err(Mismatch::BoardSize(theirs.board_size));
+        }
+        let game_rules = |rules: &RuleSet| RuleSet { pass: PassPolicy::Explicit, ..*rules };
+        if game_rules(&theirs.rules) != game_rules(&self.rules) {
+            return Err(Mismatch::Rules);
+        }
+
+        let session = Session { version, capabilities: self.capabilities & theirs.capabilities };
+        let timed = self.time_control.is_some();
+        if theirs.time_control != self.time_control || (timed && !session.supports(CAP_CLOCK)) {
+            return Err(Mismatch::TimeControl);
+        }
+        Ok(session)
+    }
+}
+
+/// A ready-made chat message
+///
+/// Sent as its number, so each device shows it in its own language.
+/// New messages go at the end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CannedChat {
+    Hello,
+    GoodLuck,
+    NiceMove,
+    NiceCorner,
+    Oops,
+    Thanks,
+    GoodGame,
+}
+
+impl CannedChat {
+    /// Every canned message, in the order they are numbered
+    pub const ALL: [CannedChat; 7] = [
+        CannedChat::Hello,
+        CannedChat::GoodLuck,
+        CannedChat::NiceMove,
+        CannedChat::NiceCorner,
+        CannedChat::Oops,
+        CannedChat::Thanks,
+        CannedChat::GoodGame,
+    ];
+
+    /// The message in English
+    pub const fn english(self) -> &'static str {
+        match self {
+            CannedChat::Hello => "Hi!",
+            CannedChat::GoodLuck => "Good luck",
+            CannedChat::NiceMove => "Nice move",
+            CannedChat::NiceCorner => "Nice corner",
+            CannedChat::Oops => "Oops",
+            CannedChat::Thanks => "Thanks",
+            CannedChat::GoodGame => "gg",
+        }
+    }
+}
+
+/// Typed chat text, at most `MAX_CHAT` bytes of UTF-8
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChatText {
+    bytes: [u8; MAX_CHAT],
+    len: u8,
+}
+
+impl ChatText {
+    /// Text to send, cut at a character boundary to fit
+    pub fn new(text: &str) -> Self {
+        let mut len = text.len().min(MAX_CHAT);
+        while !text.is_char_boundary(len) {
+            len -= 1;
+        }
+        let mut bytes = [0; MAX_CHAT];
+        bytes[..len].copy_from_slice(&text.as_bytes()[..len]);
+        Self { bytes, len: len as u8 }
+    }
+
+    pub fn as_str(&self) -> &str {
+        // Only ever built from whole characters of a str
+        core::str::from_utf8(&self.bytes[..self.len as usize]).unwrap_or("")
+    }
+}
+
+/// A chat message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Chat {
+    Canned(CannedChat),
+    Text(ChatText),
+}
+
+/// A message between the two devices
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Message {
+    Hello(Hello),
+    /// The handshake failed; the connection closes after this
+    Reject(Mismatch),
+    /// A move, `None` for a pass, with its times
+    Move { pos: Option<Position>, stamp: MoveStamp },
+    Resign,
+    /// Only sent when both sides have `CAP_CHAT`
+    Chat(Chat),
+}
+
+/// Why bytes could not be read as a message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProtocolError {
+    /// The frame isn't all there yet; read more and try again
+    Incomplete,
+    /// A `Hello` without the magic bytes, from something that isn't this
+    /// app
+    NotOthello,
+    /// A message type this build doesn't know
+    UnknownMessage(u8),
+    /// A payload too short or out of range for its type
+    Malformed,
+}
+
+/// An encoded message, ready to send
+#[derive(Debug, Clone, Copy)]
+pub struct Frame {
+    bytes: [u8; MAX_FRAME],
+    len: usize,
+}
+
+impl Frame {
+    fn new(kind: u8) -> Self {
+        let mut frame = Self { bytes: [0; MAX_FRAME], len: 3 };
+        frame.bytes[2] = kind;
+        frame
+    }
+
+    fn push(&mut self, bytes: &[u8]) {
+        self.bytes[self.len..self.len + bytes.len()].copy_from_slice(bytes);
+        self.len += bytes.len();
+    }
+
+    /// The frame's bytes, length prefix first
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+}
+
+impl Message {
+    /// Encode the message as one frame
+    pub fn encode(&self) -> Frame {
+        let mut frame = match self {
+            Message::Hello(hello) => {
+                let mut frame = Frame::new(HELLO);
+                let rules = hello.rules;
+                let control = hello.time_control.unwrap_or(TimeControl::new(0, 0));
+                frame.push(&PROTOCOL_MAGIC);
+                frame.push(&hello.version.to_le_bytes());
+                frame.push(&hello.capabilities.to_le_bytes());
+                frame.push(&[
+                    hello.board_size,
+                    rules.scoring as u8,
+                    rules.misere as u8,
+                    rules.pass as u8,
+                    rules.start as u8,
+                    hello.time_control.is_some() as u8,
+                ]);
+                frame.push(&control.initial_ms.to_le_bytes());
+                frame.push(&control.increment_ms.to_le_bytes());
+                frame
+            }
+            Message::Reject(mismatch) => {
+                let mut frame = Frame::new(REJECT);
+                let (code, value) = match *mismatch {
+                    Mismatch::Version(version) => (0, version),
+                    Mismatch::BoardSize(size) => (1, size as u16),
+                    Mismatch::Rules => (2, 0),
+                    Mismatch::TimeControl => (3, 0),
+                };
+                frame.push(&[code]);
+                frame.push(&value.to_le_bytes());
+                frame
+            }
+            Message::Move { pos, stamp } => {
+                let mut frame = Frame::new(MOVE);
+                frame.push(&[pos.unwrap_or(255)]);
+                frame.push(&stamp.to_bytes());
+                frame
+            }
+            Message::Resign => Frame::new(RESIGN),
+            Message::Chat(Chat::Canned(canned)) => {
+                let mut frame = Frame::new(CHAT);
+                frame.push(&[0, *canned as u8]);
+                frame
+            }
+            Message::Chat(Chat::Text(text)) => {
+                let mut frame = Frame::new(CHAT);
+                frame.push(&[1, text.len]);
+                frame.push(text.as_str().as_bytes());
+                frame
+            }
+        };
+        let len = (frame.len - 2) as u16;
+        frame.bytes[..2].copy_from_slice(&len.to_le_bytes());
+        frame
+    }
+
+    /// Read the frame at the start of `bytes`, returning the message and
+    /// the number of bytes it took
+    pub fn decode(bytes: &[u8]) -> Result<(Message, usize), ProtocolError> {
+        let len = match bytes.get(..2) {
+            Some(prefix) => u16::from_le_bytes([prefix[0], prefix[1]]) as usize,
+            None => return Err(ProtocolError::Incomplete),
+        };
+        let body = bytes.get(2..2 + len).ok_or(ProtocolError::Incomplete)?;
+        let (&kind, payload) = body.split_first().ok_or(ProtocolError::Malformed)?;
+        let read_u16 = |at: usize| payload.get(at..at + 2).map(|b| u16::from_le_bytes([b[0], b[1]]));
+        let read_u32 = |at: usize| payload.get(at..at + 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]));
+
+        let message = match kind {
+            HELLO => {
+                if payload.get(..4) != Some(&PROTOCOL_MAGIC[..]) {
+                    return Err(ProtocolError::NotOthello);
+                }
+                let fields = payload.get(10..16).ok_or(ProtocolError::Malformed)?;
+                let scoring = match fields[1] {
+                    0 => Scoring::Discs,
+                    1 => Scoring::EmptiesToWinner,
+                    _ => return Err(ProtocolError::Malformed),
+                };
+                let pass = match fields[3] {
+                    0 => PassPolicy::Explicit,
+                    1 => PassPolicy::Automatic,
+                    _ => return Err(ProtocolError::Malformed),
+                };
+                let start = match fields[4] {
+                    0 => StartPosition::Standard,
+                    1 => StartPosition::Parallel,
+                    _ => return Err(ProtocolError::Malformed),
+                };
+                let control = TimeControl::new(
+                    read_u32(16).ok_or(ProtocolError::Malformed)?,
+                    read_u32(20).ok_or(ProtocolError::Malformed)?,
+                );
+                Message::Hello(Hello {
+                    version: read_u16(4).ok_or(ProtocolError::Malformed)?,
+                    capabilities: read_u32(6).ok_or(ProtocolError::Malformed)?,
+                    rules: RuleSet { scoring, misere: fields[2] != 0, pass, start },
+                    board_size: fields[0],
+                    time_control: (fields[5] != 0).then_some(control),
+                })
+            }
+            REJECT => {
+                let value = read_u16(1).ok_or(ProtocolError::Malformed)?;
+                Message::Reject(match payload[0] {
+                    0 => Mismatch::Version(value),
+                    1 => Mismatch::BoardSize(value as u8),
+                    2 => Mismatch::Rules,
+                    3 => Mismatch::TimeControl,
+                    _ => return Err(ProtocolError::Malformed),
+                })
+            }
+            MOVE => {
+                let pos = match payload.first() {
+                    Some(255) => None,
+                    Some(&pos) if pos < 64 => Some(pos),
+                    _ => return Err(ProtocolError::Malformed),
+                };
+                let stamp = MoveStamp::from_bytes(&payload[1..]).ok_or(ProtocolError::Malformed)?;
+                Message::Move { pos, stamp }
+            }
+            RESIGN => Message::Resign,
+            CHAT => {
+                let (&style, rest) = payload.split_first().ok_or(ProtocolError::Malformed)?;
+                let (&value, rest) = rest.split_first().ok_or(ProtocolError::Malformed)?;
+                Message::Chat(match style {
+                    0 => Chat::Canned(*CannedChat::ALL.get(value as usize).ok_or(ProtocolError::Malformed)?),
+                    1 => {
+                        let text = rest.get(..value as usize).filter(|text| text.len() <= MAX_CHAT);
+                        let text = text.and_then(|text| core::str::from_utf8(text).ok()).ok_or(ProtocolError::Malformed)?;
+                        Chat::Text(ChatText::new(text))
+                    }
+                    _ => return Err(ProtocolError::Malformed),
+                })
+            }
+            _ => return Err(ProtocolError::UnknownMessage(kind)),
+        };
+        Ok((message, 2 + len))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(message: Message) {
+        let frame = message.encode();
+        assert_eq!(Message::decode(frame.as_bytes()), Ok((message, frame.as_bytes().len())));
+        // Any shorter read waits for the rest
+        for cut in 0..frame.as_bytes().len() {
+            assert_eq!(Message::decode(&frame.as_bytes()[..cut]), Err(ProtocolError::Incomplete));
+        }
+    }
+
+    #[test]
+    fn test_messages_round_trip() {
+        let rules = RuleSet { scoring: Scoring::EmptiesToWinner, misere: true, ..RuleSet::STANDARD };
+        round_trip(Message::Hello(Hello::new(rules, Some(TimeControl::BLITZ))));
+        round_trip(Message::Hello(Hello::new(RuleSet::STANDARD, None)));
+        round_trip(Message::Reject(Mismatch::Version(7)));
+        round_trip(Message::Reject(Mismatch::BoardSize(10)));
+        round_trip(Message::Reject(Mismatch::TimeControl));
+        round_trip(Message::Move { pos: Some(37), stamp: MoveStamp { ply: 4, think_ms: 1_500, ack_ms: 900 } });
+        round_trip(Message::Move { pos: None, stamp: MoveStamp { ply: 31, think_ms: 0, ack_ms: 0 } });
+        round_trip(Message::Resign);
+        for canned in CannedChat::ALL {
+            round_trip(Message::Chat(Chat::Canned(canned)));
+        }
+        round_trip(Message::Chat(Chat::Text(ChatText::new("Schöne Ecke!"))));
+        round_trip(Message::Chat(Chat::Text(ChatText::new(""))));
+    }
+
+    #[test]
+    fn test_chat_text() {
+        // Long text is cut to fit, never inside a character
+        let long = "ä".repeat(30);
+        let text = ChatText::new(&long);
+        assert_eq!(text.as_str(), "ä".repeat(MAX_CHAT / 2));
+        let text = ChatText::new(&format!("x{}", long));
+        assert_eq!(text.as_str().len(), MAX_CHAT - 1);
+        let frame = Message::Chat(Chat::Text(text)).encode();
+        assert!(frame.as_bytes().len() <= MAX_FRAME);
+
+        assert_eq!(Message::decode(&[3, 0, CHAT, 0, 99]), Err(ProtocolError::Malformed));
+        assert_eq!(Message::decode(&[5, 0, CHAT, 1, 2, 0xff, 0xfe]), Err(ProtocolError::Malformed));
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        let hello = Message::Hello(Hello::new(RuleSet::STANDARD, None)).encode();
+        let mut bad = hello;
+        bad.bytes[3] = b'X';
+        assert_eq!(Message::decode(bad.as_bytes()), Err(ProtocolError::NotOthello));
+        assert_eq!(Message::decode(&[1, 0, 9]), Err(ProtocolError::UnknownMessage(9)));
+        assert_eq!(Message::decode(&[2, 0, MOVE, 64]), Err(ProtocolError::Malformed));
+        assert_eq!(Message::decode(&[0, 0]), Err(ProtocolError::Malformed));
+
+        // Fields a later version appends are skipped
+        let mut longer = [0; 40];
+        longer[..hello.len].copy_from_slice(hello.as_bytes());
+        longer[..2].copy_from_slice(&(hello.len as u16 - 2 + 3).to_le_bytes());
+        let (message, used) = Message::decode(&longer).unwrap();
+        assert_eq!(message, Message::Hello(Hello::new(RuleSet::STANDARD, None)));
+        assert_eq!(used, hello.len + 3);
+    }
+
+    #[test]
+    fn test_negotiate() {
+        let ours = Hello::new(RuleSet::STANDARD, Some(TimeControl::BLITZ));
+        let session = ours.negotiate(&ours).unwrap();
+        assert_eq!(session, Session { version: PROTOCOL_VERSION, capabilities: CAPABILITIES });
+        assert!(session.supports(CAP_CLOCK));
+
+        // A newer device speaks down to this one
+        let newer = Hello { version: PROTOCOL_VERSION + 1, capabilities: CAPABILITIES | 1 << 20, ..ours };
+        assert_eq!(ours.negotiate(&newer), Ok(session));
+
+        // The pass policy is each device's own choice
+        let automatic = Hello { rules: RuleSet { pass: PassPolicy::Automatic, ..RuleSet::STANDARD }, ..ours };
+        assert!(ours.negotiate(&automatic).is_ok());
+
+        let misere = Hello { rules: RuleSet { misere: true, ..RuleSet::STANDARD }, ..ours };
+        assert_eq!(ours.negotiate(&misere), Err(Mismatch::Rules));
+        let big = Hello { board_size: 10, ..ours };
+        assert_eq!(ours.negotiate(&big), Err(Mismatch::BoardSize(10)));
+        let ancient = Hello { version: 0, ..ours };
+        assert_eq!(ours.negotiate(&ancient), Err(Mismatch::Version(0)));
+        let untimed = Hello { time_control: None, ..ours };
+        assert_eq!(ours.negotiate(&untimed), Err(Mismatch::TimeControl));
+        let no_clock = Hello { capabilities: 0, ..ours };
+        assert_eq!(ours.negotiate(&no_clock), Err(Mismatch::TimeControl));
+        assert!(untimed.negotiate(&Hello { capabilities: 0, ..untimed }).is_ok());
+    }
+}
@@ -0,0 +1,229 @@
+//! Slow reference implementation of the rules, for checking the engine
+//!
+//! Squares are a plain 8x8 array and every rule is written out square by
+//! square, the way the rules are stated, with no bitboards and no shared
+//! code beyond the types. Property tests play random games through both
+//! this and the bitboard engine and compare them after every move, so a
+//! rewrite of move generation or flipping has something to be checked
+//! against. Built for tests, or with the `testsupport` feature.
+
+use crate::{Board, GameResult, Player, Position};
+
+/// The eight directions as (row step, column step)
+const STEPS: [(i8, i8); 8] = [(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)];
+
+/// A game of Othello on an array of squares
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReferenceGame {
+    /// Disc on each square, by row then column
+    cells: [[Option<Player>; 8]; 8],
+    to_move: Player,
+}
+
+impl Default for ReferenceGame {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReferenceGame {
+    /// The standard start: white on D4 and E5, black on E4 and D5
+    pub fn new() -> Self {
+        let mut cells = [[None; 8]; 8];
+        cells[3][3] = Some(Player::White);
+        cells[3][4] = Some(Player::Black);
+        cells[4][3] = Some(Player::Black);
+        cells[4][4] = Some(Player::White);
+        Self { cells, to_move: Player::Black }
+    }
+
+    /// Copy a bitboard position, one square at a time
+    pub fn from_board(board: &Board, to_move: Player) -> Self {
+        let mut cells = [[None; 8]; 8];
+        for (row, cells) in cells.iter_mut().enumerate() {
+            for (col, cell) in cells.iter_mut().enumerate() {
+                *cell = board.get_disc((row * 8 + col) as Position);
+            }
+        }
+        Self { cells, to_move }
+    }
+
+    /// The position as a bitboard, for comparing with the engine
+    pub fn to_board(&self) -> Board {
+        let mut board = Board::empty();
+        for row in 0..8 {
+            for col in 0..8 {
+                if let Some(player) = self.cells[row][col] {
+                    board.place(player, (row * 8 + col) as Position);
+                }
+            }
+        }
+        board
+    }
+
+    /// Side to move
+    pub fn to_move(&self) -> Player {
+        self.to_move
+    }
+
+    /// Discs `player` would flip by playing on a square, as a bitboard
+    pub fn flips(&self, player: Player, pos: Position) -> u64 {
+        let (row, col) = ((pos / 8) as i8, (pos % 8) as i8);
+        if self.cells[row as usize][col as usize].is_some() {
+            return 0;
+        }
+
+        let mut flipped = 0u64;
+        for (dr, dc) in STEPS {
+            // Walk over opponent discs until something else turns up
+            let mut run = 0u64;
+            let (mut r, mut c) = (row + dr, col + dc);
+            while (0..8).contains(&r) && (0..8).contains(&c) {
+                match self.cells[r as usize][c as usize] {
+                    Some(disc) if disc == player.opponent() => run |= 1 << (r * 8 + c),
+                    Some(_) => {
+                        // Our own disc closes the run
+                        flipped |= run;
+                        break;
+                    }
+                    None => break,
+                }
+                r += dr;
+                c += dc;
+            }
+        }
+        flipped
+    }
+
+    /// Squares `player` can play, as a bitboard
+    pub fn legal_moves(&self, player: Player) -> u64 {
+        (0..64).filter(|&pos| self.flips(player, pos) != 0).fold(0, |bits, pos| bits | 1 << pos)
+    }
+
+    /// Play a move for the side to move, returning false if it is illegal
+    pub fn play(&mut self, pos: Position) -> bool {
+        let flipped = self.flips(self.to_move, pos);
+        if flipped == 0 {
+            return false;
+        }
+        for square in 0..64 {
+            if flipped & (1 << square) != 0 || square == pos {
+                self.cells[(square / 8) as usize][(square % 8) as usize] = Some(self.to_move);
+            }
+        }
+        self.to_move = self.to_move.opponent();
+        true
+    }
+
+    /// Pass, returning false if the side to move has a move
+    pub fn pass(&mut self) -> bool {
+        if self.legal_moves(self.to_move) != 0 {
+            return false;
+        }
+        self.to_move = self.to_move.opponent();
+        true
+    }
+
+    /// Discs of each colour, (black, white)
+    pub fn counts(&self) -> (u32, u32) {
+        let count = |player| self.cells.iter().flatten().filter(|&&cell| cell == Some(player)).count() as u32;
+        (count(Player::Black), count(Player::White))
+    }
+
+    /// Whether neither side can move
+    pub fn is_over(&self) -> bool {
+        self.legal_moves(Player::Black) == 0 && self.legal_moves(Player::White) == 0
+    }
+
+    /// Result once the game is over: more discs wins
+    pub fn result(&self) -> Option<GameResult> {
+        if !self.is_over() {
+            return None;
+        }
+        let (black, white) = self.counts();
+        Some(if black > white {
+            GameResult::Win(Player::Black, black, white)
+        } else if white > black {
+            GameResult::Win(Player::White, black, white)
+        } else {
+            GameResult::Draw(black)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{calculate_flips, count_moves, legal_moves_bitboard, GameState};
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    /// Check the engine against the reference in one position
+    fn check_position(game: &GameState, reference: &ReferenceGame) {
+        let board = game.board();
+        assert_eq!(*board, reference.to_board());
+        assert_eq!(game.current_player(), reference.to_move());
+
+        for player in [Player::Black, Player::White] {
+            let legal = reference.legal_moves(player);
+            assert_eq!(legal_moves_bitboard(board, player), legal);
+            assert_eq!(count_moves(board, player), legal.count_ones());
+            for pos in 0..64 {
+                assert_eq!(calculate_flips(board, player, pos), reference.flips(player, pos));
+            }
+        }
+
+        let moves = game.legal_moves();
+        assert_eq!(moves.as_bitboard(), reference.legal_moves(reference.to_move()));
+        for m in moves.iter() {
+            assert_eq!(m.flipped, reference.flips(reference.to_move(), m.pos));
+        }
+        assert_eq!(game.counts(), reference.counts());
+    }
+
+    #[test]
+    fn test_engine_matches_reference() {
+        let mut rng = StdRng::seed_from_u64(0x07e1_1000);
+
+        for _ in 0..100 {
+            let mut game = GameState::new();
+            let mut reference = ReferenceGame::new();
+
+            while !reference.is_over() {
+                check_position(&game, &reference);
+
+                let legal = reference.legal_moves(reference.to_move());
+                if legal == 0 {
+                    assert!(game.pass());
+                    assert!(reference.pass());
+                    continue;
+                }
+
+                // An illegal move is refused by both
+                let illegal = (0..64).find(|&pos| legal & (1 << pos) == 0).unwrap();
+                assert!(game.make_move(illegal).is_none());
+                assert!(!reference.clone().play(illegal));
+
+                let pick = rng.gen_range(0..legal.count_ones() as usize);
+                let pos = Board::iter_bits(legal).nth(pick).unwrap();
+                assert!(game.make_move(pos).is_some());
+                assert!(reference.play(pos));
+            }
+            check_position(&game, &reference);
+
+            // The engine ends the game after the passes it still owes
+            while !game.is_game_over() {
+                assert!(game.pass());
+            }
+            assert_eq!(game.result(), reference.result());
+        }
+    }
+
+    #[test]
+    fn test_reference_round_trip() {
+        let game = crate::parse_transcript("F5 D6 C3 D3 C4").unwrap();
+        let reference = ReferenceGame::from_board(game.board(), game.current_player());
+        assert_eq!(reference.to_board(), *game.board());
+        assert_eq!(ReferenceGame::new().to_board(), Board::new());
+    }
+}
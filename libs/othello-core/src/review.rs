@@ -0,0 +1,516 @@
+//! Move-by-move review of a finished game
+//!
+//! A review searches every move, which is too slow to do in one go on the
+//! device, so [`GameReview::step`] looks at one move per call and the caller
+//! can show progress in between.
+//!
+//! Every reviewed move keeps a [`Judgement`], from which the move's
+//! [`Annotation`] follows. A review can be saved with
+//! [`GameReview::encode`] and read back with [`GameReview::decode`], so
+//! reopening a game doesn't search it again and a review cut short picks
+//! up where it stopped.
+
+use crate::ai::{analyze_at_depth, Analysis, BLUNDER_MARGIN};
+use crate::board::{Board, Player, CORNER_MASK};
+use crate::eval::Score;
+use crate::game::{GameState, MAX_MOVES};
+use crate::Position;
+
+/// Score gap within which a move still counts as accurate
+///
+/// Larger than an X-square penalty, smaller than a corner.
+pub const ACCURATE_MARGIN: Score = 40;
+
+/// Score given away from which a move counts as a mistake rather than an
+/// inaccuracy; from `BLUNDER_MARGIN` it is a blunder
+pub const MISTAKE_MARGIN: Score = 70;
+
+/// Saved review format version
+///
+/// Version 1 had no count of moves reviewed and was only kept once complete.
+pub const REVIEW_VERSION: u8 = 2;
+
+/// Bytes per move in a saved review
+const JUDGEMENT_LEN: usize = 6;
+
+/// How a reviewed move compared with the best one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Judgement {
+    pub best: Position,
+    /// Score given away, from the mover's perspective
+    pub loss: Score,
+    /// Whether the move was the best and every other gave away more than
+    /// `ACCURATE_MARGIN`
+    pub only: bool,
+}
+
+/// Symbol marking a reviewed move, as in annotated game scores
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Annotation {
+    /// The one good move, `!`
+    Only,
+    /// Gave away more than `ACCURATE_MARGIN`, `?!`
+    Inaccuracy,
+    /// Gave away `MISTAKE_MARGIN` or more, `?`
+    Mistake,
+    /// Gave away `BLUNDER_MARGIN` or more, about a corner, `??`
+    Blunder,
+}
+
+impl Annotation {
+    /// Every annotation, from best to worst
+    pub const ALL: [Annotation; 4] = [Annotation::Only, Annotation::Inaccuracy, Annotation::Mistake, Annotation::Blunder];
+
+    /// The annotation a judgement earns, if any
+    pub const fn of(judgement: &Judgement) -> Option<Self> {
+        match judgement.loss {
+            loss if loss >= BLUNDER_MARGIN => Some(Annotation::Blunder),
+            loss if loss >= MISTAKE_MARGIN => Some(Annotation::Mistake),
+            loss if loss > ACCURATE_MARGIN => Some(Annotation::Inaccuracy),
+            _ if judgement.only => Some(Annotation::Only),
+            _ => None,
+        }
+    }
+
+    pub const fn symbol(self) -> &'static str {
+        match self {
+            Annotation::Only => "!",
+            Annotation::Inaccuracy => "?!",
+            Annotation::Mistake => "?",
+            Annotation::Blunder => "??",
+        }
+    }
+}
+
+/// The move that gave away the most against the best one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mistake {
+    /// Index of the move in the game history
+    pub index: usize,
+    pub player: Player,
+    pub played: Position,
+    pub best: Position,
+    /// Score given away, from the mover's perspective
+    pub loss: Score,
+}
+
+/// The move that changed the disc difference the most
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Swing {
+    /// Index of the move in the game history
+    pub index: usize,
+    pub player: Player,
+    pub pos: Position,
+    /// Change in the mover's disc lead
+    pub discs: u32,
+}
+
+/// Running totals for one side
+#[derive(Debug, Clone, Copy, Default)]
+struct Tally {
+    /// Moves with a real choice
+    choices: u32,
+    /// Of those, moves within `ACCURATE_MARGIN` of the best
+    accurate: u32,
+    corners: u32,
+    mistake: Option<Mistake>,
+}
+
+/// Review of a finished game, filled in one move at a time
+#[derive(Debug, Clone)]
+pub struct GameReview {
+    /// Search depth for each move
+    depth: u8,
+    /// The game replayed up to the next move to review
+    replay: GameState,
+    /// Moves in the game being reviewed
+    total: usize,
+    tally: [Tally; 2],
+    swing: Option<Swing>,
+    /// Indexed by move; None for passes, forced moves and moves not yet
+    /// reviewed
+    judgements: [Option<Judgement>; MAX_MOVES],
+}
+
+impl GameReview {
+    /// Start reviewing a game, searching each move to `depth`
+    ///
+    /// Corners and the biggest swing need no search and are counted here.
+    pub fn new(game: &GameState, depth: u8) -> Self {
+        let mut review = Self {
+            depth,
+            replay: GameState::from_board(*game.start_board(), game.start_player()),
+            total: game.move_count(),
+            tally: [Tally::default(); 2],
+            swing: None,
+            judgements: [None; MAX_MOVES],
+        };
+
+        for (index, entry) in game.history().iter().enumerate() {
+            if entry.is_pass() {
+                continue;
+            }
+            if CORNER_MASK & (1u64 << entry.pos) != 0 {
+                review.tally[side(entry.player)].corners += 1;
+            }
+            let discs = 2 * entry.flipped.count_ones() + 1;
+            if review.swing.is_none_or(|s| discs > s.discs) {
+                review.swing = Some(Swing { index, player: entry.player, pos: entry.pos, discs });
+            }
+        }
+        review
+    }
+
+    /// Review the next move, returning false once every move is done
+    pub fn step(&mut self, game: &GameState) -> bool {
+        if let Some((board, player)) = self.pending(game) {
+            let analysis = analyze_at_depth(&board, player, self.depth);
+            self.record_analysis(game, &analysis);
+        }
+        !self.is_complete()
+    }
+
+    /// Position of the next move to review and the side that made it,
+    /// playing through any passes first
+    ///
+    /// None once every move is done. A caller running its own search, such
+    /// as one it can cancel, hands the result to `record_analysis`.
+    pub fn pending(&mut self, game: &GameState) -> Option<(Board, Player)> {
+        loop {
+            let index = self.replay.move_count();
+            let entry = game.history().get(index).filter(|_| index < self.total)?;
+            if !entry.is_pass() {
+                return Some((*self.replay.board(), entry.player));
+            }
+            self.replay.pass();
+        }
+    }
+
+    /// Judge the pending move from an analysis of its position and move on
+    pub fn record_analysis(&mut self, game: &GameState, analysis: &Analysis) {
+        let index = self.replay.move_count();
+        let entry = match game.history().get(index) {
+            Some(entry) if index < self.total && !entry.is_pass() => *entry,
+            _ => return,
+        };
+        self.judge(index, entry.player, entry.pos, analysis);
+        self.replay.make_move(entry.pos);
+    }
+
+    /// Compare a move with the best one in its position
+    fn judge(&mut self, index: usize, player: Player, played: Position, analysis: &Analysis) {
+        if analysis.len() < 2 {
+            // Forced moves say nothing about accuracy
+            return;
+        }
+        let (best, runner_up, score) = match (analysis.best(), analysis.get(1), analysis.score_of(played)) {
+            (Some(best), Some(runner_up), Some(score)) => (best, runner_up, score),
+            _ => return,
+        };
+        let loss = best.score - score;
+        let only = played == best.pos && best.score - runner_up.score > ACCURATE_MARGIN;
+        self.record(index, player, played, Judgement { best: best.pos, loss, only });
+    }
+
+    /// Count a judged move in its side's tally
+    fn record(&mut self, index: usize, player: Player, played: Position, judgement: Judgement) {
+        let tally = &mut self.tally[side(player)];
+        let loss = judgement.loss;
+        tally.choices += 1;
+        if loss <= ACCURATE_MARGIN {
+            tally.accurate += 1;
+        } else if tally.mistake.is_none_or(|m| loss > m.loss) {
+            tally.mistake = Some(Mistake { index, player, played, best: judgement.best, loss });
+        }
+        self.judgements[index] = Some(judgement);
+    }
+
+    /// How a move compared with the best one, once reviewed
+    ///
+    /// None for passes and forced moves.
+    pub fn judgement(&self, index: usize) -> Option<Judgement> {
+        self.judgements.get(index).copied().flatten()
+    }
+
+    /// Symbol for a move, once reviewed
+    pub fn annotation(&self, index: usize) -> Option<Annotation> {
+        self.judgement(index).as_ref().and_then(Annotation::of)
+    }
+
+    /// Length of the review in bytes from `encode`
+    pub const fn encoded_len(&self) -> usize {
+        4 + self.total * JUDGEMENT_LEN
+    }
+
+    /// The review as bytes: version, depth, move count and moves reviewed,
+    /// then for each move a flags byte (bit 0 judged, bit 1 only move), the
+    /// best square and the loss as a little-endian i32
+    pub fn encode(&self) -> impl Iterator<Item = u8> + '_ {
+        let header = [REVIEW_VERSION, self.depth, self.total as u8, self.replay.move_count() as u8];
+        let moves = self.judgements[..self.total].iter().flat_map(|judgement| {
+            let mut bytes = [0; JUDGEMENT_LEN];
+            if let Some(j) = judgement {
+                bytes[0] = 1 | (j.only as u8) << 1;
+                bytes[1] = j.best;
+                bytes[2..].copy_from_slice(&j.loss.to_le_bytes());
+            }
+            bytes
+        });
+        header.into_iter().chain(moves)
+    }
+
+    /// Read a review of `game` written by `encode`, complete or not
+    ///
+    /// A partial review goes on from the first move it hadn't reviewed.
+    /// Returns None if the bytes are malformed or for a different game.
+    pub fn decode(game: &GameState, data: &[u8]) -> Option<Self> {
+        let (&[version, depth, total], rest) = data.split_first_chunk::<3>()?;
+        let (reviewed, records) = match version {
+            1 => (total, rest),
+            REVIEW_VERSION => (*rest.first()?, &rest[1..]),
+            _ => return None,
+        };
+        let (total, reviewed) = (total as usize, reviewed as usize);
+        if total != game.move_count() || reviewed > total || records.len() != total * JUDGEMENT_LEN {
+            return None;
+        }
+
+        let mut review = Self::new(game, depth);
+        for (index, (record, entry)) in records.chunks_exact(JUDGEMENT_LEN).zip(game.history()).enumerate() {
+            if record[0] & 1 == 0 {
+                continue;
+            }
+            if entry.is_pass() || record[1] >= 64 || index >= reviewed {
+                return None;
+            }
+            let loss = Score::from_le_bytes([record[2], record[3], record[4], record[5]]);
+            let judgement = Judgement { best: record[1], loss, only: record[0] & 2 != 0 };
+            review.record(index, entry.player, entry.pos, judgement);
+        }
+        review.replay = game.clone_at_move(reviewed);
+        Some(review)
+    }
+
+    /// Moves reviewed and moves in the game
+    pub fn progress(&self) -> (usize, usize) {
+        (self.replay.move_count(), self.total)
+    }
+
+    /// Whether every move has been reviewed
+    pub fn is_complete(&self) -> bool {
+        self.replay.move_count() >= self.total
+    }
+
+    /// Percentage of a side's real choices that were accurate
+    ///
+    /// None until the side has had a choice to make.
+    pub fn accuracy(&self, player: Player) -> Option<u32> {
+        let tally = &self.tally[side(player)];
+        (tally.choices > 0).then(|| tally.accurate * 100 / tally.choices)
+    }
+
+    /// Corners a side took
+    pub fn corners(&self, player: Player) -> u32 {
+        self.tally[side(player)].corners
+    }
+
+    /// A side's costliest move so far
+    pub fn mistake(&self, player: Player) -> Option<Mistake> {
+        self.tally[side(player)].mistake
+    }
+
+    /// The costliest move by either side so far
+    pub fn biggest_mistake(&self) -> Option<Mistake> {
+        match (self.mistake(Player::Black), self.mistake(Player::White)) {
+            (Some(black), Some(white)) => Some(if white.loss > black.loss { white } else { black }),
+            (black, white) => black.or(white),
+        }
+    }
+
+    /// The move that changed the disc difference the most
+    pub fn swing(&self) -> Option<Swing> {
+        self.swing
+    }
+}
+
+/// Index of a side in per-side arrays
+const fn side(player: Player) -> usize {
+    match player {
+        Player::Black => 0,
+        Player::White => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_transcript, pos};
+
+    #[test]
+    fn test_review_steps_through_game() {
+        let game = parse_transcript("F5 D6 C3 D3 C4 F4 F6 F3 E6 E7").unwrap();
+        let mut review = GameReview::new(&game, 2);
+        assert_eq!(review.progress(), (0, 10));
+
+        let mut steps = 0;
+        while review.step(&game) {
+            steps += 1;
+        }
+        assert_eq!(steps, 9);
+        assert!(review.is_complete());
+        assert_eq!(review.progress(), (10, 10));
+        assert!(!review.step(&game));
+
+        for player in [Player::Black, Player::White] {
+            let accuracy = review.accuracy(player).unwrap();
+            assert!(accuracy <= 100);
+        }
+        if let Some(mistake) = review.biggest_mistake() {
+            assert!(mistake.loss > ACCURATE_MARGIN);
+            assert_eq!(game.history()[mistake.index].pos, mistake.played);
+        }
+    }
+
+    #[test]
+    fn test_review_with_own_search() {
+        let game = parse_transcript("F5 D6 C3 D3 C4 F4 F6 F3 E6 E7").unwrap();
+        let mut stepped = GameReview::new(&game, 2);
+        while stepped.step(&game) {}
+
+        // The same review from searches run outside it
+        let mut review = GameReview::new(&game, 2);
+        while let Some((board, player)) = review.pending(&game) {
+            let analysis = analyze_at_depth(&board, player, 2);
+            review.record_analysis(&game, &analysis);
+        }
+        assert!(review.is_complete());
+        for index in 0..game.move_count() {
+            assert_eq!(review.judgement(index), stepped.judgement(index));
+        }
+
+        // Stopping partway keeps the moves judged so far
+        let mut partial = GameReview::new(&game, 2);
+        for _ in 0..4 {
+            let (board, player) = partial.pending(&game).unwrap();
+            partial.record_analysis(&game, &analyze_at_depth(&board, player, 2));
+        }
+        assert_eq!(partial.progress(), (4, 10));
+        assert_eq!(partial.judgement(3), stepped.judgement(3));
+        assert_eq!(partial.judgement(4), None);
+    }
+
+    #[test]
+    fn test_corners_and_swing() {
+        let mut board = Board::empty();
+        board.place(Player::Black, pos(0, 0));
+        for col in 1..7 {
+            board.place(Player::White, pos(0, col));
+        }
+        board.place(Player::Black, pos(1, 1));
+        board.place(Player::White, pos(2, 2));
+        let mut game = GameState::from_board(board, Player::Black);
+        assert!(game.make_move(pos(0, 7)).is_some());
+
+        let review = GameReview::new(&game, 1);
+        assert_eq!(review.corners(Player::Black), 1);
+        assert_eq!(review.corners(Player::White), 0);
+        let swing = review.swing().unwrap();
+        assert_eq!((swing.index, swing.pos, swing.discs), (0, pos(0, 7), 13));
+    }
+
+    #[test]
+    fn test_annotations() {
+        let judgement = |loss, only| Judgement { best: 0, loss, only };
+        assert_eq!(Annotation::of(&judgement(0, false)), None);
+        assert_eq!(Annotation::of(&judgement(0, true)), Some(Annotation::Only));
+        assert_eq!(Annotation::of(&judgement(ACCURATE_MARGIN, false)), None);
+        assert_eq!(Annotation::of(&judgement(ACCURATE_MARGIN + 1, false)), Some(Annotation::Inaccuracy));
+        assert_eq!(Annotation::of(&judgement(MISTAKE_MARGIN, false)), Some(Annotation::Mistake));
+        assert_eq!(Annotation::of(&judgement(BLUNDER_MARGIN, false)), Some(Annotation::Blunder));
+
+        let game = parse_transcript("F5 D6 C3 D3 C4 F4 F6 F3 E6 E7").unwrap();
+        let mut review = GameReview::new(&game, 2);
+        while review.step(&game) {}
+        for index in 0..game.move_count() {
+            let annotation = review.annotation(index);
+            assert_eq!(annotation, review.judgement(index).and_then(|j| Annotation::of(&j)));
+        }
+        if let Some(mistake) = review.biggest_mistake() {
+            assert!(review.annotation(mistake.index).is_some_and(|a| a != Annotation::Only));
+        }
+    }
+
+    #[test]
+    fn test_review_round_trip() {
+        let game = parse_transcript("F5 D6 C3 D3 C4 F4 F6 F3 E6 E7").unwrap();
+        let mut review = GameReview::new(&game, 2);
+        while review.step(&game) {}
+
+        let mut bytes = [0u8; 4 + 10 * JUDGEMENT_LEN];
+        assert_eq!(review.encoded_len(), bytes.len());
+        for (slot, byte) in bytes.iter_mut().zip(review.encode()) {
+            *slot = byte;
+        }
+        let decoded = GameReview::decode(&game, &bytes).unwrap();
+        assert!(decoded.is_complete());
+        for player in [Player::Black, Player::White] {
+            assert_eq!(decoded.accuracy(player), review.accuracy(player));
+            assert_eq!(decoded.mistake(player), review.mistake(player));
+        }
+        for index in 0..game.move_count() {
+            assert_eq!(decoded.judgement(index), review.judgement(index));
+        }
+
+        // A review of another game or a damaged one is refused
+        let other = parse_transcript("F5 D6 C3 D3 C4").unwrap();
+        assert!(GameReview::decode(&other, &bytes).is_none());
+        assert!(GameReview::decode(&game, &bytes[..bytes.len() - 1]).is_none());
+        bytes[0] = REVIEW_VERSION + 1;
+        assert!(GameReview::decode(&game, &bytes).is_none());
+
+        // Version 1 had no count of moves reviewed and was always complete
+        bytes[0] = 1;
+        assert!(GameReview::decode(&game, &[&bytes[..3], &bytes[4..]].concat()).unwrap().is_complete());
+    }
+
+    #[test]
+    fn test_partial_review_resumes() {
+        let game = parse_transcript("F5 D6 C3 D3 C4 F4 F6 F3 E6 E7").unwrap();
+        let mut whole = GameReview::new(&game, 2);
+        while whole.step(&game) {}
+
+        let mut partial = GameReview::new(&game, 2);
+        for _ in 0..6 {
+            partial.step(&game);
+        }
+        let mut bytes = [0u8; 4 + 10 * JUDGEMENT_LEN];
+        for (slot, byte) in bytes.iter_mut().zip(partial.encode()) {
+            *slot = byte;
+        }
+
+        let mut resumed = GameReview::decode(&game, &bytes).unwrap();
+        assert_eq!(resumed.progress(), (6, 10));
+        while resumed.step(&game) {}
+        for index in 0..game.move_count() {
+            assert_eq!(resumed.judgement(index), whole.judgement(index));
+        }
+        for player in [Player::Black, Player::White] {
+            assert_eq!(resumed.accuracy(player), whole.accuracy(player));
+        }
+
+        // A judgement past the moves reviewed is refused
+        bytes[3] = 2;
+        assert!(GameReview::decode(&game, &bytes).is_none());
+    }
+
+    #[test]
+    fn test_empty_game() {
+        let game = GameState::new();
+        let mut review = GameReview::new(&game, 2);
+        assert!(review.is_complete());
+        assert!(!review.step(&game));
+        assert_eq!(review.accuracy(Player::Black), None);
+        assert_eq!(review.biggest_mistake(), None);
+        assert_eq!(review.swing(), None);
+    }
+}
@@ -0,0 +1,129 @@
+//! Rule variants a game can be played under
+//!
+//! A [`RuleSet`] travels with each `GameState` and is passed to the search
+//! through `SearchConfig`, so the result, the pass handling and the AI all
+//! follow the same rules. The default is standard Othello.
+
+use crate::{Board, Player};
+
+/// How a finished game is counted
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Scoring {
+    /// Each side scores the discs it has on the board
+    #[default]
+    Discs,
+    /// Tournament rule: squares still empty at the end go to the winner,
+    /// or are split evenly in a draw
+    EmptiesToWinner,
+}
+
+impl Scoring {
+    /// Final counts (black, white) for the discs left on the board
+    pub const fn final_counts(self, black: u32, white: u32) -> (u32, u32) {
+        let empty = 64 - black - white;
+        match self {
+            Scoring::Discs => (black, white),
+            Scoring::EmptiesToWinner if black > white => (black + empty, white),
+            Scoring::EmptiesToWinner if white > black => (black, white + empty),
+            // Both counts are equal, so the empty squares are even
+            Scoring::EmptiesToWinner => (black + empty / 2, white + empty / 2),
+        }
+    }
+}
+
+/// When a side with no legal move passes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PassPolicy {
+    /// The caller passes with `GameState::pass`
+    #[default]
+    Explicit,
+    /// `GameState::make_move` passes for the next side when it has no move
+    Automatic,
+}
+
+/// Opening layout of the four centre discs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StartPosition {
+    /// Othello's diagonal start: white on D4 and E5, black on E4 and D5
+    #[default]
+    Standard,
+    /// Reversi's parallel start: white on D4 and E4, black on D5 and E5
+    Parallel,
+}
+
+impl StartPosition {
+    /// The starting board
+    pub const fn board(self) -> Board {
+        match self {
+            StartPosition::Standard => Board::new(),
+            StartPosition::Parallel => Board { black: (1 << 35) | (1 << 36), white: (1 << 27) | (1 << 28) },
+        }
+    }
+}
+
+/// The rules of a game
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RuleSet {
+    pub scoring: Scoring,
+    /// Misère play: the side with fewer discs wins
+    pub misere: bool,
+    pub pass: PassPolicy,
+    pub start: StartPosition,
+}
+
+impl RuleSet {
+    /// Standard Othello
+    pub const STANDARD: RuleSet = RuleSet {
+        scoring: Scoring::Discs,
+        misere: false,
+        pass: PassPolicy::Explicit,
+        start: StartPosition::Standard,
+    };
+
+    /// Winner of a finished game from its final counts, `None` for a draw
+    pub const fn winner(&self, black: u32, white: u32) -> Option<Player> {
+        let leader = if black > white {
+            Player::Black
+        } else if white > black {
+            Player::White
+        } else {
+            return None;
+        };
+        Some(if self.misere { leader.opponent() } else { leader })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::count_moves;
+
+    #[test]
+    fn test_start_positions() {
+        assert_eq!(RuleSet::default(), RuleSet::STANDARD);
+        assert_eq!(StartPosition::Standard.board(), Board::new());
+
+        let parallel = StartPosition::Parallel.board();
+        assert_eq!(parallel.count(Player::Black), 2);
+        assert_eq!(parallel.count(Player::White), 2);
+        assert_ne!(parallel, Board::new());
+        // Black opens on C3, D3, E3 or F3
+        assert_eq!(count_moves(&parallel, Player::Black), 4);
+    }
+
+    #[test]
+    fn test_misere_winner() {
+        let misere = RuleSet { misere: true, ..RuleSet::STANDARD };
+        assert_eq!(RuleSet::STANDARD.winner(40, 24), Some(Player::Black));
+        assert_eq!(misere.winner(40, 24), Some(Player::White));
+        assert_eq!(misere.winner(32, 32), None);
+    }
+
+    #[test]
+    fn test_empties_to_winner_counts() {
+        assert_eq!(Scoring::Discs.final_counts(20, 23), (20, 23));
+        assert_eq!(Scoring::EmptiesToWinner.final_counts(20, 23), (20, 44));
+        // A draw splits them
+        assert_eq!(Scoring::EmptiesToWinner.final_counts(30, 30), (32, 32));
+    }
+}
@@ -0,0 +1,231 @@
+//! Recording of the searched tree for debugging (std only)
+//!
+//! [`trace_search`] runs the real move search with a recorder attached, so
+//! the tree shows exactly what the engine looked at: every move down to a
+//! small ply limit, the score it returned, the window it was searched with
+//! and why its search ended. The tree dumps as indented text or as a DOT
+//! graph for Graphviz.
+
+use std::fmt::Write;
+
+use crate::ai::{search_traced, NodeEnd, SearchConfig};
+use crate::board::{Board, Player};
+use crate::eval::Score;
+use crate::{pos_to_algebraic, Position};
+
+/// One searched move in the tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceNode {
+    /// Move leading here, `None` for the root
+    pub pos: Option<Position>,
+    /// Side that made the move
+    pub player: Player,
+    /// Index of the parent node, `None` for the root
+    pub parent: Option<usize>,
+    /// Moves from the root
+    pub ply: u8,
+    /// Window the node was searched with
+    pub alpha: Score,
+    pub beta: Score,
+    /// Score returned to the parent, from the searching side's perspective
+    pub score: Score,
+    /// The side to move had to pass before searching
+    pub passed: bool,
+    pub end: NodeEnd,
+}
+
+/// The searched tree, in the order nodes were visited
+pub struct SearchTree {
+    nodes: Vec<TraceNode>,
+    /// Recorded nodes on the current line, root first
+    path: Vec<usize>,
+    /// Nodes entered below the ply limit and not yet left
+    hidden: usize,
+    max_ply: u8,
+    best: Option<Position>,
+}
+
+impl SearchTree {
+    fn new(player: Player, max_ply: u8) -> Self {
+        let root = TraceNode {
+            pos: None,
+            player: player.opponent(),
+            parent: None,
+            ply: 0,
+            alpha: crate::eval::SCORE_LOSS,
+            beta: crate::eval::SCORE_WIN,
+            score: 0,
+            passed: false,
+            end: NodeEnd::Open,
+        };
+        Self { nodes: vec![root], path: vec![0], hidden: 0, max_ply, best: None }
+    }
+
+    /// Recorded nodes, root first, each before its children
+    pub fn nodes(&self) -> &[TraceNode] {
+        &self.nodes
+    }
+
+    /// Move the search chose
+    pub fn best(&self) -> Option<Position> {
+        self.best
+    }
+
+    /// Note a move about to be searched with the given window
+    pub(crate) fn enter(&mut self, pos: Position, player: Player, alpha: Score, beta: Score) {
+        let ply = self.path.len() as u8;
+        if self.hidden > 0 || ply > self.max_ply {
+            self.hidden += 1;
+            return;
+        }
+        self.nodes.push(TraceNode {
+            pos: Some(pos),
+            player,
+            parent: self.path.last().copied(),
+            ply,
+            alpha,
+            beta,
+            score: 0,
+            passed: false,
+            end: NodeEnd::Open,
+        });
+        self.path.push(self.nodes.len() - 1);
+    }
+
+    /// Note the score of the move last entered
+    pub(crate) fn leave(&mut self, score: Score) {
+        if self.hidden > 0 {
+            self.hidden -= 1;
+            return;
+        }
+        if let Some(index) = self.path.pop() {
+            self.nodes[index].score = score;
+        }
+    }
+
+    /// Note a pass at the node being searched
+    pub(crate) fn pass(&mut self) {
+        if let Some(node) = self.current() {
+            node.passed = true;
+        }
+    }
+
+    /// Note why the node being searched stopped
+    pub(crate) fn end(&mut self, end: NodeEnd) {
+        if let Some(node) = self.current() {
+            node.end = end;
+        }
+    }
+
+    fn current(&mut self) -> Option<&mut TraceNode> {
+        if self.hidden > 0 {
+            return None;
+        }
+        let index = *self.path.last()?;
+        Some(&mut self.nodes[index])
+    }
+
+    /// The tree as indented text, one node per line
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        for node in &self.nodes {
+            let _ = writeln!(out, "{:indent$}{}", "", describe(node, ", "), indent = node.ply as usize * 2);
+        }
+        out
+    }
+
+    /// The tree as a Graphviz DOT graph
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph search {\n    node [shape=box, fontname=monospace];\n");
+        for (index, node) in self.nodes.iter().enumerate() {
+            let _ = writeln!(out, "    n{} [label=\"{}\"];", index, describe(node, "\\n"));
+            if let Some(parent) = node.parent {
+                let _ = writeln!(out, "    n{} -> n{};", parent, index);
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+/// Move, score, window and end reason of a node, joined by `separator`
+fn describe(node: &TraceNode, separator: &str) -> String {
+    let mut out = String::new();
+    match node.pos {
+        Some(pos) => {
+            let square = pos_to_algebraic(pos);
+            let _ = write!(out, "{}{} {:?}", square[0] as char, square[1] as char, node.player);
+        }
+        None => out.push_str("root"),
+    }
+    if node.parent.is_some() {
+        let _ = write!(out, "{}{} [{}, {}]", separator, node.score, node.alpha, node.beta);
+    }
+    if node.passed {
+        let _ = write!(out, "{}pass", separator);
+    }
+    let _ = match node.end {
+        NodeEnd::Open => write!(out, "{}not finished", separator),
+        NodeEnd::Leaf => write!(out, "{}leaf", separator),
+        NodeEnd::GameOver => write!(out, "{}game over", separator),
+        NodeEnd::Searched { moves } => write!(out, "{}searched {} moves", separator, moves),
+        NodeEnd::BetaCutoff { searched, moves } => {
+            write!(out, "{}beta cutoff after {} of {}", separator, searched, moves)
+        }
+        NodeEnd::AlphaCutoff { searched, moves } => {
+            write!(out, "{}alpha cutoff after {} of {}", separator, searched, moves)
+        }
+        NodeEnd::Table => write!(out, "{}from table", separator),
+    };
+    out
+}
+
+/// Search a position as `find_best_move_with` would, recording every node
+/// down to `max_ply` moves from the root
+///
+/// Book moves and forced moves are played without a search, which leaves
+/// only the root in the tree.
+pub fn trace_search(board: &Board, player: Player, config: SearchConfig, max_ply: u8) -> SearchTree {
+    let mut tree = SearchTree::new(player, max_ply);
+    let best = search_traced(board, player, config, &mut tree);
+    tree.best = best;
+    tree
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::Difficulty;
+
+    #[test]
+    fn test_trace_records_search() {
+        let game = crate::parse_transcript("D3 C5 C6 C3 C4 B5 A6 E3 E6 B7 F4 F5").unwrap();
+        let board = *game.board();
+        let config = SearchConfig::new(Difficulty::Medium);
+        let tree = trace_search(&board, Player::Black, config, 2);
+
+        // Same move as the untraced search
+        let cancel = core::sync::atomic::AtomicBool::new(false);
+        assert_eq!(tree.best(), crate::find_best_move_with(&board, Player::Black, config, &cancel));
+
+        // Every root move is recorded, nothing past the ply limit, and
+        // every node either finished or was cut off
+        let nodes = tree.nodes();
+        let root_moves = nodes.iter().filter(|n| n.ply == 1).count();
+        assert_eq!(root_moves, crate::count_moves(&board, Player::Black) as usize);
+        assert!(nodes.iter().all(|n| n.ply <= 2 && n.end != NodeEnd::Open));
+        assert!(nodes.iter().any(|n| matches!(n.end, NodeEnd::BetaCutoff { .. } | NodeEnd::AlphaCutoff { .. })));
+        for (index, node) in nodes.iter().enumerate().skip(1) {
+            let parent = node.parent.unwrap();
+            assert!(parent < index);
+            assert_eq!(nodes[parent].ply + 1, node.ply);
+        }
+
+        let text = tree.to_text();
+        assert_eq!(text.lines().count(), nodes.len());
+        assert!(text.starts_with("root"));
+        let dot = tree.to_dot();
+        assert!(dot.starts_with("digraph search {"));
+        assert_eq!(dot.matches(" -> ").count(), nodes.len() - 1);
+    }
+}
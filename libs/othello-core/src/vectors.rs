@@ -0,0 +1,106 @@
+//! Position to move test vectors for the search
+//!
+//! Each vector is a position, the limits to search it with and the move the
+//! search must choose. The search is deterministic (see the `ai` module),
+//! so every build has to agree with these, whatever its word size. Hosted
+//! tests check them here; a device build can run the same checks with
+//! `SearchVector::passes` and report the ones that differ, so a bug report
+//! from either build can be reproduced on the other.
+//!
+//! The starting position is symmetric, so its vectors also pin down the
+//! tie-break between equal moves.
+
+use crate::{Difficulty, Position, SearchLimits, algebraic_to_pos, find_best_move, parse_position};
+
+/// A position, the limits to search it with and the expected move
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchVector {
+    /// Position in the notation of `parse_position`
+    pub position: &'static str,
+    pub limits: SearchLimits,
+    /// The move the search must choose, like `"D3"`
+    pub best: &'static str,
+}
+
+impl SearchVector {
+    /// Search the position, returning the move chosen
+    pub fn search(&self) -> Option<Position> {
+        let (board, player) = parse_position(self.position)?;
+        find_best_move(&board, player, self.limits)
+    }
+
+    /// Whether the search chooses the expected move
+    pub fn passes(&self) -> bool {
+        let best = algebraic_to_pos(self.best.as_bytes());
+        best.is_some() && self.search() == best
+    }
+}
+
+const START: &str = "---------------------------OX------XO---------------------------X";
+const AFTER_F5: &str = "---------------------------OX------XXX--------------------------O";
+const AFTER_F5_D6_C3: &str = "------------------X--------XX------OXX-----O--------------------O";
+const PERPENDICULAR: &str = "---------------------------OOO-----XXO------XO------------------X";
+const MIDGAME: &str = "------------------OOO-----XXOX---XOOOO--X-O-X----O--------------X";
+const LATE_20: &str = "----OX----OXX--X--OXOOXX--XXOXXXOOOOOXXXOOXXOXXXOOOXXO--X-XX--O-X";
+const LATE_14: &str = "-X-OOX--O-XOOO-X-OXXOXXX--OOOXXXOOOOOXXXOOXXOXXXOOOXXX--X-XXX-O-X";
+const LATE_10: &str = "-X-OOOO-OOOOOO-X-OOXOXXXXOXOXXXXXOXOOXXXXOXXOXXXXOOXXX--X-XXX-O-X";
+
+const fn vector(position: &'static str, limits: SearchLimits, best: &'static str) -> SearchVector {
+    SearchVector { position, limits, best }
+}
+
+/// Vectors every build must pass, from the opening to exact endgame solves
+pub const SEARCH_VECTORS: &[SearchVector] = &[
+    // Four equal opening moves: the first square searched wins
+    vector(START, Difficulty::Easy.limits(), "D3"),
+    vector(START, Difficulty::Hard.limits(), "D3"),
+    vector(AFTER_F5, Difficulty::Medium.limits(), "D6"),
+    vector(AFTER_F5, Difficulty::Hard.limits(), "F6"),
+    vector(AFTER_F5_D6_C3, Difficulty::Easy.limits(), "F4"),
+    vector(AFTER_F5_D6_C3, Difficulty::Hard.limits(), "D3"),
+    vector(PERPENDICULAR, Difficulty::Easy.limits(), "C3"),
+    vector(PERPENDICULAR, Difficulty::Medium.limits(), "E3"),
+    vector(PERPENDICULAR, Difficulty::Hard.limits(), "G3"),
+    vector(MIDGAME, Difficulty::Medium.limits(), "C7"),
+    vector(MIDGAME, Difficulty::Hard.limits(), "D6"),
+    // Stopped by the node limit partway through deepening
+    vector(MIDGAME, SearchLimits::depth(6).with_nodes(2000), "C7"),
+    vector(LATE_20, Difficulty::Easy.limits(), "A4"),
+    vector(LATE_20, Difficulty::Hard.limits(), "F8"),
+    // Searched to depth, short of the solver's threshold
+    vector(LATE_14, Difficulty::Hard.limits(), "C1"),
+    vector(LATE_10, Difficulty::Medium.limits(), "A1"),
+    // Solved exactly
+    vector(LATE_14, SearchLimits::depth(4).with_endgame_empties(14), "C1"),
+    vector(LATE_10, Difficulty::Hard.limits(), "A3"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_vectors() {
+        for v in SEARCH_VECTORS {
+            assert!(parse_position(v.position).is_some(), "{}", v.position);
+            assert!(v.passes(), "{} {:?}: expected {}, got {:?}", v.position, v.limits, v.best, v.search());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_vectors_repeat() {
+        use crate::{SearchConfig, find_best_move_parallel};
+
+        // Repeated and threaded searches choose the same move; threads
+        // count nodes separately, so node limits are left out
+        for v in SEARCH_VECTORS {
+            assert_eq!(v.search(), v.search());
+            if v.limits.nodes.is_none() {
+                let (board, player) = parse_position(v.position).unwrap();
+                let threaded = find_best_move_parallel(&board, player, SearchConfig::from(v.limits), 3);
+                assert_eq!(threaded, v.search(), "{}", v.position);
+            }
+        }
+    }
+}
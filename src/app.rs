@@ -1,33 +1,425 @@
 //! Main application state and logic
 
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
 use gam::Gid;
 use gam::menu::Point;
-use othello_core::{GameState, GameResult, Player, Difficulty, find_best_move, pos};
+use othello_core::{Analysis, Board, CORNER_MASK, MoveScore, PassPolicy, RuleSet, GameReview, GameState, GameResult, HistoryEntry, LearnedBook, Mistake, Player, Position, Variation, Difficulty, find_best_move, analyze_moves_cancellable, Searcher, SearchConfig, SearchMethod, SearchReport, HINT_LIMITS, find_gentle_move, find_adaptive_move, find_blunder, identify_opening, count_moves, evaluate, frontier_bitboard, Score, pos, pos_to_algebraic, pos_to_rc, replay_transcript, TranscriptFault};
 
 use crate::menu::{Menu, MenuItem, MenuContext};
-use crate::storage::{Settings, Statistics};
+use crate::command::Command;
+use crate::dialog::{ChoiceAction, ConfirmAction, Dialog, DialogResult, PromptAction};
+use crate::coach::CoachRequest;
+use crate::export::format_compact;
+use crate::puzzle::{Objective, Puzzle};
+use crate::storage::{ArchiveMode, ArchivedGame, GameClock, GameMeta, Handicap, HapticCue, MAX_NOTE, Settings, Statistics, StatusItem, STATS_LEVELS, margin_bucket, mode_code, mode_from_code};
 use crate::ui;
 use crate::help::HelpContext;
+use crate::review_queue::ReviewQueue;
+use crate::toast::Toast;
+use crate::transfer::Transfer;
+use crate::i18n::{fill, Strings};
 use crate::AppOp;
 
+/// Search depth for each move of the game over review
+///
+/// Medium strength, so a full game takes a few seconds per move at worst.
+pub const REVIEW_DEPTH: u8 = 4;
+
+/// Strength of each move's search in a deep analysis
+///
+/// The endgame solve makes a full game take minutes on the device.
+const DEEP_ANALYSIS: Difficulty = Difficulty::Hard;
+
+/// Longest a CPU move at a fixed-depth level may search before it is cut
+/// short; levels that search against the clock keep to their own time
+const AI_SEARCH_CAP_MS: u64 = 30_000;
+
+/// A game clock under this many seconds flashes and gives the low time cue
+pub const LOW_TIME_SECS: u16 = 30;
+
+/// What the key and background handlers need from the system besides the
+/// screen: the ticktimer's clock and the GAM's menu
+///
+/// `Xous` holds the live connections; the headless tests stand in fakes.
+pub trait Services {
+    /// Milliseconds since boot
+    fn elapsed_ms(&self) -> u64;
+    /// Wait before carrying on
+    fn sleep_ms(&self, ms: usize);
+    /// Raise the app's F1 menu, returning true if it came up
+    fn raise_menu(&self) -> bool;
+}
+
+/// The GAM and ticktimer connections of the running app
+pub struct Xous<'a> {
+    pub gam: &'a gam::Gam,
+    pub ticktimer: &'a ticktimer_server::Ticktimer,
+}
+
+impl Services for Xous<'_> {
+    fn elapsed_ms(&self) -> u64 {
+        self.ticktimer.elapsed_ms()
+    }
+
+    fn sleep_ms(&self, ms: usize) {
+        self.ticktimer.sleep_ms(ms).ok();
+    }
+
+    fn raise_menu(&self) -> bool {
+        self.gam.raise_menu(gam::APP_MENU_0_OTHELLO).is_ok()
+    }
+}
+
 /// Game mode (vs CPU or two player)
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum GameMode {
     VsCpu(Difficulty),
     TwoPlayer,
+    /// CPU whose strength follows the player's results
+    Adaptive,
+}
+
+impl GameMode {
+    /// Every mode, in New Game menu order
+    pub const ALL: [GameMode; 7] = [
+        GameMode::VsCpu(Difficulty::Easy),
+        GameMode::VsCpu(Difficulty::Medium),
+        GameMode::VsCpu(Difficulty::Hard),
+        GameMode::VsCpu(Difficulty::Expert),
+        GameMode::VsCpu(Difficulty::Grandmaster),
+        GameMode::TwoPlayer,
+        GameMode::Adaptive,
+    ];
+
+    /// Side scores and evaluations are shown for: the player against the
+    /// CPU, Black between two players
+    pub const fn scoring_side(self, player_color: Player) -> Player {
+        match self {
+            GameMode::VsCpu(_) | GameMode::Adaptive => player_color,
+            GameMode::TwoPlayer => Player::Black,
+        }
+    }
+
+    /// Index of the level's record in the statistics, for CPU modes
+    pub const fn stats_level(self) -> Option<usize> {
+        match self {
+            GameMode::VsCpu(Difficulty::Easy) => Some(0),
+            GameMode::VsCpu(Difficulty::Medium) => Some(1),
+            GameMode::VsCpu(Difficulty::Hard) => Some(2),
+            GameMode::VsCpu(Difficulty::Expert) => Some(3),
+            GameMode::VsCpu(Difficulty::Grandmaster) => Some(4),
+            GameMode::Adaptive => Some(5),
+            GameMode::TwoPlayer => None,
+        }
+    }
+}
+
+/// What the game screen shows about the position, worked out once per
+/// move rather than on every redraw and cursor step
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlyInfo {
+    /// Squares the side to move can play
+    pub legal: u64,
+    /// Legal moves for Black and for White
+    pub mobility: (u32, u32),
+    /// Discs next to an empty square, Black's then White's
+    pub frontier: [u64; 2],
+    /// Evaluation from the scoring side's view
+    pub eval: Score,
+    /// The last move, such as "D3", the word for a pass, or "--" before
+    /// the first
+    pub last_move: String,
+}
+
+impl PlyInfo {
+    /// Work out the facts for a game's current position
+    pub fn new(game: &GameState, mode: GameMode, player_color: Player, s: &Strings) -> Self {
+        let board = game.board();
+        let last_move = match game.last_move() {
+            Some(entry) if entry.is_pass() => String::from(s.pass),
+            Some(entry) => {
+                let alg = pos_to_algebraic(entry.pos);
+                String::from(core::str::from_utf8(&alg).unwrap_or("--"))
+            }
+            None => String::from("--"),
+        };
+        Self {
+            legal: game.legal_moves_bitboard(),
+            mobility: (count_moves(board, Player::Black), count_moves(board, Player::White)),
+            frontier: [frontier_bitboard(board, Player::Black), frontier_bitboard(board, Player::White)],
+            eval: evaluate(board, mode.scoring_side(player_color)),
+            last_move,
+        }
+    }
+}
+
+/// Pages of the Statistics screen: records, margin histograms, openings
+pub const STATS_PAGES: usize = 4;
+
+/// Moves of each demo game picked at random among the good ones, so no
+/// two demos play alike
+const DEMO_OPENING_PLIES: usize = 4;
+
+/// Seconds each demo move stays on screen before the next
+const DEMO_MOVE_SECS: u8 = 2;
+
+/// Seconds a finished demo game stays on screen before the next starts
+const DEMO_END_SECS: u8 = 10;
+
+/// Seconds the main menu sits untouched before archived games are reviewed,
+/// a move a second
+const IDLE_REVIEW_SECS: u32 = 30;
+
+/// Slow puzzle-screen work run on the next pump
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PuzzleTask {
+    /// Generate the daily puzzle
+    Daily(u16),
+}
+
+/// Which way a backup transfer goes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackupTask {
+    /// Wait for a host to collect a backup
+    Send,
+    /// Wait for a backup to restore
+    Receive,
+}
+
+/// Search of one position, running on its own thread so keys can still
+/// stop it
+#[derive(Debug, Clone)]
+pub struct AnalysisWorker<T = Analysis> {
+    /// The search's stop flag
+    cancel: Arc<AtomicBool>,
+    /// Filled in by the thread when its search ends
+    result: Arc<Mutex<Option<T>>>,
+}
+
+impl AnalysisWorker {
+    /// Start a deep analysis search, pumping `cid` once the result is in
+    fn spawn(board: Board, player: Player, cid: Option<xous::CID>) -> Self {
+        Self::run(cid, move |cancel| analyze_moves_cancellable(&board, player, DEEP_ANALYSIS.limits(), cancel))
+    }
+}
+
+impl AnalysisWorker<(Analysis, Variation)> {
+    /// Start the analysis board's search, pumping `cid` once the result is in
+    fn spawn_line(board: Board, player: Player, depth: u8, cid: Option<xous::CID>) -> Self {
+        Self::run(cid, move |cancel| {
+            let mut searcher = Searcher::<ANALYSIS_TABLE_ENTRIES>::new();
+            searcher.analyze_line(&board, player, depth, cancel)
+        })
+    }
+}
+
+impl<T: Send + 'static> AnalysisWorker<T> {
+    /// Run `search` on a thread of its own
+    fn run(cid: Option<xous::CID>, search: impl FnOnce(&AtomicBool) -> T + Send + 'static) -> Self {
+        let worker = Self { cancel: Arc::new(AtomicBool::new(false)), result: Arc::new(Mutex::new(None)) };
+        let (cancel, result) = (worker.cancel.clone(), worker.result.clone());
+        std::thread::spawn(move || {
+            let analysis = search(&cancel);
+            if let Ok(mut slot) = result.lock() {
+                *slot = Some(analysis);
+            }
+            if let Some(cid) = cid {
+                xous::send_message(cid, xous::Message::new_scalar(AppOp::AiPump as usize, 0, 0, 0, 0)).ok();
+            }
+        });
+        worker
+    }
+
+    /// Tell the search to stop; its result is no longer wanted
+    fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
+    /// The finished search, once the thread has left it
+    fn take_result(&self) -> Option<T> {
+        self.result.lock().ok()?.take()
+    }
+}
+
+/// Thread sending a Tick each second, run only while something counts
+/// seconds so an idle app leaves the event loop asleep
+#[derive(Debug, Default)]
+struct Ticker {
+    /// The running thread's stop flag
+    stop: Option<Arc<AtomicBool>>,
+}
+
+impl Ticker {
+    /// Start ticking `cid`, unless already running
+    fn start(&mut self, cid: xous::CID) {
+        if self.stop.is_some() {
+            return;
+        }
+        let stop = Arc::new(AtomicBool::new(false));
+        let stopped = stop.clone();
+        std::thread::spawn(move || {
+            let ticktimer = ticktimer_server::Ticktimer::new().unwrap();
+            loop {
+                ticktimer.sleep_ms(1000).ok();
+                if stopped.load(Ordering::Relaxed) {
+                    break;
+                }
+                xous::send_message(cid, xous::Message::new_scalar(AppOp::Tick as usize, 0, 0, 0, 0)).ok();
+            }
+        });
+        self.stop = Some(stop);
+    }
+
+    /// Stop ticking; the thread ends within a second
+    fn stop(&mut self) {
+        if let Some(stop) = self.stop.take() {
+            stop.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+/// How much of the board blindfold mode hides
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Blindfold {
+    Off,
+    /// Hide everything but the edges
+    Inner,
+    /// Hide every disc
+    All,
+}
+
+impl Blindfold {
+    /// Next level when toggling
+    pub const fn next(self) -> Self {
+        match self {
+            Blindfold::Off => Blindfold::Inner,
+            Blindfold::Inner => Blindfold::All,
+            Blindfold::All => Blindfold::Off,
+        }
+    }
+
+    /// Bitboard of squares whose discs are hidden
+    pub const fn mask(self) -> u64 {
+        match self {
+            Blindfold::Off => 0,
+            Blindfold::Inner => 0x007e_7e7e_7e7e_7e00,
+            Blindfold::All => !0,
+        }
+    }
+
+    /// Notice shown after toggling
+    pub const fn describe(self, s: &Strings) -> &'static str {
+        match self {
+            Blindfold::Off => s.blindfold_off,
+            Blindfold::Inner => s.blindfold_inner,
+            Blindfold::All => s.blindfold_all,
+        }
+    }
+}
+
+/// Line graph What If shows in place of the board
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReviewGraph {
+    Off,
+    /// Legal moves for each side
+    Mobility,
+    /// Discs next to an empty square for each side
+    Frontier,
+}
+
+impl ReviewGraph {
+    /// Next graph when toggling
+    pub const fn next(self) -> Self {
+        match self {
+            ReviewGraph::Off => ReviewGraph::Mobility,
+            ReviewGraph::Mobility => ReviewGraph::Frontier,
+            ReviewGraph::Frontier => ReviewGraph::Off,
+        }
+    }
+}
+
+/// Most bookmarks What If keeps; the oldest goes first
+const MAX_BOOKMARKS: usize = 12;
+
+/// A What If position to come back to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bookmark {
+    /// Moves from the start of the game, as a compact transcript
+    pub transcript: String,
+    /// Moves played to reach the position, including any branch
+    pub moves: usize,
+    /// Position in the reviewed game, or where the branch left it
+    pub view_index: usize,
+    pub branched: bool,
+}
+
+/// Search depth the analysis board starts at
+const DEFAULT_ANALYSIS_DEPTH: u8 = 6;
+/// Deepest search the analysis board offers
+const MAX_ANALYSIS_DEPTH: u8 = 10;
+/// Table entries for the analysis board's search, small enough to keep on
+/// the worker's stack
+const ANALYSIS_TABLE_ENTRIES: usize = 1024;
+
+/// Longest notation line that can be typed (a full transcript with spaces)
+const MAX_NOTATION_INPUT: usize = 192;
+
+/// Summary of the saved game for the main menu
+#[derive(Debug, Clone)]
+pub struct SavePreview {
+    pub board: Board,
+    pub to_move: Player,
+    pub move_count: usize,
+    pub mode: GameMode,
+}
+
+impl SavePreview {
+    /// Read the saved game, if there is one
+    pub fn load() -> Option<Self> {
+        crate::storage::load_game().map(|(game, mode, _, _)| SavePreview {
+            board: *game.board(),
+            to_move: game.current_player(),
+            move_count: game.move_count(),
+            mode,
+        })
+    }
 }
 
 /// Main application state
 #[derive(Debug, Clone)]
 pub enum AppState {
     /// Main menu
-    MainMenu,
+    MainMenu {
+        selected: usize,
+        /// Saved game shown beside Resume
+        preview: Option<SavePreview>,
+    },
     /// New game selection
-    NewGameMenu,
+    NewGameMenu {
+        /// Index into [`GameMode::ALL`]
+        selected: usize,
+    },
+    /// Handicap choice for the selected mode
+    HandicapMenu {
+        mode: GameMode,
+    },
     /// Settings screen
     SettingsMenu,
+    /// Choice of what the status lines show
+    StatusLines {
+        selected: usize,
+    },
+    /// Vibration as a whole, then each haptic cue
+    Vibration {
+        selected: usize,
+    },
     /// Statistics display
-    Statistics,
+    Statistics {
+        page: usize,
+        /// Level whose margins are charted, see [`GameMode::stats_level`]
+        level: usize,
+    },
     /// Active game
     Playing {
         game: GameState,
@@ -37,12 +429,49 @@ pub enum AppState {
         ai_thinking: bool,
         thinking_dots: u8,
         show_pass_notice: bool,
+        /// One-shot status message, cleared by the next key
+        notice: Option<String>,
+        /// Assistance used so far
+        meta: GameMeta,
+        /// Move waiting for coach feedback
+        coach_pending: Option<CoachRequest>,
+        blindfold: Blindfold,
+        /// Board revealed until the next key
+        peeking: bool,
+        /// Hints and overlays hidden until the next player takes the device
+        handover: bool,
+        /// The position's legal moves, mobility and the like, refreshed
+        /// whenever the game changes
+        ply: PlyInfo,
     },
     /// Game over screen
     GameOver {
         game: GameState,
         mode: GameMode,
         player_color: Player,
+        meta: GameMeta,
+        /// Summary filled in on the background pump
+        review: Box<GameReview>,
+        /// Whether the game is in the archive
+        archived: bool,
+        /// Archive key of the game, where its review is kept once finished
+        archive_key: Option<String>,
+    },
+    /// Archived games being reviewed one after another
+    ArchiveReview {
+        /// Games finished since the screen opened
+        finished: usize,
+    },
+    /// Review of a finished game at `DEEP_ANALYSIS` strength
+    DeepAnalysis {
+        game: GameState,
+        review: Box<GameReview>,
+        /// Best score in the last position searched, from Black's side
+        eval: Option<MoveScore>,
+        /// Seconds spent searching on this screen
+        elapsed_secs: u32,
+        /// Search of the next move; None once finished or cancelled
+        worker: Option<AnalysisWorker>,
     },
     /// What If review mode
     WhatIf {
@@ -51,17 +480,243 @@ pub enum AppState {
         view_index: usize,
         branched: bool,
         cursor_pos: (u8, u8),
+        graph: ReviewGraph,
+        /// Positions marked while exploring
+        bookmarks: Vec<Bookmark>,
+        /// Send of the line to a host, until it reports back
+        sending: Option<Transfer>,
+    },
+    /// Bookmarks of the What If screen underneath
+    Bookmarks {
+        selected: usize,
+    },
+    /// Finished games kept in PDDB
+    Archive {
+        /// Games with their keys, newest first
+        games: Vec<(String, ArchivedGame)>,
+        selected: usize,
+        /// Send of the selected game to a host, until it reports back
+        sending: Option<Transfer>,
     },
     /// Move history view
     MoveHistory {
         game: GameState,
         scroll_offset: usize,
     },
+    /// The position in words, for reading aloud or large print
+    PositionText {
+        lines: Vec<String>,
+        scroll_offset: usize,
+    },
     /// Help screen
     Help {
         context: HelpContext,
-        previous: Box<AppState>,
+        /// Page shown, from 0
+        page: usize,
+    },
+    /// Tutorial lesson
+    Tutorial {
+        /// Index into `tutorial::LESSONS`
+        lesson: usize,
+        game: GameState,
+        cursor_pos: (u8, u8),
+        /// Whether the teaching move has been played
+        solved: bool,
+        /// Feedback on the last attempt
+        message: Option<&'static str>,
+    },
+    /// Stored puzzle packs
+    PuzzleMenu {
+        packs: Vec<String>,
+        selected: usize,
+        /// Result of the last open or import
+        status: Option<String>,
+        /// Work waiting for the next pump
+        task: Option<PuzzleTask>,
+        /// Pack being received
+        import: Option<Transfer>,
+        /// Today's day number, if the clock is set
+        today: Option<u16>,
+    },
+    /// Solving a puzzle from a pack
+    Puzzle {
+        pack: String,
+        puzzles: Vec<Puzzle>,
+        index: usize,
+        game: GameState,
+        cursor_pos: (u8, u8),
+        /// CPU is defending
+        cpu_thinking: bool,
+        /// Some(true) once solved, Some(false) once failed
+        outcome: Option<bool>,
+        message: Option<String>,
+        /// Day number when this is the daily puzzle
+        daily: Option<u16>,
+    },
+    /// Setting up a position by hand
+    Editor {
+        board: Board,
+        to_move: Player,
+        cursor_pos: (u8, u8),
+        /// Validation error or analysis result
+        message: Option<String>,
+    },
+    /// Analysis board for a set-up position
+    Analysis {
+        board: Board,
+        to_move: Player,
+        depth: u8,
+        /// Move scores and expected line from the last search
+        result: Option<(Analysis, Variation)>,
+        /// Search under way; None once finished or cancelled
+        worker: Option<AnalysisWorker<(Analysis, Variation)>>,
     },
+    /// Typing or receiving a position or transcript
+    LoadPosition {
+        input: String,
+        status: Option<String>,
+        /// Notation being received over TCP
+        receiving: Option<Transfer>,
+    },
+    /// CPU-vs-CPU demo started from an idle main menu
+    Demo {
+        game: GameState,
+        /// Seconds left before the next move or game
+        wait: u8,
+    },
+    /// Sending or receiving a backup of all stored data
+    Backup {
+        /// Result of the last transfer
+        status: Option<String>,
+        /// Direction of the running transfer
+        task: Option<BackupTask>,
+        /// The running transfer
+        transfer: Option<Transfer>,
+    },
+}
+
+impl AppState {
+    /// Create the playing state for a game, with the cursor centred
+    ///
+    /// Passes are made by the game itself as moves are played.
+    pub fn playing(mut game: GameState, mode: GameMode, player_color: Player, meta: GameMeta, s: &Strings) -> Self {
+        game.set_rules(RuleSet { pass: PassPolicy::Automatic, ..*game.rules() });
+        let ply = PlyInfo::new(&game, mode, player_color, s);
+        AppState::Playing {
+            game,
+            mode,
+            player_color,
+            cursor_pos: (3, 3),
+            ai_thinking: false,
+            thinking_dots: 0,
+            show_pass_notice: false,
+            notice: None,
+            meta,
+            coach_pending: None,
+            blindfold: Blindfold::Off,
+            peeking: false,
+            handover: false,
+            ply,
+        }
+    }
+
+    /// Create the game over screen, with its review still to run
+    pub fn game_over(game: GameState, mode: GameMode, player_color: Player, meta: GameMeta) -> Self {
+        let review = Box::new(GameReview::new(&game, REVIEW_DEPTH));
+        AppState::GameOver { game, mode, player_color, meta, review, archived: false, archive_key: None }
+    }
+
+    /// Create the game over screen for an archived game, reusing its saved
+    /// review when there is one
+    pub fn archived_game_over(name: String, archived: ArchivedGame) -> Self {
+        let review = GameReview::decode(&archived.game, &archived.review)
+            .unwrap_or_else(|| GameReview::new(&archived.game, REVIEW_DEPTH));
+        AppState::GameOver {
+            game: archived.game,
+            mode: archived.mode,
+            player_color: archived.player_color,
+            meta: archived.meta,
+            review: Box::new(review),
+            archived: true,
+            archive_key: Some(name),
+        }
+    }
+
+    /// Create the main menu, reading the saved game for its preview
+    pub fn main_menu() -> Self {
+        AppState::MainMenu {
+            selected: 0,
+            preview: SavePreview::load(),
+        }
+    }
+
+    /// Create the state for a tutorial lesson
+    pub fn lesson(index: usize) -> Self {
+        let lesson = &crate::tutorial::LESSONS[index];
+        AppState::Tutorial {
+            lesson: index,
+            game: GameState::from_board(lesson.board(), lesson.to_move),
+            cursor_pos: (3, 3),
+            solved: false,
+            message: None,
+        }
+    }
+
+    /// Create the archive list, newest first
+    pub fn archive() -> Self {
+        AppState::Archive {
+            games: crate::storage::load_archive(),
+            selected: 0,
+            sending: None,
+        }
+    }
+
+    /// Create the pack list screen
+    pub fn puzzle_menu() -> Self {
+        AppState::PuzzleMenu {
+            packs: crate::storage::list_puzzle_packs(),
+            selected: 0,
+            status: None,
+            task: None,
+            import: None,
+            today: crate::puzzle::today(),
+        }
+    }
+
+    /// Create the state for a puzzle in a pack
+    pub fn puzzle(pack: String, puzzles: Vec<Puzzle>, index: usize) -> Self {
+        let game = puzzles[index].start();
+        AppState::Puzzle {
+            pack,
+            puzzles,
+            index,
+            game,
+            cursor_pos: (3, 3),
+            cpu_thinking: false,
+            outcome: None,
+            message: None,
+            daily: None,
+        }
+    }
+
+    /// Create the editor, starting from the standard position
+    pub fn editor() -> Self {
+        AppState::Editor {
+            board: Board::new(),
+            to_move: Player::Black,
+            cursor_pos: (3, 3),
+            message: None,
+        }
+    }
+
+    /// Create the state for a day's daily puzzle
+    pub fn daily_puzzle(day: u16, s: &Strings) -> Self {
+        let mut state = Self::puzzle(String::from(s.daily_pack), vec![crate::puzzle::daily_puzzle(day)], 0);
+        if let AppState::Puzzle { daily, .. } = &mut state {
+            *daily = Some(day);
+        }
+        state
+    }
 }
 
 /// Main Othello app
@@ -72,16 +727,40 @@ pub struct OthelloApp {
     pub screensize: Point,
     /// Current application state
     pub state: AppState,
+    /// Screens to return to on Back, most recent last
+    pub nav_stack: Vec<AppState>,
     /// Context menu
     pub menu: Menu,
+    /// Confirmation dialog
+    pub dialog: Dialog,
+    /// Notice shown over the screen for a few seconds
+    pub toast: Toast,
+    /// Whether keys are going to the input line for a typed command
+    pub command_line: bool,
     /// User settings
     pub settings: Settings,
     /// Game statistics
     pub stats: Statistics,
     /// Whether we have a saved game
     pub has_save: bool,
+    /// Whether the active game is the one in the save slot
+    pub playing_saved: bool,
     /// Whether the app should quit
     pub should_quit: bool,
+    /// Self-connection used to schedule AI and coach work
+    pump_cid: Option<xous::CID>,
+    /// CPU search state, kept from move to move of a game
+    searcher: Box<Searcher>,
+    /// The last CPU search of the game and the milliseconds it took
+    last_search: Option<(SearchReport, u64)>,
+    /// Opening moves learned from finished games, tried before the built-in book
+    learned_book: Box<LearnedBook>,
+    /// Seconds since the last key, counted on the main menu
+    idle_secs: u32,
+    /// Archived games waiting for a review
+    review_queue: ReviewQueue,
+    /// Second ticks, while anything counts them
+    ticker: Ticker,
 }
 
 impl OthelloApp {
@@ -90,12 +769,62 @@ impl OthelloApp {
         Self {
             gid,
             screensize,
-            state: AppState::MainMenu,
+            state: AppState::main_menu(),
+            nav_stack: Vec::new(),
             menu: Menu::new(),
+            dialog: Dialog::new(),
+            toast: Toast::new(),
+            command_line: false,
             settings: Settings::default(),
             stats: Statistics::default(),
             has_save: false,
+            playing_saved: false,
             should_quit: false,
+            pump_cid: None,
+            searcher: Box::default(),
+            last_search: None,
+            learned_book: Box::default(),
+            idle_secs: 0,
+            review_queue: ReviewQueue::new(),
+            ticker: Ticker::default(),
+        }
+    }
+
+    /// Set the connection used to schedule background work
+    pub fn set_pump(&mut self, cid: xous::CID) {
+        self.pump_cid = Some(cid);
+    }
+
+    /// Whether the AI or the coach has work queued
+    pub fn has_background_work(&self) -> bool {
+        match &self.state {
+            AppState::Playing { ai_thinking, coach_pending, .. } => *ai_thinking || coach_pending.is_some(),
+            AppState::PuzzleMenu { task, .. } => task.is_some(),
+            AppState::ArchiveReview { .. } => !self.review_queue.is_empty(),
+            AppState::Puzzle { cpu_thinking, .. } => *cpu_thinking,
+            AppState::GameOver { review, .. } => !review.is_complete(),
+            _ => false,
+        }
+    }
+
+    /// Whether a worker thread is running or holds a result not yet taken
+    fn has_worker(&self) -> bool {
+        matches!(
+            self.state,
+            AppState::DeepAnalysis { worker: Some(_), .. }
+                | AppState::Analysis { worker: Some(_), .. }
+                | AppState::PuzzleMenu { import: Some(_), .. }
+                | AppState::LoadPosition { receiving: Some(_), .. }
+                | AppState::Backup { transfer: Some(_), .. }
+                | AppState::WhatIf { sending: Some(_), .. }
+                | AppState::Archive { sending: Some(_), .. }
+        )
+    }
+
+    /// Queue an AiPump message so queued work runs after the next redraw
+    fn pump(&self) {
+        if let Some(cid) = self.pump_cid {
+            xous::send_message(cid, xous::Message::new_scalar(AppOp::AiPump as usize, 0, 0, 0, 0)).ok();
         }
     }
 
@@ -108,6 +837,51 @@ impl OthelloApp {
             self.stats = stats;
         }
         self.has_save = crate::storage::has_saved_game();
+        if let Some(book) = crate::storage::load_learned_book() {
+            *self.learned_book = book;
+        }
+        self.review_queue = ReviewQueue::load();
+    }
+
+    /// Archived games waiting for a review
+    pub fn review_queue(&self) -> &ReviewQueue {
+        &self.review_queue
+    }
+
+    /// The game's last CPU search and how long it took in milliseconds
+    pub fn last_search(&self) -> Option<(SearchReport, u64)> {
+        self.last_search
+    }
+
+    /// Review the next move of the archived games waiting, returning true
+    /// if a game's review was finished
+    fn review_queue_step(&mut self) -> bool {
+        match self.review_queue.step() {
+            Some((name, review)) => {
+                self.update_archive_copy(&name, review);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Stop reviewing archived games until the app is idle again
+    fn pause_review_queue(&mut self) {
+        if let Some((name, review)) = self.review_queue.pause() {
+            self.update_archive_copy(&name, review);
+        }
+    }
+
+    /// Give a saved review to the archive list, if one is open
+    fn update_archive_copy(&mut self, name: &str, review: Vec<u8>) {
+        update_archive_copy(core::iter::once(&mut self.state).chain(self.nav_stack.iter_mut()), name, review);
+    }
+
+    /// Create the New Game menu on the last CPU level played
+    fn new_game_menu(&self) -> AppState {
+        AppState::NewGameMenu {
+            selected: GameMode::ALL.iter().position(|&m| m == mode_from_code(self.settings.last_difficulty)).unwrap_or(0),
+        }
     }
 
     /// Save settings to PDDB
@@ -121,380 +895,2309 @@ impl OthelloApp {
         if let AppState::Playing { ai_thinking, .. } = &mut self.state {
             *ai_thinking = false;
         }
+        crate::storage::sync();
+        self.ticker.stop();
+    }
+
+    /// Save the game in progress before a suspend
+    ///
+    /// The game carries on after resume; the save only matters if power is
+    /// lost while suspended. It replaces any older saved game.
+    pub fn on_suspend(&mut self) {
+        self.pause_review_queue();
+        if let AppState::Playing { game, mode, player_color, meta, .. } = &self.state {
+            log::info!("Saving game before suspend");
+            if !crate::storage::save_game(game, *mode, *player_color, meta) {
+                log::warn!("Couldn't save the game before suspend");
+                self.toast.show(self.text().toast_save_failed);
+                return;
+            }
+            self.has_save = true;
+            self.playing_saved = true;
+        }
+        crate::storage::sync();
     }
 
     /// Handle returning to foreground
     pub fn on_foreground(&mut self) {
+        // Coming back from the F1 menu, chosen from or not
+        self.menu.close();
         // Resume AI if it was their turn
-        self.check_ai_turn();
+        self.mark_ai_turn();
+        self.idle_secs = 0;
+        // Pumps sent in the background were dropped, including those from a
+        // menu action; queued work, and any worker that finished meanwhile,
+        // need a fresh one
+        if self.has_background_work() || self.has_worker() {
+            self.pump();
+        }
+        self.update_ticker();
     }
 
-    /// Draw the current state
-    pub fn draw(&self, gam: &gam::Gam) {
-        ui::draw(self, gam);
-
-        // Draw menu overlay if visible
-        if self.menu.visible {
-            ui::draw_menu(self, gam);
+    /// Run the tick thread only while something counts seconds
+    ///
+    /// Called after every event handled in the foreground.
+    pub fn update_ticker(&mut self) {
+        match self.pump_cid {
+            Some(cid) if self.needs_tick() => self.ticker.start(cid),
+            _ => self.ticker.stop(),
         }
     }
 
-    /// Handle a key press
-    pub fn handle_key(
-        &mut self,
-        key: char,
-        gam: &gam::Gam,
-        ticktimer: &ticktimer_server::Ticktimer,
-        self_cid: xous::CID,
-    ) -> bool {
-        // Handle menu if visible
-        if self.menu.visible {
-            return self.handle_menu_key(key, gam, ticktimer, self_cid);
-        }
+    /// Whether anything counts seconds: the main menu's idle timer, the
+    /// demo, a game's clocks, a deep analysis, a notice or an unsynced save
+    fn needs_tick(&self) -> bool {
+        let screen = match &self.state {
+            AppState::MainMenu { .. } | AppState::Demo { .. } => true,
+            AppState::Playing { game, .. } => !game.is_game_over(),
+            AppState::DeepAnalysis { worker, .. } => worker.is_some(),
+            _ => false,
+        };
+        screen || self.toast.text().is_some() || crate::storage::sync_pending()
+    }
 
-        // Handle F-keys first
-        match key {
-            '\u{F001}' | '\u{0091}' => {
-                // F1 - Open menu
-                self.open_context_menu();
-                return true;
-            }
-            '\u{F004}' | '\u{0094}' => {
-                // F4 - Exit/Back
-                return self.handle_f4(gam, ticktimer);
-            }
-            _ => {}
-        }
+    /// Count a second: sync saves that have waited long enough, time out
+    /// a notice, and move the current screen on
+    ///
+    /// Returns true if the screen changed.
+    pub fn tick(&mut self) -> bool {
+        crate::storage::sync_tick();
+        let toast_gone = self.toast.tick();
+        self.tick_screen() || toast_gone
+    }
 
-        // State-specific key handling
-        match &mut self.state {
-            AppState::MainMenu => self.handle_main_menu_key(key),
-            AppState::NewGameMenu => self.handle_new_game_menu_key(key, self_cid),
-            AppState::SettingsMenu => self.handle_settings_menu_key(key),
-            AppState::Statistics => self.handle_statistics_key(key),
-            AppState::Playing { .. } => self.handle_playing_key(key, self_cid),
-            AppState::GameOver { .. } => self.handle_game_over_key(key, self_cid),
-            AppState::WhatIf { .. } => self.handle_what_if_key(key),
-            AppState::MoveHistory { .. } => self.handle_history_key(key),
-            AppState::Help { .. } => self.handle_help_key(key),
+    /// Count a second of the idle timer, play on in the demo, time a deep
+    /// analysis or a game clock, and review archived games while idle
+    fn tick_screen(&mut self) -> bool {
+        // Archived games are reviewed while nobody uses the app
+        let idle = match self.state {
+            AppState::Demo { .. } => true,
+            AppState::MainMenu { .. } => self.idle_secs >= IDLE_REVIEW_SECS,
+            _ => false,
+        };
+        if idle {
+            self.review_queue_step();
         }
-    }
 
-    /// Handle F4 (Exit/Back)
-    fn handle_f4(
-        &mut self,
-        _gam: &gam::Gam,
-        _ticktimer: &ticktimer_server::Ticktimer,
-    ) -> bool {
-        match &self.state {
-            AppState::MainMenu => {
-                // Exit the app
-                self.should_quit = true;
-                false
+        match &mut self.state {
+            AppState::Demo { game, wait } => {
+                if *wait > 1 {
+                    *wait -= 1;
+                    return false;
+                }
+                if game.is_game_over() {
+                    *game = GameState::new();
+                    *wait = DEMO_MOVE_SECS;
+                    return true;
+                }
+                Self::demo_move(game);
+                *wait = if game.is_game_over() { DEMO_END_SECS } else { DEMO_MOVE_SECS };
+                true
             }
-            AppState::NewGameMenu | AppState::SettingsMenu | AppState::Statistics => {
-                self.state = AppState::MainMenu;
+            AppState::Playing { game, mode, player_color, ai_thinking: false, meta, .. }
+                if !self.menu.visible && !self.dialog.is_open() && !game.is_game_over() =>
+            {
+                // Two players share the time spent; only the CPU's opponents
+                // have a game clock
+                let to_move = game.current_player();
+                if *mode != GameMode::TwoPlayer && to_move != *player_color {
+                    return false;
+                }
+                meta.player_secs = meta.player_secs.saturating_add(1);
+                if !meta.timed() {
+                    return false;
+                }
+                let left = meta.tick_clock(to_move);
+                if left == LOW_TIME_SECS - 1 && self.settings.haptic_cue(HapticCue::LowTime) {
+                    crate::feedback::vibrate_cue(HapticCue::LowTime);
+                }
+                // Running out of time forfeits the game like a resignation
+                if left == 0 {
+                    game.resign(to_move);
+                    self.handle_game_over();
+                }
                 true
             }
-            AppState::Playing { game, mode, player_color, .. } => {
-                // Save game and go to main menu
-                crate::storage::save_game(game, *mode, *player_color);
-                self.has_save = true;
-                self.state = AppState::MainMenu;
+            AppState::DeepAnalysis { elapsed_secs, worker: Some(_), .. } => {
+                *elapsed_secs += 1;
+                true
+            }
+            AppState::MainMenu { .. } if !self.menu.visible && !self.dialog.is_open() => {
+                self.idle_secs += 1;
+                if self.settings.idle_demo.seconds().is_none_or(|secs| self.idle_secs < secs) {
+                    return false;
+                }
+                self.idle_secs = 0;
+                self.push_state(AppState::Demo { game: GameState::new(), wait: DEMO_MOVE_SECS });
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Play the next move of a demo game, or pass
+    fn demo_move(game: &mut GameState) {
+        let player = game.current_player();
+        let choice = if game.move_count() < DEMO_OPENING_PLIES {
+            find_gentle_move(game.board(), player, crate::feedback::random_u32())
+        } else {
+            find_best_move(game.board(), player, Difficulty::Medium.limits())
+        };
+        match choice {
+            Some(pos) => {
+                game.make_move(pos);
+            }
+            None => {
+                game.pass();
+            }
+        }
+    }
+
+    /// Strings for the chosen language
+    pub fn text(&self) -> &'static Strings {
+        crate::i18n::strings(self.settings.language)
+    }
+
+    /// Number of opening moves learned from finished games
+    pub fn learned_book_len(&self) -> usize {
+        self.learned_book.len()
+    }
+
+    /// Draw the current state
+    pub fn draw(&self, gam: &gam::Gam) {
+        ui::draw(self, gam);
+
+        if let Some(text) = self.toast.text() {
+            ui::draw_toast(self, gam, text);
+        }
+
+        if self.command_line {
+            ui::draw_command_line(self, gam);
+        }
+
+        // A dialog sits above everything
+        if let Some(dialog) = self.dialog.kind() {
+            ui::draw_dialog(self, gam, dialog);
+        }
+    }
+
+    /// Turn a click at a point on the canvas into a key press
+    ///
+    /// A click on a board square or a main menu entry moves the cursor or
+    /// the selection there and returns the key that acts on it, so the rest
+    /// goes through `handle_key` as if typed. The F1 menu belongs to the
+    /// GAM and takes its own clicks.
+    #[cfg(not(target_os = "none"))]
+    pub fn click(&mut self, point: Point) -> Option<char> {
+        if self.dialog.is_open() {
+            return None;
+        }
+
+        let hidden = match &self.state {
+            AppState::Playing { blindfold, peeking: false, .. } => blindfold.mask(),
+            _ => 0,
+        };
+        let square = ui::square_at(self, point, hidden);
+        let entry = ui::main_menu_entry_at(self, point);
+        match &mut self.state {
+            AppState::MainMenu { selected, .. } => {
+                *selected = entry?;
+                Some('\r')
+            }
+            AppState::Playing { cursor_pos, .. }
+            | AppState::WhatIf { cursor_pos, .. }
+            | AppState::Tutorial { cursor_pos, .. }
+            | AppState::Puzzle { cursor_pos, .. }
+            | AppState::Editor { cursor_pos, .. } => {
+                *cursor_pos = square?;
+                Some('\r')
+            }
+            _ => None,
+        }
+    }
+
+    /// Handle a key press
+    pub fn handle_key(&mut self, key: char, services: &dyn Services, self_cid: xous::CID) -> bool {
+        // A key takes down any notice and still does its usual job
+        let dismissed = self.toast.dismiss();
+        self.dispatch_key(key, services, self_cid) || dismissed
+    }
+
+    /// Handle a key on the current screen or its overlays
+    fn dispatch_key(&mut self, key: char, services: &dyn Services, self_cid: xous::CID) -> bool {
+        self.idle_secs = 0;
+        if !matches!(self.state, AppState::ArchiveReview { .. }) {
+            self.pause_review_queue();
+        }
+
+        // An open dialog takes every key
+        if self.dialog.is_open() {
+            return self.handle_dialog_key(key);
+        }
+
+        // Keys typed into the input line aren't shortcuts; F4 gives up
+        if self.command_line {
+            if matches!(key, '\u{F004}' | '\u{0094}' | '\u{001B}') {
+                self.command_line = false;
+                return true;
+            }
+            return false;
+        }
+
+        // Any key ends the demo
+        if matches!(self.state, AppState::Demo { .. }) {
+            self.pop_state();
+            return true;
+        }
+
+        // Handle F-keys first
+        match key {
+            '\u{F001}' | '\u{0091}' => {
+                // F1 - Open menu
+                self.open_context_menu(services);
+                return true;
+            }
+            '\u{F004}' | '\u{0094}' => {
+                // F4 - Exit/Back
+                return self.handle_f4();
+            }
+            ':' if self.menu_context().is_some() => {
+                // Type a command on the input line
+                self.command_line = true;
+                return true;
+            }
+            _ => {}
+        }
+
+        // State-specific key handling
+        match &mut self.state {
+            AppState::MainMenu { .. } => self.handle_main_menu_key(key, self_cid),
+            AppState::NewGameMenu { .. } => self.handle_new_game_menu_key(key),
+            AppState::HandicapMenu { .. } => self.handle_handicap_menu_key(key, self_cid),
+            AppState::SettingsMenu => self.handle_settings_menu_key(key),
+            AppState::StatusLines { .. } => self.handle_status_lines_key(key),
+            AppState::Vibration { .. } => self.handle_vibration_key(key),
+            AppState::Statistics { .. } => self.handle_statistics_key(key),
+            AppState::Playing { .. } => self.handle_playing_key(key, self_cid),
+            AppState::GameOver { .. } => self.handle_game_over_key(key, self_cid),
+            AppState::Archive { .. } => self.handle_archive_key(key),
+            AppState::WhatIf { .. } => self.handle_what_if_key(key),
+            AppState::Bookmarks { .. } => self.handle_bookmarks_key(key),
+            AppState::MoveHistory { .. } => self.handle_history_key(key),
+            AppState::PositionText { .. } => self.handle_position_text_key(key),
+            AppState::Help { .. } => self.handle_help_key(key),
+            AppState::Tutorial { .. } => self.handle_tutorial_key(key),
+            AppState::PuzzleMenu { .. } => self.handle_puzzle_menu_key(key),
+            AppState::Puzzle { .. } => self.handle_puzzle_key(key),
+            AppState::Editor { .. } => self.handle_editor_key(key),
+            AppState::LoadPosition { .. } => self.handle_load_position_key(key),
+            AppState::Analysis { .. } => self.handle_analysis_key(key),
+            AppState::DeepAnalysis { .. } => self.handle_deep_analysis_key(key),
+            AppState::Backup { .. } => self.handle_backup_key(key),
+            AppState::Demo { .. } | AppState::ArchiveReview { .. } => false,
+        }
+    }
+
+    /// Handle F4 (Exit/Back)
+    fn handle_f4(&mut self) -> bool {
+        match &mut self.state {
+            AppState::MainMenu { .. } => {
+                // Exit the app
+                self.dialog.confirm(ConfirmAction::Quit);
+                true
+            }
+            AppState::PuzzleMenu { import: Some(transfer), .. }
+            | AppState::LoadPosition { receiving: Some(transfer), .. }
+            | AppState::Backup { transfer: Some(transfer), .. }
+            | AppState::WhatIf { sending: Some(transfer), .. }
+            | AppState::Archive { sending: Some(transfer), .. } => {
+                transfer.cancel();
+                self.pop_state();
+                true
+            }
+            AppState::NewGameMenu { .. }
+            | AppState::HandicapMenu { .. }
+            | AppState::SettingsMenu
+            | AppState::StatusLines { .. }
+            | AppState::Vibration { .. }
+            | AppState::Statistics { .. }
+            | AppState::Tutorial { .. }
+            | AppState::PuzzleMenu { .. }
+            | AppState::Puzzle { .. }
+            | AppState::Editor { .. }
+            | AppState::LoadPosition { .. }
+            | AppState::Backup { .. }
+            | AppState::Demo { .. }
+            | AppState::Archive { .. }
+            | AppState::Bookmarks { .. } => {
+                self.pop_state();
+                true
+            }
+            AppState::Playing { .. } => {
+                // Save game and go to main menu
+                self.request_save_and_exit();
                 true
             }
             AppState::GameOver { .. } => {
-                self.state = AppState::MainMenu;
+                // A game opened from the archive goes back to the list
+                if matches!(self.nav_stack.last(), Some(AppState::Archive { .. })) {
+                    self.pop_state();
+                } else {
+                    self.reset_state(AppState::main_menu());
+                }
+                true
+            }
+            AppState::DeepAnalysis { worker, .. } => {
+                if let Some(worker) = worker.take() {
+                    worker.cancel();
+                }
+                self.pop_state();
+                true
+            }
+            AppState::Analysis { worker, .. } => {
+                if let Some(worker) = worker.take() {
+                    worker.cancel();
+                }
+                self.pop_state();
+                true
+            }
+            AppState::ArchiveReview { .. } => {
+                self.pause_review_queue();
+                self.pop_state();
+                true
+            }
+            AppState::WhatIf { .. } | AppState::MoveHistory { .. } | AppState::PositionText { .. } | AppState::Help { .. } => {
+                // Return to the screen we came from
+                self.pop_state();
+                true
+            }
+        }
+    }
+
+    /// Enter a screen, remembering the current one for Back
+    fn push_state(&mut self, state: AppState) {
+        let previous = core::mem::replace(&mut self.state, state);
+        self.nav_stack.push(previous);
+    }
+
+    /// Return to the screen we came from (main menu if none)
+    fn pop_state(&mut self) {
+        self.state = self.nav_stack.pop().unwrap_or_else(AppState::main_menu);
+        // A game we return to may be waiting on the AI
+        self.check_ai_turn();
+    }
+
+    /// Replace the current screen and forget where we came from
+    fn reset_state(&mut self, state: AppState) {
+        self.nav_stack.clear();
+        if matches!(state, AppState::Playing { .. }) {
+            self.searcher.clear();
+            self.last_search = None;
+        }
+        self.state = state;
+    }
+
+    /// Enter What If mode from the finished game
+    ///
+    /// Opens on the final position, or just before move `at` when given.
+    fn enter_what_if(&mut self, at: Option<usize>) {
+        if let AppState::GameOver { game, .. } = &self.state {
+            let view_index = at.unwrap_or(game.move_count()).min(game.move_count());
+            self.push_state(AppState::WhatIf {
+                base_game: game.clone(),
+                current_game: game.clone_at_move(view_index),
+                view_index,
+                branched: false,
+                cursor_pos: (3, 3),
+                graph: ReviewGraph::Off,
+                bookmarks: Vec::new(),
+                sending: None,
+            });
+        }
+    }
+
+    /// List the What If bookmarks, newest selected
+    fn open_bookmarks(&mut self) {
+        if let AppState::WhatIf { bookmarks, .. } = &self.state {
+            let selected = bookmarks.len().saturating_sub(1);
+            self.push_state(AppState::Bookmarks { selected });
+        }
+    }
+
+    /// Send the What If line over TCP as an analysis record
+    fn start_export_line(&mut self) {
+        let cid = self.pump_cid;
+        let AppState::WhatIf { base_game, current_game, sending: sending @ None, .. } = &mut self.state else {
+            return;
+        };
+        // Moves shared with the game under review come before the branch
+        let branch_at = base_game
+            .history()
+            .iter()
+            .zip(current_game.history())
+            .take_while(|(base, line)| base.pos == line.pos && base.player == line.player)
+            .count();
+        let record = crate::export::format_analysis_record(current_game, branch_at);
+        *sending = Some(Transfer::send(crate::export::EXPORT_PORT, record.into_bytes(), cid));
+    }
+
+    /// Report the What If line's send once the host has it or it failed
+    fn collect_line_export(&mut self) {
+        let AppState::WhatIf { sending: sending @ Some(_), .. } = &mut self.state else {
+            return;
+        };
+        // Not done yet; the thread pumps again when it is
+        let Some(sent) = sending.as_ref().and_then(Transfer::take_result) else {
+            return;
+        };
+        *sending = None;
+        let s = self.text();
+        self.toast.show(if sent.is_some() { s.what_if_sent } else { s.what_if_not_sent });
+    }
+
+    /// Bookmarks of the What If screen the picker was opened from
+    pub fn what_if_bookmarks(&self) -> &[Bookmark] {
+        match self.nav_stack.last() {
+            Some(AppState::WhatIf { bookmarks, .. }) => bookmarks,
+            _ => &[],
+        }
+    }
+
+    /// Review of the game shown, or of the game over screen beneath the
+    /// current one
+    pub fn shown_review(&self) -> Option<&GameReview> {
+        match (&self.state, self.nav_stack.last()) {
+            (AppState::GameOver { review, .. }, _) | (_, Some(AppState::GameOver { review, .. })) => Some(review),
+            _ => None,
+        }
+    }
+
+    /// Menu context for the current state, if it has a menu
+    fn menu_context(&self) -> Option<MenuContext> {
+        let context = match &self.state {
+            AppState::MainMenu { .. } => MenuContext::MainMenu { has_save: self.has_save },
+            AppState::Playing { .. } => MenuContext::Playing,
+            AppState::GameOver { .. } => MenuContext::GameOver,
+            AppState::WhatIf { .. } => MenuContext::WhatIf,
+            AppState::SettingsMenu => MenuContext::Settings,
+            AppState::Archive { .. } => MenuContext::Archive,
+            // Help itself has nothing to offer
+            AppState::Help { .. } => return None,
+            _ => MenuContext::Screen,
+        };
+        Some(context)
+    }
+
+    /// Open the context menu for current state
+    fn open_context_menu(&mut self, services: &dyn Services) {
+        if let Some(context) = self.menu_context() {
+            self.menu.open(context, self.text(), services);
+        }
+    }
+
+    /// Act on an item chosen from the F1 menu, which the GAM has closed
+    pub fn menu_chosen(&mut self, item: MenuItem, self_cid: xous::CID) {
+        self.menu.chosen(item);
+        self.handle_menu_action(item, self_cid);
+    }
+
+    /// Act on a line typed on the input line
+    ///
+    /// Lines entered while no command was asked for are ignored.
+    pub fn command(&mut self, line: &str, services: &dyn Services, self_cid: xous::CID) -> bool {
+        if !self.command_line {
+            return false;
+        }
+        self.command_line = false;
+        self.run_command(line, services, self_cid)
+    }
+
+    /// Act on a command sent by a test or demo script
+    #[cfg(any(debug_assertions, not(target_os = "none")))]
+    pub fn remote_command(&mut self, line: &str, services: &dyn Services, self_cid: xous::CID) -> bool {
+        self.idle_secs = 0;
+        self.command_line = false;
+        self.run_command(line, services, self_cid)
+    }
+
+    /// Run a command, typed or sent
+    ///
+    /// A square is played as if the cursor were on it and Enter pressed; a
+    /// new game starts where the F1 menu offers one; a word does what its
+    /// F1 menu entry does, if the screen's menu has it.
+    fn run_command(&mut self, line: &str, services: &dyn Services, self_cid: xous::CID) -> bool {
+        self.toast.dismiss();
+        match crate::command::parse(line) {
+            Some(Command::Move(position)) if self.move_cursor_to(position) => {
+                self.dispatch_key('\r', services, self_cid);
+            }
+            Some(Command::NewGame(mode, color)) if self.menu_offers(MenuItem::NewGame) => {
+                self.dialog.close();
+                self.start_game_as(mode, Handicap::Even, color.unwrap_or_else(random_player));
+            }
+            Some(Command::Menu(item)) if self.menu_offers(item) => {
+                self.handle_menu_action(item, self_cid);
+            }
+            _ => self.toast.show(fill(self.text().command_unknown, &[&crate::command::typed(line)])),
+        }
+        true
+    }
+
+    /// Whether the current screen's F1 menu has an item
+    fn menu_offers(&self, item: MenuItem) -> bool {
+        self.menu_context().is_some_and(|context| crate::menu::context_items(context).contains(&item))
+    }
+
+    /// Put the board cursor on a square, returning false on screens
+    /// where Enter doesn't play a move
+    fn move_cursor_to(&mut self, position: Position) -> bool {
+        match &mut self.state {
+            AppState::Playing { cursor_pos, .. }
+            | AppState::WhatIf { cursor_pos, .. }
+            | AppState::Tutorial { cursor_pos, .. }
+            | AppState::Puzzle { cursor_pos, .. } => {
+                *cursor_pos = pos_to_rc(position);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Handle a menu action
+    fn handle_menu_action(&mut self, item: MenuItem, self_cid: xous::CID) {
+        match item {
+            MenuItem::Help => {
+                let context = self.help_context();
+                self.push_state(AppState::Help { context, page: 0 });
+            }
+            MenuItem::NewGame => {
+                if matches!(self.state, AppState::Playing { .. }) {
+                    self.dialog.confirm(ConfirmAction::NewGame);
+                } else {
+                    self.push_state(self.new_game_menu());
+                }
+            }
+            MenuItem::Resume => {
+                if let Some((game, mode, player_color, meta)) = crate::storage::load_game() {
+                    self.reset_state(AppState::playing(game, mode, player_color, meta, self.text()));
+                    self.playing_saved = true;
+                    self.check_ai_turn();
+                }
+            }
+            MenuItem::Statistics => {
+                self.push_state(AppState::Statistics { page: 0, level: 0 });
+            }
+            MenuItem::Settings => {
+                self.push_state(AppState::SettingsMenu);
+            }
+            MenuItem::Backup => {
+                self.push_state(AppState::Backup { status: None, task: None, transfer: None });
+            }
+            MenuItem::Tutorial => {
+                self.push_state(AppState::lesson(0));
+            }
+            MenuItem::Puzzles => {
+                self.push_state(AppState::puzzle_menu());
+            }
+            MenuItem::Editor => {
+                self.push_state(AppState::editor());
+            }
+            MenuItem::Archive => {
+                self.push_state(AppState::archive());
+            }
+            MenuItem::MoveHistory => {
+                if let AppState::Playing { game, .. } | AppState::GameOver { game, .. } = &self.state {
+                    let game = game.clone();
+                    self.push_state(AppState::MoveHistory {
+                        game,
+                        scroll_offset: 0,
+                    });
+                }
+            }
+            MenuItem::Hint => {
+                self.show_hint();
+            }
+            MenuItem::Undo => {
+                self.undo_move();
+            }
+            MenuItem::Redo => {
+                self.redo_move();
+            }
+            MenuItem::Resign => {
+                if matches!(self.state, AppState::Playing { .. }) {
+                    self.dialog.confirm(ConfirmAction::Resign);
+                }
+            }
+            MenuItem::SaveAndExit => {
+                self.request_save_and_exit();
+            }
+            MenuItem::WhatIf => {
+                self.enter_what_if(None);
+            }
+            MenuItem::DeepAnalysis => {
+                self.start_deep_analysis();
+            }
+            MenuItem::ReviewArchive => {
+                self.start_archive_review();
+            }
+            MenuItem::ExitWhatIf => {
+                self.pop_state();
+            }
+            MenuItem::Bookmarks => {
+                self.open_bookmarks();
+            }
+            MenuItem::ExportLine => {
+                self.start_export_line();
+            }
+            MenuItem::PositionText => {
+                self.show_position_text();
+            }
+            MenuItem::MainMenu => {
+                self.reset_state(AppState::main_menu());
+            }
+            MenuItem::Back => {
+                self.pop_state();
+            }
+        }
+    }
+
+    /// Help topic for the current screen
+    fn help_context(&self) -> HelpContext {
+        match &self.state {
+            AppState::MainMenu { .. } | AppState::Help { .. } | AppState::Demo { .. } => HelpContext::MainMenu,
+            AppState::NewGameMenu { .. } | AppState::HandicapMenu { .. } => HelpContext::NewGame,
+            AppState::SettingsMenu | AppState::StatusLines { .. } | AppState::Vibration { .. } | AppState::Backup { .. } => {
+                HelpContext::Settings
+            }
+            AppState::Statistics { .. } => HelpContext::Statistics,
+            AppState::Playing { .. } => HelpContext::Playing,
+            AppState::GameOver { .. } | AppState::DeepAnalysis { .. } => HelpContext::GameOver,
+            AppState::Archive { .. } | AppState::ArchiveReview { .. } => HelpContext::Archive,
+            AppState::WhatIf { .. } | AppState::Bookmarks { .. } => HelpContext::WhatIf,
+            AppState::MoveHistory { .. } | AppState::PositionText { .. } => HelpContext::MoveHistory,
+            AppState::Tutorial { .. } => HelpContext::Tutorial,
+            AppState::PuzzleMenu { .. } | AppState::Puzzle { .. } => HelpContext::Puzzles,
+            AppState::Editor { .. } | AppState::Analysis { .. } | AppState::LoadPosition { .. } => HelpContext::Editor,
+        }
+    }
+
+    /// Handle key in a dialog, acting on its answer once it closes
+    fn handle_dialog_key(&mut self, key: char) -> bool {
+        match self.dialog.key(key) {
+            DialogResult::Ignored => false,
+            DialogResult::Handled | DialogResult::Cancelled => true,
+            DialogResult::Confirmed(action) => self.accept_confirm(action),
+            DialogResult::Chosen(action, index) => {
+                self.accept_choice(action, index);
+                true
+            }
+            DialogResult::Entered(action, text) => {
+                self.accept_prompt(action, text);
+                true
+            }
+        }
+    }
+
+    /// Run the action behind an accepted confirmation
+    fn accept_confirm(&mut self, action: ConfirmAction) -> bool {
+        match action {
+            ConfirmAction::Resign => {
+                self.resign();
+                true
+            }
+            ConfirmAction::NewGame => {
+                // The abandoned game is not kept on the navigation stack
+                self.reset_state(AppState::main_menu());
+                self.push_state(self.new_game_menu());
+                true
+            }
+            ConfirmAction::OverwriteSave => {
+                self.save_and_exit();
+                true
+            }
+            ConfirmAction::Quit => {
+                self.should_quit = true;
+                false
+            }
+            ConfirmAction::Archive => {
+                self.archive_finished_game();
+                true
+            }
+            ConfirmAction::PlayMove { pos, .. } => {
+                self.play_move(pos);
+                true
+            }
+            ConfirmAction::ResetLearnedBook => {
+                self.learned_book.clear();
+                crate::storage::delete_learned_book();
+                true
+            }
+            ConfirmAction::RestoreBackup => {
+                let cid = self.pump_cid;
+                if let AppState::Backup { status, task, transfer } = &mut self.state {
+                    *task = Some(BackupTask::Receive);
+                    *transfer = Some(Transfer::receive(crate::export::RESTORE_PORT, crate::export::MAX_BACKUP_BYTES, cid));
+                    *status = None;
+                }
+                true
+            }
+            ConfirmAction::DeleteArchived => {
+                if let AppState::Archive { games, selected, .. } = &mut self.state {
+                    if *selected < games.len() {
+                        let (name, _) = games.remove(*selected);
+                        crate::storage::delete_archived_game(&name);
+                        self.review_queue.remove(&name);
+                        *selected = (*selected).min(games.len().saturating_sub(1));
+                    }
+                }
+                true
+            }
+        }
+    }
+
+    /// Apply an option picked in a dialog
+    fn accept_choice(&mut self, action: ChoiceAction, index: usize) {
+        match action {
+            ChoiceAction::GameClock => {
+                if let Some(&clock) = GameClock::ALL.get(index) {
+                    self.settings.game_clock = clock;
+                    self.save_settings();
+                }
+            }
+        }
+    }
+
+    /// Use the text typed into a dialog
+    fn accept_prompt(&mut self, action: PromptAction, text: String) {
+        match action {
+            PromptAction::ArchiveNote => {
+                if let AppState::Archive { games, selected, .. } = &mut self.state {
+                    if let Some((name, archived)) = games.get_mut(*selected) {
+                        archived.note = text;
+                        crate::storage::update_archived_game(name, archived);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Save the active game and exit, confirming before replacing another save
+    fn request_save_and_exit(&mut self) {
+        if !matches!(self.state, AppState::Playing { .. }) {
+            return;
+        }
+        if self.has_save && !self.playing_saved {
+            self.dialog.confirm(ConfirmAction::OverwriteSave);
+        } else {
+            self.save_and_exit();
+        }
+    }
+
+    /// Save the active game and return to the main menu, or stay in the
+    /// game if it couldn't be saved
+    fn save_and_exit(&mut self) {
+        if let AppState::Playing { game, mode, player_color, meta, .. } = &self.state {
+            let s = self.text();
+            if !crate::storage::save_game(game, *mode, *player_color, meta) {
+                self.toast.show(s.toast_save_failed);
+                return;
+            }
+            self.has_save = true;
+            self.playing_saved = true;
+            self.reset_state(AppState::main_menu());
+            self.toast.show(s.toast_saved);
+        }
+    }
+
+    /// Resign the active game
+    fn resign(&mut self) {
+        if let AppState::Playing { game, mode, player_color, ai_thinking, .. } = &mut self.state {
+            // vs CPU the human always resigns; hot-seat resigns for whoever is to move
+            let resigner = match mode {
+                GameMode::VsCpu(_) | GameMode::Adaptive => *player_color,
+                GameMode::TwoPlayer => game.current_player(),
+            };
+            if game.resign(resigner) {
+                *ai_thinking = false;
+                self.handle_game_over();
+            }
+        }
+    }
+
+    /// Handle key in main menu
+    fn handle_main_menu_key(&mut self, key: char, self_cid: xous::CID) -> bool {
+        let items = crate::menu::main_menu_items(self.has_save);
+        let selected = match &mut self.state {
+            AppState::MainMenu { selected, .. } => selected,
+            _ => return false,
+        };
+
+        match key {
+            '↑' | '\u{2191}' => {
+                *selected = selected.saturating_sub(1);
+                true
+            }
+            '↓' | '\u{2193}' => {
+                *selected = (*selected + 1).min(items.len() - 1);
+                true
+            }
+            '\r' | '\n' => {
+                let item = items[(*selected).min(items.len() - 1)];
+                self.handle_menu_action(item, self_cid);
+                true
+            }
+            'r' | 'R' if self.has_save => {
+                self.handle_menu_action(MenuItem::Resume, self_cid);
+                true
+            }
+            'n' | 'N' => {
+                self.push_state(self.new_game_menu());
+                true
+            }
+            's' | 'S' => {
+                self.push_state(AppState::SettingsMenu);
+                true
+            }
+            'a' | 'A' => {
+                self.handle_menu_action(MenuItem::Archive, self_cid);
+                true
+            }
+            't' | 'T' => {
+                self.push_state(AppState::lesson(0));
+                true
+            }
+            'p' | 'P' => {
+                self.push_state(AppState::puzzle_menu());
+                true
+            }
+            'e' | 'E' => {
+                self.push_state(AppState::editor());
+                true
+            }
+            'q' | 'Q' => {
+                self.dialog.confirm(ConfirmAction::Quit);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Handle key in new game menu
+    fn handle_new_game_menu_key(&mut self, key: char) -> bool {
+        let selected = match &mut self.state {
+            AppState::NewGameMenu { selected } => selected,
+            _ => return false,
+        };
+
+        let mode = match key {
+            '↑' | '\u{2191}' => {
+                *selected = selected.saturating_sub(1);
+                return true;
+            }
+            '↓' | '\u{2193}' => {
+                *selected = (*selected + 1).min(GameMode::ALL.len() - 1);
+                return true;
+            }
+            '\r' | '\n' => GameMode::ALL[*selected],
+            '1' => GameMode::VsCpu(Difficulty::Easy),
+            '2' => GameMode::VsCpu(Difficulty::Medium),
+            '3' => GameMode::VsCpu(Difficulty::Hard),
+            '4' => GameMode::VsCpu(Difficulty::Expert),
+            '5' => GameMode::VsCpu(Difficulty::Grandmaster),
+            '6' | 't' | 'T' => GameMode::TwoPlayer,
+            '7' | 'a' | 'A' => GameMode::Adaptive,
+            _ => return false,
+        };
+
+        // Remember the CPU mode so the menu opens on it next time
+        if mode != GameMode::TwoPlayer {
+            let level = mode_code(mode);
+            if self.settings.last_difficulty != level {
+                self.settings.last_difficulty = level;
+                self.save_settings();
+            }
+        }
+        self.push_state(AppState::HandicapMenu { mode });
+        true
+    }
+
+    /// Handle key in the handicap step of a new game
+    fn handle_handicap_menu_key(&mut self, key: char, self_cid: xous::CID) -> bool {
+        let mode = match self.state {
+            AppState::HandicapMenu { mode } => mode,
+            _ => return false,
+        };
+        let handicap = match key {
+            '\r' | '\n' | '0' => Handicap::Even,
+            '1'..='4' => Handicap::PlayerCorners(key as u8 - b'0'),
+            '5'..='8' => Handicap::OpponentCorners(key as u8 - b'4'),
+            'g' | 'G' if matches!(mode, GameMode::VsCpu(_)) => Handicap::Gentle,
+            'c' | 'C' => {
+                let options = GameClock::ALL.iter().map(|&clock| ui::clock_label(self, clock)).collect();
+                let current = GameClock::ALL.iter().position(|&clock| clock == self.settings.game_clock).unwrap_or(0);
+                self.dialog.choose(ChoiceAction::GameClock, options, current);
+                return true;
+            }
+            _ => return false,
+        };
+        self.start_game(mode, handicap, self_cid);
+        true
+    }
+
+    /// Start a new game
+    fn start_game(&mut self, mode: GameMode, handicap: Handicap, _self_cid: xous::CID) {
+        // Random player color for vs CPU
+        let player_color = match mode {
+            GameMode::VsCpu(_) | GameMode::Adaptive => random_player(),
+            GameMode::TwoPlayer => Player::Black, // Not used in two-player
+        };
+        self.start_game_as(mode, handicap, player_color);
+    }
+
+    /// Start a new game with the player on a colour
+    fn start_game_as(&mut self, mode: GameMode, handicap: Handicap, player_color: Player) {
+        // Corner handicaps are placed before Black's first move
+        let board = match handicap {
+            Handicap::PlayerCorners(n) => Board::with_handicap(player_color, n),
+            Handicap::OpponentCorners(n) => Board::with_handicap(player_color.opponent(), n),
+            Handicap::Even | Handicap::Gentle => Board::new(),
+        };
+        let game = GameState::from_board(board, Player::Black);
+        let meta = GameMeta::new(handicap, self.settings.game_clock);
+
+        self.reset_state(AppState::playing(game, mode, player_color, meta, self.text()));
+        self.playing_saved = false;
+
+        // Start AI if it goes first
+        self.check_ai_turn();
+    }
+
+    /// Start a game from a set-up position
+    fn start_from_position(&mut self, board: Board, to_move: Player, mode: GameMode) {
+        // Against the CPU the player takes the side to move
+        let game = GameState::from_board(board, to_move);
+        self.reset_state(AppState::playing(game, mode, to_move, GameMeta::default(), self.text()));
+        self.playing_saved = false;
+        self.check_ai_turn();
+    }
+
+    /// Handle key in the position editor
+    fn handle_editor_key(&mut self, key: char) -> bool {
+        let (board, to_move, cursor_pos, message) = match &mut self.state {
+            AppState::Editor { board, to_move, cursor_pos, message } => (board, to_move, cursor_pos, message),
+            _ => return false,
+        };
+        message.take();
+        let square = pos(cursor_pos.0, cursor_pos.1);
+
+        match key {
+            '↑' | '\u{2191}' => {
+                if cursor_pos.0 > 0 {
+                    cursor_pos.0 -= 1;
+                }
+                true
+            }
+            '↓' | '\u{2193}' => {
+                if cursor_pos.0 < 7 {
+                    cursor_pos.0 += 1;
+                }
+                true
+            }
+            '←' | '\u{2190}' => {
+                if cursor_pos.1 > 0 {
+                    cursor_pos.1 -= 1;
+                }
+                true
+            }
+            '→' | '\u{2192}' => {
+                if cursor_pos.1 < 7 {
+                    cursor_pos.1 += 1;
+                }
+                true
+            }
+            '\r' | '\n' => {
+                crate::editor::cycle_square(board, square);
+                true
+            }
+            'b' | 'B' => {
+                crate::editor::set_square(board, square, Some(Player::Black));
+                true
+            }
+            'w' | 'W' => {
+                crate::editor::set_square(board, square, Some(Player::White));
+                true
+            }
+            'x' | 'X' | '\u{8}' => {
+                crate::editor::set_square(board, square, None);
+                true
+            }
+            's' | 'S' => {
+                *to_move = to_move.opponent();
+                true
+            }
+            'c' | 'C' => {
+                *board = Board::empty();
+                true
+            }
+            'n' | 'N' => {
+                *board = Board::new();
+                *to_move = Player::Black;
+                true
+            }
+            'l' | 'L' => {
+                self.push_state(AppState::LoadPosition {
+                    input: String::new(),
+                    status: None,
+                    receiving: None,
+                });
+                true
+            }
+            'a' | 'A' => {
+                if let Err(e) = crate::editor::validate(board, *to_move) {
+                    *message = Some(String::from(e));
+                    return true;
+                }
+                let (board, to_move) = (*board, *to_move);
+                let worker = AnalysisWorker::spawn_line(board, to_move, DEFAULT_ANALYSIS_DEPTH, self.pump_cid);
+                self.push_state(AppState::Analysis {
+                    board,
+                    to_move,
+                    depth: DEFAULT_ANALYSIS_DEPTH,
+                    result: None,
+                    worker: Some(worker),
+                });
+                true
+            }
+            'p' | 'P' | '1'..='4' => {
+                if let Err(e) = crate::editor::validate(board, *to_move) {
+                    *message = Some(String::from(e));
+                    return true;
+                }
+                let mode = match key {
+                    '1' => GameMode::VsCpu(Difficulty::Easy),
+                    '2' => GameMode::VsCpu(Difficulty::Medium),
+                    '3' => GameMode::VsCpu(Difficulty::Hard),
+                    '4' => GameMode::VsCpu(Difficulty::Expert),
+                    _ => GameMode::TwoPlayer,
+                };
+                let (board, to_move) = (*board, *to_move);
+                self.start_from_position(board, to_move, mode);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Handle key on the analysis board
+    fn handle_analysis_key(&mut self, key: char) -> bool {
+        let cid = self.pump_cid;
+        let (board, to_move, depth, result, worker) = match &mut self.state {
+            AppState::Analysis { board, to_move, depth, result, worker } => (board, to_move, depth, result, worker),
+            _ => return false,
+        };
+        // F4 stops the search
+        if worker.is_some() {
+            return false;
+        }
+
+        match key {
+            '←' | '\u{2190}' | '-' => {
+                if *depth > 1 {
+                    *depth -= 1;
+                    result.take();
+                }
+                true
+            }
+            '→' | '\u{2192}' | '+' => {
+                if *depth < MAX_ANALYSIS_DEPTH {
+                    *depth += 1;
+                    result.take();
+                }
+                true
+            }
+            '\r' | '\n' => {
+                *worker = Some(AnalysisWorker::spawn_line(*board, *to_move, *depth, cid));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Show the analysis board's search once the worker has finished it
+    fn collect_analysis(&mut self) {
+        let AppState::Analysis { result, worker: worker @ Some(_), .. } = &mut self.state else {
+            return;
+        };
+        // Not done yet; the worker pumps again when it is
+        let Some(found) = worker.as_ref().and_then(AnalysisWorker::take_result) else {
+            return;
+        };
+        *worker = None;
+        *result = Some(found);
+    }
+
+    /// Handle key while typing notation
+    fn handle_load_position_key(&mut self, key: char) -> bool {
+        let s = self.text();
+        let cid = self.pump_cid;
+        let (input, status, receiving) = match &mut self.state {
+            AppState::LoadPosition { input, status, receiving } => (input, status, receiving),
+            _ => return false,
+        };
+        // F4 stops the wait
+        if receiving.is_some() {
+            return false;
+        }
+
+        match key {
+            // An empty line listens on TCP instead
+            '\r' | '\n' if input.trim().is_empty() => {
+                *receiving = Some(Transfer::receive(crate::editor::NOTATION_PORT, crate::editor::MAX_NOTATION_BYTES, cid));
+                *status = Some(String::from(s.load_listening));
+                true
+            }
+            '\r' | '\n' => {
+                let text = input.clone();
+                self.load_notation(&text);
+                true
+            }
+            '\u{8}' | '\u{7f}' => {
+                input.pop();
+                true
+            }
+            c if (c.is_ascii_graphic() || c == ' ') && input.len() < MAX_NOTATION_INPUT => {
+                input.push(c);
+                status.take();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Load notation received over TCP
+    fn collect_notation(&mut self) {
+        let s = self.text();
+        let AppState::LoadPosition { receiving: receiving @ Some(_), status, .. } = &mut self.state else {
+            return;
+        };
+        // Not done yet; the thread pumps again when it is
+        let Some(received) = receiving.as_ref().and_then(Transfer::take_result) else {
+            return;
+        };
+        *receiving = None;
+        match received.and_then(|data| String::from_utf8(data).ok()) {
+            Some(text) => self.load_notation(&text),
+            None => *status = Some(String::from(s.load_nothing_received)),
+        }
+    }
+
+    /// Open a position in the editor or a transcript as a game
+    fn load_notation(&mut self, text: &str) {
+        match crate::editor::parse_notation(text) {
+            Ok(crate::editor::Loaded::Position(loaded, side)) => {
+                self.pop_state();
+                if let AppState::Editor { board, to_move, message, .. } = &mut self.state {
+                    *board = loaded;
+                    *to_move = side;
+                    *message = crate::editor::validate(board, side).err().map(String::from);
+                }
+            }
+            Ok(crate::editor::Loaded::Game(game)) if game.is_game_over() => {
+                // A finished transcript goes straight to review, without touching statistics
+                self.reset_state(AppState::game_over(game, GameMode::TwoPlayer, Player::Black, GameMeta::default()));
+                self.pump();
+            }
+            Ok(crate::editor::Loaded::Game(game)) => {
+                self.reset_state(AppState::playing(game, GameMode::TwoPlayer, Player::Black, GameMeta::default(), self.text()));
+                self.playing_saved = false;
+            }
+            Err(error) => {
+                // Text that doesn't start like a transcript is probably a bad position
+                let s = self.text();
+                let message = if error.index == 0 && error.fault == TranscriptFault::BadNotation {
+                    String::from(s.load_not_notation)
+                } else {
+                    let fault = match error.fault {
+                        TranscriptFault::BadNotation => s.fault_notation,
+                        TranscriptFault::Occupied => s.fault_occupied,
+                        TranscriptFault::Illegal => s.fault_illegal,
+                    };
+                    fill(s.load_bad_move, &[&(error.index + 1), &error.token, &fault])
+                };
+                if let AppState::LoadPosition { status, .. } = &mut self.state {
+                    *status = Some(message);
+                }
+            }
+        }
+    }
+
+    /// Check if it's the AI's turn and start thinking
+    fn check_ai_turn(&mut self) {
+        self.mark_ai_turn();
+        if self.has_background_work() {
+            self.pump();
+        }
+    }
+
+    /// Set the AI thinking if it's its turn, without pumping
+    fn mark_ai_turn(&mut self) {
+        if let AppState::Playing { game, mode, player_color, ai_thinking, .. } = &mut self.state {
+            if let GameMode::VsCpu(_) | GameMode::Adaptive = mode {
+                if game.current_player() != *player_color && !game.is_game_over() {
+                    *ai_thinking = true;
+                }
+            }
+        }
+    }
+
+    /// Handle key while playing
+    fn handle_playing_key(&mut self, key: char, _self_cid: xous::CID) -> bool {
+        let s = self.text();
+        // Get mutable access to playing state
+        let (game, cursor_pos, ai_thinking, show_pass_notice, notice, blindfold, peeking, handover, meta) = match &mut self.state {
+            AppState::Playing {
+                game,
+                cursor_pos,
+                ai_thinking,
+                show_pass_notice,
+                notice,
+                blindfold,
+                peeking,
+                handover,
+                meta,
+                ..
+            } => (game, cursor_pos, ai_thinking, show_pass_notice, notice, blindfold, peeking, handover, meta),
+            _ => return false,
+        };
+
+        // If AI is thinking, ignore most keys
+        if *ai_thinking {
+            return false;
+        }
+
+        // The next player starts their turn with any key; coach feedback
+        // was for the previous player and goes with the handover
+        if *handover {
+            *handover = false;
+            notice.take();
+            return true;
+        }
+
+        // If showing pass notice, any key dismisses
+        if *show_pass_notice {
+            *show_pass_notice = false;
+            return true;
+        }
+
+        // Any other notice goes away on the next key, as does a peek
+        notice.take();
+        *peeking = false;
+
+        match key {
+            // Arrow keys for cursor movement
+            '↑' | '\u{2191}' => {
+                if cursor_pos.0 > 0 {
+                    cursor_pos.0 -= 1;
+                }
+                true
+            }
+            '↓' | '\u{2193}' => {
+                if cursor_pos.0 < 7 {
+                    cursor_pos.0 += 1;
+                }
+                true
+            }
+            '←' | '\u{2190}' => {
+                if cursor_pos.1 > 0 {
+                    cursor_pos.1 -= 1;
+                }
+                true
+            }
+            '→' | '\u{2192}' => {
+                if cursor_pos.1 < 7 {
+                    cursor_pos.1 += 1;
+                }
+                true
+            }
+            // Enter to place disc
+            '\r' | '\n' => {
+                let position = pos(cursor_pos.0, cursor_pos.1);
+                if !game.is_legal(position) {
+                    crate::feedback::vibrate_invalid();
+                    return false;
+                }
+
+                // Ask first when the move looks like a blunder
+                if self.settings.blunder_check {
+                    if let Some(blunder) = find_blunder(game.board(), game.current_player(), position) {
+                        self.dialog.confirm(ConfirmAction::PlayMove { pos: position, blunder });
+                        return true;
+                    }
+                }
+                self.play_move(position);
+                true
+            }
+            // F2 for hint
+            '\u{F002}' | '\u{0092}' => {
+                self.show_hint();
+                true
+            }
+            // U for undo
+            'u' | 'U' => {
+                self.undo_move();
+                true
+            }
+            // R for redo
+            'r' | 'R' => {
+                self.redo_move();
+                true
+            }
+            // B cycles blindfold mode
+            'b' | 'B' => {
+                *blindfold = blindfold.next();
+                *notice = Some(String::from(blindfold.describe(s)));
+                true
+            }
+            'f' | 'F' => {
+                self.toggle_frontier();
+                true
+            }
+            // P peeks at the hidden discs, at the cost of a hint-free win
+            'p' | 'P' => {
+                if *blindfold == Blindfold::Off {
+                    return false;
+                }
+                *peeking = true;
+                meta.peeks = meta.peeks.saturating_add(1);
+                true
+            }
+            't' | 'T' => {
+                self.show_position_text();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Turn the frontier disc marks on or off, on every screen that shows them
+    fn toggle_frontier(&mut self) {
+        self.settings.show_frontier = !self.settings.show_frontier;
+        self.save_settings();
+    }
+
+    /// Play the human's move, then hand over to the CPU or the next player
+    fn play_move(&mut self, position: Position) {
+        let (coach, hot_seat) = (self.settings.coach, self.settings.hot_seat);
+        let pass_cue = self.settings.haptic_cue(HapticCue::Pass);
+        if let AppState::Playing { game, mode, show_pass_notice, coach_pending, handover, .. } = &mut self.state {
+            if !game.is_legal(position) {
+                return;
+            }
+            if coach {
+                *coach_pending = Some(CoachRequest {
+                    board: *game.board(),
+                    player: game.current_player(),
+                    played: position,
+                });
+            }
+            let passed = game.play(position).is_some_and(|(_, passed)| passed);
+            crate::feedback::vibrate_move();
+
+            // Check for game over
+            if game.is_game_over() {
+                self.handle_game_over();
+                return;
+            }
+
+            // The opponent had to pass; in two-player mode they are the
+            // one holding the device next
+            if passed {
+                *show_pass_notice = true;
+                if *mode == GameMode::TwoPlayer && pass_cue {
+                    crate::feedback::vibrate_cue(HapticCue::Pass);
+                }
+            }
+
+            // Hand the device over unless the same player moves again
+            *handover = *mode == GameMode::TwoPlayer && hot_seat && !*show_pass_notice;
+        }
+        self.refresh_ply();
+
+        // Start AI thinking
+        self.check_ai_turn();
+    }
+
+    /// Work out the game screen's facts again after the game changed
+    fn refresh_ply(&mut self) {
+        let s = self.text();
+        if let AppState::Playing { game, mode, player_color, ply, .. } = &mut self.state {
+            *ply = PlyInfo::new(game, *mode, *player_color, s);
+        }
+    }
+
+    /// Move the cursor to the suggested move and count the hint
+    fn show_hint(&mut self) {
+        if let AppState::Playing { game, cursor_pos, meta, .. } = &mut self.state {
+            if let Some(pos) = othello_core::get_hint(game.board(), game.current_player(), HINT_LIMITS) {
+                let (row, col) = othello_core::pos_to_rc(pos);
+                *cursor_pos = (row, col);
+                meta.hints = meta.hints.saturating_add(1);
+                let alg = pos_to_algebraic(pos);
+                self.toast.show(fill(self.text().toast_hint, &[&core::str::from_utf8(&alg).unwrap_or("--")]));
+            }
+        }
+    }
+
+    /// Take back the last move according to the game mode
+    fn undo_move(&mut self) {
+        if !self.settings.allow_undo {
+            return;
+        }
+
+        let s = self.text();
+        let max_undos = self.settings.undo_limit.max_undos();
+        if let AppState::Playing { game, mode, player_color, ai_thinking, show_pass_notice, notice, meta, .. } = &mut self.state {
+            if self.settings.coach {
+                *notice = Some(String::from(s.undo_coach));
+                return;
+            }
+            if max_undos.is_some_and(|max| meta.undos >= max) {
+                *notice = Some(String::from(s.undo_none_left));
+                return;
+            }
+            if let Some(entry) = take_back(game, *mode, *player_color) {
+                meta.undos = meta.undos.saturating_add(1);
+                let alg = pos_to_algebraic(entry.pos);
+                let square = core::str::from_utf8(&alg).unwrap_or("??");
+                *notice = Some(fill(s.undo_done, &[&whose_move(s, *mode, entry.player), &square]));
+                *show_pass_notice = false;
+                *ai_thinking = false;
+            }
+        }
+        self.refresh_ply();
+
+        // The AI may be on move again (e.g. it played first)
+        self.check_ai_turn();
+    }
+
+    /// Replay the moves taken back by the last undo
+    fn redo_move(&mut self) {
+        let s = self.text();
+        let mut finished = false;
+        if let AppState::Playing { game, mode, player_color, ai_thinking, show_pass_notice, notice, meta, .. } = &mut self.state {
+            if !game.can_redo() {
+                *notice = Some(String::from(s.redo_nothing));
+                return;
+            }
+            if let Some(entry) = bring_forward(game, *mode, *player_color) {
+                // Reversing an undo gives it back
+                meta.undos = meta.undos.saturating_sub(1);
+                let alg = pos_to_algebraic(entry.pos);
+                let square = core::str::from_utf8(&alg).unwrap_or("??");
+                *notice = Some(fill(s.redo_done, &[&whose_move(s, *mode, entry.player), &square]));
+                *show_pass_notice = false;
+                *ai_thinking = false;
+            }
+            finished = game.is_game_over();
+        }
+        self.refresh_ply();
+
+        if finished {
+            self.handle_game_over();
+        } else {
+            self.check_ai_turn();
+        }
+    }
+
+    /// Handle game over transition
+    fn handle_game_over(&mut self) {
+        // Extract values before mutating
+        let data = if let AppState::Playing { game, mode, player_color, meta, .. } = &self.state {
+            let result = game.result();
+            let winner = result.as_ref().and_then(|r| r.winner());
+            Some((game.clone(), *mode, *player_color, *meta, winner))
+        } else {
+            None
+        };
+
+        if let Some((game_clone, mode_copy, player_color_copy, meta, winner)) = data {
+            if game_clone.resigned().is_some_and(|player| !meta.out_of_time(player)) {
+                self.stats.resignations = self.stats.resignations.saturating_add(1);
+            }
+
+            // Thinking time; gentle moves are picked without a search
+            self.stats.player_secs = self.stats.player_secs.saturating_add(meta.player_secs);
+            if let Some(level) = mode_copy.stats_level().filter(|_| meta.handicap != Handicap::Gentle) {
+                let cpu_moves = game_clone
+                    .history()
+                    .iter()
+                    .filter(|entry| entry.player != player_color_copy && !entry.is_pass())
+                    .count();
+                self.stats.thinking[level].add(cpu_moves as u32, meta.cpu_ms);
+            }
+
+            // Update statistics; handicap games don't count towards the level records
+            match mode_copy {
+                GameMode::VsCpu(_) | GameMode::Adaptive if meta.handicap != Handicap::Even => {}
+                GameMode::VsCpu(_) | GameMode::Adaptive => {
+                    match winner {
+                        Some(w) if w == player_color_copy => {
+                            self.update_stats_win(mode_copy);
+                            if meta.heavily_assisted() {
+                                self.stats.assisted_wins = self.stats.assisted_wins.saturating_add(1);
+                            }
+                            if meta.hints == 0 && meta.peeks == 0 {
+                                self.stats.hint_free_wins = self.stats.hint_free_wins.saturating_add(1);
+                            }
+                        }
+                        Some(_) => {
+                            self.update_stats_loss(mode_copy);
+                        }
+                        None => {
+                            self.update_stats_draw(mode_copy);
+                        }
+                    }
+
+                    // Resigned games, and games lost on time, have no final margin
+                    if game_clone.resigned().is_none() {
+                        let (black, white) = game_clone.counts();
+                        let (own, other) = match player_color_copy {
+                            Player::Black => (black, white),
+                            Player::White => (white, black),
+                        };
+                        if let (Some(level), Some(bucket)) = (mode_copy.stats_level(), margin_bucket(own, other)) {
+                            let count = &mut self.stats.margins[level][bucket];
+                            *count = count.saturating_add(1);
+                        }
+                    }
+
+                    // Named openings are scored for the player, whoever opened
+                    if let Some(opening) = identify_opening(&game_clone) {
+                        let result = match winner {
+                            Some(w) if w == player_color_copy => 0,
+                            Some(_) => 1,
+                            None => 2,
+                        };
+                        let count = &mut self.stats.openings[opening][result];
+                        *count = count.saturating_add(1);
+                    }
+                }
+                GameMode::TwoPlayer => {
+                    self.stats.two_player_games += 1;
+                }
+            }
+
+            // The adaptive CPU steps up after a win and down after a loss
+            if mode_copy == GameMode::Adaptive && meta.handicap == Handicap::Even {
+                self.stats.adaptive_level = self.stats.adaptive_level.after(winner.map(|w| w == player_color_copy));
+            }
+
+            crate::storage::save_statistics(&self.stats);
+            if self.settings.learn_book && self.learned_book.learn(&game_clone) {
+                crate::storage::save_learned_book(&self.learned_book);
+            }
+            crate::feedback::vibrate_game_over();
+
+            // Clear saved game
+            crate::storage::delete_saved_game();
+            self.has_save = false;
+
+            self.state = AppState::game_over(game_clone, mode_copy, player_color_copy, meta);
+            match self.settings.archive {
+                ArchiveMode::Always => self.archive_finished_game(),
+                ArchiveMode::Ask => self.dialog.confirm(ConfirmAction::Archive),
+                ArchiveMode::Never => {}
+            }
+            self.pump();
+        }
+    }
+
+    /// Keep the game on the Game Over screen in the archive
+    fn archive_finished_game(&mut self) {
+        if let AppState::GameOver { game, mode, player_color, meta, review, archived, archive_key } = &mut self.state {
+            if *archived {
+                return;
+            }
+            // A review still running is saved when it finishes, or by the
+            // review queue if the screen is left first
+            let key = crate::storage::archive_game(&ArchivedGame {
+                game: game.clone(),
+                mode: *mode,
+                player_color: *player_color,
+                meta: *meta,
+                finished: crate::feedback::local_time_ms().map_or(0, |ms| ms / 1000),
+                note: String::new(),
+                review: review.encode().collect(),
+            });
+            // Left unarchived, so the archive key can try again
+            let Some(name) = key else {
+                self.toast.show(self.text().toast_save_failed);
+                return;
+            };
+            if !review.is_complete() {
+                self.review_queue.push(&name);
+            }
+            *archive_key = Some(name);
+            *archived = true;
+        }
+    }
+
+    /// Update stats for a win
+    fn update_stats_win(&mut self, mode: GameMode) {
+        match mode {
+            GameMode::VsCpu(Difficulty::Easy) => self.stats.easy_wins += 1,
+            GameMode::VsCpu(Difficulty::Medium) => self.stats.medium_wins += 1,
+            GameMode::VsCpu(Difficulty::Hard) => self.stats.hard_wins += 1,
+            GameMode::VsCpu(Difficulty::Expert) => self.stats.expert_wins += 1,
+            GameMode::VsCpu(Difficulty::Grandmaster) => self.stats.grandmaster_wins += 1,
+            GameMode::TwoPlayer => self.stats.two_player_games += 1,
+            GameMode::Adaptive => self.stats.adaptive_wins += 1,
+        }
+    }
+
+    /// Update stats for a loss
+    fn update_stats_loss(&mut self, mode: GameMode) {
+        match mode {
+            GameMode::VsCpu(Difficulty::Easy) => self.stats.easy_losses += 1,
+            GameMode::VsCpu(Difficulty::Medium) => self.stats.medium_losses += 1,
+            GameMode::VsCpu(Difficulty::Hard) => self.stats.hard_losses += 1,
+            GameMode::VsCpu(Difficulty::Expert) => self.stats.expert_losses += 1,
+            GameMode::VsCpu(Difficulty::Grandmaster) => self.stats.grandmaster_losses += 1,
+            GameMode::TwoPlayer => self.stats.two_player_games += 1,
+            GameMode::Adaptive => self.stats.adaptive_losses += 1,
+        }
+    }
+
+    /// Update stats for a draw
+    fn update_stats_draw(&mut self, mode: GameMode) {
+        match mode {
+            GameMode::VsCpu(Difficulty::Easy) => self.stats.easy_draws += 1,
+            GameMode::VsCpu(Difficulty::Medium) => self.stats.medium_draws += 1,
+            GameMode::VsCpu(Difficulty::Hard) => self.stats.hard_draws += 1,
+            GameMode::VsCpu(Difficulty::Expert) => self.stats.expert_draws += 1,
+            GameMode::VsCpu(Difficulty::Grandmaster) => self.stats.grandmaster_draws += 1,
+            GameMode::TwoPlayer => self.stats.two_player_games += 1,
+            GameMode::Adaptive => self.stats.adaptive_draws += 1,
+        }
+    }
+
+    /// Handle key in game over state
+    fn handle_game_over_key(&mut self, key: char, self_cid: xous::CID) -> bool {
+        match key {
+            '\r' | '\n' => {
+                // New game with same mode and handicap
+                if let AppState::GameOver { mode, meta, .. } = self.state {
+                    self.start_game(mode, meta.handicap, self_cid);
+                }
+                true
+            }
+            'w' | 'W' => {
+                // Enter What If mode
+                self.enter_what_if(None);
+                true
+            }
+            'a' | 'A' => {
+                self.archive_finished_game();
+                true
+            }
+            'd' | 'D' => {
+                self.start_deep_analysis();
+                true
+            }
+            'm' | 'M' => {
+                // Jump to just before the biggest mistake
+                let at = match &self.state {
+                    AppState::GameOver { review, mode, player_color, .. } => {
+                        shown_mistake(review, *mode, *player_color).map(|m| m.index)
+                    }
+                    _ => None,
+                };
+                if at.is_some() {
+                    self.enter_what_if(at);
+                }
+                at.is_some()
+            }
+            'n' | 'N' => {
+                self.push_state(self.new_game_menu());
+                true
+            }
+            'f' | 'F' => {
+                self.toggle_frontier();
+                true
+            }
+            't' | 'T' => {
+                self.show_position_text();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Show the position on screen in words, one line per fact
+    fn show_position_text(&mut self) {
+        let game = match &self.state {
+            // Reading the discs out would undo the blindfold
+            AppState::Playing { blindfold, peeking: false, .. } if *blindfold != Blindfold::Off => {
+                self.toast.show(self.text().position_blindfold);
+                return;
+            }
+            AppState::Playing { game, .. } | AppState::GameOver { game, .. } => game,
+            AppState::WhatIf { current_game, .. } => current_game,
+            _ => return,
+        };
+        let lines = crate::export::describe_position(game, self.text());
+        self.push_state(AppState::PositionText { lines, scroll_offset: 0 });
+    }
+
+    /// Review the finished game again at `DEEP_ANALYSIS` strength
+    fn start_deep_analysis(&mut self) {
+        if let AppState::GameOver { game, .. } = &self.state {
+            let review = Box::new(GameReview::new(game, DEEP_ANALYSIS.depth()));
+            self.push_state(AppState::DeepAnalysis { game: game.clone(), review, eval: None, elapsed_secs: 0, worker: None });
+            self.next_deep_search();
+        }
+    }
+
+    /// Start searching the next move of the deep analysis, or keep the
+    /// finished review for the game over screen
+    fn next_deep_search(&mut self) {
+        let cid = self.pump_cid;
+        let AppState::DeepAnalysis { game, review, worker, .. } = &mut self.state else {
+            return;
+        };
+        if let Some((board, player)) = review.pending(game) {
+            *worker = Some(AnalysisWorker::spawn(board, player, cid));
+            return;
+        }
+
+        // Finished: the summary, move marks and archive all take it up
+        let review = review.clone();
+        let name = match self.nav_stack.last_mut() {
+            Some(AppState::GameOver { review: shown, archive_key, .. }) => {
+                *shown = review.clone();
+                archive_key.clone()
+            }
+            _ => None,
+        };
+        if let Some(name) = name {
+            keep_archived_review(&mut self.nav_stack, &name, &review);
+        }
+    }
+
+    /// Take a deep analysis search's result and start the next
+    fn collect_deep_analysis(&mut self) {
+        let AppState::DeepAnalysis { game, review, eval, worker: worker @ Some(_), .. } = &mut self.state else {
+            return;
+        };
+        // Not done yet; the thread pumps again when it is
+        let Some(analysis) = worker.as_ref().and_then(AnalysisWorker::take_result) else {
+            return;
+        };
+        let Some((_, player)) = review.pending(game) else {
+            return;
+        };
+        *eval = analysis.best().map(|best| match player {
+            Player::Black => best,
+            Player::White => MoveScore { score: -best.score, ..best },
+        });
+        review.record_analysis(game, &analysis);
+        *worker = None;
+        self.next_deep_search();
+    }
+
+    /// Handle key during a deep analysis
+    fn handle_deep_analysis_key(&mut self, key: char) -> bool {
+        let AppState::DeepAnalysis { review, worker, .. } = &mut self.state else {
+            return false;
+        };
+        match key {
+            // Stop, keeping the moves judged so far
+            'c' | 'C' => match worker.take() {
+                Some(worker) => {
+                    worker.cancel();
+                    true
+                }
+                None => false,
+            },
+            '\r' | '\n' if worker.is_none() && !review.is_complete() => {
+                self.next_deep_search();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Handle key in the archive list
+    fn handle_archive_key(&mut self, key: char) -> bool {
+        let (games, selected) = match &mut self.state {
+            AppState::Archive { games, selected, sending: None, .. } => (games, selected),
+            _ => return false,
+        };
+
+        match key {
+            '↑' | '\u{2191}' => {
+                *selected = selected.saturating_sub(1);
+                true
+            }
+            '↓' | '\u{2193}' => {
+                *selected = (*selected + 1).min(games.len().saturating_sub(1));
+                true
+            }
+            '\r' | '\n' => {
+                if let Some((name, archived)) = games.get(*selected) {
+                    let state = AppState::archived_game_over(name.clone(), archived.clone());
+                    self.push_state(state);
+                    self.pump();
+                }
+                true
+            }
+            'd' | 'D' if !games.is_empty() => {
+                self.dialog.confirm(ConfirmAction::DeleteArchived);
+                true
+            }
+            'n' | 'N' => {
+                if let Some((_, archived)) = games.get(*selected) {
+                    self.dialog.prompt(PromptAction::ArchiveNote, archived.note.clone(), MAX_NOTE);
+                }
+                true
+            }
+            'x' | 'X' if !games.is_empty() => {
+                self.export_archived_game();
+                true
+            }
+            'r' | 'R' => {
+                self.start_archive_review();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Queue every archived game without a finished review and review them
+    /// one after another
+    fn start_archive_review(&mut self) {
+        if let AppState::Archive { games, .. } = &self.state {
+            self.review_queue.push_unreviewed(games);
+            self.push_state(AppState::ArchiveReview { finished: 0 });
+            self.pump();
+        }
+    }
+
+    /// Send the selected archived game over TCP as a game record
+    fn export_archived_game(&mut self) {
+        let AppState::Archive { games, selected, sending: None, .. } = &self.state else {
+            return;
+        };
+        let Some((_, archived)) = games.get(*selected) else {
+            return;
+        };
+        let record = crate::export::format_game_record(
+            &archived.game,
+            &ui::mode_label(self, archived.mode),
+            (archived.mode != GameMode::TwoPlayer).then_some(archived.player_color),
+            &archived.date().unwrap_or_default(),
+            &archived.meta,
+            &archived.note,
+        );
+        let transfer = Transfer::send(crate::export::EXPORT_PORT, record.into_bytes(), self.pump_cid);
+        if let AppState::Archive { sending, .. } = &mut self.state {
+            *sending = Some(transfer);
+        }
+    }
+
+    /// Report the archived game's send once the host has it or it failed
+    fn collect_archive_export(&mut self) {
+        let AppState::Archive { sending: sending @ Some(_), .. } = &mut self.state else {
+            return;
+        };
+        // Not done yet; the thread pumps again when it is
+        let Some(sent) = sending.as_ref().and_then(Transfer::take_result) else {
+            return;
+        };
+        *sending = None;
+        let s = self.text();
+        self.toast.show(if sent.is_some() { s.archive_sent } else { s.archive_not_sent });
+    }
+
+    /// Handle key in What If mode
+    fn handle_what_if_key(&mut self, key: char) -> bool {
+        let (base_game, current_game, view_index, branched, cursor_pos, graph, bookmarks) = match &mut self.state {
+            AppState::WhatIf {
+                base_game,
+                current_game,
+                view_index,
+                branched,
+                cursor_pos,
+                graph,
+                bookmarks,
+                sending: None,
+            } => {
+                (base_game, current_game, view_index, branched, cursor_pos, graph, bookmarks)
+            }
+            _ => return false,
+        };
+
+        match key {
+            // Step back in history
+            '←' | '\u{2190}' => {
+                if *view_index > 0 {
+                    *view_index -= 1;
+                    *current_game = base_game.clone_at_move(*view_index);
+                }
+                true
+            }
+            // Step forward in history
+            '→' | '\u{2192}' => {
+                if *view_index < base_game.move_count() {
+                    *view_index += 1;
+                    *current_game = base_game.clone_at_move(*view_index);
+                }
+                true
+            }
+            // Cursor movement
+            '↑' | '\u{2191}' => {
+                if cursor_pos.0 > 0 {
+                    cursor_pos.0 -= 1;
+                }
+                true
+            }
+            '↓' | '\u{2193}' => {
+                if cursor_pos.0 < 7 {
+                    cursor_pos.0 += 1;
+                }
+                true
+            }
+            'f' | 'F' => {
+                self.toggle_frontier();
+                true
+            }
+            // Cycle mobility graph, frontier graph and board
+            'g' | 'G' => {
+                *graph = graph.next();
+                true
+            }
+            // Bookmark the position on screen
+            'b' | 'B' => {
+                let bookmark = Bookmark {
+                    transcript: format_compact(current_game),
+                    moves: current_game.move_count(),
+                    view_index: *view_index,
+                    branched: *branched,
+                };
+                if !bookmarks.contains(&bookmark) {
+                    if bookmarks.len() == MAX_BOOKMARKS {
+                        bookmarks.remove(0);
+                    }
+                    bookmarks.push(bookmark);
+                }
+                true
+            }
+            'j' | 'J' => {
+                self.open_bookmarks();
+                true
+            }
+            // Send the line on screen to a host as analysis
+            'x' | 'X' => {
+                self.start_export_line();
+                true
+            }
+            't' | 'T' => {
+                self.show_position_text();
+                true
+            }
+            // Play alternate move (branch), only with the board in view
+            '\r' | '\n' if *graph == ReviewGraph::Off => {
+                let position = pos(cursor_pos.0, cursor_pos.1);
+                if current_game.is_legal(position) {
+                    current_game.make_move(position);
+                    *branched = true;
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Handle key in the bookmark picker
+    fn handle_bookmarks_key(&mut self, key: char) -> bool {
+        let count = self.what_if_bookmarks().len();
+        let selected = match &mut self.state {
+            AppState::Bookmarks { selected } => selected,
+            _ => return false,
+        };
+
+        match key {
+            '↑' | '\u{2191}' => {
+                *selected = selected.saturating_sub(1);
                 true
             }
-            AppState::WhatIf { .. } => {
-                // Exit What If mode
-                self.state = AppState::MainMenu;
+            '↓' | '\u{2193}' => {
+                *selected = (*selected + 1).min(count.saturating_sub(1));
                 true
             }
-            AppState::MoveHistory { .. } => {
-                // Return to previous state (game over or playing)
-                self.state = AppState::MainMenu;
+            // Jump back to the bookmarked position
+            '\r' | '\n' => {
+                let index = *selected;
+                let Some(bookmark) = self.what_if_bookmarks().get(index).cloned() else {
+                    return true;
+                };
+                self.pop_state();
+                if let AppState::WhatIf { base_game, current_game, view_index, branched, graph, .. } = &mut self.state {
+                    let start = base_game.clone_at_move(0);
+                    *current_game = replay_transcript(start, &bookmark.transcript)
+                        .unwrap_or_else(|| base_game.clone_at_move(bookmark.view_index));
+                    *view_index = bookmark.view_index;
+                    *branched = bookmark.branched;
+                    *graph = ReviewGraph::Off;
+                }
                 true
             }
-            AppState::Help { previous, .. } => {
-                self.state = *previous.clone();
+            'd' | 'D' if count > 0 => {
+                let index = *selected;
+                *selected = index.min(count.saturating_sub(2));
+                if let Some(AppState::WhatIf { bookmarks, .. }) = self.nav_stack.last_mut() {
+                    bookmarks.remove(index);
+                }
                 true
             }
+            _ => false,
         }
     }
 
-    /// Open the context menu for current state
-    fn open_context_menu(&mut self) {
-        let context = match &self.state {
-            AppState::MainMenu => MenuContext::MainMenu { has_save: self.has_save },
-            AppState::Playing { .. } => MenuContext::Playing,
-            AppState::GameOver { .. } => MenuContext::GameOver,
-            AppState::WhatIf { .. } => MenuContext::WhatIf,
-            _ => return, // No menu for other states
+    /// Handle key in history view
+    fn handle_history_key(&mut self, key: char) -> bool {
+        let scroll_offset = match &mut self.state {
+            AppState::MoveHistory { scroll_offset, .. } => scroll_offset,
+            _ => return false,
         };
-        self.menu.open(context);
-    }
 
-    /// Handle key in menu
-    fn handle_menu_key(
-        &mut self,
-        key: char,
-        _gam: &gam::Gam,
-        _ticktimer: &ticktimer_server::Ticktimer,
-        self_cid: xous::CID,
-    ) -> bool {
         match key {
-            '\u{F004}' | '\u{0094}' | '\u{001B}' => {
-                // F4 or Esc - Close menu
-                self.menu.close();
-                true
-            }
             '↑' | '\u{2191}' => {
-                self.menu.up();
+                if *scroll_offset > 0 {
+                    *scroll_offset -= 1;
+                }
                 true
             }
             '↓' | '\u{2193}' => {
-                self.menu.down();
-                true
-            }
-            '\r' | '\n' => {
-                // Select
-                if let Some(item) = self.menu.select() {
-                    self.handle_menu_action(item, self_cid);
-                }
+                *scroll_offset += 1;
                 true
             }
             _ => false,
         }
     }
 
-    /// Handle a menu action
-    fn handle_menu_action(&mut self, item: MenuItem, self_cid: xous::CID) {
-        self.menu.close();
+    /// Handle key in the position text view
+    fn handle_position_text_key(&mut self, key: char) -> bool {
+        let (lines, scroll_offset) = match &mut self.state {
+            AppState::PositionText { lines, scroll_offset } => (lines, scroll_offset),
+            _ => return false,
+        };
 
-        match item {
-            MenuItem::Help => {
-                let context = match &self.state {
-                    AppState::Playing { .. } => HelpContext::Playing,
-                    AppState::WhatIf { .. } => HelpContext::WhatIf,
-                    _ => HelpContext::MainMenu,
-                };
-                let previous = Box::new(self.state.clone());
-                self.state = AppState::Help { context, previous };
+        match key {
+            '↑' | '\u{2191}' => {
+                *scroll_offset = scroll_offset.saturating_sub(1);
+                true
             }
-            MenuItem::NewGame => {
-                self.state = AppState::NewGameMenu;
+            '↓' | '\u{2193}' => {
+                *scroll_offset = (*scroll_offset + 1).min(lines.len().saturating_sub(1));
+                true
             }
-            MenuItem::Resume => {
-                if let Some((game, mode, player_color)) = crate::storage::load_game() {
-                    self.state = AppState::Playing {
-                        game,
-                        mode,
-                        player_color,
-                        cursor_pos: (3, 3),
-                        ai_thinking: false,
-                        thinking_dots: 0,
-                        show_pass_notice: false,
-                    };
-                    self.check_ai_turn();
-                }
+            _ => false,
+        }
+    }
+
+    /// Handle key in settings
+    fn handle_settings_menu_key(&mut self, key: char) -> bool {
+        match key {
+            '1' => {
+                self.settings.show_coordinates = !self.settings.show_coordinates;
+                self.save_settings();
+                true
             }
-            MenuItem::Statistics => {
-                self.state = AppState::Statistics;
+            '2' => {
+                self.settings.show_valid_moves = !self.settings.show_valid_moves;
+                self.save_settings();
+                true
             }
-            MenuItem::Settings => {
-                self.state = AppState::SettingsMenu;
+            '3' => {
+                self.settings.allow_undo = !self.settings.allow_undo;
+                self.save_settings();
+                true
             }
-            MenuItem::MoveHistory => {
-                if let AppState::Playing { game, .. } | AppState::GameOver { game, .. } = &self.state {
-                    self.state = AppState::MoveHistory {
-                        game: game.clone(),
-                        scroll_offset: 0,
-                    };
-                }
+            '4' => {
+                self.push_state(AppState::Vibration { selected: 0 });
+                true
             }
-            MenuItem::Hint => {
-                if let AppState::Playing { game, cursor_pos, .. } = &mut self.state {
-                    if let Some(pos) = othello_core::get_hint(game.board(), game.current_player()) {
-                        let (row, col) = othello_core::pos_to_rc(pos);
-                        *cursor_pos = (row, col);
-                    }
-                }
+            '5' => {
+                self.settings.undo_limit = self.settings.undo_limit.next();
+                self.save_settings();
+                true
             }
-            MenuItem::Undo => {
-                if let AppState::Playing { game, .. } = &mut self.state {
-                    game.undo();
-                    // In vs CPU mode, undo opponent's move too
-                    game.undo();
-                }
+            '6' => {
+                self.settings.coach = !self.settings.coach;
+                self.save_settings();
+                true
             }
-            MenuItem::Resign => {
-                // Extract values before mutating
-                let data = if let AppState::Playing { game, mode, player_color, .. } = &self.state {
-                    Some((game.clone(), *mode, *player_color))
-                } else {
-                    None
-                };
-                if let Some((game_clone, mode_copy, player_copy)) = data {
-                    // Record loss and go to game over
-                    self.update_stats_loss(mode_copy);
-                    self.state = AppState::GameOver {
-                        game: game_clone,
-                        mode: mode_copy,
-                        player_color: player_copy,
-                    };
-                }
+            '7' => {
+                self.settings.language = self.settings.language.next();
+                self.save_settings();
+                true
             }
-            MenuItem::SaveAndExit => {
-                if let AppState::Playing { game, mode, player_color, .. } = &self.state {
-                    crate::storage::save_game(game, *mode, *player_color);
-                    self.has_save = true;
-                    self.state = AppState::MainMenu;
-                }
+            '8' => {
+                self.settings.text_size = self.settings.text_size.next();
+                self.save_settings();
+                true
             }
-            MenuItem::WhatIf => {
-                if let AppState::GameOver { game, .. } = &self.state {
-                    self.state = AppState::WhatIf {
-                        base_game: game.clone(),
-                        current_game: game.clone(),
-                        view_index: game.move_count(),
-                        branched: false,
-                        cursor_pos: (3, 3),
-                    };
-                }
+            '9' => {
+                self.push_state(AppState::StatusLines { selected: 0 });
+                true
             }
-            MenuItem::ExitWhatIf => {
-                self.state = AppState::MainMenu;
+            '0' => {
+                self.settings.archive = self.settings.archive.next();
+                self.save_settings();
+                true
             }
-            MenuItem::MainMenu => {
-                self.state = AppState::MainMenu;
+            'h' | 'H' => {
+                self.settings.hot_seat = !self.settings.hot_seat;
+                self.save_settings();
+                true
             }
-        }
-    }
-
-    /// Handle key in main menu
-    fn handle_main_menu_key(&mut self, key: char) -> bool {
-        match key {
-            '↑' | '\u{2191}' | '↓' | '\u{2193}' | '\r' | '\n' => {
-                // Main menu uses F1 menu system
-                self.open_context_menu();
+            'b' | 'B' => {
+                self.settings.blunder_check = !self.settings.blunder_check;
+                self.save_settings();
                 true
             }
-            'n' | 'N' => {
-                self.state = AppState::NewGameMenu;
+            'f' | 'F' => {
+                self.toggle_frontier();
                 true
             }
-            's' | 'S' => {
-                self.state = AppState::SettingsMenu;
+            'l' | 'L' => {
+                self.settings.learn_book = !self.settings.learn_book;
+                self.save_settings();
                 true
             }
-            'q' | 'Q' => {
-                self.should_quit = true;
-                false
+            'r' | 'R' => {
+                if !self.learned_book.is_empty() {
+                    self.dialog.confirm(ConfirmAction::ResetLearnedBook);
+                }
+                true
+            }
+            'd' | 'D' => {
+                self.settings.idle_demo = self.settings.idle_demo.next();
+                self.save_settings();
+                true
             }
             _ => false,
         }
     }
 
-    /// Handle key in new game menu
-    fn handle_new_game_menu_key(&mut self, key: char, self_cid: xous::CID) -> bool {
+    /// Handle key in the status line settings
+    fn handle_status_lines_key(&mut self, key: char) -> bool {
+        let selected = match &mut self.state {
+            AppState::StatusLines { selected } => selected,
+            _ => return false,
+        };
+
         match key {
-            '1' => {
-                self.start_game(GameMode::VsCpu(Difficulty::Easy), self_cid);
-                true
-            }
-            '2' => {
-                self.start_game(GameMode::VsCpu(Difficulty::Medium), self_cid);
-                true
-            }
-            '3' => {
-                self.start_game(GameMode::VsCpu(Difficulty::Hard), self_cid);
+            '↑' | '\u{2191}' => {
+                *selected = selected.saturating_sub(1);
                 true
             }
-            '4' => {
-                self.start_game(GameMode::VsCpu(Difficulty::Expert), self_cid);
+            '↓' | '\u{2193}' => {
+                *selected = (*selected + 1).min(StatusItem::ALL.len() - 1);
                 true
             }
-            '5' | 't' | 'T' => {
-                self.start_game(GameMode::TwoPlayer, self_cid);
+            '\r' | '\n' | ' ' | '→' | '\u{2192}' => {
+                let item = StatusItem::ALL[*selected];
+                self.settings.cycle_status_item(item);
+                self.save_settings();
                 true
             }
             _ => false,
         }
     }
 
-    /// Start a new game
-    fn start_game(&mut self, mode: GameMode, _self_cid: xous::CID) {
-        let game = GameState::new();
+    /// Handle key in the vibration settings
+    fn handle_vibration_key(&mut self, key: char) -> bool {
+        let selected = match &mut self.state {
+            AppState::Vibration { selected } => selected,
+            _ => return false,
+        };
 
-        // Random player color for vs CPU
-        let player_color = match mode {
-            GameMode::VsCpu(_) => {
-                // Use hardware TRNG
-                if crate::feedback::random_bit() {
-                    Player::Black
-                } else {
-                    Player::White
+        match key {
+            '↑' | '\u{2191}' => {
+                *selected = selected.saturating_sub(1);
+                true
+            }
+            '↓' | '\u{2193}' => {
+                *selected = (*selected + 1).min(HapticCue::ALL.len());
+                true
+            }
+            '\r' | '\n' | ' ' | '→' | '\u{2192}' => {
+                match selected.checked_sub(1) {
+                    Some(cue) => self.settings.toggle_haptic_cue(HapticCue::ALL[cue]),
+                    None => self.settings.vibration = !self.settings.vibration,
                 }
+                self.save_settings();
+                true
             }
-            GameMode::TwoPlayer => Player::Black, // Not used in two-player
-        };
+            _ => false,
+        }
+    }
 
-        self.state = AppState::Playing {
-            game,
-            mode,
-            player_color,
-            cursor_pos: (3, 3),
-            ai_thinking: false,
-            thinking_dots: 0,
-            show_pass_notice: false,
+    /// Handle key in statistics view
+    fn handle_statistics_key(&mut self, key: char) -> bool {
+        let (page, level) = match &mut self.state {
+            AppState::Statistics { page, level } => (page, level),
+            _ => return false,
         };
 
-        // Start AI if it goes first
-        self.check_ai_turn();
-    }
-
-    /// Check if it's the AI's turn and start thinking
-    fn check_ai_turn(&mut self) {
-        if let AppState::Playing { game, mode, player_color, ai_thinking, .. } = &mut self.state {
-            if let GameMode::VsCpu(_) = mode {
-                if game.current_player() != *player_color && !game.is_game_over() {
-                    *ai_thinking = true;
-                }
+        match key {
+            '←' | '\u{2190}' => {
+                *page = page.saturating_sub(1);
+                true
+            }
+            '→' | '\u{2192}' => {
+                *page = (*page + 1).min(STATS_PAGES - 1);
+                true
             }
+            // Up/Down choose the level on the margins page
+            '↑' | '\u{2191}' if *page == 1 => {
+                *level = (*level + STATS_LEVELS - 1) % STATS_LEVELS;
+                true
+            }
+            '↓' | '\u{2193}' if *page == 1 => {
+                *level = (*level + 1) % STATS_LEVELS;
+                true
+            }
+            _ => false,
         }
     }
 
-    /// Handle key while playing
-    fn handle_playing_key(&mut self, key: char, _self_cid: xous::CID) -> bool {
-        // Get mutable access to playing state
-        let (game, mode, player_color, cursor_pos, ai_thinking, show_pass_notice) = match &mut self.state {
-            AppState::Playing {
-                game,
-                mode,
-                player_color,
-                cursor_pos,
-                ai_thinking,
-                show_pass_notice,
-                ..
-            } => (game, mode, player_color, cursor_pos, ai_thinking, show_pass_notice),
+    /// Handle key in a tutorial lesson
+    fn handle_tutorial_key(&mut self, key: char) -> bool {
+        let (lesson, game, cursor_pos, solved, message) = match &mut self.state {
+            AppState::Tutorial { lesson, game, cursor_pos, solved, message } => {
+                (lesson, game, cursor_pos, solved, message)
+            }
             _ => return false,
         };
 
-        // If AI is thinking, ignore most keys
-        if *ai_thinking {
+        // Once solved, Enter moves on to the next lesson
+        if *solved {
+            if matches!(key, '\r' | '\n') {
+                let next = *lesson + 1;
+                if next < crate::tutorial::LESSONS.len() {
+                    self.state = AppState::lesson(next);
+                } else {
+                    self.pop_state();
+                }
+                return true;
+            }
             return false;
         }
 
-        // If showing pass notice, any key dismisses
-        if *show_pass_notice {
-            *show_pass_notice = false;
-            return true;
-        }
-
         match key {
-            // Arrow keys for cursor movement
             '↑' | '\u{2191}' => {
                 if cursor_pos.0 > 0 {
                     cursor_pos.0 -= 1;
@@ -519,56 +3222,81 @@ impl OthelloApp {
                 }
                 true
             }
-            // Enter to place disc
             '\r' | '\n' => {
                 let position = pos(cursor_pos.0, cursor_pos.1);
-                if game.is_legal(position) {
+                let current = &crate::tutorial::LESSONS[*lesson];
+                if !game.is_legal(position) {
+                    *message = Some(crate::tutorial::ILLEGAL_MOVE);
+                    crate::feedback::vibrate_invalid();
+                } else if current.accepts(position) {
                     game.make_move(position);
+                    *solved = true;
+                    *message = Some(current.explanation);
                     crate::feedback::vibrate_move();
+                } else {
+                    *message = Some(current.retry);
+                    crate::feedback::vibrate_invalid();
+                }
+                true
+            }
+            _ => false,
+        }
+    }
 
-                    // Check for game over
-                    if game.is_game_over() {
-                        self.handle_game_over();
-                        return true;
-                    }
-
-                    // Check if opponent must pass
-                    if !game.has_moves() {
-                        game.pass();
-                        *show_pass_notice = true;
+    /// Handle key in the puzzle pack list
+    fn handle_puzzle_menu_key(&mut self, key: char) -> bool {
+        let s = self.text();
+        let cid = self.pump_cid;
+        let (packs, selected, status, task, import, today) = match &mut self.state {
+            AppState::PuzzleMenu { packs, selected, status, task, import, today } => {
+                (packs, selected, status, task, import, *today)
+            }
+            _ => return false,
+        };
 
-                        // Check if now we must pass (game over)
-                        if !game.has_moves() {
-                            game.pass();
-                            if game.is_game_over() {
-                                self.handle_game_over();
-                                return true;
-                            }
-                        }
-                    }
+        // Nothing to do until the pending work finishes; F4 stops an import
+        if task.is_some() || import.is_some() {
+            return false;
+        }
 
-                    // Start AI thinking
-                    self.check_ai_turn();
-                    true
-                } else {
-                    crate::feedback::vibrate_invalid();
-                    false
+        match key {
+            '↑' | '\u{2191}' => {
+                if *selected > 0 {
+                    *selected -= 1;
                 }
+                true
             }
-            // F2 for hint
-            '\u{F002}' | '\u{0092}' => {
-                if let Some(pos) = othello_core::get_hint(game.board(), game.current_player()) {
-                    let (row, col) = othello_core::pos_to_rc(pos);
-                    *cursor_pos = (row, col);
+            '↓' | '\u{2193}' => {
+                if *selected + 1 < packs.len() {
+                    *selected += 1;
                 }
                 true
             }
-            // U for undo
-            'u' | 'U' => {
-                if self.settings.allow_undo {
-                    game.undo();
-                    if matches!(mode, GameMode::VsCpu(_)) {
-                        game.undo(); // Undo AI move too
+            '\r' | '\n' => {
+                let Some(name) = packs.get(*selected).cloned() else {
+                    return false;
+                };
+                match crate::storage::load_puzzle_pack(&name) {
+                    Some(puzzles) => self.push_state(AppState::puzzle(name, puzzles, 0)),
+                    None => *status = Some(fill(s.pack_unreadable, &[&name])),
+                }
+                true
+            }
+            'i' | 'I' => {
+                *import = Some(Transfer::receive(crate::puzzle::IMPORT_PORT, crate::puzzle::MAX_PACK_BYTES, cid));
+                *status = Some(String::from(s.pack_listening));
+                true
+            }
+            'd' | 'D' => {
+                match today {
+                    None => *status = Some(String::from(s.daily_no_clock)),
+                    Some(day) if self.stats.daily_last_played == day => {
+                        *status = Some(String::from(s.daily_played));
+                    }
+                    Some(day) => {
+                        *task = Some(PuzzleTask::Daily(day));
+                        *status = Some(String::from(s.daily_preparing));
+                        self.pump();
                     }
                 }
                 true
@@ -577,148 +3305,161 @@ impl OthelloApp {
         }
     }
 
-    /// Handle game over transition
-    fn handle_game_over(&mut self) {
-        // Extract values before mutating
-        let data = if let AppState::Playing { game, mode, player_color, .. } = &self.state {
-            let result = game.result();
-            let winner = result.as_ref().and_then(|r| r.winner());
-            Some((game.clone(), *mode, *player_color, winner))
-        } else {
-            None
+    /// Run the puzzle screen's pending task
+    fn run_puzzle_task(&mut self) {
+        let task = match &mut self.state {
+            AppState::PuzzleMenu { task, .. } => task.take(),
+            _ => None,
         };
-
-        if let Some((game_clone, mode_copy, player_color_copy, winner)) = data {
-            // Update statistics
-            match mode_copy {
-                GameMode::VsCpu(_) => {
-                    match winner {
-                        Some(w) if w == player_color_copy => {
-                            self.update_stats_win(mode_copy);
-                        }
-                        Some(_) => {
-                            self.update_stats_loss(mode_copy);
-                        }
-                        None => {
-                            self.update_stats_draw(mode_copy);
-                        }
-                    }
-                }
-                GameMode::TwoPlayer => {
-                    self.stats.two_player_games += 1;
+        match task {
+            Some(PuzzleTask::Daily(day)) => {
+                // Opening the puzzle uses up today's try
+                self.stats.daily_last_played = day;
+                crate::storage::save_statistics(&self.stats);
+                if let AppState::PuzzleMenu { status, .. } = &mut self.state {
+                    *status = None;
                 }
+                self.push_state(AppState::daily_puzzle(day, self.text()));
             }
+            None => {}
+        }
+    }
 
-            crate::storage::save_statistics(&self.stats);
-            crate::feedback::vibrate_game_over();
-
-            // Clear saved game
-            crate::storage::delete_saved_game();
-            self.has_save = false;
+    /// Handle key on the backup screen
+    fn handle_backup_key(&mut self, key: char) -> bool {
+        let s = self.text();
+        let cid = self.pump_cid;
+        let AppState::Backup { status, task, transfer } = &mut self.state else {
+            return false;
+        };
 
-            self.state = AppState::GameOver {
-                game: game_clone,
-                mode: mode_copy,
-                player_color: player_color_copy,
-            };
+        // Nothing to do until the transfer finishes; F4 stops it
+        if task.is_some() {
+            return false;
         }
-    }
 
-    /// Update stats for a win
-    fn update_stats_win(&mut self, mode: GameMode) {
-        match mode {
-            GameMode::VsCpu(Difficulty::Easy) => self.stats.easy_wins += 1,
-            GameMode::VsCpu(Difficulty::Medium) => self.stats.medium_wins += 1,
-            GameMode::VsCpu(Difficulty::Hard) => self.stats.hard_wins += 1,
-            GameMode::VsCpu(Difficulty::Expert) => self.stats.expert_wins += 1,
-            GameMode::TwoPlayer => self.stats.two_player_games += 1,
+        match key {
+            'b' | 'B' => {
+                *task = Some(BackupTask::Send);
+                *transfer = Some(Transfer::send(crate::export::BACKUP_PORT, crate::storage::backup(), cid));
+                *status = Some(String::from(s.backup_sending));
+                true
+            }
+            'r' | 'R' => {
+                self.dialog.confirm(ConfirmAction::RestoreBackup);
+                true
+            }
+            _ => false,
         }
     }
 
-    /// Update stats for a loss
-    fn update_stats_loss(&mut self, mode: GameMode) {
-        match mode {
-            GameMode::VsCpu(Difficulty::Easy) => self.stats.easy_losses += 1,
-            GameMode::VsCpu(Difficulty::Medium) => self.stats.medium_losses += 1,
-            GameMode::VsCpu(Difficulty::Hard) => self.stats.hard_losses += 1,
-            GameMode::VsCpu(Difficulty::Expert) => self.stats.expert_losses += 1,
-            GameMode::TwoPlayer => self.stats.two_player_games += 1,
+    /// Finish the backup screen's transfer once it is done
+    fn collect_backup(&mut self) {
+        let AppState::Backup { task, transfer: transfer @ Some(_), .. } = &mut self.state else {
+            return;
+        };
+        // Not done yet; the thread pumps again when it is
+        let Some(moved) = transfer.as_ref().and_then(Transfer::take_result) else {
+            return;
+        };
+        *transfer = None;
+        let task = task.take();
+        let s = self.text();
+        let result = match task {
+            Some(BackupTask::Send) => match moved {
+                Some(backup) => fill(s.backup_sent, &[&backup.len()]),
+                None => String::from(s.backup_not_sent),
+            },
+            Some(BackupTask::Receive) => {
+                let restored = moved.and_then(|data| crate::storage::restore(&data));
+                match restored {
+                    Some(count) => {
+                        // Anything the backup didn't hold is back to its default
+                        self.settings = Settings::default();
+                        self.stats = Statistics::default();
+                        self.learned_book.clear();
+                        self.load_settings();
+                        // The backup may have changed the language
+                        fill(self.text().restore_done, &[&count])
+                    }
+                    None => String::from(s.restore_failed),
+                }
+            }
+            None => return,
+        };
+        if let AppState::Backup { status, .. } = &mut self.state {
+            *status = Some(result);
         }
     }
 
-    /// Update stats for a draw
-    fn update_stats_draw(&mut self, mode: GameMode) {
-        match mode {
-            GameMode::VsCpu(Difficulty::Easy) => self.stats.easy_draws += 1,
-            GameMode::VsCpu(Difficulty::Medium) => self.stats.medium_draws += 1,
-            GameMode::VsCpu(Difficulty::Hard) => self.stats.hard_draws += 1,
-            GameMode::VsCpu(Difficulty::Expert) => self.stats.expert_draws += 1,
-            GameMode::TwoPlayer => self.stats.two_player_games += 1,
+    /// Record a finished daily puzzle in the statistics
+    fn record_daily_result(&mut self) {
+        if let AppState::Puzzle { daily: Some(day), outcome: Some(solved), .. } = self.state {
+            self.stats.record_daily(day, solved);
+            crate::storage::save_statistics(&self.stats);
         }
     }
 
-    /// Handle key in game over state
-    fn handle_game_over_key(&mut self, key: char, self_cid: xous::CID) -> bool {
-        match key {
-            '\r' | '\n' => {
-                // New game with same mode
-                if let AppState::GameOver { mode, .. } = self.state {
-                    self.start_game(mode, self_cid);
-                }
-                true
-            }
-            'w' | 'W' => {
-                // Enter What If mode
-                if let AppState::GameOver { game, .. } = &self.state {
-                    self.state = AppState::WhatIf {
-                        base_game: game.clone(),
-                        current_game: game.clone(),
-                        view_index: game.move_count(),
-                        branched: false,
-                        cursor_pos: (3, 3),
-                    };
-                }
-                true
-            }
-            'n' | 'N' => {
-                self.state = AppState::NewGameMenu;
-                true
+    /// Store a received puzzle pack and refresh the list
+    fn collect_puzzle_import(&mut self) {
+        let s = self.text();
+        let AppState::PuzzleMenu { packs, selected, status, import: import @ Some(_), .. } = &mut self.state else {
+            return;
+        };
+        // Not done yet; the thread pumps again when it is
+        let Some(received) = import.as_ref().and_then(Transfer::take_result) else {
+            return;
+        };
+        *import = None;
+        let imported = received.and_then(|data| crate::puzzle::import_pack(&data));
+        *packs = crate::storage::list_puzzle_packs();
+        match imported {
+            Some(name) => {
+                *selected = packs.iter().position(|p| *p == name).unwrap_or(0);
+                *status = Some(fill(s.pack_imported, &[&name]));
             }
-            _ => false,
+            None => *status = Some(String::from(s.pack_import_failed)),
         }
     }
 
-    /// Handle key in What If mode
-    fn handle_what_if_key(&mut self, key: char) -> bool {
-        let (base_game, current_game, view_index, branched, cursor_pos) = match &mut self.state {
-            AppState::WhatIf {
-                base_game,
-                current_game,
-                view_index,
-                branched,
-                cursor_pos,
-            } => (base_game, current_game, view_index, branched, cursor_pos),
+    /// Handle key while solving a puzzle
+    fn handle_puzzle_key(&mut self, key: char) -> bool {
+        let s = self.text();
+        let (pack, puzzles, index, game, cursor_pos, cpu_thinking, outcome, message, daily) = match &mut self.state {
+            AppState::Puzzle { pack, puzzles, index, game, cursor_pos, cpu_thinking, outcome, message, daily } => {
+                (pack, puzzles, index, game, cursor_pos, cpu_thinking, outcome, message, *daily)
+            }
             _ => return false,
         };
 
-        match key {
-            // Step back in history
-            '←' | '\u{2190}' => {
-                if *view_index > 0 {
-                    *view_index -= 1;
-                    *current_game = base_game.clone_at_move(*view_index);
+        if *cpu_thinking {
+            return false;
+        }
+
+        // Finished: Enter for the next puzzle, R to try again
+        if outcome.is_some() {
+            match key {
+                '\r' | '\n' => {
+                    if *index + 1 < puzzles.len() {
+                        self.state = AppState::puzzle(pack.clone(), core::mem::take(puzzles), *index + 1);
+                    } else {
+                        self.pop_state();
+                        if let AppState::PuzzleMenu { status, .. } = &mut self.state {
+                            *status = Some(String::from(s.pack_complete));
+                        }
+                    }
+                    return true;
                 }
-                true
-            }
-            // Step forward in history
-            '→' | '\u{2192}' => {
-                if *view_index < base_game.move_count() {
-                    *view_index += 1;
-                    *current_game = base_game.clone_at_move(*view_index);
+                // The daily puzzle is one try only
+                'r' | 'R' if daily.is_none() => {
+                    self.state = AppState::puzzle(pack.clone(), core::mem::take(puzzles), *index);
+                    return true;
                 }
-                true
+                _ => return false,
             }
-            // Cursor movement
+        }
+
+        match key {
             '↑' | '\u{2191}' => {
                 if cursor_pos.0 > 0 {
                     cursor_pos.0 -= 1;
@@ -731,12 +3472,45 @@ impl OthelloApp {
                 }
                 true
             }
-            // Play alternate move (branch)
+            '←' | '\u{2190}' => {
+                if cursor_pos.1 > 0 {
+                    cursor_pos.1 -= 1;
+                }
+                true
+            }
+            '→' | '\u{2192}' => {
+                if cursor_pos.1 < 7 {
+                    cursor_pos.1 += 1;
+                }
+                true
+            }
             '\r' | '\n' => {
                 let position = pos(cursor_pos.0, cursor_pos.1);
-                if current_game.is_legal(position) {
-                    current_game.make_move(position);
-                    *branched = true;
+                if !game.is_legal(position) {
+                    crate::feedback::vibrate_invalid();
+                    return false;
+                }
+                let puzzle = puzzles[*index];
+                game.make_move(position);
+                crate::feedback::vibrate_move();
+
+                match puzzle.objective {
+                    Objective::FindMove(answer) => {
+                        *outcome = Some(answer == position);
+                        *message = Some(crate::puzzle::find_move_feedback(answer, position, s));
+                    }
+                    Objective::Win | Objective::WinBy(_) => {
+                        if settle_puzzle(game, puzzle.to_move) {
+                            *cpu_thinking = true;
+                        } else {
+                            finish_puzzle(&puzzle, game, outcome, message, s);
+                        }
+                    }
+                }
+                if *cpu_thinking {
+                    self.pump();
+                } else if outcome.is_some() {
+                    self.record_daily_result();
                 }
                 true
             }
@@ -744,82 +3518,107 @@ impl OthelloApp {
         }
     }
 
-    /// Handle key in history view
-    fn handle_history_key(&mut self, key: char) -> bool {
-        let scroll_offset = match &mut self.state {
-            AppState::MoveHistory { scroll_offset, .. } => scroll_offset,
+    /// Play the CPU's defence in a puzzle
+    fn puzzle_reply(&mut self) {
+        let s = self.text();
+        if let AppState::Puzzle { puzzles, index, game, cpu_thinking, outcome, message, .. } = &mut self.state {
+            if !*cpu_thinking {
+                return;
+            }
+            let puzzle = puzzles[*index];
+            if let Some(reply) = find_best_move(game.board(), game.current_player(), Difficulty::Expert) {
+                game.make_move(reply);
+                crate::feedback::vibrate_move();
+            }
+            *cpu_thinking = settle_puzzle(game, puzzle.to_move);
+            if !*cpu_thinking && game.is_game_over() {
+                finish_puzzle(&puzzle, game, outcome, message, s);
+                self.record_daily_result();
+            }
+        }
+    }
+
+    /// Handle key in help screen
+    fn handle_help_key(&mut self, key: char) -> bool {
+        let strings = self.text();
+        let (context, page) = match &mut self.state {
+            AppState::Help { context, page } => (*context, page),
             _ => return false,
         };
+        let last = crate::help::help(strings, context).pages.len() - 1;
 
         match key {
-            '↑' | '\u{2191}' => {
-                if *scroll_offset > 0 {
-                    *scroll_offset -= 1;
-                }
-                true
+            '↑' | '\u{2191}' | '←' | '\u{2190}' => {
+                *page = page.saturating_sub(1);
             }
-            '↓' | '\u{2193}' => {
-                *scroll_offset += 1;
-                true
+            '↓' | '\u{2193}' | '→' | '\u{2192}' | ' ' => {
+                *page = (*page + 1).min(last);
             }
-            _ => false,
+            // Any other key dismisses help
+            _ => self.pop_state(),
         }
+        true
     }
 
-    /// Handle key in settings
-    fn handle_settings_menu_key(&mut self, key: char) -> bool {
-        match key {
-            '1' => {
-                self.settings.show_coordinates = !self.settings.show_coordinates;
-                self.save_settings();
-                true
+    /// AI thinking tick
+    pub fn ai_tick(&mut self, services: &dyn Services) {
+        let s = self.text();
+        match &mut self.state {
+            AppState::PuzzleMenu { .. } => {
+                self.collect_puzzle_import();
+                self.run_puzzle_task();
             }
-            '2' => {
-                self.settings.show_valid_moves = !self.settings.show_valid_moves;
-                self.save_settings();
-                true
+            AppState::LoadPosition { .. } => self.collect_notation(),
+            AppState::Backup { .. } => self.collect_backup(),
+            AppState::WhatIf { .. } => self.collect_line_export(),
+            AppState::Archive { .. } => self.collect_archive_export(),
+            AppState::Analysis { .. } => self.collect_analysis(),
+            AppState::DeepAnalysis { .. } => self.collect_deep_analysis(),
+            AppState::ArchiveReview { .. } => {
+                if self.review_queue_step() {
+                    if let AppState::ArchiveReview { finished } = &mut self.state {
+                        *finished += 1;
+                    }
+                }
             }
-            '3' => {
-                self.settings.allow_undo = !self.settings.allow_undo;
-                self.save_settings();
-                true
+            AppState::Puzzle { .. } => self.puzzle_reply(),
+            // Coach feedback goes up before the AI replies
+            AppState::Playing { coach_pending, notice, .. } if coach_pending.is_some() => {
+                if let Some(request) = coach_pending.take() {
+                    *notice = Some(crate::coach::review(&request, s));
+                }
             }
-            '4' => {
-                self.settings.vibration = !self.settings.vibration;
-                self.save_settings();
-                true
+            AppState::GameOver { game, review, archive_key, .. } => {
+                // Saved once, on the step that finishes the review
+                if !review.is_complete() && !review.step(game) {
+                    if let Some(name) = archive_key {
+                        keep_archived_review(&mut self.nav_stack, name, review);
+                    }
+                }
+            }
+            _ => {
+                self.ai_move(services);
+                // A game that just ended has already queued its review
+                if matches!(self.state, AppState::GameOver { .. }) {
+                    return;
+                }
             }
-            _ => false,
         }
-    }
 
-    /// Handle key in statistics view
-    fn handle_statistics_key(&mut self, _key: char) -> bool {
-        false
-    }
-
-    /// Handle key in help screen
-    fn handle_help_key(&mut self, _key: char) -> bool {
-        // Any key dismisses help
-        if let AppState::Help { previous, .. } = &self.state {
-            self.state = *previous.clone();
-            return true;
+        if self.has_background_work() {
+            self.pump();
         }
-        false
     }
 
-    /// AI thinking tick
-    pub fn ai_tick(
-        &mut self,
-        _gam: &gam::Gam,
-        ticktimer: &ticktimer_server::Ticktimer,
-    ) {
+    /// Compute and play the AI's move
+    fn ai_move(&mut self, services: &dyn Services) {
         if let AppState::Playing {
             game,
-            mode: GameMode::VsCpu(difficulty),
+            mode,
             ai_thinking,
             thinking_dots,
             show_pass_notice,
+            meta,
             ..
         } = &mut self.state
         {
@@ -829,12 +3628,48 @@ impl OthelloApp {
 
                 // Add delay if enabled
                 if self.settings.ai_delay {
-                    ticktimer.sleep_ms(100).ok();
+                    services.sleep_ms(100);
                 }
 
                 // Actually compute AI move
-                if let Some(pos) = find_best_move(game.board(), game.current_player(), *difficulty) {
-                    game.make_move(pos);
+                let started = services.elapsed_ms();
+                let choice = match *mode {
+                    _ if meta.handicap == Handicap::Gentle => {
+                        find_gentle_move(game.board(), game.current_player(), crate::feedback::random_u32())
+                    }
+                    GameMode::Adaptive => {
+                        find_adaptive_move(game.board(), game.current_player(), self.stats.adaptive_level, crate::feedback::random_u32())
+                    }
+                    GameMode::VsCpu(difficulty) => {
+                        // The core has no clock, so the search reads the system's
+                        let now = || services.elapsed_ms();
+                        let limit = difficulty.time_ms().map_or(AI_SEARCH_CAP_MS, |ms| ms.min(AI_SEARCH_CAP_MS));
+                        let config = SearchConfig::new(difficulty)
+                            .with_rules(*game.rules())
+                            .with_limits(difficulty.limits().with_time(limit));
+                        // Levels that play from the book try the learned moves first;
+                        // misère games leave both books out
+                        let learned = (self.settings.learn_book && difficulty.use_opening_book() && !game.rules().misere)
+                            .then(|| self.learned_book.lookup(game.board()))
+                            .flatten()
+                            .filter(|&pos| game.is_legal(pos));
+                        let choice = learned.or_else(|| self.searcher.best_move_timed(game.board(), game.current_player(), config, &now));
+                        let report = match learned {
+                            Some(_) => SearchReport { method: SearchMethod::Book, ..SearchReport::default() },
+                            None => *self.searcher.last_search(),
+                        };
+                        self.last_search = Some((report, now() - started));
+                        if difficulty.time_ms().is_none() && now() - started >= AI_SEARCH_CAP_MS {
+                            log::warn!("CPU search ran {} ms; playing the best move found in time", now() - started);
+                        }
+                        choice
+                    }
+                    GameMode::TwoPlayer => return,
+                };
+                let spent = u32::try_from(services.elapsed_ms() - started).unwrap_or(u32::MAX);
+                meta.cpu_ms = meta.cpu_ms.saturating_add(spent);
+                if let Some(pos) = choice {
+                    let passed = game.play(pos).is_some_and(|(_, passed)| passed);
                     *ai_thinking = false;
 
                     // Check for game over
@@ -843,24 +3678,20 @@ impl OthelloApp {
                         return;
                     }
 
-                    // Check if player must pass
-                    if !game.has_moves() {
-                        game.pass();
+                    // The player had to pass: AI's turn again
+                    if passed {
                         *show_pass_notice = true;
-
-                        // Check if AI must also pass (game over)
-                        if !game.has_moves() {
-                            game.pass();
-                            if game.is_game_over() {
-                                self.handle_game_over();
-                            }
-                        } else {
-                            // AI's turn again
-                            *ai_thinking = true;
-                        }
+                        *ai_thinking = true;
                     }
 
-                    crate::feedback::vibrate_move();
+                    if CORNER_MASK & (1u64 << pos) != 0 && self.settings.haptic_cue(HapticCue::Corner) {
+                        crate::feedback::vibrate_cue(HapticCue::Corner);
+                    } else {
+                        crate::feedback::vibrate_move();
+                    }
+                    if passed && self.settings.haptic_cue(HapticCue::Pass) {
+                        crate::feedback::vibrate_cue(HapticCue::Pass);
+                    }
                 } else {
                     // AI must pass
                     game.pass();
@@ -872,5 +3703,133 @@ impl OthelloApp {
                 }
             }
         }
+        self.refresh_ply();
+    }
+}
+
+/// Save a finished review with its archived game, and with the copy in the
+/// archive list beneath the current screen so reopening the game uses it
+fn keep_archived_review(nav_stack: &mut [AppState], name: &str, review: &GameReview) {
+    let saved: Vec<u8> = review.encode().collect();
+    crate::storage::save_archived_review(name, &saved);
+    update_archive_copy(nav_stack.iter_mut(), name, saved);
+}
+
+/// Give a saved review to the archive list among `states`, so reopening
+/// the game uses it
+fn update_archive_copy<'a>(states: impl Iterator<Item = &'a mut AppState>, name: &str, review: Vec<u8>) {
+    let games = states.filter_map(|state| match state {
+        AppState::Archive { games, .. } => Some(games),
+        _ => None,
+    });
+    for games in games {
+        if let Some((_, archived)) = games.iter_mut().find(|(key, _)| key == name) {
+            archived.review = review;
+            return;
+        }
+    }
+}
+
+/// Mistake the game over summary points at
+///
+/// Against the CPU only the player's own moves are of interest.
+pub fn shown_mistake(review: &GameReview, mode: GameMode, player_color: Player) -> Option<Mistake> {
+    match mode {
+        GameMode::VsCpu(_) | GameMode::Adaptive => review.mistake(player_color),
+        GameMode::TwoPlayer => review.biggest_mistake(),
+    }
+}
+
+/// A colour picked with the hardware TRNG
+fn random_player() -> Player {
+    if crate::feedback::random_bit() {
+        Player::Black
+    } else {
+        Player::White
+    }
+}
+
+/// Pass for whichever side can't move in a puzzle
+///
+/// Returns true if the CPU (the side not solving) is now on move.
+fn settle_puzzle(game: &mut GameState, solver: Player) -> bool {
+    loop {
+        if game.is_game_over() {
+            return false;
+        }
+        if !game.has_moves() {
+            game.pass();
+            continue;
+        }
+        return game.current_player() != solver;
+    }
+}
+
+/// Record the result of a played-out puzzle
+fn finish_puzzle(puzzle: &Puzzle, game: &GameState, outcome: &mut Option<bool>, message: &mut Option<String>, s: &Strings) {
+    let solved = puzzle.check_result(game);
+    let (black, white) = game.counts();
+    *outcome = Some(solved);
+    *message = Some(fill(if solved { s.puzzle_solved } else { s.puzzle_failed }, &[&black, &white]));
+}
+
+/// Whose move an undo or redo stepped over, as its notice names it
+fn whose_move(s: &Strings, mode: GameMode, player: Player) -> &'static str {
+    match (mode, player) {
+        (GameMode::VsCpu(_) | GameMode::Adaptive, _) => s.whose_yours,
+        (GameMode::TwoPlayer, Player::Black) => s.whose_black,
+        (GameMode::TwoPlayer, Player::White) => s.whose_white,
+    }
+}
+
+/// Take back moves for a game mode, returning the last real move undone
+///
+/// Hot-seat takes back a single ply; vs CPU takes back to before the human's
+/// last move. Passes are always undone together with the move before them.
+fn take_back(game: &mut GameState, mode: GameMode, player_color: Player) -> Option<HistoryEntry> {
+    match mode {
+        GameMode::TwoPlayer => {
+            while let Some(entry) = game.undo() {
+                if !entry.is_pass() {
+                    return Some(entry);
+                }
+            }
+            None
+        }
+        GameMode::VsCpu(_) | GameMode::Adaptive => {
+            // Nothing to take back until the human has moved
+            let human_moved = game.history().iter().any(|e| !e.is_pass() && e.player == player_color);
+            if !human_moved {
+                return None;
+            }
+            while let Some(entry) = game.undo() {
+                if !entry.is_pass() && entry.player == player_color {
+                    return Some(entry);
+                }
+            }
+            None
+        }
+    }
+}
+
+/// Redo the moves that `take_back` removed, returning the first real move redone
+///
+/// Replays one real move plus any passes after it; vs CPU also replays the
+/// CPU's reply so the human is on move again.
+fn bring_forward(game: &mut GameState, mode: GameMode, player_color: Player) -> Option<HistoryEntry> {
+    let mut first = None;
+    while let Some(next) = game.redo_history().first().copied() {
+        let keep_going = match first {
+            None => true,
+            Some(_) => next.is_pass() || (matches!(mode, GameMode::VsCpu(_) | GameMode::Adaptive) && game.current_player() != player_color),
+        };
+        if !keep_going {
+            break;
+        }
+        game.redo();
+        if first.is_none() && !next.is_pass() {
+            first = Some(next);
+        }
     }
+    first
 }
@@ -0,0 +1,44 @@
+//! Coach mode feedback on the player's moves
+
+use othello_core::{Board, Player, Position, Score, Difficulty, SearchLimits, analyze_moves, pos_to_algebraic};
+
+use crate::i18n::{Strings, fill};
+
+/// Search strength used for coaching (kept short so feedback is prompt)
+const COACH_LIMITS: SearchLimits = Difficulty::Medium.limits();
+
+/// Score gap for another move to count as clearly better
+///
+/// Larger than an X-square penalty, smaller than a corner.
+pub const CLEAR_MARGIN: Score = 40;
+
+/// A move waiting for the coach to review it
+#[derive(Debug, Clone, Copy)]
+pub struct CoachRequest {
+    /// Board before the move
+    pub board: Board,
+    /// Player who moved
+    pub player: Player,
+    /// Move that was played
+    pub played: Position,
+}
+
+/// Review a move, returning a one-line note for the status area
+pub fn review(request: &CoachRequest, s: &Strings) -> String {
+    let analysis = analyze_moves(&request.board, request.player, COACH_LIMITS);
+    let played_alg = pos_to_algebraic(request.played);
+    let played = core::str::from_utf8(&played_alg).unwrap_or("??");
+
+    let (best, played_score) = match (analysis.best(), analysis.score_of(request.played)) {
+        (Some(best), Some(score)) => (best, score),
+        _ => return fill(s.coach_forced, &[&played]),
+    };
+
+    if best.pos != request.played && best.score - played_score >= CLEAR_MARGIN {
+        let best_alg = pos_to_algebraic(best.pos);
+        let best_sq = core::str::from_utf8(&best_alg).unwrap_or("??");
+        fill(s.coach_better, &[&best_sq, &played])
+    } else {
+        fill(s.coach_good, &[&played])
+    }
+}
@@ -0,0 +1,98 @@
+//! Commands typed on the input line
+//!
+//! ':' opens the GAM's input line, where the system predictor helps with
+//! typing. The line that comes back is a square such as "d3" (or "move
+//! d3"), played as if the cursor were there and Enter pressed; "new game"
+//! and a level, such as "new game expert black", which starts that game at
+//! once; or a word naming an F1 menu entry, acted on if the current
+//! screen's menu has it. Test scripts send the same commands.
+
+use othello_core::{algebraic_to_pos, Difficulty, Player, Position};
+use crate::app::GameMode;
+use crate::menu::MenuItem;
+
+/// Words for the F1 menu entries
+const WORDS: &[(&str, MenuItem)] = &[
+    ("help", MenuItem::Help),
+    ("new", MenuItem::NewGame),
+    ("tutorial", MenuItem::Tutorial),
+    ("puzzles", MenuItem::Puzzles),
+    ("editor", MenuItem::Editor),
+    ("archive", MenuItem::Archive),
+    ("resume", MenuItem::Resume),
+    ("stats", MenuItem::Statistics),
+    ("settings", MenuItem::Settings),
+    ("backup", MenuItem::Backup),
+    ("review", MenuItem::ReviewArchive),
+    ("history", MenuItem::MoveHistory),
+    ("hint", MenuItem::Hint),
+    ("undo", MenuItem::Undo),
+    ("redo", MenuItem::Redo),
+    ("resign", MenuItem::Resign),
+    ("save", MenuItem::SaveAndExit),
+    ("whatif", MenuItem::WhatIf),
+    ("analyze", MenuItem::DeepAnalysis),
+    ("exit", MenuItem::ExitWhatIf),
+    ("bookmarks", MenuItem::Bookmarks),
+    ("export", MenuItem::ExportLine),
+    ("text", MenuItem::PositionText),
+    ("menu", MenuItem::MainMenu),
+    ("back", MenuItem::Back),
+];
+
+/// A typed command
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    /// Play on a square
+    Move(Position),
+    /// Start a game in a mode, on a colour against the CPU or a random one
+    NewGame(GameMode, Option<Player>),
+    /// Do what an F1 menu entry does
+    Menu(MenuItem),
+}
+
+/// The command in a line from the input line
+///
+/// The input line also collects keys typed as shortcuts before the ':'
+/// that opened it, so only the text after the last ':' counts.
+pub fn typed(line: &str) -> &str {
+    line.rsplit(':').next().unwrap_or(line).trim()
+}
+
+/// Read a command from the input line, in any case
+pub fn parse(line: &str) -> Option<Command> {
+    let text = typed(line).to_ascii_lowercase();
+    let words: Vec<&str> = text.split_whitespace().collect();
+    match words[..] {
+        ["move", square] => algebraic_to_pos(square.as_bytes()).map(Command::Move),
+        ["new", "game"] => Some(Command::Menu(MenuItem::NewGame)),
+        ["new", "game", level] | ["new", level] => Some(Command::NewGame(mode(level)?, None)),
+        ["new", "game", level, color] | ["new", level, color] => Some(Command::NewGame(mode(level)?, Some(player(color)?))),
+        [word] => algebraic_to_pos(word.as_bytes())
+            .map(Command::Move)
+            .or_else(|| WORDS.iter().find(|(name, _)| *name == word).map(|&(_, item)| Command::Menu(item))),
+        _ => None,
+    }
+}
+
+/// Game mode by name
+fn mode(name: &str) -> Option<GameMode> {
+    match name {
+        "easy" => Some(GameMode::VsCpu(Difficulty::Easy)),
+        "medium" => Some(GameMode::VsCpu(Difficulty::Medium)),
+        "hard" => Some(GameMode::VsCpu(Difficulty::Hard)),
+        "expert" => Some(GameMode::VsCpu(Difficulty::Expert)),
+        "grandmaster" => Some(GameMode::VsCpu(Difficulty::Grandmaster)),
+        "adaptive" => Some(GameMode::Adaptive),
+        "twoplayer" => Some(GameMode::TwoPlayer),
+        _ => None,
+    }
+}
+
+fn player(name: &str) -> Option<Player> {
+    match name {
+        "black" => Some(Player::Black),
+        "white" => Some(Player::White),
+        _ => None,
+    }
+}
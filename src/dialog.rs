@@ -0,0 +1,229 @@
+//! Modal dialogs: confirmations, pickers and short text prompts
+//!
+//! A dialog sits above the screen and takes every key until it closes.
+//! It only collects an answer; the app runs the action the answer is for.
+
+use othello_core::{Blunder, Position, pos_to_algebraic};
+
+use crate::i18n::{Strings, fill};
+
+/// Action guarded by a confirmation dialog
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfirmAction {
+    /// Resign the active game
+    Resign,
+    /// Abandon the active game for the New Game screen
+    NewGame,
+    /// Replace the saved game with the active one
+    OverwriteSave,
+    /// Quit the app
+    Quit,
+    /// Keep the finished game in the archive
+    Archive,
+    /// Remove the selected game from the archive
+    DeleteArchived,
+    /// Forget the opening moves learned from past games
+    ResetLearnedBook,
+    /// Replace all stored data with a backup
+    RestoreBackup,
+    /// Play a move the blunder check flagged
+    PlayMove { pos: Position, blunder: Blunder },
+}
+
+impl ConfirmAction {
+    /// Get the question shown in the dialog
+    pub fn prompt(&self, s: &Strings) -> String {
+        let text = match self {
+            ConfirmAction::Resign => s.confirm_resign,
+            ConfirmAction::NewGame => s.confirm_new_game,
+            ConfirmAction::OverwriteSave => s.confirm_overwrite,
+            ConfirmAction::Quit => s.confirm_quit,
+            ConfirmAction::Archive => s.confirm_archive,
+            ConfirmAction::DeleteArchived => s.confirm_delete_archived,
+            ConfirmAction::ResetLearnedBook => s.confirm_reset_learned,
+            ConfirmAction::RestoreBackup => s.confirm_restore,
+            ConfirmAction::PlayMove { blunder: Blunder::Corner(corner), .. } => {
+                let alg = pos_to_algebraic(*corner);
+                return fill(s.confirm_blunder_corner, &[&core::str::from_utf8(&alg).unwrap_or("--")]);
+            }
+            ConfirmAction::PlayMove { blunder: Blunder::Drop(_), .. } => s.confirm_blunder_drop,
+        };
+        String::from(text)
+    }
+}
+
+/// Setting picked from a list of options
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChoiceAction {
+    /// Time each player gets in new games
+    GameClock,
+}
+
+impl ChoiceAction {
+    /// Get the dialog's title
+    pub fn title(&self, s: &Strings) -> &'static str {
+        match self {
+            ChoiceAction::GameClock => s.choose_game_clock,
+        }
+    }
+}
+
+/// Text typed into a prompt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptAction {
+    /// Note kept with the selected archived game
+    ArchiveNote,
+}
+
+impl PromptAction {
+    /// Get the dialog's title
+    pub fn title(&self, s: &Strings) -> &'static str {
+        match self {
+            PromptAction::ArchiveNote => s.prompt_archive_note,
+        }
+    }
+}
+
+/// What an open dialog asks for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DialogKind {
+    /// Yes or no before an action
+    Confirm {
+        action: ConfirmAction,
+        /// Whether "Yes" is highlighted (defaults to "No")
+        yes_selected: bool,
+    },
+    /// One of a list of options
+    Choice {
+        action: ChoiceAction,
+        options: Vec<String>,
+        selected: usize,
+    },
+    /// A short line of text
+    Prompt {
+        action: PromptAction,
+        text: String,
+        /// Longest text accepted, in characters
+        max_len: usize,
+    },
+}
+
+/// Outcome of a key in a dialog
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DialogResult {
+    /// The dialog has no use for the key
+    Ignored,
+    /// The dialog took the key and is still open
+    Handled,
+    /// The dialog closed without an answer
+    Cancelled,
+    /// The dialog closed with "Yes"
+    Confirmed(ConfirmAction),
+    /// The dialog closed on an option's index
+    Chosen(ChoiceAction, usize),
+    /// The dialog closed on the typed text
+    Entered(PromptAction, String),
+}
+
+/// Modal dialog state
+pub struct Dialog {
+    /// The open dialog, if any
+    kind: Option<DialogKind>,
+}
+
+impl Dialog {
+    /// Create a hidden dialog
+    pub fn new() -> Self {
+        Self { kind: None }
+    }
+
+    /// Whether a dialog is open
+    pub fn is_open(&self) -> bool {
+        self.kind.is_some()
+    }
+
+    /// The open dialog, if any
+    pub fn kind(&self) -> Option<&DialogKind> {
+        self.kind.as_ref()
+    }
+
+    /// Ask for confirmation before an action
+    pub fn confirm(&mut self, action: ConfirmAction) {
+        self.kind = Some(DialogKind::Confirm { action, yes_selected: false });
+    }
+
+    /// Ask for one of a list of options, starting on `selected`
+    pub fn choose(&mut self, action: ChoiceAction, options: Vec<String>, selected: usize) {
+        let selected = selected.min(options.len().saturating_sub(1));
+        self.kind = Some(DialogKind::Choice { action, options, selected });
+    }
+
+    /// Ask for a line of text, starting from `text`
+    pub fn prompt(&mut self, action: PromptAction, text: String, max_len: usize) {
+        self.kind = Some(DialogKind::Prompt { action, text, max_len });
+    }
+
+    /// Hide the dialog
+    pub fn close(&mut self) {
+        self.kind = None;
+    }
+
+    /// Handle a key, closing the dialog once it has an answer
+    pub fn key(&mut self, key: char) -> DialogResult {
+        let Some(kind) = &mut self.kind else {
+            return DialogResult::Ignored;
+        };
+        let result = match kind {
+            DialogKind::Confirm { action, yes_selected } => match key {
+                '\u{F004}' | '\u{0094}' | '\u{001B}' | 'n' | 'N' => DialogResult::Cancelled,
+                '←' | '\u{2190}' | '→' | '\u{2192}' => {
+                    *yes_selected = !*yes_selected;
+                    DialogResult::Handled
+                }
+                'y' | 'Y' => DialogResult::Confirmed(*action),
+                '\r' | '\n' if *yes_selected => DialogResult::Confirmed(*action),
+                '\r' | '\n' => DialogResult::Cancelled,
+                _ => DialogResult::Ignored,
+            },
+            DialogKind::Choice { action, options, selected } => match key {
+                '\u{F004}' | '\u{0094}' | '\u{001B}' => DialogResult::Cancelled,
+                '↑' | '\u{2191}' => {
+                    *selected = selected.saturating_sub(1);
+                    DialogResult::Handled
+                }
+                '↓' | '\u{2193}' => {
+                    *selected = (*selected + 1).min(options.len().saturating_sub(1));
+                    DialogResult::Handled
+                }
+                '\r' | '\n' => DialogResult::Chosen(*action, *selected),
+                '1'..='9' if (key as usize - '1' as usize) < options.len() => {
+                    DialogResult::Chosen(*action, key as usize - '1' as usize)
+                }
+                _ => DialogResult::Ignored,
+            },
+            DialogKind::Prompt { action, text, max_len } => match key {
+                '\u{F004}' | '\u{0094}' | '\u{001B}' => DialogResult::Cancelled,
+                '\r' | '\n' => DialogResult::Entered(*action, String::from(text.trim())),
+                '\u{8}' | '\u{7f}' => {
+                    text.pop();
+                    DialogResult::Handled
+                }
+                c if (c.is_ascii_graphic() || c == ' ') && text.len() < *max_len => {
+                    text.push(c);
+                    DialogResult::Handled
+                }
+                _ => DialogResult::Ignored,
+            },
+        };
+        if !matches!(result, DialogResult::Ignored | DialogResult::Handled) {
+            self.close();
+        }
+        result
+    }
+}
+
+impl Default for Dialog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
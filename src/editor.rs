@@ -0,0 +1,72 @@
+//! Position setup editor
+//!
+//! Lets a position from a book or magazine be entered disc by disc and
+//! checked before it is played or analyzed.
+
+use othello_core::{Board, GameState, Player, Position, TranscriptError, count_moves, parse_position};
+
+/// The four centre squares, which are filled in every real game
+const CENTRE: u64 = (1 << 27) | (1 << 28) | (1 << 35) | (1 << 36);
+
+/// Step a square through empty, black and white
+pub fn cycle_square(board: &mut Board, p: Position) {
+    match board.get_disc(p) {
+        None => board.place(Player::Black, p),
+        Some(Player::Black) => {
+            board.remove(Player::Black, p);
+            board.place(Player::White, p);
+        }
+        Some(Player::White) => board.remove(Player::White, p),
+    }
+}
+
+/// Put a disc on a square, replacing whatever was there
+pub fn set_square(board: &mut Board, p: Position, disc: Option<Player>) {
+    board.remove(Player::Black, p);
+    board.remove(Player::White, p);
+    if let Some(player) = disc {
+        board.place(player, p);
+    }
+}
+
+/// Check that play can start from a position
+///
+/// Returns a message for the editor status line if it can't.
+pub fn validate(board: &Board, to_move: Player) -> Result<(), &'static str> {
+    if board.occupied() & CENTRE != CENTRE {
+        return Err("The four centre squares must be filled");
+    }
+    if count_moves(board, to_move) > 0 {
+        Ok(())
+    } else if count_moves(board, to_move.opponent()) > 0 {
+        Err("Side to move has no legal move")
+    } else {
+        Err("Neither side can move")
+    }
+}
+
+/// A position or game read from text
+pub enum Loaded {
+    /// A bare position, opened in the editor
+    Position(Board, Player),
+    /// A transcript, played out from the start
+    Game(GameState),
+}
+
+/// Read a position string or a move transcript
+///
+/// Text that is neither gives the transcript move that went wrong.
+pub fn parse_notation(text: &str) -> Result<Loaded, TranscriptError<'_>> {
+    if let Some((board, to_move)) = parse_position(text) {
+        return Ok(Loaded::Position(board, to_move));
+    }
+    let mut game = GameState::new();
+    game.apply_transcript(text)?;
+    Ok(Loaded::Game(game))
+}
+
+/// Port notation is received on
+pub const NOTATION_PORT: u16 = 7882;
+
+/// Longest notation accepted; a transcript is at most a few hundred bytes
+pub const MAX_NOTATION_BYTES: u64 = 4096;
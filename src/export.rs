@@ -3,7 +3,11 @@
 //! Exports game records over TCP for external analysis.
 //! This is an optional feature that can be enabled in settings.
 
-use othello_core::{GameState, Player, pos_to_algebraic};
+use othello_core::{Board, GameState, Player, Scoring, format_position, pos_to_algebraic};
+
+use crate::i18n::{Language, Strings, fill, strings};
+use crate::storage::GameMeta;
+use crate::transfer::Transfer;
 
 /// Export a game record as a formatted string
 pub fn format_game_record(
@@ -11,6 +15,8 @@ pub fn format_game_record(
     mode: &str,
     player_color: Option<Player>,
     date: &str,
+    meta: &GameMeta,
+    note: &str,
 ) -> String {
     let mut output = String::new();
 
@@ -26,68 +32,169 @@ pub fn format_game_record(
         ));
     }
 
-    // Result
+    output.push_str(&format!("Undos: {}\n", meta.undos));
+    output.push_str(&format!("Hints: {}\n", meta.hints));
+    if meta.peeks > 0 {
+        output.push_str(&format!("Peeks: {}\n", meta.peeks));
+    }
+    if !note.is_empty() {
+        output.push_str(&format!("Note: {}\n", note));
+    }
+
+    // Games from a handicap or set-up position record where they began
+    if *game.start_board() != Board::new() || game.start_player() != Player::Black {
+        let start = format_position(game.start_board(), game.start_player());
+        output.push_str(&format!("Start: {}\n", core::str::from_utf8(&start).unwrap_or("??")));
+    }
+
+    // Result, counted under the game's rules
+    let rules = game.rules();
+    if rules.scoring == Scoring::EmptiesToWinner {
+        output.push_str("Scoring: empty squares to the winner\n");
+    }
+    if rules.misere {
+        output.push_str("Rules: misere, fewer discs wins\n");
+    }
     if let Some(result) = game.result() {
         let (black, white) = result.counts();
-        match result.winner() {
+        match result.resigned() {
             Some(Player::Black) => {
-                output.push_str(&format!("Result: Black wins {}-{}\n", black, white))
+                output.push_str(&format!("Result: Black resigned, White wins ({}-{})\n", black, white))
             }
             Some(Player::White) => {
-                output.push_str(&format!("Result: White wins {}-{}\n", black, white))
+                output.push_str(&format!("Result: White resigned, Black wins ({}-{})\n", black, white))
             }
-            None => output.push_str(&format!("Result: Draw {}-{}\n", black, white)),
+            None => match result.winner() {
+                Some(Player::Black) => {
+                    output.push_str(&format!("Result: Black wins {}-{}\n", black, white))
+                }
+                Some(Player::White) => {
+                    output.push_str(&format!("Result: White wins {}-{}\n", black, white))
+                }
+                None => output.push_str(&format!("Result: Draw {}-{}\n", black, white)),
+            },
         }
     }
 
     output.push_str("\nMoves:\n");
+    push_moves(&mut output, game, game.history().len());
 
-    // Move list
-    let history = game.history();
-    let mut move_num = 1;
-    let mut i = 0;
-
-    while i < history.len() {
-        let black_move = if i < history.len() {
-            let entry = &history[i];
-            if entry.is_pass() {
-                "--".to_string()
-            } else {
-                let alg = pos_to_algebraic(entry.pos);
-                core::str::from_utf8(&alg).unwrap_or("??").to_string()
-            }
-        } else {
-            "".to_string()
-        };
+    // Final score
+    let (black, white) = game.counts();
+    output.push_str(&format!(
+        "\nFinal: \u{25CF} {} - \u{25CB} {}\n",
+        black, white
+    ));
+    push_position(&mut output, game);
 
-        let white_move = if i + 1 < history.len() {
-            let entry = &history[i + 1];
-            if entry.is_pass() {
-                "--".to_string()
-            } else {
-                let alg = pos_to_algebraic(entry.pos);
-                core::str::from_utf8(&alg).unwrap_or("??").to_string()
-            }
-        } else {
-            "".to_string()
-        };
+    output
+}
 
-        output.push_str(&format!("{:2}. {} {}\n", move_num, black_move, white_move));
+/// Export an explored What If line, tagged as analysis
+///
+/// The first `branch_at` moves come from the game under review; later
+/// moves are the alternate line and are marked with `*`.
+pub fn format_analysis_record(line: &GameState, branch_at: usize) -> String {
+    let mut output = String::new();
 
-        move_num += 1;
-        i += 2;
+    output.push_str("[Othello Analysis]\n");
+    output.push_str("Type: Analysis, not a played game\n");
+    if branch_at < line.move_count() {
+        output.push_str(&format!("Branch: after move {}\n", branch_at));
     }
 
-    // Final score
-    let (black, white) = game.counts();
+    if *line.start_board() != Board::new() || line.start_player() != Player::Black {
+        let start = format_position(line.start_board(), line.start_player());
+        output.push_str(&format!("Start: {}\n", core::str::from_utf8(&start).unwrap_or("??")));
+    }
+
+    output.push_str("\nMoves:\n");
+    push_moves(&mut output, line, branch_at);
+    output.push_str(&format!("\nTranscript: {}\n", format_compact(line)));
+
+    let (black, white) = line.counts();
     output.push_str(&format!(
-        "\nFinal: \u{25CF} {} - \u{25CB} {}\n",
+        "\nPosition: \u{25CF} {} - \u{25CB} {}\n",
         black, white
     ));
+    push_position(&mut output, line);
 
     output
 }
 
+/// Write the last position in words, always in English
+fn push_position(output: &mut String, game: &GameState) {
+    output.push('\n');
+    for line in describe_position(game, strings(Language::English)) {
+        output.push_str(&line);
+        output.push('\n');
+    }
+}
+
+/// Describe a position in words, one line each for either side's discs,
+/// the side to move and its legal moves
+///
+/// Plain text suits a screen reader, large print, or dictating the
+/// position to someone, e.g. "Black (2): E4 D5".
+pub fn describe_position(game: &GameState, s: &Strings) -> Vec<String> {
+    let board = game.board();
+    let squares = |bits: u64| -> String {
+        if bits == 0 {
+            return String::from(s.position_none);
+        }
+        let names: Vec<String> = Board::iter_bits(bits)
+            .map(|pos| {
+                let alg = pos_to_algebraic(pos);
+                String::from(core::str::from_utf8(&alg).unwrap_or("??"))
+            })
+            .collect();
+        names.join(" ")
+    };
+
+    let mut lines: Vec<String> = [Player::Black, Player::White]
+        .iter()
+        .map(|&player| fill(s.position_discs, &[&s.player(player), &board.count(player), &squares(board.get(player))]))
+        .collect();
+    if game.is_game_over() {
+        lines.push(String::from(s.position_over));
+        return lines;
+    }
+
+    let player = game.current_player();
+    lines.push(fill(s.position_to_move, &[&s.player(player)]));
+    let moves = game.legal_moves_bitboard();
+    if moves == 0 {
+        lines.push(fill(s.position_pass, &[&s.player(player)]));
+    } else {
+        lines.push(fill(s.position_moves, &[&moves.count_ones(), &squares(moves)]));
+    }
+    lines
+}
+
+/// Write the move list two moves to a line, marking moves from `marked` on
+fn push_moves(output: &mut String, game: &GameState, marked: usize) {
+    let history = game.history();
+    let name = |i: usize| -> String {
+        let Some(entry) = history.get(i) else {
+            return String::new();
+        };
+        let mut text = if entry.is_pass() {
+            "--".to_string()
+        } else {
+            let alg = pos_to_algebraic(entry.pos);
+            core::str::from_utf8(&alg).unwrap_or("??").to_string()
+        };
+        if i >= marked {
+            text.push('*');
+        }
+        text
+    };
+
+    for (move_num, i) in (0..history.len()).step_by(2).enumerate() {
+        output.push_str(&format!("{:2}. {} {}\n", move_num + 1, name(i), name(i + 1)));
+    }
+}
+
 /// Export as compact move notation (just the moves)
 pub fn format_compact(game: &GameState) -> String {
     let mut moves = Vec::new();
@@ -104,28 +211,27 @@ pub fn format_compact(game: &GameState) -> String {
     moves.join(" ")
 }
 
-/// Export game over TCP (port 7880)
-/// Returns true if successful
+/// Port game and analysis records are sent from
+pub const EXPORT_PORT: u16 = 7880;
+
+/// Export game over TCP (port 7880), pumping `cid` when done
 #[allow(dead_code)]
-pub fn export_via_tcp(game: &GameState, mode: &str, player_color: Option<Player>) -> bool {
-    #[cfg(target_os = "none")]
-    {
-        use std::io::Write;
-        use std::net::TcpListener;
+pub fn export_via_tcp(
+    game: &GameState,
+    mode: &str,
+    player_color: Option<Player>,
+    meta: &GameMeta,
+    cid: Option<xous::CID>,
+) -> Transfer {
+    let record = format_game_record(game, mode, player_color, "", meta, "");
+    Transfer::send(EXPORT_PORT, record.into_bytes(), cid)
+}
 
-        let record = format_game_record(game, mode, player_color, "");
+/// Port a backup is sent from
+pub const BACKUP_PORT: u16 = 7883;
 
-        if let Ok(listener) = TcpListener::bind("0.0.0.0:7880") {
-            log::info!("Waiting for connection on port 7880...");
+/// Port a backup to restore is received on
+pub const RESTORE_PORT: u16 = 7884;
 
-            if let Ok((mut stream, _)) = listener.accept() {
-                if stream.write_all(record.as_bytes()).is_ok() {
-                    log::info!("Game record exported successfully");
-                    return true;
-                }
-            }
-        }
-    }
-    let _ = (game, mode, player_color);
-    false
-}
+/// Largest backup accepted for a restore
+pub const MAX_BACKUP_BYTES: u64 = 4 * 1024 * 1024;
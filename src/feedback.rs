@@ -2,15 +2,30 @@
 
 /// Get a random bit using hardware TRNG
 pub fn random_bit() -> bool {
+    (random_u32() & 1) != 0
+}
+
+/// Random number from the hardware TRNG
+pub fn random_u32() -> u32 {
     #[cfg(target_os = "none")]
     {
         let trng = trng::Trng::new(&xous_names::XousNames::new().unwrap()).ok();
         if let Some(t) = trng {
-            return (t.get_u32().unwrap_or(0) & 1) != 0;
+            return t.get_u32().unwrap_or(0);
         }
     }
     // Fallback for hosted mode
-    false
+    0
+}
+
+/// Local wall-clock time in milliseconds since 1970, if the clock is set
+pub fn local_time_ms() -> Option<u64> {
+    #[cfg(target_os = "none")]
+    {
+        return llio::LocalTime::new().get_local_time_ms();
+    }
+    #[allow(unreachable_code)]
+    None
 }
 
 /// Vibrate for a move being played
@@ -33,6 +48,27 @@ pub fn vibrate_invalid() {
     }
 }
 
+/// Vibrate for a haptic cue, in a pattern unlike the ordinary move buzz
+///
+/// Patterns are queued and played one after the other.
+pub fn vibrate_cue(cue: crate::storage::HapticCue) {
+    #[cfg(target_os = "none")]
+    {
+        use crate::storage::HapticCue;
+        use llio::VibePattern;
+        if let Ok(llio) = llio::Llio::new(&xous_names::XousNames::new().unwrap()) {
+            let (first, second) = match cue {
+                HapticCue::Corner => (VibePattern::Double, VibePattern::Short),
+                HapticCue::Pass => (VibePattern::Long, VibePattern::Short),
+                HapticCue::LowTime => (VibePattern::Double, VibePattern::Double),
+            };
+            llio.vibe(first).ok();
+            llio.vibe(second).ok();
+        }
+    }
+    let _ = cue;
+}
+
 /// Vibrate for game over
 pub fn vibrate_game_over() {
     #[cfg(target_os = "none")]
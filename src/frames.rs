@@ -0,0 +1,291 @@
+//! Game replays drawn to PNG images (hosted only)
+//!
+//! Set `OTHELLO_FRAMES` to a game file, for example `OTHELLO_FRAMES=game.txt`,
+//! and the app draws every position of the game to `game-000.png`,
+//! `game-001.png` and so on beside the file, then exits without
+//! registering with GAM. `OTHELLO_FRAMES=game.txt,sheet` draws a single
+//! contact sheet, `game-sheet.png`, with the positions in rows of eight
+//! instead. The file can be a game record as sent over TCP or a bare
+//! transcript such as `F5 D6 C3`. Positions after a pass are the same as
+//! before it and are left out.
+
+use std::path::{Path, PathBuf};
+
+use othello_core::{Board, GameState, Player, Position, parse_position, pos_to_rc};
+
+/// Environment variable that turns the frame export on
+const ENV_VAR: &str = "OTHELLO_FRAMES";
+
+/// Cell size of a single frame, as on the device
+const FRAME_CELL: usize = 38;
+
+/// Cell size and spacing of the boards on a contact sheet
+const SHEET_CELL: usize = 16;
+const SHEET_GAP: usize = 12;
+const SHEET_COLUMNS: usize = 8;
+
+/// Border around each board
+const BORDER: usize = 4;
+
+/// Game file to draw and how
+pub struct Config {
+    path: PathBuf,
+    sheet: bool,
+}
+
+/// Read the export settings, if the export was asked for
+pub fn from_env() -> Option<Config> {
+    let value = std::env::var(ENV_VAR).ok()?;
+    let (path, sheet) = match value.rsplit_once(',') {
+        Some((path, "sheet")) => (path, true),
+        _ => (value.as_str(), false),
+    };
+    if path.is_empty() {
+        eprintln!("{}: expected a game file, optionally followed by ,sheet", ENV_VAR);
+        return None;
+    }
+    Some(Config { path: PathBuf::from(path), sheet })
+}
+
+/// Draw the game's positions, printing what was written
+pub fn run(config: &Config) {
+    let game = match std::fs::read_to_string(&config.path).map_err(|e| e.to_string()).and_then(|text| read_game(&text)) {
+        Ok(game) => game,
+        Err(error) => {
+            eprintln!("{}: {}", config.path.display(), error);
+            return;
+        }
+    };
+
+    // Each position with the move that led to it
+    let history = game.history();
+    let positions: Vec<(Board, Option<Position>)> = (0..=history.len())
+        .filter(|&ply| ply == 0 || !history[ply - 1].is_pass())
+        .map(|ply| (*game.clone_at_move(ply).board(), ply.checked_sub(1).map(|last| history[last].pos)))
+        .collect();
+
+    let written = if config.sheet {
+        let path = sibling(&config.path, "sheet");
+        write(&path, &contact_sheet(&positions)).map(|_| 1)
+    } else {
+        positions.iter().enumerate().try_fold(0, |count, (index, &(board, last))| {
+            let mut image = Image::new(board_size(FRAME_CELL), board_size(FRAME_CELL));
+            image.board(0, 0, FRAME_CELL, &board, last);
+            write(&sibling(&config.path, &format!("{:03}", index)), &image).map(|_| count + 1)
+        })
+    };
+    match written {
+        Ok(count) => println!("Frames: {} image(s) for {} positions", count, positions.len()),
+        Err(error) => eprintln!("{}", error),
+    }
+}
+
+/// The game in a file: a game record as sent over TCP, or a bare transcript
+fn read_game(text: &str) -> Result<GameState, String> {
+    if !text.lines().any(|line| line.trim() == "Moves:") {
+        return replay(GameState::new(), text);
+    }
+
+    let mut game = GameState::new();
+    let mut lines = text.lines();
+    for line in lines.by_ref() {
+        if let Some(start) = line.strip_prefix("Start: ") {
+            let (board, player) = parse_position(start).ok_or("unreadable Start position")?;
+            game = GameState::from_board(board, player);
+        }
+        if line.trim() == "Moves:" {
+            break;
+        }
+    }
+
+    // Lines like ` 1. F5 D6`, ending at the blank line before the score;
+    // analysis records mark their own moves with `*`
+    let mut moves = String::new();
+    for line in lines.take_while(|line| !line.trim().is_empty()) {
+        let pair = line.split_once('.').map_or(line, |(_, pair)| pair);
+        moves.push_str(&pair.replace('*', " "));
+        moves.push(' ');
+    }
+    replay(game, &moves)
+}
+
+/// Play a transcript on from a game, saying where it went wrong
+fn replay(mut game: GameState, moves: &str) -> Result<GameState, String> {
+    game.apply_transcript(moves)
+        .map_err(|error| format!("move {} ({}) can't be played: {:?}", error.index + 1, error.token, error.fault))?;
+    Ok(game)
+}
+
+/// Path beside the game file, named after it with a suffix
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let stem = path.file_stem().map_or("game".into(), |stem| stem.to_string_lossy());
+    path.with_file_name(format!("{}-{}.png", stem, suffix))
+}
+
+fn write(path: &Path, image: &Image) -> Result<(), String> {
+    std::fs::write(path, image.png()).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// Width and height of a board with its border
+fn board_size(cell: usize) -> usize {
+    8 * cell + 2 * BORDER
+}
+
+/// Every position in rows, left to right
+fn contact_sheet(positions: &[(Board, Option<Position>)]) -> Image {
+    let size = board_size(SHEET_CELL);
+    let columns = positions.len().clamp(1, SHEET_COLUMNS);
+    let rows = positions.len().div_ceil(SHEET_COLUMNS).max(1);
+    let mut image = Image::new(columns * (size + SHEET_GAP) + SHEET_GAP, rows * (size + SHEET_GAP) + SHEET_GAP);
+    for (index, (board, last)) in positions.iter().enumerate() {
+        let x = SHEET_GAP + index % SHEET_COLUMNS * (size + SHEET_GAP);
+        let y = SHEET_GAP + index / SHEET_COLUMNS * (size + SHEET_GAP);
+        image.board(x, y, SHEET_CELL, board, *last);
+    }
+    image
+}
+
+/// A 1-bit image, like the display
+struct Image {
+    width: usize,
+    height: usize,
+    /// Row by row, true for dark
+    dark: Vec<bool>,
+}
+
+impl Image {
+    /// A light image
+    fn new(width: usize, height: usize) -> Self {
+        Self { width, height, dark: vec![false; width * height] }
+    }
+
+    fn set(&mut self, x: usize, y: usize, dark: bool) {
+        if x < self.width && y < self.height {
+            self.dark[y * self.width + x] = dark;
+        }
+    }
+
+    fn fill(&mut self, x: usize, y: usize, width: usize, height: usize, dark: bool) {
+        for y in y..y + height {
+            for x in x..x + width {
+                self.set(x, y, dark);
+            }
+        }
+    }
+
+    /// Draw a disc the way the device does: Black filled, White outlined
+    fn disc(&mut self, center_x: usize, center_y: usize, radius: usize, player: Player) {
+        let outer = (radius * radius) as isize;
+        let inner = (radius.saturating_sub(2) * radius.saturating_sub(2)) as isize;
+        for y in center_y.saturating_sub(radius)..=center_y + radius {
+            for x in center_x.saturating_sub(radius)..=center_x + radius {
+                let (dx, dy) = (x as isize - center_x as isize, y as isize - center_y as isize);
+                let distance = dx * dx + dy * dy;
+                if distance <= outer {
+                    self.set(x, y, player == Player::Black || distance > inner);
+                }
+            }
+        }
+    }
+
+    /// Draw a board with its top left corner at a point, marking the last
+    /// move's square at its corners as the device does
+    fn board(&mut self, x: usize, y: usize, cell: usize, board: &Board, last: Option<Position>) {
+        let size = board_size(cell);
+        self.fill(x, y, size, size, true);
+        self.fill(x + BORDER, y + BORDER, 8 * cell, 8 * cell, false);
+        let (left, top) = (x + BORDER, y + BORDER);
+        for line in 1..8 {
+            self.fill(left + line * cell, top, 1, 8 * cell, true);
+            self.fill(left, top + line * cell, 8 * cell, 1, true);
+        }
+
+        let radius = cell * 3 / 8;
+        for (position, disc) in board.squares() {
+            let Some(player) = disc else {
+                continue;
+            };
+            let (row, col) = pos_to_rc(position);
+            let center_x = left + col as usize * cell + cell / 2;
+            let center_y = top + row as usize * cell + cell / 2;
+            self.disc(center_x, center_y, radius, player);
+        }
+
+        if let Some(last) = last {
+            let (row, col) = pos_to_rc(last);
+            let corner = (cell / 10).max(4);
+            let (near_x, near_y) = (left + col as usize * cell + 2, top + row as usize * cell + 2);
+            let (far_x, far_y) = (near_x + cell - 4 - corner, near_y + cell - 4 - corner);
+            for (x, y) in [(near_x, near_y), (far_x, near_y), (near_x, far_y), (far_x, far_y)] {
+                self.fill(x, y, corner, corner, true);
+            }
+        }
+    }
+
+    /// Encode as a 1-bit greyscale PNG
+    ///
+    /// The pixel data is stored without compression, which keeps the
+    /// encoder a few lines long; a 1-bit board is small either way.
+    fn png(&self) -> Vec<u8> {
+        // Each row is a filter byte (none) and the pixels, 1 for light
+        let mut raw = Vec::with_capacity((self.width.div_ceil(8) + 1) * self.height);
+        for row in self.dark.chunks(self.width) {
+            raw.push(0);
+            for pixels in row.chunks(8) {
+                let byte = pixels.iter().enumerate().fold(0u8, |byte, (i, &dark)| if dark { byte } else { byte | 0x80 >> i });
+                raw.push(byte);
+            }
+        }
+
+        let mut header = Vec::with_capacity(13);
+        header.extend_from_slice(&(self.width as u32).to_be_bytes());
+        header.extend_from_slice(&(self.height as u32).to_be_bytes());
+        // Bit depth 1, greyscale, default compression, filtering and no interlace
+        header.extend_from_slice(&[1, 0, 0, 0, 0]);
+
+        let mut out = b"\x89PNG\r\n\x1a\n".to_vec();
+        push_chunk(&mut out, b"IHDR", &header);
+        push_chunk(&mut out, b"IDAT", &zlib_stored(&raw));
+        push_chunk(&mut out, b"IEND", &[]);
+        out
+    }
+}
+
+/// Append a PNG chunk: length, type, data and the CRC of type and data
+fn push_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = out.len();
+    out.extend_from_slice(kind);
+    out.extend_from_slice(data);
+    let crc = crc32(&out[start..]);
+    out.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// Wrap data in a zlib stream of stored deflate blocks
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x01];
+    let blocks: Vec<&[u8]> = if data.is_empty() { vec![data] } else { data.chunks(0xFFFF).collect() };
+    for (index, block) in blocks.iter().enumerate() {
+        out.push((index + 1 == blocks.len()) as u8);
+        let len = block.len() as u16;
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (a, b) = data.iter().fold((1u32, 0u32), |(a, b), &byte| {
+        let a = (a + byte as u32) % 65521;
+        (a, (b + a) % 65521)
+    });
+    b << 16 | a
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0u32, |crc, &byte| {
+        (0..8).fold(crc ^ byte as u32, |crc, _| if crc & 1 != 0 { crc >> 1 ^ 0xEDB8_8320 } else { crc >> 1 })
+    })
+}
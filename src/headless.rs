@@ -0,0 +1,271 @@
+//! Headless tests of the app loop
+//!
+//! `Driver` stands in for the message loop in `main`: it holds an
+//! `OthelloApp` with no GAM or ticktimer behind it, presses keys, sends
+//! commands and F1 menu choices, and runs the background work the AI pump
+//! would, so a test can check the screen, the board and what was saved.
+//! Nothing is drawn, and storage is an empty in-memory store per test.
+
+use std::cell::Cell;
+
+use gam::Gid;
+use gam::menu::Point;
+use othello_core::{GameState, PassPolicy, Player, RuleSet};
+
+use crate::app::{AppState, GameMode, OthelloApp, Services};
+use crate::menu::MenuItem;
+
+/// Connection handed to the app; with no pump set, nothing is sent on it
+const SELF_CID: xous::CID = 0;
+
+/// Background steps after one input before the work is taken to be stuck
+const MAX_STEPS: usize = 1000;
+
+const F1: char = '\u{F001}';
+const F4: char = '\u{F004}';
+const UP: char = '↑';
+
+/// Stand-ins for the ticktimer and GAM
+///
+/// Every reading moves the clock on a millisecond, so searches against the
+/// clock still end; the F1 menu always comes up.
+#[derive(Default)]
+struct FakeServices {
+    now: Cell<u64>,
+}
+
+impl Services for FakeServices {
+    fn elapsed_ms(&self) -> u64 {
+        self.now.set(self.now.get() + 1);
+        self.now.get()
+    }
+
+    fn sleep_ms(&self, ms: usize) {
+        self.now.set(self.now.get() + ms as u64);
+    }
+
+    fn raise_menu(&self) -> bool {
+        true
+    }
+}
+
+/// The app on its main menu, fed input the way `main` feeds it
+struct Driver {
+    app: OthelloApp,
+    services: FakeServices,
+}
+
+impl Driver {
+    fn new() -> Self {
+        let mut app = OthelloApp::new(Gid::new([0; 4]), Point::new(336, 536));
+        app.load_settings();
+        Self { app, services: FakeServices::default() }
+    }
+
+    /// Press keys in order, letting the background work finish after each
+    fn keys(&mut self, keys: &str) {
+        for key in keys.chars() {
+            self.app.handle_key(key, &self.services, SELF_CID);
+            self.settle();
+        }
+    }
+
+    /// Send a command, as a script would
+    fn command(&mut self, line: &str) {
+        self.app.remote_command(line, &self.services, SELF_CID);
+        self.settle();
+    }
+
+    /// Play squares one after another, by command
+    fn play(&mut self, moves: &str) {
+        for square in moves.split_whitespace() {
+            self.command(square);
+        }
+    }
+
+    /// Choose an item from the F1 menu
+    fn choose(&mut self, item: MenuItem) {
+        self.app.menu_chosen(item, SELF_CID);
+        self.settle();
+    }
+
+    /// Run the CPU, coach and review work the pump would
+    fn settle(&mut self) {
+        for _ in 0..MAX_STEPS {
+            if !self.app.has_background_work() {
+                return;
+            }
+            self.app.ai_tick(&self.services);
+        }
+        panic!("background work still running after {} steps", MAX_STEPS);
+    }
+
+    /// The game on the playing or game over screen
+    fn game(&self) -> &GameState {
+        match &self.app.state {
+            AppState::Playing { game, .. } | AppState::GameOver { game, .. } => game,
+            _ => panic!("no game on screen"),
+        }
+    }
+
+    fn pass_notice(&self) -> bool {
+        matches!(self.app.state, AppState::Playing { show_pass_notice: true, .. })
+    }
+}
+
+/// A game played from the start the way the app plays it
+fn replayed(moves: &str) -> GameState {
+    let mut game = GameState::new();
+    game.set_rules(RuleSet { pass: PassPolicy::Automatic, ..*game.rules() });
+    game.apply_transcript(moves).expect("legal transcript");
+    game
+}
+
+#[test]
+fn keys_start_a_game_and_play_on_the_cursor() {
+    let mut driver = Driver::new();
+    driver.keys("n6\r");
+    assert!(matches!(driver.app.state, AppState::Playing { mode: GameMode::TwoPlayer, .. }));
+
+    // The cursor starts on d4, so one up is d3
+    driver.keys(&format!("{}\r", UP));
+    assert_eq!(driver.game().board(), replayed("d3").board());
+
+    // d3 is taken now, so Enter does nothing
+    driver.keys("\r");
+    assert_eq!(driver.game().move_count(), 1);
+    assert_eq!(driver.game().current_player(), Player::White);
+}
+
+#[test]
+fn a_move_that_leaves_no_reply_passes_and_shows_a_notice() {
+    let mut driver = Driver::new();
+    driver.keys("n6\r");
+    driver.play("d3 c3 e6 d2 d1 e1 b2");
+    assert!(!driver.pass_notice());
+
+    // After c1 Black has no move
+    driver.play("c1");
+    assert!(driver.pass_notice());
+    assert_eq!(driver.game().current_player(), Player::White);
+    assert!(driver.game().history().last().is_some_and(|entry| entry.is_pass()));
+
+    // Any key takes the notice down without playing
+    driver.keys("\r");
+    assert!(!driver.pass_notice());
+    assert_eq!(driver.game().board(), replayed("d3 c3 e6 d2 d1 e1 b2 c1").board());
+}
+
+#[test]
+fn undo_and_redo_step_through_the_moves() {
+    let mut driver = Driver::new();
+    driver.keys("n6\r");
+    driver.play("d3 c3");
+
+    driver.keys("u");
+    assert_eq!(driver.game().board(), replayed("d3").board());
+    driver.choose(MenuItem::Undo);
+    assert_eq!(driver.game().board(), GameState::new().board());
+
+    // One move at a time, as they were taken back
+    driver.keys("r");
+    assert_eq!(driver.game().board(), replayed("d3").board());
+    driver.choose(MenuItem::Redo);
+    assert_eq!(driver.game().board(), replayed("d3 c3").board());
+}
+
+#[test]
+fn the_cpu_replies_to_a_move() {
+    let mut driver = Driver::new();
+    driver.command("new easy black");
+    assert!(matches!(
+        driver.app.state,
+        AppState::Playing { player_color: Player::Black, ai_thinking: false, .. }
+    ));
+
+    driver.play("d3");
+    assert_eq!(driver.game().move_count(), 2);
+    assert_eq!(driver.game().current_player(), Player::Black);
+    assert!(matches!(driver.app.state, AppState::Playing { ai_thinking: false, .. }));
+}
+
+#[test]
+fn save_and_exit_keeps_the_game_for_resume() {
+    let mut driver = Driver::new();
+    driver.keys("n6\r");
+    driver.play("f5 d6");
+    driver.choose(MenuItem::SaveAndExit);
+
+    assert!(matches!(driver.app.state, AppState::MainMenu { .. }));
+    assert!(driver.app.has_save);
+    assert!(crate::storage::has_saved_game());
+    assert_eq!(driver.app.toast.text(), Some(driver.app.text().toast_saved));
+
+    // A fresh start finds the save and resumes it
+    let mut driver = Driver::new();
+    assert!(driver.app.has_save);
+    driver.keys("r");
+    assert_eq!(driver.game().board(), replayed("f5 d6").board());
+    assert!(driver.app.playing_saved);
+}
+
+#[test]
+fn resigning_ends_the_game_and_records_the_loss() {
+    let mut driver = Driver::new();
+    driver.command("new easy black");
+    driver.play("d3");
+
+    driver.choose(MenuItem::Resign);
+    assert!(driver.app.dialog.is_open());
+    driver.keys("y");
+    assert!(matches!(driver.app.state, AppState::GameOver { .. }));
+    assert_eq!(driver.game().resigned(), Some(Player::Black));
+
+    // Asked whether to archive it; no
+    driver.keys("n");
+    assert!(!driver.app.dialog.is_open());
+
+    let stats = crate::storage::load_statistics().expect("statistics saved");
+    assert_eq!((stats.easy_losses, stats.resignations), (1, 1));
+}
+
+#[test]
+fn settings_changed_in_a_game_are_saved() {
+    let mut driver = Driver::new();
+    driver.keys("n6\rf");
+    assert!(driver.app.settings.show_frontier);
+    assert!(crate::storage::load_settings().is_some_and(|settings| settings.show_frontier));
+}
+
+#[test]
+fn help_from_the_menu_returns_to_the_game() {
+    let mut driver = Driver::new();
+    driver.keys("n6\r");
+    driver.play("f5");
+    driver.choose(MenuItem::Help);
+    assert!(matches!(driver.app.state, AppState::Help { .. }));
+
+    driver.keys(&F4.to_string());
+    assert_eq!(driver.game().board(), replayed("f5").board());
+}
+
+#[test]
+fn commands_outside_the_menu_are_refused() {
+    let mut driver = Driver::new();
+    driver.command("undo");
+    assert!(matches!(driver.app.state, AppState::MainMenu { .. }));
+    assert!(driver.app.toast.text().is_some());
+
+    // The F1 key on its own leaves the screen alone
+    driver.keys(&F1.to_string());
+    assert!(matches!(driver.app.state, AppState::MainMenu { .. }));
+}
+
+#[test]
+fn f4_on_the_main_menu_asks_before_quitting() {
+    let mut driver = Driver::new();
+    driver.keys(&F4.to_string());
+    assert!(!driver.app.should_quit);
+    driver.keys("y");
+    assert!(driver.app.should_quit);
+}
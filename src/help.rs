@@ -4,25 +4,30 @@ use gam::{Gam, GlyphStyle};
 use gam::menu::{Point, Rectangle, TextView, TextBounds};
 
 use crate::app::OthelloApp;
+use crate::i18n::{Help, Strings, fill};
 
 /// Help context determines which help text is shown
 #[derive(Debug, Clone, Copy)]
 pub enum HelpContext {
     MainMenu,
+    NewGame,
+    Settings,
+    Statistics,
     Playing,
+    GameOver,
+    Archive,
     WhatIf,
+    MoveHistory,
+    Tutorial,
+    Puzzles,
+    Editor,
 }
 
-/// Draw help screen
-pub fn draw_help(app: &OthelloApp, gam: &Gam, context: HelpContext) {
+/// Draw a page of the help screen
+pub fn draw_help(app: &OthelloApp, gam: &Gam, context: HelpContext, page: usize) {
     let gid = app.gid;
 
-    // Title
-    let title = match context {
-        HelpContext::MainMenu => "OTHELLO v1.0",
-        HelpContext::Playing => "OTHELLO - Playing",
-        HelpContext::WhatIf => "OTHELLO - What If",
-    };
+    let help = help(app.text(), context);
 
     // Border
     let margin = 20isize;
@@ -52,15 +57,13 @@ pub fn draw_help(app: &OthelloApp, gam: &Gam, context: HelpContext) {
     );
     tv.style = GlyphStyle::Bold;
     use core::fmt::Write;
-    write!(tv.text, "{}", title).ok();
+    write!(tv.text, "{}", help.title).ok();
     gam.post_textview(&mut tv).ok();
 
     // Content based on context
-    let content = match context {
-        HelpContext::MainMenu => HELP_MAIN_MENU,
-        HelpContext::Playing => HELP_PLAYING,
-        HelpContext::WhatIf => HELP_WHAT_IF,
-    };
+    let pages = help.pages;
+    let page = page.min(pages.len() - 1);
+    let content = pages[page];
 
     let mut tv = TextView::new(
         gid,
@@ -86,58 +89,28 @@ pub fn draw_help(app: &OthelloApp, gam: &Gam, context: HelpContext) {
         )),
     );
     tv.style = GlyphStyle::Small;
-    write!(tv.text, "Press any key to close").ok();
+    if pages.len() > 1 {
+        write!(tv.text, "{}", fill(app.text().help_paged, &[&(page + 1), &pages.len()])).ok();
+    } else {
+        write!(tv.text, "{}", app.text().help_close).ok();
+    }
     gam.post_textview(&mut tv).ok();
 }
 
-const HELP_MAIN_MENU: &str = r"Classic Reversi strategy game.
-Outflank your opponent's discs
-to flip them to your color.
-
-Controls:
-
-F1        Menu
-F4        Exit
-Up/Down   Navigate
-Enter     Select
-
-The player with the most discs
-when the board is full wins!
-
-In vs CPU mode, your color is
-randomly assigned each game.";
-
-const HELP_PLAYING: &str = r"Controls:
-
-F1        Menu
-F4        Save & Exit
-F2        Show Hint
-
-Arrows    Move cursor
-Enter     Place disc
-H         Toggle hints
-U         Undo last move
-
-Legend:
-[=]  Your cursor
- *   Valid move
- #   Last move played";
-
-const HELP_WHAT_IF: &str = r"Review and explore alternate
-moves from any point in the game.
-
-Controls:
-
-F1        Menu
-F4        Exit What If
-
-Left/Right  Step back/forward
-Home        Jump to start
-End         Jump to end
-
-Arrows      Move cursor
-Enter       Play alternate move
-            (branches the game)
-
-Once you branch, continue playing
-to explore 'what if' scenarios.";
+/// Help screen for a context
+pub fn help(strings: &Strings, context: HelpContext) -> &Help {
+    match context {
+        HelpContext::MainMenu => &strings.help_main_menu,
+        HelpContext::NewGame => &strings.help_new_game,
+        HelpContext::Settings => &strings.help_settings,
+        HelpContext::Statistics => &strings.help_statistics,
+        HelpContext::Playing => &strings.help_playing,
+        HelpContext::GameOver => &strings.help_game_over,
+        HelpContext::Archive => &strings.help_archive,
+        HelpContext::WhatIf => &strings.help_what_if,
+        HelpContext::MoveHistory => &strings.help_move_history,
+        HelpContext::Tutorial => &strings.help_tutorial,
+        HelpContext::Puzzles => &strings.help_puzzles,
+        HelpContext::Editor => &strings.help_editor,
+    }
+}
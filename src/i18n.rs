@@ -0,0 +1,573 @@
+//! User interface strings by language
+//!
+//! Everything the screens, menus, dialogs and help draw comes from a
+//! [`Strings`] table. Text with values in it uses `{}` placeholders that
+//! are filled in order by [`fill`].
+
+mod de;
+mod en;
+
+use core::fmt::Display;
+
+/// Interface language
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+}
+
+impl Language {
+    /// Name of the language in itself, for the settings screen
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::German => "Deutsch",
+        }
+    }
+
+    /// Cycle to the next language
+    pub const fn next(self) -> Self {
+        match self {
+            Language::English => Language::German,
+            Language::German => Language::English,
+        }
+    }
+
+    pub const fn to_u8(self) -> u8 {
+        match self {
+            Language::English => 0,
+            Language::German => 1,
+        }
+    }
+
+    /// Unknown values fall back to English
+    pub const fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Language::German,
+            _ => Language::English,
+        }
+    }
+}
+
+/// Get the string table for a language
+pub fn strings(language: Language) -> &'static Strings {
+    match language {
+        Language::English => &en::STRINGS,
+        Language::German => &de::STRINGS,
+    }
+}
+
+/// Fill the `{}` placeholders of a template in order
+pub fn fill(template: &str, args: &[&dyn Display]) -> String {
+    use core::fmt::Write;
+
+    let mut out = String::new();
+    let mut args = args.iter();
+    let mut parts = template.split("{}");
+    if let Some(first) = parts.next() {
+        out.push_str(first);
+    }
+    for part in parts {
+        if let Some(arg) = args.next() {
+            write!(out, "{}", arg).ok();
+        }
+        out.push_str(part);
+    }
+    out
+}
+
+/// Title and pages of one help screen
+pub struct Help {
+    pub title: &'static str,
+    pub pages: &'static [&'static str],
+}
+
+/// Every user-facing string for one language
+pub struct Strings {
+    // Shared
+    pub black: &'static str,
+    pub white: &'static str,
+    pub footer_menu: &'static str,
+    pub footer_exit: &'static str,
+
+    // Screen titles
+    pub title_app: &'static str,
+    pub title_new_game: &'static str,
+    pub title_handicap: &'static str,
+    pub title_settings: &'static str,
+    pub title_statistics: &'static str,
+    pub title_game_over: &'static str,
+    pub title_what_if: &'static str,
+    pub title_what_if_branched: &'static str,
+    /// Lesson number and count
+    pub title_lesson: &'static str,
+    pub title_setup: &'static str,
+    pub title_analysis: &'static str,
+    pub title_deep_analysis: &'static str,
+    pub title_load: &'static str,
+    pub title_puzzles: &'static str,
+    pub title_daily: &'static str,
+    /// Puzzle number and count
+    pub title_puzzle: &'static str,
+    pub title_history: &'static str,
+    pub title_position_text: &'static str,
+    pub title_archive: &'static str,
+    pub title_archive_review: &'static str,
+    pub title_bookmarks: &'static str,
+    pub title_demo: &'static str,
+    pub title_backup: &'static str,
+
+    // Main menu and new game
+    pub main_keys: &'static str,
+    pub saved_game: &'static str,
+    /// Moves played
+    pub saved_move: &'static str,
+    /// Level name
+    pub mode_vs_cpu: &'static str,
+    pub mode_two_player: &'static str,
+    /// CPU level names, Easy to Grandmaster
+    pub levels: [&'static str; 5],
+    /// Name of the adaptive CPU where a level name would go
+    pub level_adaptive: &'static str,
+    /// CPU levels, two players, then the adaptive CPU; an empty entry leaves a gap
+    pub new_game_options: [&'static str; 8],
+    /// One line or two per CPU level, Easy to Grandmaster
+    pub level_descriptions: [&'static str; 5],
+    pub level_strengths: [&'static str; 5],
+    pub level_think_times: [&'static str; 5],
+    /// Strength name
+    pub level_strength: &'static str,
+    /// Think time
+    pub level_think: &'static str,
+    /// Search depth in plies
+    pub level_depth: &'static str,
+    /// Yes or no
+    pub level_book: &'static str,
+    /// Empty squares left when solving starts
+    pub level_solver: &'static str,
+    pub level_no_solver: &'static str,
+    /// Empty squares left when solving for the outcome starts
+    pub level_wld: &'static str,
+    pub two_player_description: &'static str,
+    pub adaptive_description: &'static str,
+    /// Current level and highest level
+    pub adaptive_level: &'static str,
+    /// Wins, losses and draws
+    pub adaptive_record: &'static str,
+    pub new_game_keys: &'static str,
+    pub handicap_you_start: &'static str,
+    pub handicap_cpu_starts: &'static str,
+    pub handicap_black_starts: &'static str,
+    pub handicap_white_starts: &'static str,
+    pub handicap_even: &'static str,
+    /// Who starts with the corners
+    pub handicap_player: &'static str,
+    /// Who starts with the corners
+    pub handicap_opponent: &'static str,
+    pub handicap_gentle: &'static str,
+    /// Clock setting
+    pub handicap_clock: &'static str,
+    pub handicap_label_even: &'static str,
+    /// Who has the corners and how many
+    pub handicap_label_corners: &'static str,
+    pub handicap_label_gentle: &'static str,
+    pub handicap_label_you: &'static str,
+    pub handicap_label_cpu: &'static str,
+    pub clock_off: &'static str,
+    /// Minutes per player
+    pub clock_minutes: &'static str,
+
+    // Settings, in key order; the value follows each label
+    pub settings_labels: [&'static str; 16],
+    pub undo_once: &'static str,
+    pub undo_three: &'static str,
+    pub undo_unlimited: &'static str,
+    pub text_small: &'static str,
+    pub text_regular: &'static str,
+    pub text_large: &'static str,
+    pub archive_ask: &'static str,
+    pub archive_always: &'static str,
+    pub archive_never: &'static str,
+    /// Number of moves in the learned book
+    pub learned_moves: &'static str,
+    pub idle_off: &'static str,
+    /// Minutes idle
+    pub idle_minutes: &'static str,
+    pub title_status_lines: &'static str,
+    /// One name per status item, in list order
+    pub status_items: [&'static str; 7],
+    /// Off, line 1, line 2
+    pub status_placements: [&'static str; 3],
+    pub status_lines_keys: &'static str,
+    pub title_vibration: &'static str,
+    /// Vibration as a whole, then one name per haptic cue
+    pub vibration_items: [&'static str; 4],
+    pub vibration_keys: &'static str,
+
+    // Statistics
+    /// One heading per CPU level, Easy to Grandmaster, then the adaptive CPU
+    pub stats_levels: [&'static str; 6],
+    /// Won, lost, drawn
+    pub stats_record: &'static str,
+    pub stats_two_player: &'static str,
+    pub stats_resignations: &'static str,
+    pub stats_assisted: &'static str,
+    pub stats_hint_free: &'static str,
+    /// Current and best streak
+    pub stats_daily: &'static str,
+    pub stats_records_keys: &'static str,
+    /// Level name
+    pub stats_margins_title: &'static str,
+    /// Final margin ranges, lost wipeout first and won wipeout last
+    pub stats_margin_buckets: [&'static str; 10],
+    pub stats_margins_keys: &'static str,
+    pub stats_openings_title: &'static str,
+    pub stats_openings_none: &'static str,
+    pub stats_openings_best: &'static str,
+    pub stats_openings_worst: &'static str,
+    /// Opening name, games, percentage scored
+    pub stats_opening_line: &'static str,
+    pub stats_openings_keys: &'static str,
+    pub stats_thinking_title: &'static str,
+    /// Time on the player's turns
+    pub stats_player_time: &'static str,
+    /// Seconds per move and percentage of the battery per game
+    pub stats_thinking_line: &'static str,
+    pub stats_thinking_none: &'static str,
+    /// Search current in mA and battery capacity in mAh
+    pub stats_thinking_note: &'static str,
+    pub stats_thinking_keys: &'static str,
+
+    // Playing
+    /// Black moves and white moves
+    pub status_moves: &'static str,
+    /// Last move
+    pub status_last: &'static str,
+    /// Signed score
+    pub status_eval: &'static str,
+    /// Signed disc difference
+    pub status_diff: &'static str,
+    /// Recent moves, oldest first
+    pub status_recent: &'static str,
+    /// How far the search got, nodes, seconds and table hit percentage
+    pub status_engine: &'static str,
+    /// Plies searched
+    pub engine_depth: &'static str,
+    /// Empty squares solved for the outcome
+    pub engine_wld: &'static str,
+    /// Empty squares solved exactly
+    pub engine_exact: &'static str,
+    pub engine_book: &'static str,
+    pub engine_forced: &'static str,
+    pub pass: &'static str,
+    pub cpu_thinking: &'static str,
+    pub no_legal_moves: &'static str,
+    /// Disc symbol
+    pub your_move: &'static str,
+    /// Disc symbol
+    pub cpu_move: &'static str,
+    /// Colour name and disc symbol
+    pub side_move: &'static str,
+    /// Colour name and disc symbol
+    pub hand_over: &'static str,
+    /// Heading of the move list beside the board on a wide screen
+    pub panel_moves: &'static str,
+    pub undo_coach: &'static str,
+    pub undo_none_left: &'static str,
+    /// Whose move and its square
+    pub undo_done: &'static str,
+    pub redo_nothing: &'static str,
+    /// Whose move and its square
+    pub redo_done: &'static str,
+    pub whose_yours: &'static str,
+    pub whose_black: &'static str,
+    pub whose_white: &'static str,
+    pub blindfold_off: &'static str,
+    pub blindfold_inner: &'static str,
+    pub blindfold_all: &'static str,
+    /// Square played
+    pub coach_forced: &'static str,
+    /// Better square and the square played
+    pub coach_better: &'static str,
+    /// Square played
+    pub coach_good: &'static str,
+
+    // Game over
+    pub black_resigned: &'static str,
+    pub white_resigned: &'static str,
+    pub black_out_of_time: &'static str,
+    pub white_out_of_time: &'static str,
+    pub you_win: &'static str,
+    pub cpu_wins: &'static str,
+    pub black_wins: &'static str,
+    pub white_wins: &'static str,
+    pub draw: &'static str,
+    /// Hints and undos
+    pub assistance: &'static str,
+    pub peeks: &'static str,
+    pub game_over_keys: &'static str,
+    /// Black and white accuracy
+    pub review_accuracy: &'static str,
+    /// Black and white corners
+    pub review_corners: &'static str,
+    /// Discs, square and move number
+    pub review_swing: &'static str,
+    /// Move number, square played and better square
+    pub review_mistake: &'static str,
+    pub review_no_mistake: &'static str,
+    /// Moves reviewed and move count
+    pub reviewing: &'static str,
+    pub game_over_archived: &'static str,
+    pub game_over_archive_key: &'static str,
+    /// The player's time and the CPU's
+    pub game_over_time: &'static str,
+    /// Time both players took
+    pub game_over_time_shared: &'static str,
+
+    // Deep analysis
+    /// Moves analyzed and move count
+    pub deep_progress: &'static str,
+    /// Best score in the last position, from Black's side
+    pub deep_eval: &'static str,
+    /// Minutes and seconds, such as "2:05"
+    pub deep_elapsed: &'static str,
+    pub deep_running_keys: &'static str,
+    pub deep_cancelled_keys: &'static str,
+    pub deep_done: &'static str,
+
+    // Archive
+    pub archive_empty: &'static str,
+    pub archive_keys: &'static str,
+    pub archive_no_date: &'static str,
+    /// Number of moves
+    pub archive_moves: &'static str,
+    /// Black and white discs
+    pub archive_won: &'static str,
+    /// Black and white discs
+    pub archive_lost: &'static str,
+    /// Black and white discs
+    pub archive_drawn: &'static str,
+    /// Colour name, black and white discs
+    pub archive_side_won: &'static str,
+    /// Appended to the result
+    pub archive_resigned: &'static str,
+    pub archive_out_of_time: &'static str,
+    /// The note
+    pub archive_note: &'static str,
+    pub archive_sending: &'static str,
+    pub archive_sent: &'static str,
+    pub archive_not_sent: &'static str,
+
+    // Archive review
+    pub archive_review_about: &'static str,
+    /// Games waiting
+    pub archive_review_left: &'static str,
+    /// Moves reviewed and move count of the game under review
+    pub archive_review_game: &'static str,
+    /// Games finished since the screen opened
+    pub archive_review_finished: &'static str,
+    pub archive_review_done: &'static str,
+    pub archive_review_keys: &'static str,
+
+    // What If
+    /// Move shown, move count, empty squares, bookmarks
+    pub what_if_position: &'static str,
+    pub what_if_branched: &'static str,
+    pub what_if_keys: &'static str,
+    pub what_if_sending: &'static str,
+    pub what_if_sent: &'static str,
+    pub what_if_not_sent: &'static str,
+
+    pub graph_mobility: &'static str,
+    pub graph_frontier: &'static str,
+    pub graph_legend: &'static str,
+    pub bookmarks_empty: &'static str,
+    pub bookmarks_keys: &'static str,
+    /// Number in the list, moves played, last move
+    pub bookmark_entry: &'static str,
+    /// Last move of a bookmark at the start of the game
+    pub bookmark_start: &'static str,
+    /// Appended to a bookmark in a branch
+    pub bookmark_branch: &'static str,
+
+    // Tutorial
+    pub lesson_next: &'static str,
+    pub lesson_finish: &'static str,
+
+    // Editor and analysis
+    /// Colour name
+    pub to_move: &'static str,
+    pub editor_keys: &'static str,
+    pub score_win: &'static str,
+    pub score_loss: &'static str,
+    /// Colour name and depth
+    pub analysis_status: &'static str,
+    pub searching: &'static str,
+    pub search_prompt: &'static str,
+    pub principal_variation: &'static str,
+    pub load_instructions: &'static str,
+    pub receiving: &'static str,
+    pub load_listening: &'static str,
+    pub load_nothing_received: &'static str,
+    pub load_not_notation: &'static str,
+    /// Move number, the move as written and what was wrong with it
+    pub load_bad_move: &'static str,
+    pub fault_notation: &'static str,
+    pub fault_occupied: &'static str,
+    pub fault_illegal: &'static str,
+
+    // Puzzles
+    pub no_packs: &'static str,
+    pub daily_done: &'static str,
+    pub daily_ready: &'static str,
+    /// Done or ready, and the streak
+    pub daily_status: &'static str,
+    pub puzzle_menu_keys: &'static str,
+    pub objective_find: &'static str,
+    pub objective_win: &'static str,
+    /// Margin in discs
+    pub objective_win_by: &'static str,
+    pub cpu_defending: &'static str,
+    pub puzzle_daily_keys: &'static str,
+    pub puzzle_keys: &'static str,
+    /// Pack name
+    pub pack_unreadable: &'static str,
+    pub pack_listening: &'static str,
+    /// Pack name
+    pub pack_imported: &'static str,
+    pub pack_import_failed: &'static str,
+    pub pack_complete: &'static str,
+    pub daily_no_clock: &'static str,
+    pub daily_played: &'static str,
+    pub daily_preparing: &'static str,
+    /// Pack name shown for the daily puzzle
+    pub daily_pack: &'static str,
+    pub puzzle_correct: &'static str,
+    /// The right square
+    pub puzzle_wrong: &'static str,
+    /// Final disc counts, black and white
+    pub puzzle_solved: &'static str,
+    /// Final disc counts, black and white
+    pub puzzle_failed: &'static str,
+
+    // Idle demo
+    pub demo_keys: &'static str,
+
+    // Move history
+    /// Number of moves
+    pub history_total: &'static str,
+    /// What the review's move symbols mean
+    pub history_legend: &'static str,
+
+    // Position as text
+    /// Colour name, disc count and squares
+    pub position_discs: &'static str,
+    /// Squares listed for a side without any
+    pub position_none: &'static str,
+    /// Colour name
+    pub position_to_move: &'static str,
+    /// Number of legal moves and their squares
+    pub position_moves: &'static str,
+    /// Colour name
+    pub position_pass: &'static str,
+    pub position_over: &'static str,
+    pub position_keys: &'static str,
+    pub position_blindfold: &'static str,
+
+    // Backup
+    pub backup_instructions: &'static str,
+    pub backup_sending: &'static str,
+    /// Size in bytes
+    pub backup_sent: &'static str,
+    pub backup_not_sent: &'static str,
+    /// Number of entries
+    pub restore_done: &'static str,
+    pub restore_failed: &'static str,
+
+    // Menu
+    pub menu_help: &'static str,
+    pub menu_new_game: &'static str,
+    pub menu_tutorial: &'static str,
+    pub menu_puzzles: &'static str,
+    pub menu_editor: &'static str,
+    pub menu_archive: &'static str,
+    pub menu_resume: &'static str,
+    pub menu_statistics: &'static str,
+    pub menu_settings: &'static str,
+    pub menu_backup: &'static str,
+    pub menu_review_archive: &'static str,
+    pub menu_move_history: &'static str,
+    pub menu_hint: &'static str,
+    pub menu_undo: &'static str,
+    pub menu_redo: &'static str,
+    pub menu_resign: &'static str,
+    pub menu_save_and_exit: &'static str,
+    pub menu_what_if: &'static str,
+    pub menu_deep_analysis: &'static str,
+    pub menu_exit_what_if: &'static str,
+    pub menu_bookmarks: &'static str,
+    pub menu_export_line: &'static str,
+    pub menu_position_text: &'static str,
+    pub menu_main_menu: &'static str,
+    pub menu_back: &'static str,
+    pub menu_close: &'static str,
+
+    // Notices
+    /// Square hinted
+    pub toast_hint: &'static str,
+    pub toast_saved: &'static str,
+    pub toast_save_failed: &'static str,
+
+    // Command line
+    pub command_hint: &'static str,
+    /// What was typed
+    pub command_unknown: &'static str,
+
+    // Dialogs
+    pub confirm_resign: &'static str,
+    pub confirm_new_game: &'static str,
+    pub confirm_overwrite: &'static str,
+    pub confirm_quit: &'static str,
+    pub confirm_archive: &'static str,
+    pub confirm_delete_archived: &'static str,
+    pub confirm_reset_learned: &'static str,
+    pub confirm_restore: &'static str,
+    /// Corner the move gives away
+    pub confirm_blunder_corner: &'static str,
+    pub confirm_blunder_drop: &'static str,
+    pub yes: &'static str,
+    pub no: &'static str,
+    pub confirm_keys: &'static str,
+    pub choice_keys: &'static str,
+    pub prompt_keys: &'static str,
+    pub choose_game_clock: &'static str,
+    pub prompt_archive_note: &'static str,
+
+    // Help
+    /// Page and page count
+    pub help_paged: &'static str,
+    pub help_close: &'static str,
+    pub help_main_menu: Help,
+    pub help_new_game: Help,
+    pub help_settings: Help,
+    pub help_statistics: Help,
+    pub help_playing: Help,
+    pub help_game_over: Help,
+    pub help_archive: Help,
+    pub help_what_if: Help,
+    pub help_move_history: Help,
+    pub help_tutorial: Help,
+    pub help_puzzles: Help,
+    pub help_editor: Help,
+}
+
+impl Strings {
+    /// Name of a side
+    pub fn player(&self, player: othello_core::Player) -> &'static str {
+        match player {
+            othello_core::Player::Black => self.black,
+            othello_core::Player::White => self.white,
+        }
+    }
+}
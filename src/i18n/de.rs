@@ -0,0 +1,937 @@
+//! German strings
+
+use super::{Help, Strings};
+
+pub const STRINGS: Strings = Strings {
+    black: "Schwarz",
+    white: "Weiß",
+    footer_menu: "F1 Menü",
+    footer_exit: "F4 Ende",
+
+    title_app: "OTHELLO",
+    title_new_game: "NEUES SPIEL",
+    title_handicap: "VORGABE",
+    title_settings: "EINSTELLUNGEN",
+    title_statistics: "STATISTIK",
+    title_game_over: "SPIELENDE",
+    title_what_if: "WAS WÄRE WENN",
+    title_what_if_branched: "WAS WÄRE WENN (ABZWEIG)",
+    title_lesson: "LEKTION {}/{}",
+    title_setup: "AUFBAU",
+    title_analysis: "ANALYSE",
+    title_deep_analysis: "TIEFE ANALYSE",
+    title_load: "STELLUNG LADEN",
+    title_puzzles: "AUFGABEN",
+    title_daily: "TAGESAUFGABE",
+    title_puzzle: "AUFGABE {}/{}",
+    title_history: "ZUGLISTE",
+    title_position_text: "STELLUNG ALS TEXT",
+    title_archive: "ARCHIV",
+    title_archive_review: "ARCHIV PRÜFEN",
+    title_bookmarks: "LESEZEICHEN",
+    title_demo: "DEMO",
+    title_backup: "SICHERUNG",
+
+    main_keys: "Auf/Ab: wählen   Enter: öffnen   F1: Menü",
+    saved_game: "Gespeichertes Spiel",
+    saved_move: "Zug {}",
+    mode_vs_cpu: "gegen CPU {}",
+    mode_two_player: "Zwei Spieler",
+    levels: ["Leicht", "Mittel", "Schwer", "Experte", "Großmeister"],
+    level_adaptive: "Adaptiv",
+    new_game_options: [
+        "1. Leicht",
+        "2. Mittel",
+        "3. Schwer",
+        "4. Experte",
+        "5. Großmeister",
+        "",
+        "6. Zwei Spieler",
+        "7. Adaptive CPU",
+    ],
+    level_descriptions: [
+        "Spielt auf Steine und läuft in Fallen. Gut zum Lernen der Regeln.",
+        "Weiß, dass Ecken und Ränder wertvoll sind.",
+        "Rechnet weiter voraus und spielt die letzten Züge perfekt.",
+        "Eröffnungsbuch, die tiefste Suche und ein langes exaktes Endspiel.",
+        "Sucht so tief, wie es die Zeit erlaubt, und löst die letzten 22 Felder auf Sieg.",
+    ],
+    level_strengths: ["Anfänger", "Gelegenheitsspieler", "Vereinsspieler", "Starker Vereinsspieler", "Turnierspieler"],
+    level_think_times: ["Sofort", "Unter einer Sekunde", "Etwa eine Sekunde", "1-3 Sekunden", "Bis zu 20 Sekunden"],
+    level_strength: "Stärke: {}",
+    level_think: "Bedenkzeit: {}",
+    level_depth: "Suchtiefe: {} Halbzüge",
+    level_book: "Eröffnungsbuch: {}",
+    level_solver: "Endspiellöser: letzte {} Felder",
+    level_no_solver: "Endspiellöser: aus",
+    level_wld: "Sieg/Niederlage-Löser: letzte {} Felder",
+    two_player_description: "Das Gerät zwischen zwei Spielern weitergeben. Das Ergebnis zählt zur Zwei-Spieler-Bilanz.",
+    adaptive_description: "Wird stärker, wenn du gewinnst, und schwächer, wenn du verlierst, bis du etwa die Hälfte deiner Partien gewinnst.",
+    adaptive_level: "Stufe: {} von {}",
+    adaptive_record: "Deine Bilanz: {}-{}-{}",
+    new_game_keys: "Auf/Ab: wählen   Enter: weiter",
+    handicap_you_start: "Du beginnst",
+    handicap_cpu_starts: "CPU beginnt",
+    handicap_black_starts: "Schwarz beginnt",
+    handicap_white_starts: "Weiß beginnt",
+    handicap_even: "0. Ohne Vorgabe (Enter)",
+    handicap_player: "1-4. {} mit 1-4 Ecken",
+    handicap_opponent: "5-8. {} mit 1-4 Ecken",
+    handicap_gentle: "G. Sanfte CPU (flach, variabel)",
+    handicap_clock: "C. Uhr: {}",
+    handicap_label_even: "Ohne Vorgabe",
+    handicap_label_corners: "{} +{} Ecken",
+    handicap_label_gentle: "Sanfte CPU",
+    handicap_label_you: "Du",
+    handicap_label_cpu: "CPU",
+    clock_off: "Aus",
+    clock_minutes: "je {} Min.",
+
+    settings_labels: [
+        "1. Koordinaten       ",
+        "2. Gültige Züge      ",
+        "3. Zurücknehmen      ",
+        "4. Vibration         ",
+        "5. Rücknahmen        ",
+        "6. Trainer           ",
+        "7. Sprache           ",
+        "8. Textgröße         ",
+        "9. Statuszeilen",
+        "0. Archivieren       ",
+        "H. Sichtschutz       ",
+        "B. Patzerwarnung     ",
+        "F. Frontsteine       ",
+        "L. Eröffnungen lernen",
+        "R. Gelerntes löschen ",
+        "D. Demo im Leerlauf  ",
+    ],
+    undo_once: "Einmal",
+    undo_three: "3 / Spiel",
+    undo_unlimited: "Unbegrenzt",
+    text_small: "Klein",
+    text_regular: "Normal",
+    text_large: "Groß",
+    archive_ask: "Fragen",
+    archive_always: "Immer",
+    archive_never: "Nie",
+    learned_moves: "{} Züge",
+    idle_off: "Aus",
+    idle_minutes: "{} Min.",
+    title_status_lines: "STATUSZEILEN",
+    status_items: ["Mobilität", "Letzter Zug", "Bewertung", "Uhrzeit", "Steindifferenz", "Letzte Züge", "Engine (Debug)"],
+    status_placements: ["Aus", "Zeile 1", "Zeile 2"],
+    status_lines_keys: "Auf/Ab: wählen   Enter: Zeile 1 / Zeile 2 / aus",
+    title_vibration: "VIBRATION",
+    vibration_items: ["Vibration insgesamt", "CPU nimmt eine Ecke", "Du musst passen", "Uhr unter 30 s"],
+    vibration_keys: "Auf/Ab: wählen   Enter: an / aus",
+
+    stats_levels: ["gegen CPU Leicht", "gegen CPU Mittel", "gegen CPU Schwer", "gegen CPU Experte", "gegen CPU Großmeister", "gegen CPU Adaptiv"],
+    stats_record: "  Siege: {}  Niederl.: {}  Remis: {}",
+    stats_two_player: "Zwei-Spieler-Partien: {}",
+    stats_resignations: "Aufgegeben: {}",
+    stats_assisted: "Siege mit viel Hilfe: {}",
+    stats_hint_free: "Siege ohne Tipps: {}",
+    stats_daily: "Tagesserie: {} (beste {})",
+    stats_records_keys: "Rechts: Endstände, Eröffnungen, Zeit",
+    stats_margins_title: "Endstände: {}",
+    stats_margin_buckets: [
+        "Verloren, ausgelöscht",
+        "Verloren mit 33+",
+        "Verloren mit 17-32",
+        "Verloren mit 9-16",
+        "Verloren mit 1-8",
+        "Gewonnen mit 1-8",
+        "Gewonnen mit 9-16",
+        "Gewonnen mit 17-32",
+        "Gewonnen mit 33+",
+        "Gewonnen, ausgelöscht",
+    ],
+    stats_margins_keys: "Auf/Ab: Stufe   Links/Rechts: Seite",
+    stats_openings_title: "Eröffnungen gegen die CPU",
+    stats_openings_none: "Noch keine benannte Eröffnung gespielt.",
+    stats_openings_best: "Am besten",
+    stats_openings_worst: "Am schlechtesten",
+    stats_opening_line: "  {}: {} Partien, {}%",
+    stats_openings_keys: "Links: Endstände   Rechts: Zeit",
+    stats_thinking_title: "Bedenkzeit",
+    stats_player_time: "Deine Zeit: {}",
+    stats_thinking_line: "  {} s/Zug, {}% Akku/Partie",
+    stats_thinking_none: "  Noch keine Partien",
+    stats_thinking_note: "Akku geschätzt: die Suche braucht\netwa {} mA mehr, von {} mAh",
+    stats_thinking_keys: "Links: Eröffnungen",
+
+    status_moves: "\u{25CF} {} Züge  \u{25CB} {} Züge",
+    status_last: "Zuletzt: {}",
+    status_eval: "Wert: {}",
+    status_diff: "Steine: {}",
+    status_recent: "Züge: {}",
+    status_engine: "{} {} Knoten {}s TT {}%",
+    engine_depth: "T{}",
+    engine_wld: "SRN {}",
+    engine_exact: "exakt {}",
+    engine_book: "Buchzug",
+    engine_forced: "Einziger Zug",
+    pass: "Passen",
+    cpu_thinking: "CPU denkt nach",
+    no_legal_moves: "Kein gültiger Zug! Gegner ist dran",
+    your_move: "Du bist am Zug ({})",
+    cpu_move: "CPU ist am Zug ({})",
+    side_move: "{} ist am Zug ({})",
+    hand_over: "Gerät an {} ({}): Taste drücken",
+    panel_moves: "Züge",
+    undo_coach: "Keine Zugrücknahme mit Trainer",
+    undo_none_left: "Keine Zugrücknahmen mehr in dieser Partie",
+    undo_done: "Zurückgenommen: {} {}",
+    redo_nothing: "Nichts zu wiederholen",
+    redo_done: "Wiederholt: {} {}",
+    whose_yours: "dein Zug",
+    whose_black: "Zug von Schwarz",
+    whose_white: "Zug von Weiß",
+    blindfold_off: "Blind spielen aus",
+    blindfold_inner: "Blind: innere Steine verborgen",
+    blindfold_all: "Blind: alle Steine verborgen",
+    coach_forced: "Trainer: {} war erzwungen",
+    coach_better: "Trainer: {} war klar besser als {}",
+    coach_good: "Trainer: {} war ein guter Zug",
+
+    black_resigned: "SCHWARZ GIBT AUF",
+    white_resigned: "WEISS GIBT AUF",
+    black_out_of_time: "SCHWARZ: ZEIT UM",
+    white_out_of_time: "WEISS: ZEIT UM",
+    you_win: "DU GEWINNST!",
+    cpu_wins: "CPU GEWINNT!",
+    black_wins: "SCHWARZ GEWINNT!",
+    white_wins: "WEISS GEWINNT!",
+    draw: "REMIS!",
+    assistance: "Tipps: {}   Rücknahmen: {}",
+    peeks: "   Blicke: {}",
+    game_over_keys: "Enter: Neues Spiel   W: Was wäre wenn   N: Modus",
+    review_accuracy: "Genauigkeit \u{25CF} {} \u{25CB} {}",
+    review_corners: "Ecken \u{25CF} {} \u{25CB} {}",
+    review_swing: "Größter Umschwung: {} Steine, {} (Zug {})",
+    review_mistake: "Schwächster Zug: {}. {}, {} war besser  M: zeigen",
+    review_no_mistake: "Keine klaren Fehler",
+    reviewing: "Züge werden geprüft {}/{}...",
+    game_over_archived: "Im Archiv gespeichert",
+    game_over_archive_key: "A: im Archiv speichern",
+    game_over_time: "Zeit: du {}, CPU {}",
+    game_over_time_shared: "Zeit: {}",
+
+    deep_progress: "Analysierte Züge: {}/{}",
+    deep_eval: "Bewertung für \u{25CF}: {}",
+    deep_elapsed: "Zeit: {}",
+    deep_running_keys: "C: Abbrechen   F4: Zurück",
+    deep_cancelled_keys: "Abgebrochen. Enter: Weiter   F4: Zurück",
+    deep_done: "Fertig. Die Auswertung am Spielende nutzt jetzt diese Analyse.",
+
+    archive_empty: "Noch keine archivierten Partien.",
+    archive_keys: "Enter: öffnen  D: löschen  N: Notiz  X: senden",
+    archive_no_date: "Datum unbekannt",
+    archive_moves: "{} Züge",
+    archive_won: "Gewonnen {}-{}",
+    archive_lost: "Verloren {}-{}",
+    archive_drawn: "Remis {}-{}",
+    archive_side_won: "{} gewinnt {}-{}",
+    archive_resigned: " (aufgegeben)",
+    archive_out_of_time: " (Zeit abgelaufen)",
+    archive_note: "Notiz: {}",
+    archive_sending: "Warte auf Rechner an Port 7880...",
+    archive_sent: "Partie gesendet",
+    archive_not_sent: "Nichts gesendet",
+
+    archive_review_about: "Archivierte Partien ohne fertige Auswertung werden nacheinander geprüft. Beim Anhalten bleibt der Fortschritt erhalten.\n\nDie Prüfung läuft auch, ein Zug je Sekunde, solange das Hauptmenü unberührt ist oder die Demo zeigt.",
+    archive_review_left: "Wartende Partien: {}",
+    archive_review_game: "Diese Partie: Zug {}/{}",
+    archive_review_finished: "Jetzt geprüft: {}",
+    archive_review_done: "Alle archivierten Partien sind ausgewertet.",
+    archive_review_keys: "F4: Anhalten",
+
+    what_if_position: "Zug {}/{}  Leer: {}  Marken: {}",
+    what_if_branched: "Alternativer Spielverlauf...",
+    what_if_keys: "Li/Re: Blättern  Enter: Abzweigen  G: Grafik",
+    what_if_sending: "Warte auf Rechner an Port 7880...",
+    what_if_sent: "Variante als Analyse gesendet",
+    what_if_not_sent: "Nichts gesendet",
+    graph_mobility: "Mobilität (legale Züge)",
+    graph_frontier: "Frontsteine",
+    graph_legend: "Dick: Schwarz  Dünn: Weiß",
+    bookmarks_empty: "Noch keine Lesezeichen. B in Was wäre wenn markiert eine Stellung.",
+    bookmarks_keys: "Auf/Ab: wählen   Enter: springen   D: löschen",
+    bookmark_entry: "{}. Zug {}: {}",
+    bookmark_start: "Anfang",
+    bookmark_branch: " (Abzweig)",
+
+    lesson_next: "Enter: nächste Lektion",
+    lesson_finish: "Enter: beenden",
+
+    to_move: "{} am Zug",
+    editor_keys: "Enter/B/W/X: Stein  S: Seite  C: leeren  N: Start\nP: 2 Spieler  1-4: gegen CPU  A: Analyse",
+    score_win: "Sieg",
+    score_loss: "Verlust",
+    analysis_status: "{} am Zug   Tiefe {}   \u{2190}/\u{2192} Tiefe  Enter: Suche",
+    searching: "Suche läuft...",
+    search_prompt: "Enter startet die Suche",
+    principal_variation: "HV:",
+    load_instructions: "64 Felder von A1 bis H8 (X, O oder -) und die Seite am Zug eingeben, oder Züge ab der Grundstellung wie F5 D6 C3.\n\nEnter bei leerer Zeile empfängt die Notation über TCP-Port 7882.",
+    receiving: "Empfange...",
+    load_listening: "Notation an Port 7882 senden...",
+    load_nothing_received: "Nichts empfangen",
+    load_not_notation: "Keine Stellung und keine gültige Zugfolge",
+    load_bad_move: "Zug {} ({}): {}",
+    fault_notation: "kein Feld",
+    fault_occupied: "Feld besetzt",
+    fault_illegal: "hier nicht erlaubt",
+
+    no_packs: "Noch keine Aufgabensammlungen",
+    daily_done: "gelöst",
+    daily_ready: "offen",
+    daily_status: "Tagesaufgabe: {}  Serie: {}",
+    puzzle_menu_keys: "Enter: Öffnen   D: Tagesaufgabe   I: TCP-Import",
+    objective_find: "Finde den besten Zug",
+    objective_win: "Spiele weiter und gewinne",
+    objective_win_by: "Gewinne mit {} oder mehr Steinen",
+    cpu_defending: "CPU verteidigt...",
+    puzzle_daily_keys: "Enter: Fertig",
+    puzzle_keys: "Enter: Weiter   R: Nochmal",
+    pack_unreadable: "{} nicht lesbar",
+    pack_listening: "Aufgabensammlung an Port 7881 senden...",
+    pack_imported: "{} importiert",
+    pack_import_failed: "Import fehlgeschlagen",
+    pack_complete: "Sammlung abgeschlossen",
+    daily_no_clock: "Uhr stellen, um Tagesaufgaben zu spielen",
+    daily_played: "Heutige Aufgabe erledigt - morgen wieder",
+    daily_preparing: "Heutige Aufgabe wird vorbereitet...",
+    daily_pack: "Tagesaufgabe",
+    puzzle_correct: "Richtig!",
+    puzzle_wrong: "Nein - richtig war {}",
+    puzzle_solved: "Gelöst! ({}-{})",
+    puzzle_failed: "Diesmal nicht ({}-{})",
+
+    demo_keys: "Beliebige Taste: zurück zum Menü",
+
+    history_total: "Gesamt: {} Züge",
+    history_legend: "! einziger Zug  ?! ungenau  ? Fehler  ?? Patzer",
+
+    position_discs: "{} ({}): {}",
+    position_none: "keine",
+    position_to_move: "{} am Zug",
+    position_moves: "Mögliche Züge ({}): {}",
+    position_pass: "{} hat keinen Zug und muss passen",
+    position_over: "Partie beendet",
+    position_keys: "Hoch/Runter: blättern   F4: zurück",
+    position_blindfold: "Nicht mit Augenbinde",
+
+    backup_instructions: "Eine Sicherung enthält Einstellungen, Statistik, gespeichertes Spiel, Aufgabenpakete, Archiv und gelernte Eröffnungen.\n\nB sendet eine Sicherung an den ersten Rechner, der sich mit TCP-Port 7883 verbindet.\n\nR empfängt eine Sicherung über TCP-Port 7884 und ersetzt damit alle Daten.",
+    backup_sending: "Warte auf Verbindung an Port 7883...",
+    backup_sent: "Sicherung gesendet ({} Bytes)",
+    backup_not_sent: "Nichts gesendet",
+    restore_done: "{} Einträge wiederhergestellt",
+    restore_failed: "Keine gültige Sicherung - nichts geändert",
+
+    menu_help: "Hilfe",
+    menu_new_game: "Neues Spiel",
+    menu_tutorial: "Einführung",
+    menu_puzzles: "Aufgaben",
+    menu_editor: "Stellungseditor",
+    menu_archive: "Archiv",
+    menu_resume: "Fortsetzen",
+    menu_statistics: "Statistik",
+    menu_settings: "Einstellungen",
+    menu_backup: "Sichern & Wiederherstellen",
+    menu_review_archive: "Alle prüfen",
+    menu_move_history: "Zugliste",
+    menu_hint: "Tipp",
+    menu_undo: "Zurück",
+    menu_redo: "Wiederholen",
+    menu_resign: "Aufgeben",
+    menu_save_and_exit: "Speichern & Ende",
+    menu_what_if: "Was wäre wenn",
+    menu_deep_analysis: "Tiefe Analyse",
+    menu_exit_what_if: "Was wäre wenn beenden",
+    menu_bookmarks: "Lesezeichen",
+    menu_export_line: "Variante senden",
+    menu_position_text: "Stellung als Text",
+    menu_main_menu: "Hauptmenü",
+    menu_back: "Zurück",
+    menu_close: "Menü schließen",
+
+    toast_hint: "Tipp: {}",
+    toast_saved: "Partie gespeichert",
+    toast_save_failed: "Speicherfehler: nicht gespeichert",
+    command_hint: "Zug oder Befehl tippen, dann Enter   F4: Abbruch",
+    command_unknown: "Unbekannter Befehl: {}",
+
+    confirm_resign: "Diese Partie aufgeben?",
+    confirm_new_game: "Diese Partie abbrechen?",
+    confirm_overwrite: "Gespeichertes Spiel ersetzen?",
+    confirm_quit: "Othello beenden?",
+    confirm_archive: "Diese Partie im Archiv speichern?",
+    confirm_delete_archived: "Diese Partie aus dem Archiv löschen?",
+    confirm_reset_learned: "Aus deinen Partien gelernte Eröffnungen vergessen?",
+    confirm_restore: "Alle Othello-Daten durch eine Sicherung ersetzen?",
+    confirm_blunder_corner: "Gibt {} her. Trotzdem?",
+    confirm_blunder_drop: "Viel schwächer. Trotzdem?",
+    yes: "Ja",
+    no: "Nein",
+    confirm_keys: "Y/N oder Links/Rechts + Enter",
+    choice_keys: "Hoch/Runter + Enter   F4: abbrechen",
+    prompt_keys: "Enter: speichern   F4: abbrechen",
+    choose_game_clock: "Bedenkzeit",
+    prompt_archive_note: "Notiz zu dieser Partie",
+
+    help_paged: "Seite {}/{}   Auf/Ab: blättern   Andere Taste: schließen",
+    help_close: "Beliebige Taste schließt",
+    help_main_menu: Help {
+        title: "OTHELLO v1.0",
+        pages: &[
+            r"Das klassische Strategiespiel
+Reversi. Schließe gegnerische
+Steine ein, um sie in deine
+Farbe umzudrehen.
+
+Tasten:
+
+F1        Menü
+F4        Ende
+Auf/Ab    Auswählen
+Enter     Bestätigen
+
+Wer am Ende die meisten Steine
+hat, gewinnt!
+
+Gegen die CPU wird deine Farbe
+jedes Spiel ausgelost.",
+            r"Tasten im Hauptmenü:
+
+Auf/Ab    Eintrag wählen
+Enter     Öffnen
+N         Neues Spiel
+R         Gespeichertes Spiel
+T         Einführung
+P         Aufgaben
+E         Stellungseditor
+S         Einstellungen
+Q         Beenden
+
+F1 und dann Hilfe erklärt auf
+jedem Bildschirm die Tasten.",
+            r"Netzwerk (WLAN muss an sein):
+
+Port 7881  Aufgaben empfangen
+Port 7882  Stellungen und
+           Zuglisten empfangen
+Port 7880  Partien senden
+
+Senden mit netcat vom Rechner:
+nc -N <Geräte-IP> 7882
+
+Stellungen sind 64 Felder A1-H8
+als X, O oder -, dann X oder O
+für die Seite am Zug.",
+        ],
+    },
+    help_new_game: Help {
+        title: "OTHELLO - Neues Spiel",
+        pages: &[
+            r"Wähle einen Modus:
+
+Auf/Ab    Wählen, Enter nimmt
+1-5       CPU, Leicht bis
+          Großmeister
+6 / T     Zwei Spieler
+7 / A     Adaptive CPU
+
+Das Menü öffnet auf der zuletzt
+gespielten CPU-Stufe. Die
+adaptive CPU steigt nach deinem
+Sieg eine Stufe auf und nach
+einer Niederlage eine ab.",
+            r"Dann wähle eine Vorgabe:
+
+Enter     Ohne Vorgabe
+1-4       Du beginnst mit 1-4
+          Ecken
+5-8       Der Gegner beginnt
+          mit 1-4 Ecken
+G         Sanfte CPU (flach
+          und variabel)
+C         Uhr: aus, oder je 5,
+          10 oder 20 Minuten
+
+Partien mit Vorgabe zählen nicht
+in der Statistik je Stufe.",
+            r"Spieluhr:
+
+Die Zeit jedes Spielers läuft
+nur, wenn er am Zug ist; die CPU
+hat keine Uhr. Die Restzeit
+steht unter dem Brett und
+blinkt in den letzten 30
+Sekunden, mit Vibrationssignal,
+falls eingeschaltet.
+
+Wer die Zeit überschreitet,
+verliert wie bei Aufgabe. Bei
+offenem Menü steht die Uhr.",
+        ],
+    },
+    help_settings: Help {
+        title: "OTHELLO - Einstellungen",
+        pages: &[
+            r"Taste drücken zum Ändern:
+
+1  Koordinaten anzeigen
+2  Gültige Züge (Punkte)
+3  Zurücknehmen erlauben
+4  Vibration (insgesamt und
+   jedes Signal einzeln)
+5  Rücknahmen (1, 3 oder
+   unbegrenzt je Spiel)
+6  Trainer (bewertet deine
+   Züge; keine Rücknahmen)
+7  Sprache
+8  Textgröße (Statuszeile,
+   Menüs und Zugliste)
+9  Statuszeilen
+0  Archivieren (fragen, immer
+   oder nie am Spielende)
+
+F4        Zurück",
+            r"Weitere Einstellungen:
+
+H  Sichtschutz (Hilfen zwischen
+   Zügen zu zweit verbergen)
+B  Patzerwarnung (fragt, bevor
+   du eine Ecke hergibst)
+F  Frontsteine (Steine neben
+   einem leeren Feld)
+L  Eröffnungen lernen (Experte
+   und Großmeister wiederholen
+   Gewinnzüge)
+R  Gelernte Eröffnungen löschen
+D  Demo im Leerlauf (CPU spielt
+   gegen sich, wenn das Menü ruht)
+
+F4        Zurück",
+            r"Statuszeilen:
+
+Wähle, was beim Spielen unter
+dem Brett steht:
+
+Mobilität   Gültige Züge
+Letzter Zug Zuletzt gesetzt
+Bewertung   CPU-Wertung für dich
+Uhrzeit     Aktuelle Zeit
+Steine      Vorsprung/Rückstand
+Letzte Züge Die letzten sechs
+Engine      Letzte CPU-Suche
+
+Auf/Ab wählt, Enter setzt auf
+Zeile 1, Zeile 2 oder aus.
+Zeile 2 zeigt auch, wer am
+Zug ist.
+
+Einstellungen werden sofort
+gespeichert.",
+            r"Vibration (4):
+
+Vibration insgesamt schaltet
+alle Signale zusammen aus. Jedes
+Signal hat sein eigenes Muster:
+
+Ecke     Doppelt, dann kurz: die
+         CPU nahm eine Ecke
+Passen   Lang, dann kurz: du
+         hast keinen Zug
+Uhr      Zweimal doppelt: unter
+         30 Sekunden übrig
+
+Auf/Ab wählt, Enter schaltet
+das Signal an oder aus.",
+            r"Sichern & Wiederherstellen
+(F1-Menü):
+
+Kopiert alles, was das Spiel
+speichert: Einstellungen,
+Statistik, gespeichertes Spiel,
+Aufgabenpakete, Archiv und
+gelernte Eröffnungen.
+
+B  Sicherung an den ersten
+   Rechner an TCP-Port 7883
+R  Sicherung über TCP-Port 7884
+   empfangen und alle Daten
+   ersetzen (mit Rückfrage)
+
+Eine beschädigte Sicherung
+wird abgelehnt, nichts ändert
+sich.",
+        ],
+    },
+    help_statistics: Help {
+        title: "OTHELLO - Statistik",
+        pages: &[
+            r"Deine Bilanz aller Partien:
+
+Siege, Niederlagen und Remis je
+CPU-Stufe, Zwei-Spieler-Partien
+und aufgegebene Partien.
+
+Siege mit viel Hilfe brauchten
+mehr als drei Rücknahmen. Siege
+ohne Tipps kamen ohne Tipps und
+ohne Blicke im Blindspiel aus.
+
+Die Tagesserie zählt an Folge-
+tagen gelöste Tagesaufgaben.
+
+Rechts    Endstände, Eröffnungen,
+          Bedenkzeit
+F4        Zurück",
+            r"Endstände:
+
+Ein Balkendiagramm, wie hoch du
+gegen eine CPU-Stufe gewonnen
+oder verloren hast, vom
+Auslöschen bis zu einem Stein.
+
+Auf/Ab    Stufe wählen
+Links     Zurück zur Bilanz
+
+Vorgabe- und aufgegebene
+Partien zählen nicht; Remis
+stehen auf der ersten Seite.",
+            r"Eröffnungen:
+
+Partien gegen die CPU, die mit
+einer benannten Eröffnung wie
+Tiger oder Buffalo begannen,
+egal von welcher Seite. Die
+besten und schlechtesten stehen
+mit Partienzahl und deiner
+Ausbeute da; Remis zählt halb.
+
+Links     Zurück zu den Endständen
+Rechts    Bedenkzeit",
+            r"Bedenkzeit:
+
+Deine eigene Zeit am Zug, und je
+CPU-Stufe die mittlere Zeit pro
+Zug und ein grob geschätzter
+Anteil am Akku pro Partie.
+
+Die Schätzung zählt nur die
+Suche, mit festem Mehrverbrauch
+und vollem Akku; sie dient zum
+Vergleich der Stufen. Partien
+mit sanfter Vorgabe zählen nicht.
+
+Links     Zurück zu den Eröffnungen",
+        ],
+    },
+    help_playing: Help {
+        title: "OTHELLO - Spiel",
+        pages: &[
+            r"Tasten:
+
+F1        Menü
+F4        Speichern & Ende
+F2        Tipp zeigen
+
+Pfeile    Cursor bewegen
+Enter     Stein setzen
+U         Zug zurücknehmen
+R         Zug wiederholen
+B         Blindspiel
+P         Blick (Blindspiel)
+T         Stellung als Text
+:         Zug oder Befehl tippen
+
+Legende:
+[=]  Dein Cursor
+ *   Gültiger Zug
+ #   Letzter Zug",
+            r"Trainingshilfen:
+
+Tipps und Rücknahmen werden je
+Spiel gezählt und am Ende
+gezeigt. Die Grenze für
+Rücknahmen steht in den
+Einstellungen.
+
+Der Trainer (Einstellungen)
+vergleicht jeden deiner Züge
+mit der CPU. Solange er zusieht,
+gibt es keine Rücknahmen.
+
+Das Blindspiel verbirgt erst
+die inneren, dann alle Steine;
+der letzte Zug bleibt sichtbar.
+Jeder Blick kostet den Sieg
+ohne Tipps.",
+            r"Frontsteine:
+
+F         Markierung an/aus
+
+Ein kleines Quadrat markiert
+jeden Stein neben einem leeren
+Feld: hell auf Schwarz, dunkel
+auf Weiß. Diese Frontsteine
+geben dem Gegner Züge.
+
+Halte deine eigene Front klein
+und spiele ruhige Züge von
+innen heraus.",
+        ],
+    },
+    help_game_over: Help {
+        title: "OTHELLO - Spielende",
+        pages: &[
+            r"Die Partie ist vorbei.
+
+Enter     Neues Spiel, gleicher
+          Modus und Vorgabe
+W         Was wäre wenn: Partie
+          nachspielen und andere
+          Züge ausprobieren
+N         Neuen Modus wählen
+M         Was wäre wenn beim
+          schwächsten Zug
+A         Im Archiv speichern
+F         Frontsteine markieren
+
+F1        Menü (Was wäre wenn,
+          Zugliste, Neues Spiel)
+F4        Hauptmenü
+
+Benutzte Tipps, Rücknahmen und
+Blicke stehen unter dem Ergebnis.",
+            r"Unter dem Brett wird die Partie
+Zug für Zug geprüft:
+
+Genauigkeit  Anteil der Züge
+             nahe am besten
+Ecken        Eroberte Ecken
+Umschwung    Zug, der den Vor-
+             sprung am meisten
+             änderte
+Schwächster  Teuerster Zug und
+             was besser war
+
+Gegen die CPU ist der schwächste
+Zug dein eigener. Die Prüfung
+dauert etwas; der Fortschritt
+wird angezeigt.",
+            r"D (oder Tiefe Analyse im F1-
+Menü) prüft die Partie erneut
+in Stufe Schwer und löst die
+letzten 12 Züge exakt. Das kann
+Minuten dauern; angezeigt werden
+geprüfte Züge, die letzte
+Bewertung und die Zeit.
+
+C         Abbrechen, geprüfte
+          Züge bleiben erhalten
+Enter     Nach Abbruch weiter
+F4        Zurück zur Auswertung
+
+Danach nutzen Auswertung und
+Zugmarken die tiefe Analyse.",
+        ],
+    },
+    help_archive: Help {
+        title: "OTHELLO - Archiv",
+        pages: &[
+            r"Beendete Partien, die neueste
+zuerst.
+
+Auf/Ab    Partie wählen
+Enter     Mit Zusammenfassung
+          öffnen
+D         Partie löschen
+N         Notiz zur Partie, Enter
+          speichert sie
+X         Partie mit Notiz an
+          TCP-Port 7880 senden
+R         Alle Partien ohne
+          Auswertung prüfen
+F4        Zurück
+
+Am Spielende wird gefragt, ob
+die Partie ins Archiv kommt.
+Einstellung 0 speichert immer
+oder nie.",
+        ],
+    },
+    help_what_if: Help {
+        title: "OTHELLO - Was wäre wenn",
+        pages: &[
+            r"Spiele die Partie nach und
+probiere an jeder Stelle andere
+Züge aus.
+
+Tasten:
+
+F1        Menü
+F4        Was wäre wenn beenden
+
+Links/Rechts  Zug vor/zurück
+Auf/Ab        Cursor bewegen
+Enter         Anderen Zug
+              spielen (zweigt
+              die Partie ab)
+G             Mobilitäts-, dann
+              Frontgrafik, dann
+              wieder das Brett
+F             Frontsteine
+X             Variante senden
+              (Port 7880)",
+            r"Abzweigen:
+
+Gehe zu dem Zug zurück, den du
+ändern willst, und spiele mit
+Enter einen anderen gültigen
+Zug. Ab dann läuft eine neue
+Partie; das Original bleibt
+unverändert.
+
+Spiele weiter Züge für beide
+Seiten, um zu sehen, wie es
+ausgeht. F4 kehrt zur beendeten
+Partie zurück.",
+            r"Lesezeichen:
+
+B         Stellung auf dem Schirm
+          merken
+J         Lesezeichen zeigen
+          (auch im F1-Menü)
+
+In der Liste wählt Auf/Ab, Enter
+springt zur Stellung zurück,
+samt Abzweig, und D löscht ein
+Lesezeichen. Bis zu 12 bleiben
+erhalten, solange Was wäre wenn
+offen ist; Marken in der
+Statuszeile zählt sie.",
+        ],
+    },
+    help_move_history: Help {
+        title: "OTHELLO - Zugliste",
+        pages: &[
+            r"Alle Züge der Partie, Schwarz
+und Weiß je Zeile.
+
+D3(2)     Stein auf D3 drehte 2
+Passen    Kein gültiger Zug
++/-       Vorsprung von Schwarz
+          nach der Zeile
+
+Auf/Ab    Blättern
+
+F4        Zurück zum Spiel",
+            r"Nach einer Partie markiert die
+Auswertung Züge:
+
+!         Der einzige gute Zug
+?!        Ungenau, etwas
+          schlechter als der beste
+?         Fehler
+??        Patzer, etwa eine Ecke
+
+Die Marken erscheinen auch in
+Was wäre wenn und bleiben bei
+archivierten Partien erhalten.",
+        ],
+    },
+    help_tutorial: Help {
+        title: "OTHELLO - Einführung",
+        pages: &[
+            r"Fünf Lektionen: gültige Züge,
+Ecken, X-Felder, Mobilität und
+Parität.
+
+Pfeile    Cursor bewegen
+Enter     Zug spielen
+
+Nur der Zug, um den es in der
+Lektion geht, wird angenommen;
+bei anderen gültigen Zügen gibt
+es einen Hinweis. Nach der
+Erklärung geht es mit Enter zur
+nächsten Lektion.
+
+F4        Einführung verlassen",
+        ],
+    },
+    help_puzzles: Help {
+        title: "OTHELLO - Aufgaben",
+        pages: &[
+            r"Aufgabenliste:
+
+Auf/Ab    Sammlung wählen
+Enter     Sammlung öffnen
+D         Tagesaufgabe (ein
+          Versuch pro Tag)
+I         Sammlung über
+          TCP-Port 7881 laden
+
+F4        Zurück",
+            r"Lösen:
+
+Pfeile    Cursor bewegen
+Enter     Zug spielen
+
+Bei Zug-Aufgaben zählt ein Zug.
+Gewinn-Aufgaben werden gegen
+einen perfekten Verteidiger zu
+Ende gespielt.
+
+Nach einer Aufgabe:
+Enter     Nächste Aufgabe
+R         Nochmal (nicht bei
+          der Tagesaufgabe)",
+        ],
+    },
+    help_editor: Help {
+        title: "OTHELLO - Stellungsaufbau",
+        pages: &[
+            r"Stellung aufbauen:
+
+Pfeile    Cursor bewegen
+Enter     Leer/Schwarz/Weiß
+B W X     Schwarz, Weiß, leer
+S         Seite am Zug wechseln
+C         Brett leeren
+N         Grundstellung
+
+Die vier Mittelfelder müssen
+besetzt sein und die Seite am
+Zug braucht einen gültigen Zug.",
+            r"Stellung verwenden:
+
+P         Zwei Spieler
+1-4       Gegen die CPU (du
+          spielst die Seite
+          am Zug)
+A         Analysebrett
+L         Aus Notation laden
+
+Analysebrett:
+Links/Rechts  Suchtiefe 1-10
+Enter         Neu suchen",
+            r"Notation laden:
+
+Gib 64 Felder von A1 bis H8
+zeilenweise als X, O oder - ein,
+dann X oder O für die Seite am
+Zug. Oder gib Züge ab der
+Grundstellung ein, z.B.
+F5 D6 C3.
+
+Rücktaste  Löschen
+Enter      Laden
+Enter bei leerer Zeile empfängt
+über TCP-Port 7882.",
+        ],
+    },
+};
@@ -0,0 +1,915 @@
+//! English strings
+
+use super::{Help, Strings};
+
+pub const STRINGS: Strings = Strings {
+    black: "Black",
+    white: "White",
+    footer_menu: "F1 Menu",
+    footer_exit: "F4 Exit",
+
+    title_app: "OTHELLO",
+    title_new_game: "NEW GAME",
+    title_handicap: "HANDICAP",
+    title_settings: "SETTINGS",
+    title_statistics: "STATISTICS",
+    title_game_over: "GAME OVER",
+    title_what_if: "WHAT IF",
+    title_what_if_branched: "WHAT IF (BRANCHED)",
+    title_lesson: "LESSON {}/{}",
+    title_setup: "SETUP",
+    title_analysis: "ANALYSIS",
+    title_deep_analysis: "DEEP ANALYSIS",
+    title_load: "LOAD POSITION",
+    title_puzzles: "PUZZLES",
+    title_daily: "DAILY PUZZLE",
+    title_puzzle: "PUZZLE {}/{}",
+    title_history: "MOVE HISTORY",
+    title_position_text: "POSITION AS TEXT",
+    title_archive: "ARCHIVE",
+    title_archive_review: "REVIEW ARCHIVE",
+    title_bookmarks: "BOOKMARKS",
+    title_demo: "DEMO",
+    title_backup: "BACKUP",
+
+    main_keys: "Up/Down: choose   Enter: open   F1: menu",
+    saved_game: "Saved game",
+    saved_move: "Move {}",
+    mode_vs_cpu: "vs CPU {}",
+    mode_two_player: "Two players",
+    levels: ["Easy", "Medium", "Hard", "Expert", "Grandmaster"],
+    level_adaptive: "Adaptive",
+    new_game_options: [
+        "1. Easy",
+        "2. Medium",
+        "3. Hard",
+        "4. Expert",
+        "5. Grandmaster",
+        "",
+        "6. Two Players",
+        "7. Adaptive CPU",
+    ],
+    level_descriptions: [
+        "Plays for discs and walks into traps. Good for learning the rules.",
+        "Knows that corners and edges are worth having.",
+        "Looks further ahead and plays the last moves perfectly.",
+        "Book openings, the deepest search and a long exact endgame.",
+        "Searches as deep as it can in its time and solves the last 22 squares for the win.",
+    ],
+    level_strengths: ["Beginner", "Casual player", "Club player", "Strong club player", "Expert player"],
+    level_think_times: ["Instant", "Under a second", "About a second", "1-3 seconds", "Up to 20 seconds"],
+    level_strength: "Strength: {}",
+    level_think: "Thinks: {}",
+    level_depth: "Search: {} plies",
+    level_book: "Opening book: {}",
+    level_solver: "Endgame solver: last {} squares",
+    level_no_solver: "Endgame solver: off",
+    level_wld: "Win/loss solver: last {} squares",
+    two_player_description: "Pass the device between two players. The result counts towards the two-player record.",
+    adaptive_description: "Gets stronger when you win and weaker when you lose, so it settles where you win about half your games.",
+    adaptive_level: "Level: {} of {}",
+    adaptive_record: "Your record: {}-{}-{}",
+    new_game_keys: "Up/Down: choose   Enter: next",
+    handicap_you_start: "You start",
+    handicap_cpu_starts: "CPU starts",
+    handicap_black_starts: "Black starts",
+    handicap_white_starts: "White starts",
+    handicap_even: "0. Even game (Enter)",
+    handicap_player: "1-4. {} with 1-4 corners",
+    handicap_opponent: "5-8. {} with 1-4 corners",
+    handicap_gentle: "G. Gentle CPU (shallow, varied)",
+    handicap_clock: "C. Clock: {}",
+    handicap_label_even: "Even game",
+    handicap_label_corners: "{} +{} corners",
+    handicap_label_gentle: "Gentle CPU",
+    handicap_label_you: "You",
+    handicap_label_cpu: "CPU",
+    clock_off: "Off",
+    clock_minutes: "{} min each",
+
+    settings_labels: [
+        "1. Show Coordinates  ",
+        "2. Show Valid Moves  ",
+        "3. Allow Undo        ",
+        "4. Vibration         ",
+        "5. Undo Limit        ",
+        "6. Coach Mode        ",
+        "7. Language          ",
+        "8. Text Size         ",
+        "9. Status Lines",
+        "0. Archive Games     ",
+        "H. Hot-seat Privacy  ",
+        "B. Blunder Warning   ",
+        "F. Frontier Marks    ",
+        "L. Learn Openings    ",
+        "R. Reset Learned     ",
+        "D. Idle Demo         ",
+    ],
+    undo_once: "Once",
+    undo_three: "3 / game",
+    undo_unlimited: "Unlimited",
+    text_small: "Small",
+    text_regular: "Regular",
+    text_large: "Large",
+    archive_ask: "Ask",
+    archive_always: "Always",
+    archive_never: "Never",
+    learned_moves: "{} moves",
+    idle_off: "Off",
+    idle_minutes: "{} min",
+    title_status_lines: "STATUS LINES",
+    status_items: ["Mobility", "Last move", "Evaluation", "Clock", "Disc difference", "Recent moves", "Engine (debug)"],
+    status_placements: ["Off", "Line 1", "Line 2"],
+    status_lines_keys: "Up/Down: choose   Enter: line 1 / line 2 / off",
+    title_vibration: "VIBRATION",
+    vibration_items: ["All vibration", "CPU takes a corner", "You must pass", "Clock under 30 s"],
+    vibration_keys: "Up/Down: choose   Enter: on / off",
+
+    stats_levels: ["vs CPU Easy", "vs CPU Medium", "vs CPU Hard", "vs CPU Expert", "vs CPU Grandmaster", "vs CPU Adaptive"],
+    stats_record: "  Won: {}  Lost: {}  Draw: {}",
+    stats_two_player: "Two Player Games: {}",
+    stats_resignations: "Resignations: {}",
+    stats_assisted: "Heavily assisted wins: {}",
+    stats_hint_free: "Hint-free wins: {}",
+    stats_daily: "Daily streak: {} (best {})",
+    stats_records_keys: "Right: margins, openings, time",
+    stats_margins_title: "Final margins: {}",
+    stats_margin_buckets: [
+        "Lost, wiped out",
+        "Lost by 33+",
+        "Lost by 17-32",
+        "Lost by 9-16",
+        "Lost by 1-8",
+        "Won by 1-8",
+        "Won by 9-16",
+        "Won by 17-32",
+        "Won by 33+",
+        "Won, wipeout",
+    ],
+    stats_margins_keys: "Up/Down: level   Left/Right: page",
+    stats_openings_title: "Openings vs CPU",
+    stats_openings_none: "No named openings played yet.",
+    stats_openings_best: "Best",
+    stats_openings_worst: "Worst",
+    stats_opening_line: "  {}: {} games, {}%",
+    stats_openings_keys: "Left: final margins   Right: time",
+    stats_thinking_title: "Thinking time",
+    stats_player_time: "Your time: {}",
+    stats_thinking_line: "  {} s/move, {}% battery/game",
+    stats_thinking_none: "  No games yet",
+    stats_thinking_note: "Battery estimate: searching draws\nabout {} mA more, from {} mAh",
+    stats_thinking_keys: "Left: openings",
+
+    status_moves: "\u{25CF} {} moves  \u{25CB} {} moves",
+    status_last: "Last: {}",
+    status_eval: "Eval: {}",
+    status_diff: "Discs: {}",
+    status_recent: "Moves: {}",
+    status_engine: "{} {} nodes {}s TT {}%",
+    engine_depth: "d{}",
+    engine_wld: "WLD {}",
+    engine_exact: "exact {}",
+    engine_book: "Book move",
+    engine_forced: "Forced move",
+    pass: "Pass",
+    cpu_thinking: "CPU thinking",
+    no_legal_moves: "No legal moves! Pass to opponent",
+    your_move: "Your move ({})",
+    cpu_move: "CPU's move ({})",
+    side_move: "{}'s move ({})",
+    hand_over: "Pass to {} ({}): press any key",
+    panel_moves: "Moves",
+    undo_coach: "No takebacks in coach mode",
+    undo_none_left: "No undos left this game",
+    undo_done: "Took back {} {}",
+    redo_nothing: "Nothing to redo",
+    redo_done: "Replayed {} {}",
+    whose_yours: "your",
+    whose_black: "Black's",
+    whose_white: "White's",
+    blindfold_off: "Blindfold off",
+    blindfold_inner: "Blindfold: inner discs hidden",
+    blindfold_all: "Blindfold: all discs hidden",
+    coach_forced: "Coach: {} was forced",
+    coach_better: "Coach: {} was clearly better than {}",
+    coach_good: "Coach: {} was a good move",
+
+    black_resigned: "BLACK RESIGNED",
+    white_resigned: "WHITE RESIGNED",
+    black_out_of_time: "BLACK OUT OF TIME",
+    white_out_of_time: "WHITE OUT OF TIME",
+    you_win: "YOU WIN!",
+    cpu_wins: "CPU WINS!",
+    black_wins: "BLACK WINS!",
+    white_wins: "WHITE WINS!",
+    draw: "DRAW!",
+    assistance: "Hints: {}   Undos: {}",
+    peeks: "   Peeks: {}",
+    game_over_keys: "Enter: New Game   W: What If   N: Mode",
+    review_accuracy: "Accuracy \u{25CF} {} \u{25CB} {}",
+    review_corners: "Corners \u{25CF} {} \u{25CB} {}",
+    review_swing: "Biggest swing: {} discs, {} (move {})",
+    review_mistake: "Worst move: {}. {}, {} was better  M: show",
+    review_no_mistake: "No clear mistakes",
+    reviewing: "Reviewing moves {}/{}...",
+    game_over_archived: "Kept in the archive",
+    game_over_archive_key: "A: keep in the archive",
+    game_over_time: "Time: you {}, CPU {}",
+    game_over_time_shared: "Time: {}",
+
+    deep_progress: "Moves analyzed: {}/{}",
+    deep_eval: "Eval for \u{25CF}: {}",
+    deep_elapsed: "Time: {}",
+    deep_running_keys: "C: Cancel   F4: Back",
+    deep_cancelled_keys: "Cancelled. Enter: Resume   F4: Back",
+    deep_done: "Done. The game over summary now uses this review.",
+
+    archive_empty: "No archived games yet.",
+    archive_keys: "Enter: open  D: delete  N: note  X: send",
+    archive_no_date: "Date unknown",
+    archive_moves: "{} moves",
+    archive_won: "Won {}-{}",
+    archive_lost: "Lost {}-{}",
+    archive_drawn: "Drawn {}-{}",
+    archive_side_won: "{} won {}-{}",
+    archive_resigned: " (resigned)",
+    archive_out_of_time: " (time ran out)",
+    archive_note: "Note: {}",
+    archive_sending: "Waiting for a host on port 7880...",
+    archive_sent: "Game sent",
+    archive_not_sent: "Nothing sent",
+
+    archive_review_about: "Archived games without a finished review are reviewed one after another. Progress is kept when you stop.\n\nReviews also run, a move a second, while the main menu sits idle or plays its demo.",
+    archive_review_left: "Games waiting: {}",
+    archive_review_game: "This game: move {}/{}",
+    archive_review_finished: "Reviewed now: {}",
+    archive_review_done: "Every archived game has its review.",
+    archive_review_keys: "F4: Stop",
+
+    what_if_position: "Move {}/{}  Empty: {}  Marks: {}",
+    what_if_branched: "Playing alternate timeline...",
+    what_if_keys: "Left/Right: Step  Enter: Branch  G: Graph",
+    what_if_sending: "Waiting for a host on port 7880...",
+    what_if_sent: "Line sent as analysis",
+    what_if_not_sent: "Nothing sent",
+    graph_mobility: "Mobility (legal moves)",
+    graph_frontier: "Frontier discs",
+    graph_legend: "Thick: Black  Thin: White",
+    bookmarks_empty: "No bookmarks yet. Press B in What If to mark a position.",
+    bookmarks_keys: "Up/Down: choose   Enter: jump   D: delete",
+    bookmark_entry: "{}. Move {}: {}",
+    bookmark_start: "start",
+    bookmark_branch: " (branch)",
+
+    lesson_next: "Enter: next lesson",
+    lesson_finish: "Enter: finish",
+
+    to_move: "{} to move",
+    editor_keys: "Enter/B/W/X: disc  S: side  C: clear  N: reset\nP: 2 players  1-4: vs CPU  A: analyze",
+    score_win: "Win",
+    score_loss: "Loss",
+    analysis_status: "{} to move   Depth {}   \u{2190}/\u{2192} depth  Enter: search",
+    searching: "Searching...",
+    search_prompt: "Press Enter to search",
+    principal_variation: "PV:",
+    load_instructions: "Type 64 squares from A1 to H8 (X, O or -) and the side to move, or moves from the start like F5 D6 C3.\n\nEnter on an empty line receives notation on TCP port 7882.",
+    receiving: "Receiving...",
+    load_listening: "Send notation to port 7882...",
+    load_nothing_received: "Nothing received",
+    load_not_notation: "Not a position or legal transcript",
+    load_bad_move: "Move {} ({}): {}",
+    fault_notation: "not a square",
+    fault_occupied: "square taken",
+    fault_illegal: "not legal here",
+
+    no_packs: "No puzzle packs yet",
+    daily_done: "done",
+    daily_ready: "ready",
+    daily_status: "Daily puzzle: {}  Streak: {}",
+    puzzle_menu_keys: "Enter: Open   D: Daily   I: Import over TCP",
+    objective_find: "Find the best move",
+    objective_win: "Play on and win",
+    objective_win_by: "Win by {} or more discs",
+    cpu_defending: "CPU is defending...",
+    puzzle_daily_keys: "Enter: Done",
+    puzzle_keys: "Enter: Next   R: Retry",
+    pack_unreadable: "Couldn't read {}",
+    pack_listening: "Send a pack to port 7881...",
+    pack_imported: "Imported {}",
+    pack_import_failed: "Import failed",
+    pack_complete: "Pack complete",
+    daily_no_clock: "Set the clock to play daily puzzles",
+    daily_played: "Today's puzzle is done - back tomorrow",
+    daily_preparing: "Preparing today's puzzle...",
+    daily_pack: "Daily",
+    puzzle_correct: "Correct!",
+    puzzle_wrong: "No - the answer was {}",
+    puzzle_solved: "Solved! ({}-{})",
+    puzzle_failed: "Not this time ({}-{})",
+
+    demo_keys: "Any key: back to the menu",
+
+    history_total: "Total: {} moves",
+    history_legend: "! only move  ?! inaccuracy  ? mistake  ?? blunder",
+
+    position_discs: "{} ({}): {}",
+    position_none: "none",
+    position_to_move: "{} to move",
+    position_moves: "Legal moves ({}): {}",
+    position_pass: "{} has no move and must pass",
+    position_over: "Game over",
+    position_keys: "Up/Down: scroll   F4: back",
+    position_blindfold: "Not while blindfolded",
+
+    backup_instructions: "A backup holds your settings, statistics, saved game, puzzle packs, archive and learned openings.\n\nB sends a backup to the first host to connect on TCP port 7883.\n\nR receives a backup on TCP port 7884 and replaces all data with it.",
+    backup_sending: "Waiting for a host on port 7883...",
+    backup_sent: "Backup sent ({} bytes)",
+    backup_not_sent: "Nothing sent",
+    restore_done: "Restored {} entries",
+    restore_failed: "Not a valid backup - nothing was changed",
+
+    menu_help: "Help",
+    menu_new_game: "New Game",
+    menu_tutorial: "Tutorial",
+    menu_puzzles: "Puzzles",
+    menu_editor: "Position Editor",
+    menu_archive: "Archive",
+    menu_resume: "Resume Game",
+    menu_statistics: "Statistics",
+    menu_settings: "Settings",
+    menu_backup: "Backup & Restore",
+    menu_review_archive: "Review All",
+    menu_move_history: "Move History",
+    menu_hint: "Hint",
+    menu_undo: "Undo",
+    menu_redo: "Redo",
+    menu_resign: "Resign",
+    menu_save_and_exit: "Save & Exit",
+    menu_what_if: "What If",
+    menu_deep_analysis: "Deep Analysis",
+    menu_exit_what_if: "Exit What If",
+    menu_bookmarks: "Bookmarks",
+    menu_export_line: "Export Line",
+    menu_position_text: "Position as Text",
+    menu_main_menu: "Main Menu",
+    menu_back: "Back",
+    menu_close: "Close Menu",
+
+    toast_hint: "Hint: {}",
+    toast_saved: "Game saved",
+    toast_save_failed: "Storage error: game not saved",
+    command_hint: "Type a move or command, then Enter   F4: cancel",
+    command_unknown: "Unknown command: {}",
+
+    confirm_resign: "Resign this game?",
+    confirm_new_game: "Abandon this game?",
+    confirm_overwrite: "Overwrite saved game?",
+    confirm_quit: "Quit Othello?",
+    confirm_archive: "Keep this game in the archive?",
+    confirm_delete_archived: "Delete this game from the archive?",
+    confirm_reset_learned: "Forget the openings learned from your games?",
+    confirm_restore: "Replace all Othello data with a backup?",
+    confirm_blunder_corner: "Gives up {}. Play anyway?",
+    confirm_blunder_drop: "Much weaker move. Play anyway?",
+    yes: "Yes",
+    no: "No",
+    confirm_keys: "Y/N or Left/Right + Enter",
+    choice_keys: "Up/Down + Enter   F4: cancel",
+    prompt_keys: "Enter: save   F4: cancel",
+    choose_game_clock: "Game clock",
+    prompt_archive_note: "Note for this game",
+
+    help_paged: "Page {}/{}   Up/Down: page   Other keys: close",
+    help_close: "Press any key to close",
+    help_main_menu: Help {
+        title: "OTHELLO v1.0",
+        pages: &[
+            r"Classic Reversi strategy game.
+Outflank your opponent's discs
+to flip them to your color.
+
+Controls:
+
+F1        Menu
+F4        Exit
+Up/Down   Navigate
+Enter     Select
+
+The player with the most discs
+when the board is full wins!
+
+In vs CPU mode, your color is
+randomly assigned each game.",
+            r"Main menu keys:
+
+Up/Down   Choose an entry
+Enter     Open it
+N         New game
+R         Resume saved game
+T         Tutorial
+P         Puzzles
+E         Position editor
+S         Settings
+Q         Quit
+
+F1 then Help on any screen
+explains the keys for that
+screen.",
+            r"Network (Wi-Fi must be on):
+
+Port 7881  Puzzle packs in
+Port 7882  Positions and move
+           lists in
+Port 7880  Game records out
+
+Send with netcat from a host:
+nc -N <device-ip> 7882
+
+Positions are 64 squares A1-H8
+as X, O or -, then X or O for
+the side to move.",
+        ],
+    },
+    help_new_game: Help {
+        title: "OTHELLO - New Game",
+        pages: &[
+            r"Choose a mode:
+
+Up/Down   Choose, Enter picks
+1-5       CPU, Easy to
+          Grandmaster
+6 / T     Two players
+7 / A     Adaptive CPU
+
+The menu opens on the last
+CPU level you played. The
+adaptive CPU steps up a level
+when you win and down when you
+lose.",
+            r"Then choose a handicap:
+
+Enter     Even game
+1-4       You start with 1-4
+          corners
+5-8       Opponent starts with
+          1-4 corners
+G         Gentle CPU (shallow
+          and varied)
+C         Clock: off, or 5, 10
+          or 20 minutes each
+
+Handicap games are not counted
+in the per-level records.",
+            r"Game clock:
+
+Each player's time runs only on
+their own turns; the CPU has no
+clock. The time left is shown
+under the board and flashes in
+the last 30 seconds, with a
+vibration cue if it is on.
+
+Running out of time loses the
+game, like resigning. The clock
+stops while the menu is open.",
+        ],
+    },
+    help_settings: Help {
+        title: "OTHELLO - Settings",
+        pages: &[
+            r"Press a key to change:
+
+1  Show Coordinates
+2  Show Valid Moves (dots)
+3  Allow Undo
+4  Vibration (all, and each
+   cue on its own)
+5  Undo Limit (1, 3 or no limit
+   per game)
+6  Coach Mode (reviews your
+   moves; no takebacks)
+7  Language
+8  Text Size (status line,
+   menus and move list)
+9  Status Lines
+0  Archive Games (ask, always
+   or never at game end)
+
+F4        Back",
+            r"More settings:
+
+H  Hot-seat Privacy (hide hints
+   between two-player turns)
+B  Blunder Warning (ask before
+   giving up a corner)
+F  Frontier Marks (discs next
+   to an empty square)
+L  Learn Openings (Expert and
+   Grandmaster repeat moves
+   that won)
+R  Reset Learned Openings
+D  Idle Demo (CPU plays itself
+   when the main menu sits idle)
+
+F4        Back",
+            r"Status lines:
+
+Choose what is shown under the
+board while playing:
+
+Mobility    Legal moves each
+Last move   Square just played
+Evaluation  CPU's score for you
+Clock       Time of day
+Discs       Disc lead or deficit
+Recent      Last six moves
+Engine      Last CPU search
+
+Up/Down picks an item, Enter
+moves it to line 1, line 2 or
+off. Line 2 also shows whose
+move it is.
+
+Settings are saved at once and
+kept across restarts.",
+            r"Vibration (4):
+
+All vibration turns the cues
+off together. Each cue has its
+own pattern:
+
+Corner   Double, then short:
+         the CPU took a corner
+Pass     Long, then short: you
+         have no move
+Clock    Two doubles: under 30
+         seconds left
+
+Up/Down picks, Enter turns the
+cue on or off.",
+            r"Backup & Restore (F1 menu):
+
+Copies everything the game
+keeps: settings, statistics,
+saved game, puzzle packs,
+archive and learned openings.
+
+B  Send a backup to the first
+   host on TCP port 7883
+R  Receive a backup on TCP
+   port 7884, replacing all
+   data (asks first)
+
+A damaged backup is refused
+and nothing is changed.",
+        ],
+    },
+    help_statistics: Help {
+        title: "OTHELLO - Statistics",
+        pages: &[
+            r"Your record across all games:
+
+Wins, losses and draws for each
+CPU level, two-player games and
+resignations.
+
+Heavily assisted wins used more
+than three undos. Hint-free wins
+used no hints or blindfold peeks.
+
+The daily streak counts puzzles
+solved on consecutive days.
+
+Right     Margins, openings,
+          thinking time
+F4        Back",
+            r"Final margins:
+
+A bar chart of how far you won
+or lost by against one CPU
+level, from wipeouts down to
+one disc and back up.
+
+Up/Down   Choose the level
+Left      Back to the records
+
+Handicap and resigned games are
+left out; draws are counted on
+the first page.",
+            r"Openings:
+
+Games against the CPU that
+began with a named opening,
+such as Tiger or Buffalo, from
+either side. The best and worst
+few are listed with the games
+played and your score, a draw
+counting half.
+
+Left      Back to the margins
+Right     Thinking time",
+            r"Thinking time:
+
+Your own time on move, and for
+each CPU level its average time
+per move and a rough share of
+the battery used per game.
+
+The estimate counts only the
+search, at a fixed extra current
+from a full battery, so use it
+to compare levels. Games with a
+gentle handicap are left out.
+
+Left      Back to the openings",
+        ],
+    },
+    help_playing: Help {
+        title: "OTHELLO - Playing",
+        pages: &[
+            r"Controls:
+
+F1        Menu
+F4        Save & Exit
+F2        Show Hint
+
+Arrows    Move cursor
+Enter     Place disc
+U         Undo last move
+R         Redo undone move
+B         Blindfold mode
+P         Peek (blindfold)
+T         Position as text
+:         Type a move or command
+
+Legend:
+[=]  Your cursor
+ *   Valid move
+ #   Last move played",
+            r"Training aids:
+
+Hints and undos are counted for
+each game and shown at the end.
+Undo limits are set in Settings.
+
+Coach mode (Settings) reviews
+each of your moves against the
+CPU. Takebacks are off while
+the coach is watching.
+
+Blindfold hides the inner discs,
+then all of them; the last move
+stays visible. Each peek counts
+against a hint-free win.",
+            r"Frontier marks:
+
+F         Marks on or off
+
+A small square marks every disc
+next to an empty square: light
+on Black's discs, dark on
+White's. These frontier discs
+give the opponent moves.
+
+Try to keep your own frontier
+small and build quiet moves
+from the inside.",
+        ],
+    },
+    help_game_over: Help {
+        title: "OTHELLO - Game Over",
+        pages: &[
+            r"The game has ended.
+
+Enter     New game, same mode
+          and handicap
+W         What If: replay and
+          explore other moves
+N         Choose a new mode
+M         What If at the worst
+          move
+A         Keep in the archive
+F         Frontier marks
+
+F1        Menu (What If, Move
+          History, New Game)
+F4        Main menu
+
+Hints, undos and peeks used are
+shown under the score.",
+            r"Under the board the game is
+reviewed move by move:
+
+Accuracy  Share of moves close
+          to the best one
+Corners   Corners each side took
+Swing     Move that changed the
+          disc lead the most
+Worst     Costliest move, and
+          what was better
+
+Against the CPU the worst move
+is your own. The review takes a
+moment; progress is shown.",
+            r"D (or Deep Analysis in the F1
+menu) reviews the game again at
+Hard strength, solving the last
+12 moves exactly. This can take
+minutes; the screen shows moves
+done, the latest evaluation and
+the time spent.
+
+C         Cancel, keeping the
+          moves judged so far
+Enter     Resume after cancelling
+F4        Back to the summary
+
+Once finished, the summary and
+move marks use the deep review.",
+        ],
+    },
+    help_archive: Help {
+        title: "OTHELLO - Archive",
+        pages: &[
+            r"Finished games, newest first.
+
+Up/Down   Choose a game
+Enter     Open it with its
+          summary
+D         Delete the game
+N         Write a note on the
+          game, Enter to keep it
+X         Send the game and its
+          note to TCP port 7880
+R         Review every game that
+          has no review yet
+F4        Back
+
+At the end of a game you are
+asked whether to keep it here.
+Setting 0 keeps every game or
+none.",
+        ],
+    },
+    help_what_if: Help {
+        title: "OTHELLO - What If",
+        pages: &[
+            r"Review and explore alternate
+moves from any point in the game.
+
+Controls:
+
+F1        Menu
+F4        Exit What If
+
+Left/Right  Step back/forward
+Up/Down     Move cursor
+Enter       Play alternate move
+            (branches the game)
+G           Mobility graph, then
+            frontier graph, then
+            the board again
+F           Frontier marks
+X           Send the line to a
+            host (port 7880)",
+            r"Branching:
+
+Step back to the move you want
+to change, then play a different
+legal move with Enter. From then
+on you are in a new timeline and
+the original game is untouched.
+
+Keep playing moves for both sides
+to see how the line turns out.
+F4 returns to the finished game.",
+            r"Bookmarks:
+
+B         Bookmark the position
+          on screen
+J         List bookmarks (also
+          in the F1 menu)
+
+In the list, Up/Down chooses,
+Enter jumps back to the
+position, branch and all, and D
+deletes a bookmark. Up to 12
+are kept while What If is open;
+Marks on the status line counts
+them.",
+        ],
+    },
+    help_move_history: Help {
+        title: "OTHELLO - Move History",
+        pages: &[
+            r"Every move of the game, Black
+then White on each line.
+
+D3(2)     Disc on D3 flipped 2
+Pass      No legal move
++/-       Black's disc lead
+          after the line
+
+Up/Down   Scroll
+
+F4        Back to the game",
+            r"After a finished game, the
+review marks moves:
+
+!         The only good move
+?!        Inaccuracy, a little
+          worse than the best
+?         Mistake
+??        Blunder, about a corner
+
+Marks also show in What If and
+are kept with archived games.",
+        ],
+    },
+    help_tutorial: Help {
+        title: "OTHELLO - Tutorial",
+        pages: &[
+            r"Five lessons: legal moves,
+corners, X-squares, mobility
+and parity.
+
+Arrows    Move cursor
+Enter     Play the move
+
+Only the move that makes the
+lesson's point is accepted; other
+legal moves get a hint to try
+again. After the explanation,
+Enter goes to the next lesson.
+
+F4        Leave the tutorial",
+        ],
+    },
+    help_puzzles: Help {
+        title: "OTHELLO - Puzzles",
+        pages: &[
+            r"Puzzles screen:
+
+Up/Down   Choose a pack
+Enter     Open pack
+D         Daily puzzle (one try
+          per day)
+I         Import a pack over
+          TCP port 7881
+
+F4        Back",
+            r"Solving:
+
+Arrows    Move cursor
+Enter     Play a move
+
+Find-the-move puzzles take one
+move. Win puzzles are played out
+against a perfect defender.
+
+When a puzzle is finished:
+Enter     Next puzzle
+R         Retry (not daily)",
+        ],
+    },
+    help_editor: Help {
+        title: "OTHELLO - Position Setup",
+        pages: &[
+            r"Setting up a position:
+
+Arrows    Move cursor
+Enter     Empty/black/white
+B W X     Black, white, clear
+S         Switch side to move
+C         Clear the board
+N         Starting position
+
+The four centre squares must be
+filled and the side to move must
+have a legal move.",
+            r"Using the position:
+
+P         Two-player game
+1-4       Play the CPU (you
+          take the side to move)
+A         Analysis board
+L         Load from notation
+
+Analysis board:
+Left/Right  Search depth 1-10
+Enter       Search again",
+            r"Loading notation:
+
+Type 64 squares from A1 to H8,
+rank by rank, as X, O or -, then
+X or O for the side to move.
+Or type moves from the start,
+like F5 D6 C3.
+
+Backspace  Delete
+Enter      Load
+Enter on an empty line listens
+on TCP port 7882.",
+        ],
+    },
+};
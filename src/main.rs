@@ -11,8 +11,24 @@ mod menu;
 mod help;
 mod storage;
 mod review;
+mod review_queue;
 mod feedback;
 mod export;
+mod dialog;
+mod toast;
+mod command;
+mod coach;
+mod tutorial;
+mod puzzle;
+mod editor;
+mod transfer;
+mod i18n;
+#[cfg(not(target_os = "none"))]
+mod selfplay;
+#[cfg(not(target_os = "none"))]
+mod frames;
+#[cfg(all(test, not(target_os = "none")))]
+mod headless;
 
 use num_traits::FromPrimitive;
 
@@ -32,9 +48,44 @@ enum AppOp {
     AiPump,
     /// Quit request
     Quit,
+    /// Suspend notification from susres
+    SuspendResume,
+    /// Once a second, for the idle demo
+    Tick,
+    /// Pointer click at canvas coordinates, hosted builds only
+    #[cfg(not(target_os = "none"))]
+    Click,
+    /// Item chosen from the F1 menu
+    MenuAction,
+    /// Line entered on the input line
+    Line,
+    /// Keys pressed by a test or demo script, up to four per message like
+    /// `Rawkeys`; debug and hosted builds only
+    ///
+    /// The remote-control opcodes are numbered apart from the others so
+    /// scripts don't depend on which of those are built in.
+    #[cfg(any(debug_assertions, not(target_os = "none")))]
+    RemoteKeys = 32,
+    /// A command from a script, sent as a string; see `command`
+    #[cfg(any(debug_assertions, not(target_os = "none")))]
+    RemoteCommand = 33,
 }
 
 fn main() -> ! {
+    // Hosted builds can play CPU-vs-CPU batches without the UI
+    #[cfg(not(target_os = "none"))]
+    if let Some(config) = selfplay::from_env() {
+        selfplay::run(&config);
+        xous::terminate_process(0)
+    }
+
+    // ...and draw a game's positions to PNG images
+    #[cfg(not(target_os = "none"))]
+    if let Some(config) = frames::from_env() {
+        frames::run(&config);
+        xous::terminate_process(0)
+    }
+
     // Initialize logging
     log_server::init_wait().unwrap();
     log::set_max_level(log::LevelFilter::Info);
@@ -52,15 +103,18 @@ fn main() -> ! {
     // Connect to ticktimer for delays
     let ticktimer = ticktimer_server::Ticktimer::new().unwrap();
 
+    // The app's handlers reach both through one handle
+    let services = app::Xous { gam: &gam, ticktimer: &ticktimer };
+
     // Register UX with GAM
     let token = gam
         .register_ux(gam::UxRegistration {
             app_name: String::from(APP_NAME),
             ux_type: gam::UxType::Chat,
-            predictor: None,
+            predictor: Some(String::from(ime_plugin_shell::SERVER_NAME_IME_PLUGIN_SHELL)),
             listener: sid.to_array(),
             redraw_id: AppOp::Redraw.to_u32().unwrap(),
-            gotinput_id: None,
+            gotinput_id: Some(AppOp::Line.to_u32().unwrap()),
             audioframe_id: None,
             rawkeys_id: Some(AppOp::Rawkeys.to_u32().unwrap()),
             focuschange_id: Some(AppOp::FocusChange.to_u32().unwrap()),
@@ -86,6 +140,17 @@ fn main() -> ! {
 
     // Self-connection for AI pump messages
     let self_cid = xous::connect(sid).expect("couldn't self-connect");
+    app.set_pump(self_cid);
+
+    // The F1 menu is the GAM's, sending back the item chosen
+    app.menu.register(self_cid, AppOp::MenuAction.to_u32().unwrap());
+
+    // Save the game in progress before the device suspends
+    let mut susres = susres::Susres::new(None, &xns, AppOp::SuspendResume.to_u32().unwrap(), self_cid)
+        .expect("couldn't register for suspend/resume");
+
+    // Seconds for the idle timer, clocks and notices
+    app.update_ticker();
 
     // Main event loop
     let mut allow_redraw = true;
@@ -102,28 +167,38 @@ fn main() -> ! {
             }
 
             Some(AppOp::Rawkeys) => xous::msg_scalar_unpack!(msg, k1, k2, k3, k4, {
-                let keys = [
-                    core::char::from_u32(k1 as u32).unwrap_or('\u{0000}'),
-                    core::char::from_u32(k2 as u32).unwrap_or('\u{0000}'),
-                    core::char::from_u32(k3 as u32).unwrap_or('\u{0000}'),
-                    core::char::from_u32(k4 as u32).unwrap_or('\u{0000}'),
-                ];
-
-                for &key in keys.iter() {
-                    if key != '\u{0000}' {
-                        let should_redraw = app.handle_key(key, &gam, &ticktimer, self_cid);
-                        if should_redraw && allow_redraw {
-                            app.draw(&gam);
-                            gam.redraw().ok();
-                        }
-                    }
-                }
+                handle_keys(&mut app, &services, self_cid, [k1, k2, k3, k4], allow_redraw);
                 // Check if quit was requested
                 if app.should_quit {
                     break;
                 }
             }),
 
+            // Scripts press keys the same way
+            #[cfg(any(debug_assertions, not(target_os = "none")))]
+            Some(AppOp::RemoteKeys) => xous::msg_scalar_unpack!(msg, k1, k2, k3, k4, {
+                handle_keys(&mut app, &services, self_cid, [k1, k2, k3, k4], allow_redraw);
+                if app.should_quit {
+                    break;
+                }
+            }),
+
+            #[cfg(any(debug_assertions, not(target_os = "none")))]
+            Some(AppOp::RemoteCommand) => {
+                let Some(line) = read_line(&msg) else {
+                    continue;
+                };
+                log::info!("Remote command: {}", line.as_str());
+                let should_redraw = app.remote_command(line.as_str(), &services, self_cid);
+                if should_redraw && allow_redraw {
+                    app.draw(&gam);
+                    gam.redraw().ok();
+                }
+                if app.should_quit {
+                    break;
+                }
+            }
+
             Some(AppOp::FocusChange) => xous::msg_scalar_unpack!(msg, new_state_code, _, _, _, {
                 let new_state = gam::FocusState::convert_focus_change(new_state_code);
                 match new_state {
@@ -140,28 +215,123 @@ fn main() -> ! {
                 }
             }),
 
-            Some(AppOp::AiPump) => xous::msg_blocking_scalar_unpack!(msg, _, _, _, _, {
+            Some(AppOp::AiPump) => xous::msg_scalar_unpack!(msg, _, _, _, _, {
                 if allow_redraw {
-                    app.ai_tick(&gam, &ticktimer);
+                    app.ai_tick(&services);
+                    app.draw(&gam);
+                    gam.redraw().ok();
+                }
+            }),
+
+            Some(AppOp::Tick) => {
+                if allow_redraw && app.tick() {
                     app.draw(&gam);
                     gam.redraw().ok();
                 }
-                xous::return_scalar(msg.sender, 0).ok();
+            }
+
+            Some(AppOp::SuspendResume) => xous::msg_scalar_unpack!(msg, token, _, _, _, {
+                app.on_suspend();
+                susres.suspend_until_resume(token).expect("couldn't execute suspend/resume");
             }),
 
+            // Clicks stand in for the key that acts on what was clicked
+            #[cfg(not(target_os = "none"))]
+            Some(AppOp::Click) => xous::msg_scalar_unpack!(msg, x, y, _, _, {
+                let point = gam::menu::Point::new(x as isize, y as isize);
+                if let Some(key) = app.click(point) {
+                    let should_redraw = app.handle_key(key, &services, self_cid);
+                    if should_redraw && allow_redraw {
+                        app.draw(&gam);
+                        gam.redraw().ok();
+                    }
+                }
+                if app.should_quit {
+                    break;
+                }
+            }),
+
+            Some(AppOp::MenuAction) => xous::msg_scalar_unpack!(msg, item, _, _, _, {
+                if let Some(item) = menu::MenuItem::from_usize(item) {
+                    app.menu_chosen(item, self_cid);
+                    if allow_redraw {
+                        app.draw(&gam);
+                        gam.redraw().ok();
+                    }
+                }
+            }),
+
+            // Typed commands; see `command`
+            Some(AppOp::Line) => {
+                let Some(line) = read_line(&msg) else {
+                    continue;
+                };
+                let should_redraw = app.command(line.as_str(), &services, self_cid);
+                if should_redraw && allow_redraw {
+                    app.draw(&gam);
+                    gam.redraw().ok();
+                }
+                if app.should_quit {
+                    break;
+                }
+            }
+
             Some(AppOp::Quit) => break,
 
             _ => log::error!("unknown opcode: {:?}", msg),
         }
+
+        // Whatever just happened may have started or ended something timed
+        if allow_redraw {
+            app.update_ticker();
+        }
     }
 
     // Cleanup
     log::info!("Othello shutting down");
+    storage::sync();
     xns.unregister_server(sid).unwrap();
     xous::destroy_server(sid).unwrap();
     xous::terminate_process(0)
 }
 
+/// Read the text line a memory message carries
+///
+/// Anything else is logged and dropped.
+fn read_line(msg: &xous::MessageEnvelope) -> Option<String> {
+    let Some(mem) = msg.body.memory_message() else {
+        log::warn!("Dropped a line message without a buffer");
+        return None;
+    };
+    let buffer = unsafe { xous_ipc::Buffer::from_memory_message(mem) };
+    match buffer.as_flat::<String, _>() {
+        Ok(line) => Some(String::from(line.as_str())),
+        Err(_) => {
+            log::warn!("Dropped a line message that didn't parse");
+            None
+        }
+    }
+}
+
+/// Act on the keys of one key message, redrawing after each that changes
+/// the screen; unused slots are zero
+fn handle_keys(
+    app: &mut app::OthelloApp,
+    services: &app::Xous,
+    self_cid: xous::CID,
+    codes: [usize; 4],
+    allow_redraw: bool,
+) {
+    let keys = codes.iter().filter_map(|&code| core::char::from_u32(code as u32)).filter(|&key| key != '\u{0000}');
+    for key in keys {
+        let should_redraw = app.handle_key(key, services, self_cid);
+        if should_redraw && allow_redraw {
+            app.draw(services.gam);
+            services.gam.redraw().ok();
+        }
+    }
+}
+
 use num_traits::ToPrimitive;
 
 impl AppOp {
@@ -1,4 +1,12 @@
 //! Menu system
+//!
+//! The F1 menu is the GAM's own menu, so it looks and takes keys like every
+//! other menu on the device. Which items it offers still comes from the
+//! screen's `MenuContext`, and a chosen item comes back to the app as a
+//! message carrying its `MenuItem`.
+
+use crate::app::Services;
+use crate::i18n::Strings;
 
 /// Menu context (determines which items are shown)
 #[derive(Debug, Clone, Copy)]
@@ -7,135 +15,257 @@ pub enum MenuContext {
     Playing,
     GameOver,
     WhatIf,
+    Settings,
+    Archive,
+    /// Any other screen
+    Screen,
 }
 
 /// Menu item actions
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, num_derive::FromPrimitive, num_derive::ToPrimitive)]
 pub enum MenuItem {
     Help,
     NewGame,
+    Tutorial,
+    Puzzles,
+    Editor,
+    Archive,
     Resume,
     Statistics,
     Settings,
+    Backup,
+    ReviewArchive,
     MoveHistory,
     Hint,
     Undo,
+    Redo,
     Resign,
     SaveAndExit,
     WhatIf,
+    DeepAnalysis,
     ExitWhatIf,
+    Bookmarks,
+    ExportLine,
+    PositionText,
     MainMenu,
+    Back,
 }
 
 impl MenuItem {
     /// Get the display label for this item
-    pub fn label(&self) -> &'static str {
+    pub fn label(&self, s: &Strings) -> &'static str {
         match self {
-            MenuItem::Help => "Help",
-            MenuItem::NewGame => "New Game",
-            MenuItem::Resume => "Resume Game",
-            MenuItem::Statistics => "Statistics",
-            MenuItem::Settings => "Settings",
-            MenuItem::MoveHistory => "Move History",
-            MenuItem::Hint => "Hint",
-            MenuItem::Undo => "Undo",
-            MenuItem::Resign => "Resign",
-            MenuItem::SaveAndExit => "Save & Exit",
-            MenuItem::WhatIf => "What If",
-            MenuItem::ExitWhatIf => "Exit What If",
-            MenuItem::MainMenu => "Main Menu",
+            MenuItem::Help => s.menu_help,
+            MenuItem::NewGame => s.menu_new_game,
+            MenuItem::Tutorial => s.menu_tutorial,
+            MenuItem::Puzzles => s.menu_puzzles,
+            MenuItem::Editor => s.menu_editor,
+            MenuItem::Archive => s.menu_archive,
+            MenuItem::Resume => s.menu_resume,
+            MenuItem::Statistics => s.menu_statistics,
+            MenuItem::Settings => s.menu_settings,
+            MenuItem::Backup => s.menu_backup,
+            MenuItem::ReviewArchive => s.menu_review_archive,
+            MenuItem::MoveHistory => s.menu_move_history,
+            MenuItem::Hint => s.menu_hint,
+            MenuItem::Undo => s.menu_undo,
+            MenuItem::Redo => s.menu_redo,
+            MenuItem::Resign => s.menu_resign,
+            MenuItem::SaveAndExit => s.menu_save_and_exit,
+            MenuItem::WhatIf => s.menu_what_if,
+            MenuItem::DeepAnalysis => s.menu_deep_analysis,
+            MenuItem::ExitWhatIf => s.menu_exit_what_if,
+            MenuItem::Bookmarks => s.menu_bookmarks,
+            MenuItem::ExportLine => s.menu_export_line,
+            MenuItem::PositionText => s.menu_position_text,
+            MenuItem::MainMenu => s.menu_main_menu,
+            MenuItem::Back => s.menu_back,
         }
     }
 }
 
-/// Menu state
+/// Entries listed on the main menu screen
+pub fn main_menu_items(has_save: bool) -> Vec<MenuItem> {
+    let mut items = vec![MenuItem::NewGame];
+    if has_save {
+        items.push(MenuItem::Resume);
+    }
+    items.extend([
+        MenuItem::Archive,
+        MenuItem::Tutorial,
+        MenuItem::Puzzles,
+        MenuItem::Editor,
+        MenuItem::Statistics,
+        MenuItem::Settings,
+        MenuItem::Help,
+    ]);
+    items
+}
+
+/// Entries offered by the F1 menu in a context
+pub fn context_items(context: MenuContext) -> Vec<MenuItem> {
+    match context {
+        MenuContext::MainMenu { has_save } => {
+            let mut items = vec![
+                MenuItem::Help,
+                MenuItem::NewGame,
+                MenuItem::Tutorial,
+                MenuItem::Puzzles,
+                MenuItem::Editor,
+                MenuItem::Archive,
+            ];
+            if has_save {
+                items.push(MenuItem::Resume);
+            }
+            items.push(MenuItem::Statistics);
+            items.push(MenuItem::Settings);
+            items
+        }
+        MenuContext::Playing => {
+            vec![
+                MenuItem::Help,
+                MenuItem::MoveHistory,
+                MenuItem::PositionText,
+                MenuItem::Hint,
+                MenuItem::Undo,
+                MenuItem::Redo,
+                MenuItem::Resign,
+                MenuItem::SaveAndExit,
+                MenuItem::NewGame,
+            ]
+        }
+        MenuContext::GameOver => {
+            vec![
+                MenuItem::Help,
+                MenuItem::WhatIf,
+                MenuItem::MoveHistory,
+                MenuItem::PositionText,
+                MenuItem::DeepAnalysis,
+                MenuItem::NewGame,
+                MenuItem::MainMenu,
+            ]
+        }
+        MenuContext::WhatIf => {
+            vec![
+                MenuItem::Help,
+                MenuItem::Bookmarks,
+                MenuItem::ExportLine,
+                MenuItem::PositionText,
+                MenuItem::ExitWhatIf,
+            ]
+        }
+        MenuContext::Settings => {
+            vec![
+                MenuItem::Help,
+                MenuItem::Backup,
+                MenuItem::Back,
+            ]
+        }
+        MenuContext::Archive => {
+            vec![
+                MenuItem::Help,
+                MenuItem::ReviewArchive,
+                MenuItem::Back,
+            ]
+        }
+        MenuContext::Screen => {
+            vec![
+                MenuItem::Help,
+                MenuItem::Back,
+            ]
+        }
+    }
+}
+
+/// The F1 menu, shown by the GAM
 pub struct Menu {
-    /// Whether the menu is visible
+    /// The GAM menu, once registered
+    native: Option<gam::MenuMatic>,
+    /// Connection and opcode the GAM menu sends a chosen item with
+    action: (xous::CID, u32),
+    /// Whether the menu is up
     pub visible: bool,
-    /// Currently selected index
-    pub selected: usize,
-    /// Menu items
-    pub items: Vec<MenuItem>,
+    /// Items in the GAM menu, in order
+    items: Vec<MenuItem>,
+    /// Labels the GAM menu holds, the closing entry last
+    labels: Vec<String>,
+    /// Item chosen last, highlighted again on reopening if the new menu
+    /// has it
+    last: Option<MenuItem>,
 }
 
 impl Menu {
-    /// Create a new menu
+    /// Create a new menu, not yet registered with the GAM
     pub fn new() -> Self {
         Self {
+            native: None,
+            action: (0, 0),
             visible: false,
-            selected: 0,
             items: Vec::new(),
+            labels: Vec::new(),
+            last: None,
         }
     }
 
-    /// Open the menu for a given context
-    pub fn open(&mut self, context: MenuContext) {
-        self.items = match context {
-            MenuContext::MainMenu { has_save } => {
-                let mut items = vec![
-                    MenuItem::Help,
-                    MenuItem::NewGame,
-                ];
-                if has_save {
-                    items.push(MenuItem::Resume);
-                }
-                items.push(MenuItem::Statistics);
-                items.push(MenuItem::Settings);
-                items
-            }
-            MenuContext::Playing => {
-                vec![
-                    MenuItem::Help,
-                    MenuItem::MoveHistory,
-                    MenuItem::Hint,
-                    MenuItem::Undo,
-                    MenuItem::Resign,
-                    MenuItem::SaveAndExit,
-                    MenuItem::NewGame,
-                ]
-            }
-            MenuContext::GameOver => {
-                vec![
-                    MenuItem::Help,
-                    MenuItem::WhatIf,
-                    MenuItem::MoveHistory,
-                    MenuItem::NewGame,
-                    MenuItem::MainMenu,
-                ]
-            }
-            MenuContext::WhatIf => {
-                vec![
-                    MenuItem::Help,
-                    MenuItem::ExitWhatIf,
-                ]
-            }
-        };
-        self.selected = 0;
-        self.visible = true;
+    /// Register the GAM menu, which sends chosen items to `cid` as scalar
+    /// messages with `opcode` and the item as the first argument
+    pub fn register(&mut self, cid: xous::CID, opcode: u32) {
+        self.native = gam::menu_matic(Vec::new(), gam::APP_MENU_0_OTHELLO, None);
+        if self.native.is_none() {
+            log::warn!("Couldn't register the F1 menu");
+        }
+        self.action = (cid, opcode);
     }
 
-    /// Close the menu
-    pub fn close(&mut self) {
-        self.visible = false;
-    }
+    /// Fill the GAM menu for a context and raise it
+    pub fn open(&mut self, context: MenuContext, s: &Strings, services: &dyn Services) {
+        let Some(native) = &self.native else {
+            return;
+        };
+        let items = context_items(context);
+        let mut labels: Vec<String> = items.iter().map(|item| item.label(s).to_string()).collect();
+        labels.push(s.menu_close.to_string());
 
-    /// Move selection up
-    pub fn up(&mut self) {
-        if self.selected > 0 {
-            self.selected -= 1;
+        // The GAM menu is only rebuilt when the screen or language changed
+        if labels != self.labels {
+            for label in &self.labels {
+                native.delete_item(label);
+            }
+            let (cid, opcode) = self.action;
+            for (item, label) in items.iter().zip(&labels) {
+                native.add_item(gam::MenuItem {
+                    name: label.clone(),
+                    action_conn: Some(cid),
+                    action_opcode: opcode,
+                    action_payload: gam::MenuPayload::Scalar([*item as u32, 0, 0, 0]),
+                    close_on_select: true,
+                });
+            }
+            native.add_item(gam::MenuItem {
+                name: s.menu_close.to_string(),
+                action_conn: None,
+                action_opcode: 0,
+                action_payload: gam::MenuPayload::Scalar([0; 4]),
+                close_on_select: true,
+            });
+            self.items = items;
+            self.labels = labels;
         }
+
+        let selected = self.last.and_then(|last| self.items.iter().position(|&item| item == last)).unwrap_or(0);
+        native.set_index(selected);
+        self.visible = services.raise_menu();
     }
 
-    /// Move selection down
-    pub fn down(&mut self) {
-        if self.selected < self.items.len().saturating_sub(1) {
-            self.selected += 1;
-        }
+    /// Note an item chosen from the GAM menu, which has closed
+    pub fn chosen(&mut self, item: MenuItem) {
+        self.last = Some(item);
+        self.visible = false;
     }
 
-    /// Select current item
-    pub fn select(&self) -> Option<MenuItem> {
-        self.items.get(self.selected).copied()
+    /// Note that the GAM menu closed, chosen from or not
+    pub fn close(&mut self) {
+        self.visible = false;
     }
 }
 
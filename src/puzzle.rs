@@ -0,0 +1,266 @@
+//! Puzzle packs
+//!
+//! A pack is a compact binary blob that can be imported over TCP and kept
+//! in PDDB, so new puzzle sets don't need a new app build:
+//!
+//! ```text
+//! "OTPZ" | version (1) | name_len | name (UTF-8) | count | count x record
+//! record: black u64 LE | white u64 LE | flags | objective | param   (19 bytes)
+//! flags bit 0: White to move
+//! objective 0: play the move `param` (0-63)
+//! objective 1: win the game against perfect defence
+//! objective 2: win by at least `param` discs
+//! ```
+
+use othello_core::{Board, Difficulty, GameState, Player, Position, analyze_moves, pos_to_algebraic};
+
+use crate::i18n::{Strings, fill};
+
+/// Pack header magic
+const PACK_MAGIC: &[u8; 4] = b"OTPZ";
+/// Current pack format version
+const PACK_VERSION: u8 = 1;
+/// Size of one puzzle record
+const RECORD_LEN: usize = 19;
+/// Longest pack name accepted
+const MAX_NAME_LEN: usize = 32;
+
+/// What the player has to achieve
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Objective {
+    /// Find the one correct move
+    FindMove(Position),
+    /// Win the game from here
+    Win,
+    /// Win by at least this many discs
+    WinBy(u8),
+}
+
+/// A single puzzle
+#[derive(Debug, Clone, Copy)]
+pub struct Puzzle {
+    /// Starting position
+    pub board: Board,
+    /// Side the player takes
+    pub to_move: Player,
+    /// Goal to reach
+    pub objective: Objective,
+}
+
+impl Puzzle {
+    /// Create the game the player starts from
+    pub fn start(&self) -> GameState {
+        GameState::from_board(self.board, self.to_move)
+    }
+
+    /// Decide the result of a finished game, from the solver's side
+    pub fn check_result(&self, game: &GameState) -> bool {
+        let own = game.board().count(self.to_move) as i32;
+        let opp = game.board().count(self.to_move.opponent()) as i32;
+        match self.objective {
+            Objective::FindMove(_) => false,
+            Objective::Win => own > opp,
+            Objective::WinBy(margin) => own - opp >= margin as i32,
+        }
+    }
+
+    /// Decode a puzzle record
+    fn from_bytes(data: &[u8]) -> Option<Self> {
+        if data.len() < RECORD_LEN {
+            return None;
+        }
+        let black = u64::from_le_bytes(data[0..8].try_into().ok()?);
+        let white = u64::from_le_bytes(data[8..16].try_into().ok()?);
+        if black & white != 0 {
+            return None;
+        }
+        let to_move = if data[16] & 1 == 0 { Player::Black } else { Player::White };
+        let objective = match (data[17], data[18]) {
+            (0, p) if p < 64 => Objective::FindMove(p),
+            (1, _) => Objective::Win,
+            (2, margin) => Objective::WinBy(margin),
+            _ => return None,
+        };
+
+        let puzzle = Self {
+            board: Board { black, white },
+            to_move,
+            objective,
+        };
+
+        // The player must have a legal move, and the answer must be one
+        let game = puzzle.start();
+        match objective {
+            Objective::FindMove(p) if !game.is_legal(p) => None,
+            _ if !game.has_moves() => None,
+            _ => Some(puzzle),
+        }
+    }
+}
+
+/// Decode a pack, returning its name and puzzles
+///
+/// Returns None if the header is wrong or any record is invalid.
+pub fn parse_pack(data: &[u8]) -> Option<(String, Vec<Puzzle>)> {
+    if data.len() < 7 || &data[0..4] != PACK_MAGIC || data[4] != PACK_VERSION {
+        return None;
+    }
+    let name_len = data[5] as usize;
+    if name_len == 0 || name_len > MAX_NAME_LEN {
+        return None;
+    }
+    let name = core::str::from_utf8(data.get(6..6 + name_len)?).ok()?;
+    let count = *data.get(6 + name_len)? as usize;
+    let records = data.get(7 + name_len..)?;
+    if count == 0 || records.len() != count * RECORD_LEN {
+        return None;
+    }
+
+    let puzzles = records
+        .chunks(RECORD_LEN)
+        .map(Puzzle::from_bytes)
+        .collect::<Option<Vec<_>>>()?;
+    Some((String::from(name), puzzles))
+}
+
+/// Feedback for a move in a find-the-move puzzle
+pub fn find_move_feedback(answer: Position, played: Position, s: &Strings) -> String {
+    if answer == played {
+        String::from(s.puzzle_correct)
+    } else {
+        let alg = pos_to_algebraic(answer);
+        fill(s.puzzle_wrong, &[&core::str::from_utf8(&alg).unwrap_or("??")])
+    }
+}
+
+/// Empty squares left when the daily puzzle starts
+const DAILY_EMPTIES: u32 = 8;
+
+/// Today's day number (days since 1970-01-01), if the clock is set
+pub fn today() -> Option<u16> {
+    let ms = crate::feedback::local_time_ms()?;
+    u16::try_from(ms / 86_400_000).ok()
+}
+
+/// Generate the puzzle for a day
+///
+/// Plays seeded random moves to a late-game position, then solves it
+/// exactly. A position with a single winning move becomes a find-the-move
+/// puzzle; one with several becomes a play-to-win puzzle. Positions that
+/// can't be won are skipped, so every day has a solvable puzzle.
+pub fn daily_puzzle(day: u16) -> Puzzle {
+    let mut seed = 0x9E37_79B9_7F4A_7C15u64 ^ (day as u64).wrapping_mul(0x2545_F491_4F6C_DD1D);
+    let mut next = move || {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        seed
+    };
+
+    loop {
+        let mut game = GameState::new();
+        while game.empty_count() > DAILY_EMPTIES && !game.is_game_over() {
+            let moves = game.legal_moves();
+            match moves.get(next() as usize % moves.len().max(1)) {
+                Some(m) => {
+                    game.make_move(m.pos);
+                }
+                None => {
+                    game.pass();
+                }
+            }
+        }
+        if game.empty_count() != DAILY_EMPTIES || game.count_legal_moves() < 2 {
+            continue;
+        }
+
+        let board = *game.board();
+        let to_move = game.current_player();
+        let analysis = analyze_moves(&board, to_move, Difficulty::Expert);
+        let winning = analysis.iter().filter(|m| m.score > 0).count();
+        let objective = match (winning, analysis.best()) {
+            (1, Some(best)) => Objective::FindMove(best.pos),
+            (n, _) if n > 1 => Objective::Win,
+            _ => continue,
+        };
+        return Puzzle { board, to_move, objective };
+    }
+}
+
+/// Port puzzle packs are received on
+pub const IMPORT_PORT: u16 = 7881;
+
+/// Largest pack accepted
+pub const MAX_PACK_BYTES: u64 = 64 * 1024;
+
+/// Store a received pack in PDDB
+///
+/// Returns the pack name, or None if the pack is malformed.
+pub fn import_pack(data: &[u8]) -> Option<String> {
+    let Some((name, puzzles)) = parse_pack(data) else {
+        log::warn!("Rejected malformed puzzle pack");
+        return None;
+    };
+    crate::storage::save_puzzle_pack(&name, data);
+    log::info!("Imported puzzle pack {} ({} puzzles)", name, puzzles.len());
+    Some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use othello_core::pos;
+
+    /// Encode a pack the way the host-side tools do
+    fn encode(name: &str, puzzles: &[Puzzle]) -> Vec<u8> {
+        let mut data = Vec::from(&PACK_MAGIC[..]);
+        data.push(PACK_VERSION);
+        data.push(name.len() as u8);
+        data.extend_from_slice(name.as_bytes());
+        data.push(puzzles.len() as u8);
+        for puzzle in puzzles {
+            data.extend_from_slice(&puzzle.board.black.to_le_bytes());
+            data.extend_from_slice(&puzzle.board.white.to_le_bytes());
+            data.push(if puzzle.to_move == Player::White { 1 } else { 0 });
+            let (objective, param) = match puzzle.objective {
+                Objective::FindMove(p) => (0, p),
+                Objective::Win => (1, 0),
+                Objective::WinBy(margin) => (2, margin),
+            };
+            data.extend_from_slice(&[objective, param]);
+        }
+        data
+    }
+
+    fn sample() -> Vec<u8> {
+        let opening = Puzzle { board: Board::new(), to_move: Player::Black, objective: Objective::FindMove(pos(2, 3)) };
+        let win = Puzzle { board: Board::new(), to_move: Player::White, objective: Objective::WinBy(4) };
+        encode("Openers", &[opening, win])
+    }
+
+    #[test]
+    fn test_pack_round_trip() {
+        let (name, puzzles) = parse_pack(&sample()).unwrap();
+        assert_eq!(name, "Openers");
+        assert_eq!(puzzles.len(), 2);
+        assert_eq!(puzzles[0].board, Board::new());
+        assert_eq!(puzzles[0].to_move, Player::Black);
+        assert_eq!(puzzles[0].objective, Objective::FindMove(pos(2, 3)));
+        assert_eq!(puzzles[1].to_move, Player::White);
+        assert_eq!(puzzles[1].objective, Objective::WinBy(4));
+    }
+
+    #[test]
+    fn test_truncated_pack() {
+        let data = sample();
+        assert!(parse_pack(&data[..data.len() - 1]).is_none());
+        assert!(parse_pack(&data[..6]).is_none());
+    }
+
+    #[test]
+    fn test_bad_magic() {
+        let mut data = sample();
+        data[0] = b'X';
+        assert!(parse_pack(&data).is_none());
+    }
+}
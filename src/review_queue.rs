@@ -0,0 +1,148 @@
+//! Reviewing archived games in the background
+//!
+//! Archived games without a finished review wait in a queue of archive keys
+//! kept in PDDB. While nobody is using the app, or on the Review All screen,
+//! the queue reviews one move per call. When the queue is paused the review
+//! so far goes back into the archived game, so a review cut short by a key
+//! press or a suspend goes on from where it stopped.
+
+use othello_core::GameReview;
+use crate::app::REVIEW_DEPTH;
+use crate::storage::ArchivedGame;
+
+/// Game under review, loaded from the archive
+struct Current {
+    name: String,
+    archived: ArchivedGame,
+    review: GameReview,
+    /// Moves already reviewed when it was loaded
+    loaded_at: usize,
+}
+
+/// Archived games waiting for a review
+pub struct ReviewQueue {
+    /// Archive keys, next first
+    names: Vec<String>,
+    /// The first game in the queue, once loaded
+    current: Option<Box<Current>>,
+}
+
+impl ReviewQueue {
+    /// An empty queue, before the saved one is loaded
+    pub fn new() -> Self {
+        Self { names: Vec::new(), current: None }
+    }
+
+    /// The queue saved in PDDB
+    pub fn load() -> Self {
+        Self { names: crate::storage::load_review_queue(), current: None }
+    }
+
+    /// Games waiting, counting the one under review
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Moves reviewed and moves in the game under review
+    pub fn progress(&self) -> Option<(usize, usize)> {
+        self.current.as_ref().map(|current| current.review.progress())
+    }
+
+    /// Queue an archived game, unless it is already waiting
+    pub fn push(&mut self, name: &str) {
+        if !self.names.iter().any(|queued| queued == name) {
+            self.names.push(String::from(name));
+            crate::storage::save_review_queue(&self.names);
+        }
+    }
+
+    /// Queue every archived game whose review isn't finished, returning how
+    /// many were added
+    pub fn push_unreviewed(&mut self, games: &[(String, ArchivedGame)]) -> usize {
+        let before = self.names.len();
+        for (name, archived) in games {
+            let reviewed = GameReview::decode(&archived.game, &archived.review).is_some_and(|r| r.is_complete());
+            if !reviewed && !self.names.contains(name) {
+                self.names.push(name.clone());
+            }
+        }
+        let added = self.names.len() - before;
+        if added > 0 {
+            crate::storage::save_review_queue(&self.names);
+        }
+        added
+    }
+
+    /// Drop a game that left the archive
+    pub fn remove(&mut self, name: &str) {
+        if self.current.as_ref().is_some_and(|current| current.name == name) {
+            self.current = None;
+        }
+        let before = self.names.len();
+        self.names.retain(|queued| queued != name);
+        if self.names.len() != before {
+            crate::storage::save_review_queue(&self.names);
+        }
+    }
+
+    /// Review the next move
+    ///
+    /// Returns a game's key and review once it is finished and saved, so
+    /// copies of the game already loaded can take it up.
+    pub fn step(&mut self) -> Option<(String, Vec<u8>)> {
+        if self.current.is_none() {
+            self.current = self.next_game();
+        }
+        let current = self.current.as_mut()?;
+        if current.review.step(&current.archived.game) {
+            return None;
+        }
+
+        let current = self.current.take()?;
+        self.names.retain(|queued| *queued != current.name);
+        crate::storage::save_review_queue(&self.names);
+        Some(Self::save(*current))
+    }
+
+    /// Stop reviewing for now, keeping the progress with the archived game
+    ///
+    /// Returns the game's key and review if there was progress to keep.
+    pub fn pause(&mut self) -> Option<(String, Vec<u8>)> {
+        let current = self.current.take()?;
+        (current.review.progress().0 > current.loaded_at).then(|| Self::save(*current))
+    }
+
+    /// Write a game's review back to the archive
+    fn save(current: Current) -> (String, Vec<u8>) {
+        let review: Vec<u8> = current.review.encode().collect();
+        crate::storage::save_archived_review(&current.name, &review);
+        (current.name, review)
+    }
+
+    /// Load the first game still needing a review, dropping those finished
+    /// or deleted meanwhile
+    fn next_game(&mut self) -> Option<Box<Current>> {
+        let before = self.names.len();
+        let mut next = None;
+        while let Some(name) = self.names.first() {
+            next = crate::storage::load_archived_game(name).and_then(|archived| {
+                let review = GameReview::decode(&archived.game, &archived.review)
+                    .unwrap_or_else(|| GameReview::new(&archived.game, REVIEW_DEPTH));
+                let loaded_at = review.progress().0;
+                (!review.is_complete()).then(|| Box::new(Current { name: name.clone(), archived, review, loaded_at }))
+            });
+            if next.is_some() {
+                break;
+            }
+            self.names.remove(0);
+        }
+        if self.names.len() != before {
+            crate::storage::save_review_queue(&self.names);
+        }
+        next
+    }
+}
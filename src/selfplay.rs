@@ -0,0 +1,229 @@
+//! Headless CPU-vs-CPU games for checking engine changes (hosted only)
+//!
+//! Set `OTHELLO_SELFPLAY` to `games[,level[,level]]`, for example
+//! `OTHELLO_SELFPLAY=20,hard,medium`, and the app plays that many games
+//! without registering with GAM, prints each result and a summary, and
+//! exits. The two levels swap colours every game; a missing second level
+//! plays the first against itself. Besides the difficulty levels, `mcts`,
+//! `random` and `book` play the other engines in the core.
+
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use othello_core::{
+    BookPlayer, Difficulty, Engine, GameState, Mcts, Minimax, Player, RandomMover, SearchConfig, SearchLimits,
+    find_gentle_move,
+};
+
+/// Environment variable that turns the batch runner on
+const ENV_VAR: &str = "OTHELLO_SELFPLAY";
+
+/// Opening moves picked from near-best candidates so games differ
+const OPENING_PLIES: usize = 4;
+
+/// Games and levels to play
+pub struct Config {
+    games: u32,
+    levels: [Opponent; 2],
+}
+
+/// An engine to play, by the name given on the command line
+#[derive(Debug, Clone, Copy)]
+enum Opponent {
+    Level(Difficulty),
+    Mcts,
+    Random,
+    Book,
+}
+
+impl Opponent {
+    /// A fresh engine for one game, seeded by the game number
+    fn engine(self, seed: u64) -> Box<dyn Engine> {
+        match self {
+            Opponent::Level(level) => Box::new(<Minimax>::new(SearchConfig::new(level))),
+            Opponent::Mcts => Box::new(<Mcts>::new(seed)),
+            Opponent::Random => Box::new(RandomMover::new(seed)),
+            Opponent::Book => Box::new(BookPlayer::default()),
+        }
+    }
+
+    fn limits(self) -> SearchLimits {
+        match self {
+            Opponent::Level(level) => level.limits(),
+            _ => SearchLimits::depth(1),
+        }
+    }
+}
+
+impl fmt::Display for Opponent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Opponent::Level(level) => write!(f, "{:?}", level),
+            Opponent::Mcts => f.write_str("MCTS"),
+            Opponent::Random => f.write_str("Random"),
+            Opponent::Book => f.write_str("Book"),
+        }
+    }
+}
+
+/// Read the batch settings, if the runner was asked for
+pub fn from_env() -> Option<Config> {
+    let value = std::env::var(ENV_VAR).ok()?;
+    match parse(&value) {
+        Some(config) => Some(config),
+        None => {
+            eprintln!(
+                "{}: expected games[,level[,level]] with levels easy, medium, hard, expert, grandmaster, mcts, random or book",
+                ENV_VAR
+            );
+            None
+        }
+    }
+}
+
+fn parse(value: &str) -> Option<Config> {
+    let mut parts = value.split(',').map(str::trim);
+    let games = parts.next()?.parse().ok().filter(|&n| n > 0)?;
+    let first = parts.next().map_or(Some(Opponent::Level(Difficulty::Medium)), parse_level)?;
+    let second = parts.next().map_or(Some(first), parse_level)?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some(Config { games, levels: [first, second] })
+}
+
+fn parse_level(name: &str) -> Option<Opponent> {
+    match name.to_ascii_lowercase().as_str() {
+        "1" | "easy" => Some(Opponent::Level(Difficulty::Easy)),
+        "2" | "medium" => Some(Opponent::Level(Difficulty::Medium)),
+        "3" | "hard" => Some(Opponent::Level(Difficulty::Hard)),
+        "4" | "expert" => Some(Opponent::Level(Difficulty::Expert)),
+        "5" | "grandmaster" => Some(Opponent::Level(Difficulty::Grandmaster)),
+        "mcts" => Some(Opponent::Mcts),
+        "random" => Some(Opponent::Random),
+        "book" => Some(Opponent::Book),
+        _ => None,
+    }
+}
+
+/// Search time spent by one level
+#[derive(Default)]
+struct Timing {
+    moves: u32,
+    total: Duration,
+    slowest: Duration,
+}
+
+impl Timing {
+    fn record(&mut self, elapsed: Duration) {
+        self.moves += 1;
+        self.total += elapsed;
+        self.slowest = self.slowest.max(elapsed);
+    }
+
+    fn average(&self) -> Duration {
+        self.total / self.moves.max(1)
+    }
+}
+
+/// Play every game, printing results and timing as it goes
+pub fn run(config: &Config) {
+    let [first, second] = config.levels;
+    println!("Self-play: {} games, {} vs {}", config.games, first, second);
+
+    // Wins, losses and draws for the first level; total disc lead
+    let (mut wins, mut losses, mut draws) = (0u32, 0u32, 0u32);
+    let mut lead = 0i64;
+    let mut timing = [Timing::default(), Timing::default()];
+    let started = Instant::now();
+
+    for index in 0..config.games {
+        // The first level takes Black in even games
+        let first_side = if index % 2 == 0 { Player::Black } else { Player::White };
+        let game_started = Instant::now();
+        let game = play(index, first_side, config.levels, &mut timing);
+        let (black, white) = game.counts();
+        let (own, other) = match first_side {
+            Player::Black => (black, white),
+            Player::White => (white, black),
+        };
+
+        match own.cmp(&other) {
+            core::cmp::Ordering::Greater => wins += 1,
+            core::cmp::Ordering::Less => losses += 1,
+            core::cmp::Ordering::Equal => draws += 1,
+        }
+        lead += own as i64 - other as i64;
+
+        let (black_level, white_level) = match first_side {
+            Player::Black => (first, second),
+            Player::White => (second, first),
+        };
+        println!(
+            "game {:3}: {} (B) {:2} - {:2} {} (W), {} moves, {:.2}s",
+            index + 1,
+            black_level,
+            black,
+            white,
+            white_level,
+            game.move_count(),
+            game_started.elapsed().as_secs_f64(),
+        );
+    }
+
+    println!();
+    println!(
+        "{}: {} won, {} lost, {} drawn, average disc lead {:+.1}",
+        first,
+        wins,
+        losses,
+        draws,
+        lead as f64 / config.games as f64,
+    );
+    for (level, timing) in config.levels.iter().zip(&timing) {
+        println!(
+            "{}: {} moves, average {} ms, slowest {} ms",
+            level,
+            timing.moves,
+            timing.average().as_millis(),
+            timing.slowest.as_millis(),
+        );
+    }
+    println!("Total time: {:.2}s", started.elapsed().as_secs_f64());
+}
+
+/// Play one game, timing each searched move against the level that made it
+fn play(index: u32, first_side: Player, levels: [Opponent; 2], timing: &mut [Timing; 2]) -> GameState {
+    let mut game = GameState::new();
+    let mut engines = levels.map(|level| level.engine(index as u64 + 1));
+
+    while !game.is_game_over() {
+        if !game.has_moves() {
+            game.pass();
+            continue;
+        }
+
+        let player = game.current_player();
+        let side = if player == first_side { 0 } else { 1 };
+        let choice = if game.move_count() < OPENING_PLIES {
+            // Vary the opening by game number so reruns give the same games
+            find_gentle_move(game.board(), player, index.wrapping_mul(31).wrapping_add(game.move_count() as u32))
+        } else {
+            let searched = Instant::now();
+            let choice = engines[side].best_move(&game, &levels[side].limits()).best;
+            timing[side].record(searched.elapsed());
+            choice
+        };
+
+        match choice {
+            Some(pos) => {
+                game.make_move(pos);
+            }
+            None => {
+                game.pass();
+            }
+        }
+    }
+
+    game
+}
@@ -1,17 +1,314 @@
 //! PDDB storage for settings, statistics, and saved games
 
-use othello_core::{GameState, Player};
+use othello_core::{AdaptiveLevel, Board, GameState, LearnedBook, Player, NAMED_OPENINGS};
 use crate::app::GameMode;
+use crate::i18n::{Language, Strings, fill};
+use crate::puzzle::Puzzle;
+use std::sync::atomic::{AtomicU32, Ordering};
 use othello_core::Difficulty;
 
 /// Dictionary name for Othello data
 const DICT_SETTINGS: &str = "othello.settings";
 const DICT_STATS: &str = "othello.stats";
 const DICT_SAVE: &str = "othello.save";
+const DICT_PUZZLES: &str = "othello.puzzles";
+const DICT_ARCHIVE: &str = "othello.archive";
+const DICT_BOOK: &str = "othello.book";
+const DICT_QUEUE: &str = "othello.queue";
+
+/// Every dictionary the app keeps, as copied by a backup
+const DICTS: [&str; 7] = [DICT_SETTINGS, DICT_STATS, DICT_SAVE, DICT_PUZZLES, DICT_ARCHIVE, DICT_BOOK, DICT_QUEUE];
 
 const KEY_SETTINGS: &str = "config";
 const KEY_STATS: &str = "stats";
 const KEY_GAME: &str = "current";
+const KEY_LEARNED: &str = "learned";
+const KEY_REVIEW_QUEUE: &str = "reviews";
+
+/// Saved game header magic; saves without it predate handicaps
+const SAVE_MAGIC: &[u8; 4] = b"OTSV";
+/// Current saved game format version
+const SAVE_VERSION: u8 = 3;
+
+/// Backup header magic
+const BACKUP_MAGIC: &[u8; 4] = b"OTBA";
+/// Current backup format version
+const BACKUP_VERSION: u8 = 1;
+
+/// Archived game header magic
+const ARCHIVE_MAGIC: &[u8; 4] = b"OTAR";
+/// Current archived game format version
+const ARCHIVE_VERSION: u8 = 3;
+
+/// Longest note on an archived game, in bytes
+pub const MAX_NOTE: usize = 60;
+
+/// How many takebacks a game allows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UndoLimit {
+    Once,
+    Three,
+    Unlimited,
+}
+
+impl UndoLimit {
+    /// Maximum undos per game, if limited
+    pub const fn max_undos(&self) -> Option<u8> {
+        match self {
+            UndoLimit::Once => Some(1),
+            UndoLimit::Three => Some(3),
+            UndoLimit::Unlimited => None,
+        }
+    }
+
+    /// Cycle to the next option
+    pub const fn next(self) -> Self {
+        match self {
+            UndoLimit::Once => UndoLimit::Three,
+            UndoLimit::Three => UndoLimit::Unlimited,
+            UndoLimit::Unlimited => UndoLimit::Once,
+        }
+    }
+
+    const fn to_u8(self) -> u8 {
+        match self {
+            UndoLimit::Once => 0,
+            UndoLimit::Three => 1,
+            UndoLimit::Unlimited => 2,
+        }
+    }
+
+    const fn from_u8(value: u8) -> Self {
+        match value {
+            0 => UndoLimit::Once,
+            1 => UndoLimit::Three,
+            _ => UndoLimit::Unlimited,
+        }
+    }
+}
+
+/// What happens to a finished game
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveMode {
+    /// Ask on the Game Over screen
+    Ask,
+    Always,
+    Never,
+}
+
+impl ArchiveMode {
+    /// Cycle to the next option
+    pub const fn next(self) -> Self {
+        match self {
+            ArchiveMode::Ask => ArchiveMode::Always,
+            ArchiveMode::Always => ArchiveMode::Never,
+            ArchiveMode::Never => ArchiveMode::Ask,
+        }
+    }
+
+    const fn to_u8(self) -> u8 {
+        match self {
+            ArchiveMode::Ask => 0,
+            ArchiveMode::Always => 1,
+            ArchiveMode::Never => 2,
+        }
+    }
+
+    const fn from_u8(value: u8) -> Self {
+        match value {
+            1 => ArchiveMode::Always,
+            2 => ArchiveMode::Never,
+            _ => ArchiveMode::Ask,
+        }
+    }
+}
+
+/// How long the main menu waits for a key before playing a demo game
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdleDemo {
+    Off,
+    OneMinute,
+    FiveMinutes,
+    FifteenMinutes,
+}
+
+impl IdleDemo {
+    /// Cycle to the next option
+    pub const fn next(self) -> Self {
+        match self {
+            IdleDemo::Off => IdleDemo::OneMinute,
+            IdleDemo::OneMinute => IdleDemo::FiveMinutes,
+            IdleDemo::FiveMinutes => IdleDemo::FifteenMinutes,
+            IdleDemo::FifteenMinutes => IdleDemo::Off,
+        }
+    }
+
+    /// Seconds without a key before the demo starts
+    pub const fn seconds(self) -> Option<u32> {
+        match self {
+            IdleDemo::Off => None,
+            IdleDemo::OneMinute => Some(60),
+            IdleDemo::FiveMinutes => Some(5 * 60),
+            IdleDemo::FifteenMinutes => Some(15 * 60),
+        }
+    }
+
+    const fn to_u8(self) -> u8 {
+        match self {
+            IdleDemo::Off => 0,
+            IdleDemo::OneMinute => 1,
+            IdleDemo::FiveMinutes => 2,
+            IdleDemo::FifteenMinutes => 3,
+        }
+    }
+
+    const fn from_u8(value: u8) -> Self {
+        match value {
+            1 => IdleDemo::OneMinute,
+            2 => IdleDemo::FiveMinutes,
+            3 => IdleDemo::FifteenMinutes,
+            _ => IdleDemo::Off,
+        }
+    }
+}
+
+/// Size of the status, menu and move list text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextSize {
+    Small,
+    Regular,
+    Large,
+}
+
+impl TextSize {
+    /// Cycle to the next size
+    pub const fn next(self) -> Self {
+        match self {
+            TextSize::Small => TextSize::Regular,
+            TextSize::Regular => TextSize::Large,
+            TextSize::Large => TextSize::Small,
+        }
+    }
+
+    const fn to_u8(self) -> u8 {
+        match self {
+            TextSize::Small => 0,
+            TextSize::Regular => 1,
+            TextSize::Large => 2,
+        }
+    }
+
+    const fn from_u8(value: u8) -> Self {
+        match value {
+            0 => TextSize::Small,
+            2 => TextSize::Large,
+            _ => TextSize::Regular,
+        }
+    }
+}
+
+/// Time each player gets for a whole game
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameClock {
+    Off,
+    FiveMinutes,
+    TenMinutes,
+    TwentyMinutes,
+}
+
+impl GameClock {
+    /// Every option, in picker order
+    pub const ALL: [GameClock; 4] = [
+        GameClock::Off,
+        GameClock::FiveMinutes,
+        GameClock::TenMinutes,
+        GameClock::TwentyMinutes,
+    ];
+
+    /// Seconds on each player's clock at the start
+    pub const fn seconds(self) -> Option<u16> {
+        match self {
+            GameClock::Off => None,
+            GameClock::FiveMinutes => Some(5 * 60),
+            GameClock::TenMinutes => Some(10 * 60),
+            GameClock::TwentyMinutes => Some(20 * 60),
+        }
+    }
+
+    const fn to_u8(self) -> u8 {
+        match self {
+            GameClock::Off => 0,
+            GameClock::FiveMinutes => 1,
+            GameClock::TenMinutes => 2,
+            GameClock::TwentyMinutes => 3,
+        }
+    }
+
+    const fn from_u8(value: u8) -> Self {
+        match value {
+            1 => GameClock::FiveMinutes,
+            2 => GameClock::TenMinutes,
+            3 => GameClock::TwentyMinutes,
+            _ => GameClock::Off,
+        }
+    }
+}
+
+/// Information that can be shown in the status lines during play
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusItem {
+    /// Legal move counts for both sides
+    Mobility,
+    LastMove,
+    /// Static evaluation from the player's side (Black's in two-player)
+    Evaluation,
+    /// Time of day
+    Clock,
+    /// Disc count difference from the player's side
+    DiscDiff,
+    /// The last few moves, oldest first
+    RecentMoves,
+    /// Depth, nodes, time and table hits of the last CPU search, for tuning
+    /// the levels on the device
+    Engine,
+}
+
+impl StatusItem {
+    /// Every item, in the order they are listed and drawn
+    pub const ALL: [StatusItem; 7] = [
+        StatusItem::Mobility,
+        StatusItem::LastMove,
+        StatusItem::Evaluation,
+        StatusItem::Clock,
+        StatusItem::DiscDiff,
+        StatusItem::RecentMoves,
+        StatusItem::Engine,
+    ];
+
+    const fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+/// Events that get a vibration pattern of their own
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HapticCue {
+    /// The CPU plays into a corner
+    Corner,
+    /// The player has no move and must pass
+    Pass,
+    /// The player's clock in a timed game drops under 30 seconds
+    LowTime,
+}
+
+impl HapticCue {
+    /// Every cue, in the order they are listed
+    pub const ALL: [HapticCue; 3] = [HapticCue::Corner, HapticCue::Pass, HapticCue::LowTime];
+
+    const fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
 
 /// User settings
 #[derive(Debug, Clone)]
@@ -25,9 +322,34 @@ pub struct Settings {
     pub ai_delay: bool,
     pub vibration: bool,
     pub sound: bool,
+    /// Last CPU mode picked, by its [`mode_code`]
     pub last_difficulty: u8,
+    pub undo_limit: UndoLimit,
+    pub coach: bool,
+    pub language: Language,
+    pub text_size: TextSize,
+    /// Status items on each of the two status lines, as item bits
+    pub status_lines: [u8; 2],
+    pub archive: ArchiveMode,
+    /// Hide hints between turns in Two Player mode until a key is pressed
+    pub hot_seat: bool,
+    /// Ask before playing a move that gives up a corner or a lot of score
+    pub blunder_check: bool,
+    /// Mark discs next to an empty square while playing and reviewing
+    pub show_frontier: bool,
+    /// Learn opening moves from finished games and let Expert play them
+    pub learn_book: bool,
+    /// Play a CPU-vs-CPU demo after the main menu sits idle
+    pub idle_demo: IdleDemo,
+    /// Haptic cues turned on, as cue bits
+    pub haptic_cues: u8,
+    /// Clock for new games against the CPU or between two players
+    pub game_clock: GameClock,
 }
 
+/// Size of the original settings record; newer fields are appended
+const SETTINGS_BASE_LEN: usize = 10;
+
 impl Default for Settings {
     fn default() -> Self {
         Self {
@@ -41,14 +363,27 @@ impl Default for Settings {
             vibration: true,
             sound: true,
             last_difficulty: 1, // Medium
+            undo_limit: UndoLimit::Unlimited,
+            coach: false,
+            language: Language::English,
+            text_size: TextSize::Regular,
+            status_lines: [StatusItem::Mobility.bit() | StatusItem::LastMove.bit(), 0],
+            archive: ArchiveMode::Ask,
+            hot_seat: false,
+            blunder_check: false,
+            show_frontier: false,
+            learn_book: true,
+            idle_demo: IdleDemo::FiveMinutes,
+            haptic_cues: HapticCue::ALL.iter().fold(0, |bits, cue| bits | cue.bit()),
+            game_clock: GameClock::Off,
         }
     }
 }
 
 impl Settings {
     /// Serialize to bytes
-    pub fn to_bytes(&self) -> [u8; 10] {
-        [
+    pub fn to_bytes(&self) -> Vec<u8> {
+        vec![
             self.show_coordinates as u8,
             self.show_valid_moves as u8,
             self.allow_undo as u8,
@@ -59,14 +394,32 @@ impl Settings {
             self.vibration as u8,
             self.sound as u8,
             self.last_difficulty,
+            self.undo_limit.to_u8(),
+            self.coach as u8,
+            self.language.to_u8(),
+            self.text_size.to_u8(),
+            self.status_lines[0],
+            self.status_lines[1],
+            self.archive.to_u8(),
+            self.hot_seat as u8,
+            self.blunder_check as u8,
+            self.show_frontier as u8,
+            self.learn_book as u8,
+            self.idle_demo.to_u8(),
+            self.haptic_cues,
+            self.game_clock.to_u8(),
         ]
     }
 
     /// Deserialize from bytes
+    ///
+    /// Fields appended after the original record keep their defaults when
+    /// reading settings written by an older version.
     pub fn from_bytes(data: &[u8]) -> Option<Self> {
-        if data.len() < 10 {
+        if data.len() < SETTINGS_BASE_LEN {
             return None;
         }
+        let defaults = Self::default();
         Some(Self {
             show_coordinates: data[0] != 0,
             show_valid_moves: data[1] != 0,
@@ -78,8 +431,193 @@ impl Settings {
             vibration: data[7] != 0,
             sound: data[8] != 0,
             last_difficulty: data[9],
+            undo_limit: data.get(10).map_or(defaults.undo_limit, |&b| UndoLimit::from_u8(b)),
+            coach: data.get(11).map_or(defaults.coach, |&b| b != 0),
+            language: data.get(12).map_or(defaults.language, |&b| Language::from_u8(b)),
+            text_size: data.get(13).map_or(defaults.text_size, |&b| TextSize::from_u8(b)),
+            status_lines: match (data.get(14), data.get(15)) {
+                (Some(&first), Some(&second)) => [first, second],
+                _ => defaults.status_lines,
+            },
+            archive: data.get(16).map_or(defaults.archive, |&b| ArchiveMode::from_u8(b)),
+            hot_seat: data.get(17).map_or(defaults.hot_seat, |&b| b != 0),
+            blunder_check: data.get(18).map_or(defaults.blunder_check, |&b| b != 0),
+            show_frontier: data.get(19).map_or(defaults.show_frontier, |&b| b != 0),
+            learn_book: data.get(20).map_or(defaults.learn_book, |&b| b != 0),
+            idle_demo: data.get(21).map_or(defaults.idle_demo, |&b| IdleDemo::from_u8(b)),
+            haptic_cues: data.get(22).copied().unwrap_or(defaults.haptic_cues),
+            game_clock: data.get(23).map_or(defaults.game_clock, |&b| GameClock::from_u8(b)),
         })
     }
+
+    /// Status line an item is shown on, if any
+    pub fn status_line(&self, item: StatusItem) -> Option<usize> {
+        self.status_lines.iter().position(|line| line & item.bit() != 0)
+    }
+
+    /// Move an item to the next status line, then off
+    pub fn cycle_status_item(&mut self, item: StatusItem) {
+        let next = match self.status_line(item) {
+            None => Some(0),
+            Some(0) => Some(1),
+            Some(_) => None,
+        };
+        for line in self.status_lines.iter_mut() {
+            *line &= !item.bit();
+        }
+        if let Some(line) = next {
+            self.status_lines[line] |= item.bit();
+        }
+    }
+
+    /// Whether a cue is turned on, whatever vibration as a whole is set to
+    pub fn cue_on(&self, cue: HapticCue) -> bool {
+        self.haptic_cues & cue.bit() != 0
+    }
+
+    /// Whether a cue should vibrate, with vibration on as a whole
+    pub fn haptic_cue(&self, cue: HapticCue) -> bool {
+        self.vibration && self.cue_on(cue)
+    }
+
+    /// Turn a cue on or off
+    pub fn toggle_haptic_cue(&mut self, cue: HapticCue) {
+        self.haptic_cues ^= cue.bit();
+    }
+}
+
+/// Handicap chosen for a game
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Handicap {
+    #[default]
+    Even,
+    /// Corners for the player (Black in a two-player game)
+    PlayerCorners(u8),
+    /// Corners for the opponent
+    OpponentCorners(u8),
+    /// CPU searches shallowly and varies its moves
+    Gentle,
+}
+
+impl Handicap {
+    /// Describe the handicap for a game mode
+    pub fn label(&self, mode: GameMode, s: &Strings) -> String {
+        let (player, opponent) = match mode {
+            GameMode::VsCpu(_) | GameMode::Adaptive => (s.handicap_label_you, s.handicap_label_cpu),
+            GameMode::TwoPlayer => (s.black, s.white),
+        };
+        match self {
+            Handicap::Even => String::from(s.handicap_label_even),
+            Handicap::PlayerCorners(n) => fill(s.handicap_label_corners, &[&player, n]),
+            Handicap::OpponentCorners(n) => fill(s.handicap_label_corners, &[&opponent, n]),
+            Handicap::Gentle => String::from(s.handicap_label_gentle),
+        }
+    }
+
+    /// Convert to u8 for storage
+    pub const fn to_u8(self) -> u8 {
+        match self {
+            Handicap::Even => 0,
+            Handicap::PlayerCorners(n) => n,
+            Handicap::OpponentCorners(n) => 4 + n,
+            Handicap::Gentle => 9,
+        }
+    }
+
+    /// Convert from u8
+    pub const fn from_u8(v: u8) -> Self {
+        match v {
+            1..=4 => Handicap::PlayerCorners(v),
+            5..=8 => Handicap::OpponentCorners(v - 4),
+            9 => Handicap::Gentle,
+            _ => Handicap::Even,
+        }
+    }
+}
+
+/// Per-game assistance record, saved with the game and kept in its record
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GameMeta {
+    /// Takebacks used
+    pub undos: u8,
+    /// Hints requested
+    pub hints: u8,
+    /// Blindfold peeks taken
+    pub peeks: u8,
+    /// Handicap the game started with
+    pub handicap: Handicap,
+    /// Time the CPU spent choosing its moves, in milliseconds
+    pub cpu_ms: u32,
+    /// Time spent on the player's turns (both players' between two), in seconds
+    pub player_secs: u32,
+    /// Time each player started with, in seconds; 0 for an untimed game
+    pub clock_secs: u16,
+    /// Time left on Black's and White's clocks, in seconds
+    pub time_left: [u16; 2],
+}
+
+/// Wins with more takebacks than this are counted as heavily assisted
+pub const HEAVY_ASSIST_UNDOS: u8 = 3;
+
+impl GameMeta {
+    /// Whether the game relied heavily on takebacks
+    pub const fn heavily_assisted(&self) -> bool {
+        self.undos > HEAVY_ASSIST_UNDOS
+    }
+
+    /// Meta for a new game, with full clocks if it is timed
+    pub fn new(handicap: Handicap, clock: GameClock) -> Self {
+        let clock_secs = clock.seconds().unwrap_or(0);
+        Self { handicap, clock_secs, time_left: [clock_secs; 2], ..Self::default() }
+    }
+
+    pub const fn timed(&self) -> bool {
+        self.clock_secs > 0
+    }
+
+    /// Seconds left on a player's clock
+    pub const fn time_left(&self, player: Player) -> u16 {
+        self.time_left[player as usize]
+    }
+
+    /// Take a second off a player's clock, returning the seconds left
+    pub fn tick_clock(&mut self, player: Player) -> u16 {
+        let left = &mut self.time_left[player as usize];
+        *left = left.saturating_sub(1);
+        *left
+    }
+
+    /// Whether a player lost a timed game on time
+    pub const fn out_of_time(&self, player: Player) -> bool {
+        self.timed() && self.time_left(player) == 0
+    }
+
+    /// Serialize to bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![self.undos, self.hints, self.peeks, self.handicap.to_u8()];
+        bytes.extend_from_slice(&self.cpu_ms.to_le_bytes());
+        bytes.extend_from_slice(&self.player_secs.to_le_bytes());
+        for secs in [self.clock_secs, self.time_left[0], self.time_left[1]] {
+            bytes.extend_from_slice(&secs.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Deserialize from bytes, defaulting any fields that are missing
+    pub fn from_bytes(data: &[u8]) -> Self {
+        let read_u32 = |at: usize| data.get(at..at + 4).map_or(0, |b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]));
+        let read_u16 = |at: usize| data.get(at..at + 2).map_or(0, |b| u16::from_le_bytes([b[0], b[1]]));
+        Self {
+            undos: data.first().copied().unwrap_or(0),
+            hints: data.get(1).copied().unwrap_or(0),
+            peeks: data.get(2).copied().unwrap_or(0),
+            handicap: Handicap::from_u8(data.get(3).copied().unwrap_or(0)),
+            cpu_ms: read_u32(4),
+            player_secs: read_u32(8),
+            clock_secs: read_u16(12),
+            time_left: [read_u16(14), read_u16(16)],
+        }
+    }
 }
 
 /// Game statistics
@@ -97,13 +635,129 @@ pub struct Statistics {
     pub expert_wins: u16,
     pub expert_losses: u16,
     pub expert_draws: u16,
+    pub grandmaster_wins: u16,
+    pub grandmaster_losses: u16,
+    pub grandmaster_draws: u16,
     pub two_player_games: u16,
+    pub resignations: u16,
+    pub assisted_wins: u16,
+    pub hint_free_wins: u16,
+    /// Day number of the last daily puzzle attempted
+    pub daily_last_played: u16,
+    /// Day number of the last daily puzzle solved
+    pub daily_last_solved: u16,
+    pub daily_streak: u16,
+    pub daily_best_streak: u16,
+    /// Where the adaptive opponent starts the next game
+    pub adaptive_level: AdaptiveLevel,
+    pub adaptive_wins: u16,
+    pub adaptive_losses: u16,
+    pub adaptive_draws: u16,
+    /// Final disc margins for each CPU level, see [`margin_bucket`]
+    pub margins: [[u16; MARGIN_BUCKETS]; STATS_LEVELS],
+    /// Wins, losses and draws against the CPU for each named opening
+    pub openings: [[u16; 3]; NAMED_OPENINGS.len()],
+    /// Seconds spent on the player's turns, over every game
+    pub player_secs: u32,
+    /// CPU thinking for each CPU level, as the margins are kept
+    pub thinking: [Thinking; STATS_LEVELS],
+}
+
+/// Capacity of the Precursor's battery, in mAh
+pub const BATTERY_MAH: u64 = 1100;
+
+/// Rough extra current while the CPU searches instead of idling, in mA
+///
+/// Only CPU thinking is charged for, so the estimates compare levels with
+/// each other rather than predict a whole charge.
+pub const SEARCH_MA: u64 = 50;
+
+/// Time one CPU level spent thinking over all its games
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Thinking {
+    pub games: u16,
+    /// Moves the CPU chose, passes left out
+    pub moves: u32,
+    pub cpu_ms: u32,
+}
+
+impl Thinking {
+    /// Count a finished game
+    pub fn add(&mut self, moves: u32, cpu_ms: u32) {
+        self.games = self.games.saturating_add(1);
+        self.moves = self.moves.saturating_add(moves);
+        self.cpu_ms = self.cpu_ms.saturating_add(cpu_ms);
+    }
+
+    /// Average thinking time per move, in milliseconds
+    pub fn ms_per_move(&self) -> Option<u32> {
+        (self.moves > 0).then(|| self.cpu_ms / self.moves)
+    }
+
+    /// Estimated battery charge spent thinking per game, in hundredths of
+    /// a percent
+    pub fn charge_per_game(&self) -> Option<u32> {
+        let ms = self.cpu_ms as u64 / (self.games > 0).then_some(self.games as u64)?;
+        Some((ms * SEARCH_MA * 10_000 / (3_600_000 * BATTERY_MAH)) as u32)
+    }
+}
+
+/// CPU levels with their own records: Easy to Grandmaster, then Adaptive
+pub const STATS_LEVELS: usize = 6;
+
+/// Ranges of final disc margin, from losses by a wipeout to wins by one
+pub const MARGIN_BUCKETS: usize = 10;
+
+/// Histogram bucket for a game's final disc counts, from the player's side
+///
+/// Buckets run from a lost wipeout through losses by 33+, 17-32, 9-16 and
+/// 1-8, then the same wins in reverse. Draws have no bucket.
+pub fn margin_bucket(own: u32, other: u32) -> Option<usize> {
+    let band = |margin: u32, wiped_out: bool| match margin {
+        _ if wiped_out => 4,
+        1..=8 => 0,
+        9..=16 => 1,
+        17..=32 => 2,
+        _ => 3,
+    };
+    match own.cmp(&other) {
+        core::cmp::Ordering::Greater => Some(5 + band(own - other, other == 0)),
+        core::cmp::Ordering::Less => Some(4 - band(other - own, own == 0)),
+        core::cmp::Ordering::Equal => None,
+    }
+}
+
+/// Size of the original statistics record (13 x u16); newer fields are appended
+const STATS_BASE_LEN: usize = 26;
+
+/// Where the opening records start, in u16s
+const OPENINGS_AT: usize = 24 + 5 * MARGIN_BUCKETS;
+
+/// Where the Grandmaster record starts, in u16s, followed by its margins
+const GRANDMASTER_AT: usize = OPENINGS_AT + NAMED_OPENINGS.len() * 3;
+
+/// Where the thinking times start, in u16s: the player's, then each level's
+const THINKING_AT: usize = GRANDMASTER_AT + 3 + MARGIN_BUCKETS;
+
+/// Length of one level's thinking record, in u16s
+const THINKING_LEN: usize = 5;
+
+/// Length of the statistics record, in u16s
+const STATS_LEN: usize = THINKING_AT + 2 + STATS_LEVELS * THINKING_LEN;
+
+/// Where a level's margin histogram starts, in u16s; Grandmaster's was
+/// appended after the openings, so Adaptive's keeps its place
+const fn margins_at(level: usize) -> usize {
+    match level {
+        4 => GRANDMASTER_AT + 3,
+        5 => 24 + 4 * MARGIN_BUCKETS,
+        _ => 24 + level * MARGIN_BUCKETS,
+    }
 }
 
 impl Statistics {
     /// Serialize to bytes
-    pub fn to_bytes(&self) -> [u8; 26] {
-        let mut bytes = [0u8; 26];
+    pub fn to_bytes(&self) -> Vec<u8> {
         let values = [
             self.easy_wins,
             self.easy_losses,
@@ -118,22 +772,54 @@ impl Statistics {
             self.expert_losses,
             self.expert_draws,
             self.two_player_games,
+            self.resignations,
+            self.assisted_wins,
+            self.hint_free_wins,
+            self.daily_last_played,
+            self.daily_last_solved,
+            self.daily_streak,
+            self.daily_best_streak,
+            self.adaptive_level.get() as u16,
+            self.adaptive_wins,
+            self.adaptive_losses,
+            self.adaptive_draws,
         ];
-        for (i, val) in values.iter().enumerate() {
-            bytes[i * 2] = (*val & 0xFF) as u8;
-            bytes[i * 2 + 1] = ((*val >> 8) & 0xFF) as u8;
+        let mut words = vec![0u16; STATS_LEN];
+        words[..values.len()].copy_from_slice(&values);
+        for (level, margins) in self.margins.iter().enumerate() {
+            words[margins_at(level)..][..MARGIN_BUCKETS].copy_from_slice(margins);
         }
-        bytes
+        for (opening, results) in self.openings.iter().enumerate() {
+            words[OPENINGS_AT + opening * 3..][..3].copy_from_slice(results);
+        }
+        words[GRANDMASTER_AT..][..3].copy_from_slice(&[self.grandmaster_wins, self.grandmaster_losses, self.grandmaster_draws]);
+        // 32-bit counts take two u16s, low half first
+        let split = |val: u32| [val as u16, (val >> 16) as u16];
+        words[THINKING_AT..][..2].copy_from_slice(&split(self.player_secs));
+        for (level, thinking) in self.thinking.iter().enumerate() {
+            let [moves, moves_high] = split(thinking.moves);
+            let [ms, ms_high] = split(thinking.cpu_ms);
+            words[THINKING_AT + 2 + level * THINKING_LEN..][..THINKING_LEN]
+                .copy_from_slice(&[thinking.games, moves, moves_high, ms, ms_high]);
+        }
+        words.iter().flat_map(|val| val.to_le_bytes()).collect()
     }
 
     /// Deserialize from bytes
+    ///
+    /// Fields appended after the original record default to zero when
+    /// reading statistics written by an older version.
     pub fn from_bytes(data: &[u8]) -> Option<Self> {
-        if data.len() < 26 {
+        if data.len() < STATS_BASE_LEN {
             return None;
         }
         let read_u16 = |i: usize| -> u16 {
-            u16::from_le_bytes([data[i * 2], data[i * 2 + 1]])
+            match data.get(i * 2..i * 2 + 2) {
+                Some(b) => u16::from_le_bytes([b[0], b[1]]),
+                None => 0,
+            }
         };
+        let read_u32 = |i: usize| read_u16(i) as u32 | (read_u16(i + 1) as u32) << 16;
         Some(Self {
             easy_wins: read_u16(0),
             easy_losses: read_u16(1),
@@ -147,9 +833,133 @@ impl Statistics {
             expert_wins: read_u16(9),
             expert_losses: read_u16(10),
             expert_draws: read_u16(11),
+            grandmaster_wins: read_u16(GRANDMASTER_AT),
+            grandmaster_losses: read_u16(GRANDMASTER_AT + 1),
+            grandmaster_draws: read_u16(GRANDMASTER_AT + 2),
             two_player_games: read_u16(12),
+            resignations: read_u16(13),
+            assisted_wins: read_u16(14),
+            hint_free_wins: read_u16(15),
+            daily_last_played: read_u16(16),
+            daily_last_solved: read_u16(17),
+            daily_streak: read_u16(18),
+            daily_best_streak: read_u16(19),
+            adaptive_level: if data.len() > 20 * 2 {
+                AdaptiveLevel::new(read_u16(20) as u8)
+            } else {
+                AdaptiveLevel::START
+            },
+            adaptive_wins: read_u16(21),
+            adaptive_losses: read_u16(22),
+            adaptive_draws: read_u16(23),
+            margins: core::array::from_fn(|level| {
+                core::array::from_fn(|bucket| read_u16(margins_at(level) + bucket))
+            }),
+            openings: core::array::from_fn(|opening| {
+                core::array::from_fn(|result| read_u16(OPENINGS_AT + opening * 3 + result))
+            }),
+            player_secs: read_u32(THINKING_AT),
+            thinking: core::array::from_fn(|level| {
+                let at = THINKING_AT + 2 + level * THINKING_LEN;
+                Thinking { games: read_u16(at), moves: read_u32(at + 1), cpu_ms: read_u32(at + 3) }
+            }),
         })
     }
+
+    /// Daily puzzle streak as of a day (zero once a day is missed)
+    pub fn current_daily_streak(&self, today: u16) -> u16 {
+        if self.daily_last_solved.saturating_add(1) >= today {
+            self.daily_streak
+        } else {
+            0
+        }
+    }
+
+    /// Record the result of a daily puzzle
+    pub fn record_daily(&mut self, day: u16, solved: bool) {
+        if solved {
+            self.daily_streak = if self.daily_last_solved.saturating_add(1) == day {
+                self.daily_streak.saturating_add(1)
+            } else {
+                1
+            };
+            self.daily_last_solved = day;
+            self.daily_best_streak = self.daily_best_streak.max(self.daily_streak);
+        } else {
+            self.daily_streak = 0;
+        }
+    }
+}
+
+/// Seconds a write may wait in the PDDB before it is synced to flash
+///
+/// Saves only write their keys; syncing once for a burst of them, such as
+/// the statistics, learned book and archive at the end of a game, spares
+/// the flash and keeps the interface from stalling on each save.
+const SYNC_DELAY_SECS: u32 = 10;
+
+/// Zero when every write is on flash, otherwise one more than the seconds
+/// the oldest unsynced write has waited
+static UNSYNCED: AtomicU32 = AtomicU32::new(0);
+
+/// Note a write for the next sync
+fn written() {
+    UNSYNCED.compare_exchange(0, 1, Ordering::Relaxed, Ordering::Relaxed).ok();
+}
+
+/// Whether a write is waiting for a sync
+pub fn sync_pending() -> bool {
+    UNSYNCED.load(Ordering::Relaxed) != 0
+}
+
+/// Count a second, syncing once the oldest write has waited long enough
+pub fn sync_tick() {
+    match UNSYNCED.load(Ordering::Relaxed) {
+        0 => {}
+        waited if waited > SYNC_DELAY_SECS => sync(),
+        waited => UNSYNCED.store(waited + 1, Ordering::Relaxed),
+    }
+}
+
+/// Sync any waiting writes to flash now, as before the app loses the
+/// screen, suspends or quits
+pub fn sync() {
+    if UNSYNCED.swap(0, Ordering::Relaxed) == 0 {
+        return;
+    }
+    #[cfg(target_os = "none")]
+    {
+        pddb::Pddb::new().sync().ok();
+    }
+}
+
+/// Keys the app writes while under test, kept in memory in place of the
+/// PDDB
+///
+/// Each test runs on its own thread and so starts with nothing stored.
+#[cfg(test)]
+mod memory {
+    use std::cell::RefCell;
+    use std::collections::BTreeMap;
+
+    thread_local! {
+        static KEYS: RefCell<BTreeMap<(String, String), Vec<u8>>> = const { RefCell::new(BTreeMap::new()) };
+    }
+
+    /// The data stored under a key
+    pub fn read(dict: &str, key: &str) -> Option<Vec<u8>> {
+        KEYS.with(|keys| keys.borrow().get(&(dict.into(), key.into())).cloned())
+    }
+
+    /// Store data under a key, replacing what was there
+    pub fn write(dict: &str, key: &str, data: &[u8]) {
+        KEYS.with(|keys| keys.borrow_mut().insert((dict.into(), key.into()), data.to_vec()));
+    }
+
+    /// Remove a key
+    pub fn delete(dict: &str, key: &str) {
+        KEYS.with(|keys| keys.borrow_mut().remove(&(dict.into(), key.into())));
+    }
 }
 
 /// Load settings from PDDB
@@ -160,14 +970,18 @@ pub fn load_settings() -> Option<Settings> {
         match pddb.get(DICT_SETTINGS, KEY_SETTINGS, None, false, false, None, None::<fn()>) {
             Ok(mut key) => {
                 use std::io::Read;
-                let mut data = [0u8; 10];
-                if key.read_exact(&mut data).is_ok() {
+                let mut data = Vec::new();
+                if key.read_to_end(&mut data).is_ok() {
                     return Settings::from_bytes(&data);
                 }
             }
             Err(_) => {}
         }
     }
+    #[cfg(test)]
+    if let Some(data) = memory::read(DICT_SETTINGS, KEY_SETTINGS) {
+        return Settings::from_bytes(&data);
+    }
     None
 }
 
@@ -180,11 +994,13 @@ pub fn save_settings(settings: &Settings) {
             Ok(mut key) => {
                 use std::io::Write;
                 key.write_all(&settings.to_bytes()).ok();
-                pddb.sync().ok();
+                written();
             }
             Err(_) => {}
         }
     }
+    #[cfg(test)]
+    memory::write(DICT_SETTINGS, KEY_SETTINGS, &settings.to_bytes());
     let _ = settings;
 }
 
@@ -196,14 +1012,18 @@ pub fn load_statistics() -> Option<Statistics> {
         match pddb.get(DICT_STATS, KEY_STATS, None, false, false, None, None::<fn()>) {
             Ok(mut key) => {
                 use std::io::Read;
-                let mut data = [0u8; 26];
-                if key.read_exact(&mut data).is_ok() {
+                let mut data = Vec::new();
+                if key.read_to_end(&mut data).is_ok() {
                     return Statistics::from_bytes(&data);
                 }
             }
             Err(_) => {}
         }
     }
+    #[cfg(test)]
+    if let Some(data) = memory::read(DICT_STATS, KEY_STATS) {
+        return Statistics::from_bytes(&data);
+    }
     None
 }
 
@@ -212,15 +1032,17 @@ pub fn save_statistics(stats: &Statistics) {
     #[cfg(target_os = "none")]
     {
         let pddb = pddb::Pddb::new();
-        match pddb.get(DICT_STATS, KEY_STATS, None, true, true, Some(32), None::<fn()>) {
+        match pddb.get(DICT_STATS, KEY_STATS, None, true, true, Some(64), None::<fn()>) {
             Ok(mut key) => {
                 use std::io::Write;
                 key.write_all(&stats.to_bytes()).ok();
-                pddb.sync().ok();
+                written();
             }
             Err(_) => {}
         }
     }
+    #[cfg(test)]
+    memory::write(DICT_STATS, KEY_STATS, &stats.to_bytes());
     let _ = stats;
 }
 
@@ -231,63 +1053,162 @@ pub fn has_saved_game() -> bool {
         let pddb = pddb::Pddb::new();
         pddb.get(DICT_SAVE, KEY_GAME, None, false, false, None, None::<fn()>).is_ok()
     }
-    #[cfg(not(target_os = "none"))]
+    #[cfg(all(not(target_os = "none"), test))]
+    {
+        memory::read(DICT_SAVE, KEY_GAME).is_some()
+    }
+    #[cfg(all(not(target_os = "none"), not(test)))]
     {
         false
     }
 }
 
-/// Save a game to PDDB
-pub fn save_game(game: &GameState, mode: GameMode, player_color: Player) {
+/// Byte a game mode is stored as; new modes take new codes so older saves
+/// keep their meaning
+pub fn mode_code(mode: GameMode) -> u8 {
+    match mode {
+        GameMode::VsCpu(Difficulty::Easy) => 0,
+        GameMode::VsCpu(Difficulty::Medium) => 1,
+        GameMode::VsCpu(Difficulty::Hard) => 2,
+        GameMode::VsCpu(Difficulty::Expert) => 3,
+        GameMode::TwoPlayer => 4,
+        GameMode::Adaptive => 5,
+        GameMode::VsCpu(Difficulty::Grandmaster) => 6,
+    }
+}
+
+/// Game mode stored as `code`
+pub fn mode_from_code(code: u8) -> GameMode {
+    match code {
+        0 => GameMode::VsCpu(Difficulty::Easy),
+        1 => GameMode::VsCpu(Difficulty::Medium),
+        2 => GameMode::VsCpu(Difficulty::Hard),
+        3 => GameMode::VsCpu(Difficulty::Expert),
+        5 => GameMode::Adaptive,
+        6 => GameMode::VsCpu(Difficulty::Grandmaster),
+        _ => GameMode::TwoPlayer,
+    }
+}
+
+/// Serialize a game, its mode and its assistance record
+///
+/// magic(4) + version(1) + start black(8) + start white(8) + start player(1)
+///   + black(8) + white(8) + current(1) + player_color(1) + mode(1) + move_count(2) + history + meta
+///
+/// The history takes one byte per move; versions before 3 stored each
+/// move's flips as well, in 9 bytes.
+fn encode_game(game: &GameState, mode: GameMode, player_color: Player, meta: &GameMeta) -> Vec<u8> {
+    let board = game.board();
+    let start = game.start_board();
+    let history_len = game.history().len();
+    let meta_bytes = meta.to_bytes();
+    let side = |player: Player| match player {
+        Player::Black => 0,
+        Player::White => 1,
+    };
+
+    let mut data = Vec::with_capacity(22 + 21 + history_len + meta_bytes.len());
+    data.extend_from_slice(SAVE_MAGIC);
+    data.push(SAVE_VERSION);
+    data.extend_from_slice(&start.black.to_le_bytes());
+    data.extend_from_slice(&start.white.to_le_bytes());
+    data.push(side(game.start_player()));
+    data.extend_from_slice(&board.black.to_le_bytes());
+    data.extend_from_slice(&board.white.to_le_bytes());
+    data.push(side(game.current_player()));
+    data.push(side(player_color));
+    data.push(mode_code(mode));
+    data.extend_from_slice(&(history_len as u16).to_le_bytes());
+    data.extend(game.encode_history());
+    data.extend_from_slice(&meta_bytes);
+    data
+}
+
+/// Replay a history saved before version 3, at 9 bytes per move, returning
+/// the game and the bytes after it
+fn replay_wide_history(start: Board, start_player: Player, move_count: usize, mut rest: &[u8]) -> (GameState, &[u8]) {
+    let mut game = GameState::from_board(start, start_player);
+    for _ in 0..move_count {
+        if rest.len() < 9 {
+            break;
+        }
+        let pos = rest[0];
+        if pos == 255 {
+            game.pass();
+        } else {
+            game.make_move(pos);
+        }
+        rest = &rest[9..];
+    }
+    (game, rest)
+}
+
+/// Rebuild a game serialized by `encode_game` or an older version
+fn decode_game(data: &[u8]) -> Option<(GameState, GameMode, Player, GameMeta)> {
+    // Older saves have no header and always start from the standard position
+    let (version, start, start_player, data) = if data.starts_with(SAVE_MAGIC) {
+        let version = data.get(4).copied()?;
+        if version > SAVE_VERSION {
+            return None;
+        }
+        let black = u64::from_le_bytes(data.get(5..13)?.try_into().ok()?);
+        let white = u64::from_le_bytes(data.get(13..21)?.try_into().ok()?);
+        let player = if *data.get(21)? == 0 { Player::Black } else { Player::White };
+        (version, Board { black, white }, player, &data[22..])
+    } else {
+        (0, Board::new(), Player::Black, data)
+    };
+
+    // The current board (0..17) is rebuilt by replaying the moves
+    let header = data.get(..21)?;
+    let player_color = if header[17] == 0 { Player::Black } else { Player::White };
+    let mode = mode_from_code(header[18]);
+    let move_count = u16::from_le_bytes([header[19], header[20]]) as usize;
+
+    let rest = &data[21..];
+    let (game, rest) = if version >= 3 {
+        let moves = rest.get(..move_count)?;
+        (GameState::decode_history(start, start_player, moves)?, &rest[move_count..])
+    } else {
+        replay_wide_history(start, start_player, move_count, rest)
+    };
+
+    // Saves from older versions end after the moves
+    let meta = GameMeta::from_bytes(rest);
+
+    Some((game, mode, player_color, meta))
+}
+
+/// Save a game to PDDB, returning false if it couldn't be written
+pub fn save_game(game: &GameState, mode: GameMode, player_color: Player, meta: &GameMeta) -> bool {
     #[cfg(target_os = "none")]
     {
         let pddb = pddb::Pddb::new();
-        let board = game.board();
-
-        // Serialize: black(8) + white(8) + current(1) + player_color(1) + mode(1) + move_count(2) + history
-        let history = game.history();
-        let size = 20 + history.len() * 9;
+        let data = encode_game(game, mode, player_color, meta);
 
-        match pddb.get(DICT_SAVE, KEY_GAME, None, true, true, Some(size), None::<fn()>) {
+        // Rewrite from scratch so an older, longer save leaves no tail
+        pddb.delete_key(DICT_SAVE, KEY_GAME, None).ok();
+        let saved = match pddb.get(DICT_SAVE, KEY_GAME, None, true, true, Some(data.len()), None::<fn()>) {
             Ok(mut key) => {
                 use std::io::Write;
-                key.write_all(&board.black.to_le_bytes()).ok();
-                key.write_all(&board.white.to_le_bytes()).ok();
-                key.write_all(&[match game.current_player() {
-                    Player::Black => 0,
-                    Player::White => 1,
-                }])
-                .ok();
-                key.write_all(&[match player_color {
-                    Player::Black => 0,
-                    Player::White => 1,
-                }])
-                .ok();
-                key.write_all(&[match mode {
-                    GameMode::VsCpu(Difficulty::Easy) => 0,
-                    GameMode::VsCpu(Difficulty::Medium) => 1,
-                    GameMode::VsCpu(Difficulty::Hard) => 2,
-                    GameMode::VsCpu(Difficulty::Expert) => 3,
-                    GameMode::TwoPlayer => 4,
-                }])
-                .ok();
-                key.write_all(&(history.len() as u16).to_le_bytes()).ok();
-
-                for entry in history {
-                    key.write_all(&[entry.pos]).ok();
-                    key.write_all(&entry.flipped.to_le_bytes()).ok();
-                }
-
-                pddb.sync().ok();
+                key.write_all(&data).is_ok()
             }
-            Err(_) => {}
-        }
+            Err(_) => false,
+        };
+        written();
+        saved
+    }
+    #[cfg(not(target_os = "none"))]
+    {
+        #[cfg(test)]
+        memory::write(DICT_SAVE, KEY_GAME, &encode_game(game, mode, player_color, meta));
+        let _ = (game, mode, player_color, meta);
+        true
     }
-    let _ = (game, mode, player_color);
 }
 
 /// Load a saved game from PDDB
-pub fn load_game() -> Option<(GameState, GameMode, Player)> {
+pub fn load_game() -> Option<(GameState, GameMode, Player, GameMeta)> {
     #[cfg(target_os = "none")]
     {
         let pddb = pddb::Pddb::new();
@@ -295,59 +1216,489 @@ pub fn load_game() -> Option<(GameState, GameMode, Player)> {
             Ok(mut key) => {
                 use std::io::Read;
 
-                let mut header = [0u8; 19];
-                if key.read_exact(&mut header).is_err() {
+                let mut data = Vec::new();
+                if key.read_to_end(&mut data).is_err() {
                     return None;
                 }
+                return decode_game(&data);
+            }
+            Err(_) => {}
+        }
+    }
+    #[cfg(test)]
+    if let Some(data) = memory::read(DICT_SAVE, KEY_GAME) {
+        return decode_game(&data);
+    }
+    None
+}
+
+/// Delete saved game
+pub fn delete_saved_game() {
+    #[cfg(target_os = "none")]
+    {
+        let pddb = pddb::Pddb::new();
+        pddb.delete_key(DICT_SAVE, KEY_GAME, None).ok();
+        written();
+    }
+    #[cfg(test)]
+    memory::delete(DICT_SAVE, KEY_GAME);
+}
+
+/// Load the opening book learned from finished games
+pub fn load_learned_book() -> Option<LearnedBook> {
+    #[cfg(target_os = "none")]
+    {
+        let pddb = pddb::Pddb::new();
+        if let Ok(mut key) = pddb.get(DICT_BOOK, KEY_LEARNED, None, false, false, None, None::<fn()>) {
+            use std::io::Read;
+            let mut data = Vec::new();
+            if key.read_to_end(&mut data).is_ok() {
+                return LearnedBook::from_bytes(&data).ok();
+            }
+        }
+    }
+    None
+}
+
+/// Save the learned opening book
+pub fn save_learned_book(book: &LearnedBook) {
+    #[cfg(target_os = "none")]
+    {
+        let pddb = pddb::Pddb::new();
+        let data: Vec<u8> = book.encode().collect();
+
+        // Rewrite from scratch so an older, longer book leaves no tail
+        pddb.delete_key(DICT_BOOK, KEY_LEARNED, None).ok();
+        match pddb.get(DICT_BOOK, KEY_LEARNED, None, true, true, Some(data.len()), None::<fn()>) {
+            Ok(mut key) => {
+                use std::io::Write;
+                key.write_all(&data).ok();
+                written();
+            }
+            Err(_) => {}
+        }
+    }
+    let _ = book;
+}
+
+/// Forget the learned opening book
+pub fn delete_learned_book() {
+    #[cfg(target_os = "none")]
+    {
+        let pddb = pddb::Pddb::new();
+        pddb.delete_key(DICT_BOOK, KEY_LEARNED, None).ok();
+        written();
+    }
+}
+
+/// Store a validated puzzle pack under its name, replacing any older copy
+pub fn save_puzzle_pack(name: &str, data: &[u8]) {
+    #[cfg(target_os = "none")]
+    {
+        let pddb = pddb::Pddb::new();
+        // Drop any older copy so a shorter pack leaves no stale tail
+        pddb.delete_key(DICT_PUZZLES, name, None).ok();
+        match pddb.get(DICT_PUZZLES, name, None, true, true, Some(data.len()), None::<fn()>) {
+            Ok(mut key) => {
+                use std::io::Write;
+                key.write_all(data).ok();
+                written();
+            }
+            Err(_) => {}
+        }
+    }
+    let _ = (name, data);
+}
+
+/// List the names of stored puzzle packs
+pub fn list_puzzle_packs() -> Vec<String> {
+    #[cfg(target_os = "none")]
+    {
+        let pddb = pddb::Pddb::new();
+        if let Ok(mut names) = pddb.list_keys(DICT_PUZZLES, None) {
+            names.sort();
+            return names;
+        }
+    }
+    Vec::new()
+}
+
+/// Load a stored puzzle pack
+pub fn load_puzzle_pack(name: &str) -> Option<Vec<Puzzle>> {
+    #[cfg(target_os = "none")]
+    {
+        let pddb = pddb::Pddb::new();
+        if let Ok(mut key) = pddb.get(DICT_PUZZLES, name, None, false, false, None, None::<fn()>) {
+            use std::io::Read;
+            let mut data = Vec::new();
+            if key.read_to_end(&mut data).is_ok() {
+                return crate::puzzle::parse_pack(&data).map(|(_, puzzles)| puzzles);
+            }
+        }
+    }
+    let _ = name;
+    None
+}
+
+/// A finished game kept in the archive
+#[derive(Debug, Clone)]
+pub struct ArchivedGame {
+    pub game: GameState,
+    pub mode: GameMode,
+    pub player_color: Player,
+    pub meta: GameMeta,
+    /// When the game ended, in local seconds since 1970; 0 if the clock wasn't set
+    pub finished: u64,
+    /// The player's note on the game, empty if none
+    pub note: String,
+    /// The review from `GameReview::encode`, possibly still partial; empty
+    /// until one has started
+    pub review: Vec<u8>,
+}
+
+impl ArchivedGame {
+    /// Serialize to bytes
+    ///
+    /// magic(4) + version(1) + finished(8) + resigned(1) + note length(1) +
+    /// note + review length(2) + review + saved game record
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(ARCHIVE_MAGIC);
+        data.push(ARCHIVE_VERSION);
+        data.extend_from_slice(&self.finished.to_le_bytes());
+        // A resignation isn't in the move history, so it is kept here
+        data.push(match self.game.resigned() {
+            None => 0,
+            Some(Player::Black) => 1,
+            Some(Player::White) => 2,
+        });
+        let mut end = self.note.len().min(MAX_NOTE);
+        while !self.note.is_char_boundary(end) {
+            end -= 1;
+        }
+        data.push(end as u8);
+        data.extend_from_slice(&self.note.as_bytes()[..end]);
+        // A review too long to count is dropped, to be run again
+        let review: &[u8] = if self.review.len() <= u16::MAX as usize { &self.review } else { &[] };
+        data.extend_from_slice(&(review.len() as u16).to_le_bytes());
+        data.extend_from_slice(review);
+        data.extend_from_slice(&encode_game(&self.game, self.mode, self.player_color, &self.meta));
+        data
+    }
+
+    /// Deserialize from bytes
+    ///
+    /// Games archived before version 2 have no note, and before version 3
+    /// no review.
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        let version = *data.get(4)?;
+        if !data.starts_with(ARCHIVE_MAGIC) || version > ARCHIVE_VERSION {
+            return None;
+        }
+        let finished = u64::from_le_bytes(data.get(5..13)?.try_into().ok()?);
+        let resigned = match data.get(13)? {
+            1 => Some(Player::Black),
+            2 => Some(Player::White),
+            _ => None,
+        };
+        let (note, record) = if version >= 2 {
+            let len = *data.get(14)? as usize;
+            (String::from_utf8(data.get(15..15 + len)?.to_vec()).ok()?, data.get(15 + len..)?)
+        } else {
+            (String::new(), data.get(14..)?)
+        };
+        let (review, record) = if version >= 3 {
+            let len = u16::from_le_bytes(record.get(..2)?.try_into().ok()?) as usize;
+            (record.get(2..2 + len)?.to_vec(), record.get(2 + len..)?)
+        } else {
+            (Vec::new(), record)
+        };
+        let (mut game, mode, player_color, meta) = decode_game(record)?;
+        if let Some(player) = resigned {
+            game.resign(player);
+        }
+        Some(Self { game, mode, player_color, meta, finished, note, review })
+    }
+
+    /// Date and time the game ended, such as "2026-03-14 09:26"
+    pub fn date(&self) -> Option<String> {
+        if self.finished == 0 {
+            return None;
+        }
+        let days = (self.finished / 86_400) as i64;
+        let minutes = (self.finished % 86_400) / 60;
+
+        // Civil date from days since 1970 (Howard Hinnant's algorithm)
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + (month <= 2) as i64;
+
+        Some(format!("{:04}-{:02}-{:02} {:02}:{:02}", year, month, day, minutes / 60, minutes % 60))
+    }
+}
 
-                let black = u64::from_le_bytes(header[0..8].try_into().ok()?);
-                let white = u64::from_le_bytes(header[8..16].try_into().ok()?);
-                let current = if header[16] == 0 { Player::Black } else { Player::White };
-                let player_color = if header[17] == 0 { Player::Black } else { Player::White };
-                let mode = match header[18] {
-                    0 => GameMode::VsCpu(Difficulty::Easy),
-                    1 => GameMode::VsCpu(Difficulty::Medium),
-                    2 => GameMode::VsCpu(Difficulty::Hard),
-                    3 => GameMode::VsCpu(Difficulty::Expert),
-                    _ => GameMode::TwoPlayer,
-                };
-
-                let mut count_bytes = [0u8; 2];
-                if key.read_exact(&mut count_bytes).is_err() {
+/// Store a finished game in the archive, returning its key
+pub fn archive_game(archived: &ArchivedGame) -> Option<String> {
+    #[cfg(target_os = "none")]
+    {
+        let pddb = pddb::Pddb::new();
+        let data = archived.to_bytes();
+
+        // Keys count up, so sorting them lists games in the order played
+        let next = pddb
+            .list_keys(DICT_ARCHIVE, None)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|name| name.parse::<u32>().ok())
+            .max()
+            .map_or(1, |last| last + 1);
+        let name = format!("{:06}", next);
+
+        match pddb.get(DICT_ARCHIVE, &name, None, true, true, Some(data.len()), None::<fn()>) {
+            Ok(mut key) => {
+                use std::io::Write;
+                let saved = key.write_all(&data).is_ok();
+                drop(key);
+                if !saved {
+                    // A half-written game would only be skipped on loading
+                    pddb.delete_key(DICT_ARCHIVE, &name, None).ok();
                     return None;
                 }
-                let move_count = u16::from_le_bytes(count_bytes) as usize;
+                written();
+                return Some(name);
+            }
+            Err(_) => {}
+        }
+    }
+    let _ = archived;
+    None
+}
 
-                // Reconstruct game state by replaying moves
-                let mut game = GameState::new();
+/// Load every archived game with its key, newest first
+pub fn load_archive() -> Vec<(String, ArchivedGame)> {
+    #[cfg(target_os = "none")]
+    {
+        let pddb = pddb::Pddb::new();
+        let mut names = pddb.list_keys(DICT_ARCHIVE, None).unwrap_or_default();
+        names.sort();
 
-                for _ in 0..move_count {
-                    let mut entry = [0u8; 9];
-                    if key.read_exact(&mut entry).is_err() {
-                        break;
-                    }
-                    let pos = entry[0];
-                    if pos == 255 {
-                        game.pass();
-                    } else {
-                        game.make_move(pos);
+        let mut games = Vec::new();
+        for name in names.into_iter().rev() {
+            if let Ok(mut key) = pddb.get(DICT_ARCHIVE, &name, None, false, false, None, None::<fn()>) {
+                use std::io::Read;
+                let mut data = Vec::new();
+                if key.read_to_end(&mut data).is_ok() {
+                    if let Some(archived) = ArchivedGame::from_bytes(&data) {
+                        games.push((name, archived));
                     }
                 }
+            }
+        }
+        return games;
+    }
+    #[allow(unreachable_code)]
+    Vec::new()
+}
+
+/// Replace an archived game, as after editing its note
+pub fn update_archived_game(name: &str, archived: &ArchivedGame) {
+    #[cfg(target_os = "none")]
+    {
+        let pddb = pddb::Pddb::new();
+        let data = archived.to_bytes();
 
-                return Some((game, mode, player_color));
+        // Rewrite from scratch so a longer old note leaves no tail
+        pddb.delete_key(DICT_ARCHIVE, name, None).ok();
+        if let Ok(mut key) = pddb.get(DICT_ARCHIVE, name, None, true, true, Some(data.len()), None::<fn()>) {
+            use std::io::Write;
+            key.write_all(&data).ok();
+            written();
+        }
+    }
+    let _ = (name, archived);
+}
+
+/// Load one archived game by its key
+pub fn load_archived_game(name: &str) -> Option<ArchivedGame> {
+    #[cfg(target_os = "none")]
+    {
+        let pddb = pddb::Pddb::new();
+        if let Ok(mut key) = pddb.get(DICT_ARCHIVE, name, None, false, false, None, None::<fn()>) {
+            use std::io::Read;
+            let mut data = Vec::new();
+            if key.read_to_end(&mut data).is_ok() {
+                return ArchivedGame::from_bytes(&data);
             }
-            Err(_) => {}
         }
     }
+    let _ = name;
     None
 }
 
-/// Delete saved game
-pub fn delete_saved_game() {
+/// Keep a review, finished or not, with an archived game
+pub fn save_archived_review(name: &str, review: &[u8]) {
+    if let Some(mut archived) = load_archived_game(name) {
+        archived.review = review.to_vec();
+        update_archived_game(name, &archived);
+    }
+}
+
+/// Load the archive keys of games waiting for a background review, next first
+pub fn load_review_queue() -> Vec<String> {
     #[cfg(target_os = "none")]
     {
         let pddb = pddb::Pddb::new();
-        pddb.delete_key(DICT_SAVE, KEY_GAME, None).ok();
+        if let Ok(mut key) = pddb.get(DICT_QUEUE, KEY_REVIEW_QUEUE, None, false, false, None, None::<fn()>) {
+            use std::io::Read;
+            let mut text = String::new();
+            if key.read_to_string(&mut text).is_ok() {
+                return text.lines().filter(|line| !line.is_empty()).map(String::from).collect();
+            }
+        }
+    }
+    #[allow(unreachable_code)]
+    Vec::new()
+}
+
+/// Save the background review queue, one archive key per line
+pub fn save_review_queue(names: &[String]) {
+    #[cfg(target_os = "none")]
+    {
+        let pddb = pddb::Pddb::new();
+        let data = names.join("\n");
+
+        // Rewrite from scratch so a longer old queue leaves no tail
+        pddb.delete_key(DICT_QUEUE, KEY_REVIEW_QUEUE, None).ok();
+        if !data.is_empty() {
+            if let Ok(mut key) = pddb.get(DICT_QUEUE, KEY_REVIEW_QUEUE, None, true, true, Some(data.len()), None::<fn()>) {
+                use std::io::Write;
+                key.write_all(data.as_bytes()).ok();
+            }
+        }
+        written();
+    }
+    let _ = names;
+}
+
+/// Remove a game from the archive
+pub fn delete_archived_game(name: &str) {
+    #[cfg(target_os = "none")]
+    {
+        let pddb = pddb::Pddb::new();
+        pddb.delete_key(DICT_ARCHIVE, name, None).ok();
+        written();
+    }
+    let _ = name;
+}
+
+/// One stored key in a backup
+struct BackupRecord {
+    dict: String,
+    key: String,
+    data: Vec<u8>,
+}
+
+/// Serialize stored keys as a backup
+///
+/// magic(4) + version(1) + count(4), then for each key:
+///   dict length(1) + dict + key length(1) + key + data length(4) + data
+fn encode_backup(records: &[BackupRecord]) -> Vec<u8> {
+    let mut data = Vec::new();
+    data.extend_from_slice(BACKUP_MAGIC);
+    data.push(BACKUP_VERSION);
+    data.extend_from_slice(&(records.len() as u32).to_le_bytes());
+    for record in records {
+        data.push(record.dict.len() as u8);
+        data.extend_from_slice(record.dict.as_bytes());
+        data.push(record.key.len() as u8);
+        data.extend_from_slice(record.key.as_bytes());
+        data.extend_from_slice(&(record.data.len() as u32).to_le_bytes());
+        data.extend_from_slice(&record.data);
+    }
+    data
+}
+
+/// Read a backup, rejecting it whole if any part is malformed or belongs
+/// to a dictionary this app doesn't keep
+fn decode_backup(data: &[u8]) -> Option<Vec<BackupRecord>> {
+    if !data.starts_with(BACKUP_MAGIC) || *data.get(4)? > BACKUP_VERSION {
+        return None;
+    }
+    let count = u32::from_le_bytes(data.get(5..9)?.try_into().ok()?);
+    let mut rest = &data[9..];
+    let mut take = |len: usize| -> Option<&[u8]> {
+        let (head, tail) = rest.split_at_checked(len)?;
+        rest = tail;
+        Some(head)
+    };
+
+    let mut records = Vec::new();
+    for _ in 0..count {
+        let len = take(1)?[0] as usize;
+        let dict = String::from_utf8(take(len)?.to_vec()).ok()?;
+        let len = take(1)?[0] as usize;
+        let key = String::from_utf8(take(len)?.to_vec()).ok()?;
+        let len = u32::from_le_bytes(take(4)?.try_into().ok()?) as usize;
+        let data = take(len)?.to_vec();
+        if !DICTS.contains(&dict.as_str()) || key.is_empty() {
+            return None;
+        }
+        records.push(BackupRecord { dict, key, data });
+    }
+    Some(records)
+}
+
+/// Copy everything the app keeps in PDDB into one backup
+pub fn backup() -> Vec<u8> {
+    #[allow(unused_mut)]
+    let mut records = Vec::new();
+    #[cfg(target_os = "none")]
+    {
+        use std::io::Read;
+        let pddb = pddb::Pddb::new();
+        for dict in DICTS {
+            let mut keys = pddb.list_keys(dict, None).unwrap_or_default();
+            keys.sort();
+            for key in keys {
+                if let Ok(mut stored) = pddb.get(dict, &key, None, false, false, None, None::<fn()>) {
+                    let mut data = Vec::new();
+                    if stored.read_to_end(&mut data).is_ok() {
+                        records.push(BackupRecord { dict: String::from(dict), key, data });
+                    }
+                }
+            }
+        }
+    }
+    encode_backup(&records)
+}
+
+/// Replace everything the app keeps in PDDB with a backup
+///
+/// Returns the number of keys restored, or None if the backup was
+/// rejected, in which case nothing was changed.
+pub fn restore(data: &[u8]) -> Option<usize> {
+    let records = decode_backup(data)?;
+    #[cfg(target_os = "none")]
+    {
+        use std::io::Write;
+        let pddb = pddb::Pddb::new();
+        for dict in DICTS {
+            for key in pddb.list_keys(dict, None).unwrap_or_default() {
+                pddb.delete_key(dict, &key, None).ok();
+            }
+        }
+        for record in &records {
+            if let Ok(mut key) = pddb.get(&record.dict, &record.key, None, true, true, Some(record.data.len()), None::<fn()>) {
+                key.write_all(&record.data).ok();
+            }
+        }
         pddb.sync().ok();
     }
+    Some(records.len())
 }
@@ -0,0 +1,49 @@
+//! Short notices shown over any screen
+
+/// Seconds a notice stays up unless a key dismisses it first
+const TOAST_SECS: u8 = 3;
+
+/// A notice that goes away by itself, such as "Game saved"
+pub struct Toast {
+    text: Option<String>,
+    /// Seconds left before it goes away
+    secs_left: u8,
+}
+
+impl Toast {
+    /// Create with nothing showing
+    pub fn new() -> Self {
+        Self { text: None, secs_left: 0 }
+    }
+
+    /// Show a notice, replacing any already showing
+    pub fn show(&mut self, text: impl Into<String>) {
+        self.text = Some(text.into());
+        self.secs_left = TOAST_SECS;
+    }
+
+    /// The notice showing, if any
+    pub fn text(&self) -> Option<&str> {
+        self.text.as_deref()
+    }
+
+    /// Count a second, returning true if the notice just went away
+    pub fn tick(&mut self) -> bool {
+        if self.text.is_none() {
+            return false;
+        }
+        self.secs_left = self.secs_left.saturating_sub(1);
+        self.secs_left == 0 && self.dismiss()
+    }
+
+    /// Take the notice down, returning true if one was showing
+    pub fn dismiss(&mut self) -> bool {
+        self.text.take().is_some()
+    }
+}
+
+impl Default for Toast {
+    fn default() -> Self {
+        Self::new()
+    }
+}
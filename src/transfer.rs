@@ -0,0 +1,133 @@
+//! One-shot TCP transfers
+//!
+//! Each transfer waits for a single host on a port and sends or receives one
+//! payload on a thread of its own, so keys keep working and F4 can stop the
+//! wait.
+
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::AppOp;
+
+/// Milliseconds between checks for a connection or a cancel
+#[cfg(target_os = "none")]
+const POLL_MS: u64 = 100;
+
+/// Seconds a connected host may stall before the transfer gives up
+#[cfg(target_os = "none")]
+const STALL_SECS: u64 = 10;
+
+/// Which way the payload goes
+///
+/// Only the device build has a network to move it over.
+#[derive(Debug)]
+#[cfg_attr(not(target_os = "none"), allow(dead_code))]
+enum Direction {
+    Send(Vec<u8>),
+    /// Read at most this many bytes
+    Receive(u64),
+}
+
+/// A transfer waiting for or talking to a host
+#[derive(Debug, Clone)]
+pub struct Transfer {
+    /// Stops the wait for a connection
+    cancel: Arc<AtomicBool>,
+    /// Filled in by the thread when the transfer ends: the bytes sent or
+    /// received, or None if it failed
+    result: Arc<Mutex<Option<Option<Vec<u8>>>>>,
+}
+
+impl Transfer {
+    /// Send `data` to the first host to connect on `port`, pumping `cid`
+    /// when done
+    pub fn send(port: u16, data: Vec<u8>, cid: Option<xous::CID>) -> Self {
+        Self::spawn(port, Direction::Send(data), cid)
+    }
+
+    /// Receive up to `limit` bytes from the first host to connect on
+    /// `port`, pumping `cid` when done
+    ///
+    /// The limit keeps a stray connection from exhausting memory.
+    pub fn receive(port: u16, limit: u64, cid: Option<xous::CID>) -> Self {
+        Self::spawn(port, Direction::Receive(limit), cid)
+    }
+
+    fn spawn(port: u16, direction: Direction, cid: Option<xous::CID>) -> Self {
+        let transfer = Self { cancel: Arc::new(AtomicBool::new(false)), result: Arc::new(Mutex::new(None)) };
+        let (cancel, result) = (transfer.cancel.clone(), transfer.result.clone());
+        std::thread::spawn(move || {
+            let outcome = run(port, direction, &cancel);
+            // A cancelled transfer's screen has already moved on
+            if cancel.load(Ordering::Relaxed) {
+                return;
+            }
+            if let Ok(mut slot) = result.lock() {
+                *slot = Some(outcome);
+            }
+            if let Some(cid) = cid {
+                xous::send_message(cid, xous::Message::new_scalar(AppOp::AiPump as usize, 0, 0, 0, 0)).ok();
+            }
+        });
+        transfer
+    }
+
+    /// Stop waiting; the result is no longer wanted
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
+    /// The finished transfer, once the thread has ended it
+    pub fn take_result(&self) -> Option<Option<Vec<u8>>> {
+        self.result.lock().ok()?.take()
+    }
+}
+
+/// Wait for a host, then move the payload
+#[cfg(target_os = "none")]
+fn run(port: u16, direction: Direction, cancel: &AtomicBool) -> Option<Vec<u8>> {
+    use std::io::{ErrorKind, Read, Write};
+    use std::net::TcpListener;
+    use std::time::Duration;
+
+    let listener = TcpListener::bind(("0.0.0.0", port)).ok()?;
+    listener.set_nonblocking(true).ok()?;
+    log::info!("Waiting for connection on port {}...", port);
+
+    let mut stream = loop {
+        match listener.accept() {
+            Ok((stream, _)) => break stream,
+            Err(e) if e.kind() == ErrorKind::WouldBlock => {
+                if cancel.load(Ordering::Relaxed) {
+                    log::info!("Stopped waiting on port {}", port);
+                    return None;
+                }
+                std::thread::sleep(Duration::from_millis(POLL_MS));
+            }
+            Err(_) => return None,
+        }
+    };
+    stream.set_nonblocking(false).ok()?;
+    stream.set_read_timeout(Some(Duration::from_secs(STALL_SECS))).ok()?;
+    stream.set_write_timeout(Some(Duration::from_secs(STALL_SECS))).ok()?;
+
+    match direction {
+        Direction::Send(data) => {
+            stream.write_all(&data).ok()?;
+            log::info!("Sent {} bytes on port {}", data.len(), port);
+            Some(data)
+        }
+        Direction::Receive(limit) => {
+            let mut data = Vec::new();
+            stream.take(limit).read_to_end(&mut data).ok()?;
+            log::info!("Received {} bytes on port {}", data.len(), port);
+            Some(data)
+        }
+    }
+}
+
+/// Hosted builds have no network to wait on
+#[cfg(not(target_os = "none"))]
+fn run(_port: u16, _direction: Direction, _cancel: &AtomicBool) -> Option<Vec<u8>> {
+    None
+}
@@ -0,0 +1,144 @@
+//! Interactive tutorial lessons
+//!
+//! Each lesson sets up a fixed position and only accepts the move that
+//! demonstrates its teaching point, then explains why it works.
+
+use othello_core::{Board, Player, Position, pos};
+
+/// A scripted lesson
+pub struct Lesson {
+    /// Short lesson title
+    pub title: &'static str,
+    /// Board rows from rank 1 down: 'X' black, 'O' white, '.' empty
+    pub rows: [&'static str; 8],
+    /// Side to move
+    pub to_move: Player,
+    /// Instructions shown before the move
+    pub prompt: &'static str,
+    /// Moves that make the teaching point
+    pub answers: &'static [Position],
+    /// Shown when another legal move is tried
+    pub retry: &'static str,
+    /// Shown after a correct move
+    pub explanation: &'static str,
+}
+
+impl Lesson {
+    /// Build the lesson's starting board
+    pub fn board(&self) -> Board {
+        let mut board = Board::empty();
+        for (row, line) in self.rows.iter().enumerate() {
+            for (col, ch) in line.bytes().enumerate() {
+                let p = pos(row as u8, col as u8);
+                match ch {
+                    b'X' => board.place(Player::Black, p),
+                    b'O' => board.place(Player::White, p),
+                    _ => {}
+                }
+            }
+        }
+        board
+    }
+
+    /// Whether a move makes the teaching point
+    pub fn accepts(&self, p: Position) -> bool {
+        self.answers.contains(&p)
+    }
+}
+
+/// Message for a move that isn't legal at all
+pub const ILLEGAL_MOVE: &str = "Not legal: a move must trap opponent discs in a straight line.";
+
+/// All lessons, in teaching order
+pub const LESSONS: [Lesson; 5] = [
+    Lesson {
+        title: "Legal Moves",
+        rows: [
+            "........",
+            "........",
+            "........",
+            "...OX...",
+            "...XO...",
+            "........",
+            "........",
+            "........",
+        ],
+        to_move: Player::Black,
+        prompt: "You are Black. Place a disc so a White disc is trapped between it and one of yours. The dots mark the legal squares.",
+        answers: &[pos(2, 3), pos(3, 2), pos(4, 5), pos(5, 4)],
+        retry: "",
+        explanation: "The trapped disc flipped to Black. Every move must flip at least one disc; if you have none, you pass.",
+    },
+    Lesson {
+        title: "Corners",
+        rows: [
+            ".OX.....",
+            "........",
+            "........",
+            "...OX...",
+            "...XO...",
+            "........",
+            "........",
+            "........",
+        ],
+        to_move: Player::Black,
+        prompt: "One of your moves takes a corner. Find it.",
+        answers: &[pos(0, 0)],
+        retry: "That works, but a corner is on offer. Look along the top edge.",
+        explanation: "A corner disc can never be flipped, and it anchors the edges beside it. Take corners when you can.",
+    },
+    Lesson {
+        title: "X-Squares",
+        rows: [
+            "........",
+            "........",
+            "..O.....",
+            "...OOO..",
+            "...XXX..",
+            ".....O..",
+            "........",
+            "........",
+        ],
+        to_move: Player::Black,
+        prompt: "B2 and G7 sit diagonally next to empty corners. Make a move that stays away from them.",
+        answers: &[pos(2, 3), pos(2, 4), pos(2, 5), pos(2, 6), pos(6, 5)],
+        retry: "That is an X-square: it hands White a diagonal into the corner. Try again.",
+        explanation: "Good. Playing next to an empty corner usually gives it away. Leave X-squares alone until the corner is taken.",
+    },
+    Lesson {
+        title: "Mobility",
+        rows: [
+            "........",
+            "....X...",
+            "..O.X...",
+            "..XOX...",
+            "..XXOO..",
+            ".X.XOO..",
+            "....X...",
+            ".....X..",
+        ],
+        to_move: Player::Black,
+        prompt: "Flipping lots of discs is tempting. Find the quiet move that leaves White with the fewest replies.",
+        answers: &[pos(2, 3)],
+        retry: "White still has plenty of replies after that. Count White's answers before you move.",
+        explanation: "D3 leaves White 9 moves instead of 12 or more. Fewer options for your opponent means more forced, bad moves later.",
+    },
+    Lesson {
+        title: "Parity",
+        rows: [
+            "XO.OOO..",
+            "XXXXX.O.",
+            "XXOXXOOO",
+            "XOXXOXXX",
+            "XXOOXOOO",
+            "XXOOOOOO",
+            "OOOOOOOO",
+            "OXXOOOOO",
+        ],
+        to_move: Player::Black,
+        prompt: "Five squares are left: C1 alone, and four on the right. Play so that you get the last move in each region.",
+        answers: &[pos(0, 2)],
+        retry: "That opens the even region and White gets its last move. Fill the odd region first.",
+        explanation: "Taking the lone square leaves an even region, so White must move there first and you reply last. This wins the game.",
+    },
+];
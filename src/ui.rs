@@ -1,38 +1,136 @@
 //! UI drawing functions
 
-use gam::{Gam, Gid, GlyphStyle};
+use gam::{Gam, GlyphStyle};
 use gam::menu::{Point, Rectangle, DrawStyle, PixelColor, Circle, Line, TextView, TextBounds};
-use othello_core::{Board, Player, pos, pos_to_algebraic};
+use othello_core::{AdaptiveLevel, Analysis, Annotation, Board, GameReview, MoveScore, Player, Position, SearchMethod, Variation, count_frontier, count_moves, frontier_bitboard, NAMED_OPENINGS, pos_to_algebraic, pos_to_rc};
 
-use crate::app::{OthelloApp, AppState, GameMode};
+use crate::app::{OthelloApp, AppState, BackupTask, Bookmark, GameMode, PlyInfo, ReviewGraph, SavePreview, LOW_TIME_SECS};
+use crate::dialog::DialogKind;
+use crate::i18n::fill;
 use crate::menu::MenuItem;
+use crate::puzzle::Objective;
+use crate::storage::{ArchiveMode, ArchivedGame, GameClock, GameMeta, Handicap, HapticCue, StatusItem, TextSize, UndoLimit, BATTERY_MAH, SEARCH_MA};
 
 /// Layout constants
+///
+/// Everything else is worked out from the canvas size, so the layout fits
+/// any display; on the Precursor's 336 pixel wide screen the board has
+/// 38 pixel squares, or 34 with coordinates.
 const HEADER_HEIGHT: isize = 24;
 const FOOTER_HEIGHT: isize = 24;
-const BOARD_SIZE: isize = 304;
-const CELL_SIZE: isize = 38;
-const DISC_RADIUS: isize = 14;
-const VALID_MOVE_RADIUS: isize = 4;
 const CURSOR_WIDTH: isize = 3;
+/// Space on each side of the board
+const BOARD_MARGIN: isize = 16;
+/// Room for the coordinate labels beside and above the board
+const LABEL_SPACE: isize = 16;
+/// Space kept under the board for status lines and the game clock
+const BELOW_BOARD: isize = 100;
+/// Smallest board square, however small the canvas
+const MIN_CELL_SIZE: isize = 16;
+/// Narrowest block of text, however narrow the canvas
+const MIN_TEXT_WIDTH: isize = 120;
+/// Narrowest panel worth putting beside the board
+const SIDE_PANEL_WIDTH: isize = 160;
+
+/// Whether a canvas has room for a panel beside a board as tall as the
+/// canvas allows, as a wide hosted window does
+fn wide(screensize: Point) -> bool {
+    let board = screensize.y - HEADER_HEIGHT - FOOTER_HEIGHT - 2 * BOARD_MARGIN;
+    screensize.x - board - 3 * BOARD_MARGIN - 2 * LABEL_SPACE >= SIDE_PANEL_WIDTH
+}
+
+/// Whether the screen shown puts the board on the left with a panel
+/// beside it rather than the status lines under it
+///
+/// Only the game in progress has a panel; other screens keep the board
+/// above their text on any canvas.
+fn side_panel(app: &OthelloApp) -> bool {
+    matches!(app.state, AppState::Playing { .. }) && wide(app.screensize)
+}
 
 /// Get board origin point
-fn board_origin(screensize: Point, show_coords: bool) -> Point {
-    let board_size = if show_coords { 272 } else { BOARD_SIZE };
-    let margin_x = (screensize.x - board_size) / 2;
+fn board_origin(app: &OthelloApp, show_coords: bool) -> Point {
+    let screensize = app.screensize;
+    let board_size = cell_size(app, show_coords) * 8;
     let content_height = screensize.y - HEADER_HEIGHT - FOOTER_HEIGHT;
-    let board_y = HEADER_HEIGHT + (content_height - board_size - 60) / 2;
+    let labels = if show_coords { LABEL_SPACE } else { 0 };
+
+    // Beside a panel the board sits at the left, centred top to bottom
+    if side_panel(app) {
+        let board_y = HEADER_HEIGHT + (content_height - board_size - labels) / 2;
+        return Point::new(BOARD_MARGIN + labels, board_y + labels);
+    }
+
+    let margin_x = (screensize.x - board_size) / 2;
+    // Centred a little above the middle, but never so low that the lines
+    // under the board run off a short canvas
+    let board_y = HEADER_HEIGHT
+        + ((content_height - board_size - 60) / 2).min(content_height - board_size - labels - BELOW_BOARD).max(0);
 
     if show_coords {
-        Point::new(margin_x + 16, board_y + 16) // Space for labels
+        Point::new(margin_x + LABEL_SPACE, board_y + LABEL_SPACE)
     } else {
         Point::new(margin_x, board_y)
     }
 }
 
-/// Get cell size based on coordinate display
-fn cell_size(show_coords: bool) -> isize {
-    if show_coords { 34 } else { CELL_SIZE }
+/// Get cell size: the board fills the canvas width, less room for the
+/// labels when coordinates show, and shrinks to fit a short canvas; beside
+/// a panel it fills the height instead
+fn cell_size(app: &OthelloApp, show_coords: bool) -> isize {
+    let screensize = app.screensize;
+    let labels = if show_coords { 2 * LABEL_SPACE } else { 0 };
+    let content_height = screensize.y - HEADER_HEIGHT - FOOTER_HEIGHT;
+    let (width, height) = if side_panel(app) {
+        (
+            screensize.x - SIDE_PANEL_WIDTH - 3 * BOARD_MARGIN - labels,
+            content_height - 2 * BOARD_MARGIN - labels / 2,
+        )
+    } else {
+        (screensize.x - 2 * BOARD_MARGIN - labels, content_height - BELOW_BOARD - labels / 2)
+    };
+    (width.min(height) / 8).max(MIN_CELL_SIZE)
+}
+
+/// Width of text starting at `x` that stops `margin` short of the right
+/// edge of the canvas
+fn text_width(app: &OthelloApp, x: isize, margin: isize) -> u16 {
+    (app.screensize.x - x - margin).max(MIN_TEXT_WIDTH) as u16
+}
+
+/// Board square under a point, by the layout `draw_board` uses
+#[cfg(not(target_os = "none"))]
+pub fn square_at(app: &OthelloApp, point: Point, hidden: u64) -> Option<(u8, u8)> {
+    let show_coords = app.settings.show_coordinates || hidden != 0;
+    let origin = board_origin(app, show_coords);
+    let cell = cell_size(app, show_coords);
+    let (x, y) = (point.x - origin.x, point.y - origin.y);
+    if !(0..cell * 8).contains(&x) || !(0..cell * 8).contains(&y) {
+        return None;
+    }
+    Some(((y / cell) as u8, (x / cell) as u8))
+}
+
+/// Style for status, menu and move list text at the chosen size
+fn body_style(app: &OthelloApp) -> GlyphStyle {
+    match app.settings.text_size {
+        TextSize::Small => GlyphStyle::Small,
+        TextSize::Regular => GlyphStyle::Regular,
+        TextSize::Large => GlyphStyle::Large,
+    }
+}
+
+/// Style for secondary lines, one step smaller than the body
+fn detail_style(app: &OthelloApp) -> GlyphStyle {
+    match app.settings.text_size {
+        TextSize::Small | TextSize::Regular => GlyphStyle::Small,
+        TextSize::Large => GlyphStyle::Regular,
+    }
+}
+
+/// Height of one row of text in a style, including spacing
+fn row_height(style: GlyphStyle) -> isize {
+    gam::glyph_to_height_hint(style) as isize + 7
 }
 
 /// Draw the complete app
@@ -41,24 +139,64 @@ pub fn draw(app: &OthelloApp, gam: &Gam) {
     clear_screen(gam, app.gid, app.screensize);
 
     match &app.state {
-        AppState::MainMenu => draw_main_menu(app, gam),
-        AppState::NewGameMenu => draw_new_game_menu(app, gam),
+        AppState::MainMenu { selected, preview } => draw_main_menu(app, gam, *selected, preview.as_ref()),
+        AppState::NewGameMenu { selected } => draw_new_game_menu(app, gam, *selected),
+        AppState::Analysis { board, to_move, depth, result, worker } => {
+            draw_analysis(app, gam, board, *to_move, *depth, result.as_ref(), worker.is_some());
+        }
+        AppState::LoadPosition { input, status, receiving } => {
+            draw_load_position(app, gam, input, status.as_deref(), receiving.is_some());
+        }
+        AppState::Demo { game, .. } => draw_demo(app, gam, game),
+        AppState::Backup { status, task, .. } => {
+            draw_backup(app, gam, status.as_deref(), *task == Some(BackupTask::Receive));
+        }
+        AppState::Editor { board, to_move, cursor_pos, message } => {
+            draw_editor(app, gam, board, *to_move, *cursor_pos, message.as_deref());
+        }
+        AppState::HandicapMenu { mode } => draw_handicap_menu(app, gam, *mode),
         AppState::SettingsMenu => draw_settings_menu(app, gam),
-        AppState::Statistics => draw_statistics(app, gam),
-        AppState::Playing { game, mode, player_color, cursor_pos, ai_thinking, thinking_dots, show_pass_notice } => {
-            draw_playing(app, gam, game, *mode, *player_color, *cursor_pos, *ai_thinking, *thinking_dots, *show_pass_notice);
+        AppState::StatusLines { selected } => draw_status_lines(app, gam, *selected),
+        AppState::Vibration { selected } => draw_vibration(app, gam, *selected),
+        AppState::Statistics { page: 0, .. } => draw_statistics(app, gam),
+        AppState::Statistics { page: 1, level } => draw_margins(app, gam, *level),
+        AppState::Statistics { page: 2, .. } => draw_openings(app, gam),
+        AppState::Statistics { .. } => draw_thinking(app, gam),
+        AppState::Playing { game, ply, mode, player_color, cursor_pos, ai_thinking, thinking_dots, show_pass_notice, notice, meta, blindfold, peeking, handover, .. } => {
+            let hidden = if *peeking { 0 } else { blindfold.mask() };
+            draw_playing(app, gam, game, ply, *mode, *player_color, *cursor_pos, *ai_thinking, *thinking_dots, *show_pass_notice, notice.as_deref(), meta, hidden, *handover);
+        }
+        AppState::GameOver { game, mode, player_color, meta, review, archived, .. } => {
+            draw_game_over(app, gam, game, *mode, *player_color, meta, review, *archived);
         }
-        AppState::GameOver { game, mode, player_color } => {
-            draw_game_over(app, gam, game, *mode, *player_color);
+        AppState::ArchiveReview { finished } => draw_archive_review(app, gam, *finished),
+        AppState::DeepAnalysis { game, review, eval, elapsed_secs, worker } => {
+            draw_deep_analysis(app, gam, game, review, eval.as_ref(), *elapsed_secs, worker.is_some());
         }
-        AppState::WhatIf { current_game, view_index, branched, cursor_pos, base_game } => {
-            draw_what_if(app, gam, base_game, current_game, *view_index, *branched, *cursor_pos);
+        AppState::Archive { games, selected, sending } => {
+            let notice = sending.is_some().then_some(app.text().archive_sending);
+            draw_archive(app, gam, games, *selected, notice);
         }
+        AppState::WhatIf { current_game, view_index, branched, cursor_pos, base_game, graph, bookmarks, sending } => {
+            let notice = sending.is_some().then_some(app.text().what_if_sending);
+            draw_what_if(app, gam, base_game, current_game, *view_index, *branched, *cursor_pos, *graph, bookmarks.len(), notice);
+        }
+        AppState::Bookmarks { selected } => draw_bookmarks(app, gam, app.what_if_bookmarks(), *selected),
         AppState::MoveHistory { game, scroll_offset } => {
             draw_history(app, gam, game, *scroll_offset);
         }
-        AppState::Help { context, .. } => {
-            crate::help::draw_help(app, gam, *context);
+        AppState::PositionText { lines, scroll_offset } => draw_position_text(app, gam, lines, *scroll_offset),
+        AppState::Help { context, page } => {
+            crate::help::draw_help(app, gam, *context, *page);
+        }
+        AppState::Tutorial { lesson, game, cursor_pos, solved, message } => {
+            draw_tutorial(app, gam, *lesson, game, *cursor_pos, *solved, *message);
+        }
+        AppState::PuzzleMenu { packs, selected, status, task, import, today } => {
+            draw_puzzle_menu(app, gam, packs, *selected, status.as_deref(), task.is_some() || import.is_some(), *today);
+        }
+        AppState::Puzzle { puzzles, index, game, cursor_pos, cpu_thinking, outcome, message, daily, .. } => {
+            draw_puzzle(app, gam, puzzles, *index, game, *cursor_pos, *cpu_thinking, *outcome, message.as_deref(), daily.is_some());
         }
     }
 }
@@ -138,7 +276,7 @@ fn draw_footer(app: &OthelloApp, gam: &Gam) {
     );
     tv.style = GlyphStyle::Small;
     use core::fmt::Write;
-    write!(tv.text, "F1 Menu").ok();
+    write!(tv.text, "{}", app.text().footer_menu).ok();
     gam.post_textview(&mut tv).ok();
 
     // F4 Exit hint
@@ -147,16 +285,62 @@ fn draw_footer(app: &OthelloApp, gam: &Gam) {
         TextBounds::GrowableFromTr(Point::new(app.screensize.x - 8, footer_y + 4), 100),
     );
     tv.style = GlyphStyle::Small;
-    write!(tv.text, "F4 Exit").ok();
+    write!(tv.text, "{}", app.text().footer_exit).ok();
     gam.post_textview(&mut tv).ok();
 }
 
+/// Mark frontier discs (next to an empty square) for both players
+///
+/// Black discs get a light square, White discs a dark one. Squares in
+/// `hidden` are left alone so blindfold mode gives nothing away.
+fn draw_frontier(app: &OthelloApp, gam: &Gam, board: &Board, hidden: u64) {
+    if app.settings.show_frontier {
+        let frontier = [frontier_bitboard(board, Player::Black), frontier_bitboard(board, Player::White)];
+        draw_frontier_marks(app, gam, frontier, hidden);
+    }
+}
+
+/// Mark the frontier discs worked out beforehand, Black's then White's
+fn draw_frontier_marks(app: &OthelloApp, gam: &Gam, frontier: [u64; 2], hidden: u64) {
+    if !app.settings.show_frontier {
+        return;
+    }
+    let show_coords = app.settings.show_coordinates || hidden != 0;
+    let origin = board_origin(app, show_coords);
+    let cell = cell_size(app, show_coords);
+    let half = (cell / 12).max(3);
+
+    for (player, discs) in [Player::Black, Player::White].into_iter().zip(frontier) {
+        let color = match player {
+            Player::Black => PixelColor::Light,
+            Player::White => PixelColor::Dark,
+        };
+        for position in Board::iter_bits(discs & !hidden) {
+            let (row, col) = (position as isize / 8, position as isize % 8);
+            let cx = origin.x + col * cell + cell / 2;
+            let cy = origin.y + row * cell + cell / 2;
+            gam.draw_rectangle(
+                app.gid,
+                Rectangle::new_with_style(
+                    Point::new(cx - half, cy - half),
+                    Point::new(cx + half, cy + half),
+                    DrawStyle::new(color, color, 1),
+                ),
+            )
+            .ok();
+        }
+    }
+}
+
 /// Draw the Othello board
-fn draw_board(app: &OthelloApp, gam: &Gam, board: &Board, cursor: Option<(u8, u8)>, show_valid: bool, current_player: Player, last_move: Option<u8>) {
+///
+/// Discs on squares in `hidden` are left out (blindfold mode), except the
+/// last move; coordinates are always shown while anything is hidden.
+fn draw_board(app: &OthelloApp, gam: &Gam, board: &Board, cursor: Option<(u8, u8)>, valid_moves: u64, last_move: Option<u8>, hidden: u64) {
     let gid = app.gid;
-    let show_coords = app.settings.show_coordinates;
-    let origin = board_origin(app.screensize, show_coords);
-    let cell = cell_size(show_coords);
+    let show_coords = app.settings.show_coordinates || hidden != 0;
+    let origin = board_origin(app, show_coords);
+    let cell = cell_size(app, show_coords);
     let board_px = cell * 8;
 
     // Draw coordinate labels if enabled
@@ -223,97 +407,91 @@ fn draw_board(app: &OthelloApp, gam: &Gam, board: &Board, cursor: Option<(u8, u8
         .ok();
     }
 
-    // Get valid moves bitboard
-    let valid_moves = if show_valid {
-        othello_core::legal_moves_bitboard(board, current_player)
-    } else {
-        0
-    };
-
     // Draw discs and valid move indicators
-    let disc_r: isize = if show_coords { 12 } else { DISC_RADIUS };
-    let valid_r: isize = if show_coords { 3 } else { VALID_MOVE_RADIUS };
+    let disc_r = cell * 3 / 8;
+    let valid_r = (cell / 9).max(2);
+
+    for (position, disc) in board.squares() {
+        let (row, col) = pos_to_rc(position);
+        let cx = origin.x + col as isize * cell + cell / 2;
+        let cy = origin.y + row as isize * cell + cell / 2;
+        let center = Point::new(cx, cy);
+
+        // Blindfold leaves hidden squares blank, but the last move shows
+        let concealed = hidden & (1u64 << position) != 0 && last_move != Some(position);
+
+        // Draw disc if present
+        if let Some(player) = disc.filter(|_| !concealed) {
+            let (fill, stroke) = match player {
+                Player::Black => (PixelColor::Dark, PixelColor::Dark),
+                Player::White => (PixelColor::Light, PixelColor::Dark),
+            };
+            gam.draw_circle(
+                gid,
+                Circle::new_with_style(
+                    center,
+                    disc_r,
+                    DrawStyle::new(fill, stroke, 2),  // fill first, then stroke
+                ),
+            )
+            .ok();
+        } else if (valid_moves & (1u64 << position)) != 0 {
+            // Draw valid move indicator
+            gam.draw_circle(
+                gid,
+                Circle::new_with_style(
+                    center,
+                    valid_r,
+                    DrawStyle::new(PixelColor::Dark, PixelColor::Dark, 1),
+                ),
+            )
+            .ok();
+        }
 
-    for row in 0..8 {
-        for col in 0..8 {
-            let position = pos(row, col);
-            let cx = origin.x + col as isize * cell + cell / 2;
-            let cy = origin.y + row as isize * cell + cell / 2;
-            let center = Point::new(cx, cy);
-
-            // Draw disc if present
-            if let Some(player) = board.get_disc(position) {
-                let (fill, stroke) = match player {
-                    Player::Black => (PixelColor::Dark, PixelColor::Dark),
-                    Player::White => (PixelColor::Light, PixelColor::Dark),
-                };
-                gam.draw_circle(
+        // Draw last move marker
+        if let Some(last) = last_move {
+            if last == position {
+                let corner_size = (cell / 10).max(4);
+                // Top-left corner
+                gam.draw_rectangle(
+                    gid,
+                    Rectangle::new_with_style(
+                        Point::new(origin.x + col as isize * cell + 2, origin.y + row as isize * cell + 2),
+                        Point::new(origin.x + col as isize * cell + 2 + corner_size, origin.y + row as isize * cell + 2 + corner_size),
+                        DrawStyle::new(PixelColor::Dark, PixelColor::Dark, 1),
+                    ),
+                )
+                .ok();
+                // Top-right corner
+                gam.draw_rectangle(
                     gid,
-                    Circle::new_with_style(
-                        center,
-                        disc_r,
-                        DrawStyle::new(fill, stroke, 2),  // fill first, then stroke
+                    Rectangle::new_with_style(
+                        Point::new(origin.x + (col as isize + 1) * cell - 2 - corner_size, origin.y + row as isize * cell + 2),
+                        Point::new(origin.x + (col as isize + 1) * cell - 2, origin.y + row as isize * cell + 2 + corner_size),
+                        DrawStyle::new(PixelColor::Dark, PixelColor::Dark, 1),
                     ),
                 )
                 .ok();
-            } else if (valid_moves & (1u64 << position)) != 0 {
-                // Draw valid move indicator
-                gam.draw_circle(
+                // Bottom-left corner
+                gam.draw_rectangle(
                     gid,
-                    Circle::new_with_style(
-                        center,
-                        valid_r,
+                    Rectangle::new_with_style(
+                        Point::new(origin.x + col as isize * cell + 2, origin.y + (row as isize + 1) * cell - 2 - corner_size),
+                        Point::new(origin.x + col as isize * cell + 2 + corner_size, origin.y + (row as isize + 1) * cell - 2),
+                        DrawStyle::new(PixelColor::Dark, PixelColor::Dark, 1),
+                    ),
+                )
+                .ok();
+                // Bottom-right corner
+                gam.draw_rectangle(
+                    gid,
+                    Rectangle::new_with_style(
+                        Point::new(origin.x + (col as isize + 1) * cell - 2 - corner_size, origin.y + (row as isize + 1) * cell - 2 - corner_size),
+                        Point::new(origin.x + (col as isize + 1) * cell - 2, origin.y + (row as isize + 1) * cell - 2),
                         DrawStyle::new(PixelColor::Dark, PixelColor::Dark, 1),
                     ),
                 )
                 .ok();
-            }
-
-            // Draw last move marker
-            if let Some(last) = last_move {
-                if last == position {
-                    let corner_size = 4isize;
-                    // Top-left corner
-                    gam.draw_rectangle(
-                        gid,
-                        Rectangle::new_with_style(
-                            Point::new(origin.x + col as isize * cell + 2, origin.y + row as isize * cell + 2),
-                            Point::new(origin.x + col as isize * cell + 2 + corner_size, origin.y + row as isize * cell + 2 + corner_size),
-                            DrawStyle::new(PixelColor::Dark, PixelColor::Dark, 1),
-                        ),
-                    )
-                    .ok();
-                    // Top-right corner
-                    gam.draw_rectangle(
-                        gid,
-                        Rectangle::new_with_style(
-                            Point::new(origin.x + (col as isize + 1) * cell - 2 - corner_size, origin.y + row as isize * cell + 2),
-                            Point::new(origin.x + (col as isize + 1) * cell - 2, origin.y + row as isize * cell + 2 + corner_size),
-                            DrawStyle::new(PixelColor::Dark, PixelColor::Dark, 1),
-                        ),
-                    )
-                    .ok();
-                    // Bottom-left corner
-                    gam.draw_rectangle(
-                        gid,
-                        Rectangle::new_with_style(
-                            Point::new(origin.x + col as isize * cell + 2, origin.y + (row as isize + 1) * cell - 2 - corner_size),
-                            Point::new(origin.x + col as isize * cell + 2 + corner_size, origin.y + (row as isize + 1) * cell - 2),
-                            DrawStyle::new(PixelColor::Dark, PixelColor::Dark, 1),
-                        ),
-                    )
-                    .ok();
-                    // Bottom-right corner
-                    gam.draw_rectangle(
-                        gid,
-                        Rectangle::new_with_style(
-                            Point::new(origin.x + (col as isize + 1) * cell - 2 - corner_size, origin.y + (row as isize + 1) * cell - 2 - corner_size),
-                            Point::new(origin.x + (col as isize + 1) * cell - 2, origin.y + (row as isize + 1) * cell - 2),
-                            DrawStyle::new(PixelColor::Dark, PixelColor::Dark, 1),
-                        ),
-                    )
-                    .ok();
-                }
             }
         }
     }
@@ -338,56 +516,253 @@ fn draw_board(app: &OthelloApp, gam: &Gam, board: &Board, cursor: Option<(u8, u8
     }
 }
 
+/// Top of the main menu entries and the height of each
+fn main_menu_rows(app: &OthelloApp) -> (isize, isize) {
+    (HEADER_HEIGHT + 70, row_height(body_style(app)) + 8)
+}
+
+/// Main menu entry under a point
+#[cfg(not(target_os = "none"))]
+pub fn main_menu_entry_at(app: &OthelloApp, point: Point) -> Option<usize> {
+    let (start_y, row) = main_menu_rows(app);
+    let entries = crate::menu::main_menu_items(app.has_save).len();
+    let index = (point.y - start_y).div_euclid(row);
+    (point.x >= 40 && point.x < app.screensize.x - 16 && (0..entries as isize).contains(&index)).then_some(index as usize)
+}
+
 /// Draw main menu
-fn draw_main_menu(app: &OthelloApp, gam: &Gam) {
-    draw_header(app, gam, "OTHELLO", 0, 0);
+fn draw_main_menu(app: &OthelloApp, gam: &Gam, selected: usize, preview: Option<&SavePreview>) {
+    let s = app.text();
+    draw_header(app, gam, s.title_app, 0, 0);
     draw_footer(app, gam);
 
     let gid = app.gid;
-    let center_x = app.screensize.x / 2;
-    let center_y = app.screensize.y / 2;
+    use core::fmt::Write;
 
     // Title
     let mut tv = TextView::new(
         gid,
-        TextBounds::BoundingBox(Rectangle::new_coords(0, center_y - 60, app.screensize.x, center_y)),
+        TextBounds::BoundingBox(Rectangle::new_coords(0, HEADER_HEIGHT + 12, app.screensize.x, HEADER_HEIGHT + 52)),
     );
     tv.style = GlyphStyle::ExtraLarge;
+    write!(tv.text, "{}", s.title_app).ok();
+    gam.post_textview(&mut tv).ok();
+
+    // Entries
+    let items = crate::menu::main_menu_items(app.has_save);
+    let selected = selected.min(items.len() - 1);
+    let style = body_style(app);
+    let (start_y, row) = main_menu_rows(app);
+    for (i, item) in items.iter().enumerate() {
+        let mut tv = TextView::new(
+            gid,
+            TextBounds::GrowableFromTl(Point::new(40, start_y + i as isize * row), text_width(app, 40, 16)),
+        );
+        tv.style = match app.settings.text_size {
+            TextSize::Regular if i == selected => GlyphStyle::Bold,
+            _ => style,
+        };
+        write!(tv.text, "{} {}", if i == selected { ">" } else { " " }, item.label(s)).ok();
+        gam.post_textview(&mut tv).ok();
+    }
+
+    // Saved game, under the list
+    let footer_y = app.screensize.y - FOOTER_HEIGHT;
+    if let Some(preview) = preview {
+        let cell = 12isize;
+        let box_top = footer_y - 8 * cell - 24;
+        gam.draw_rectangle(
+            gid,
+            Rectangle::new_with_style(
+                Point::new(12, box_top),
+                Point::new(app.screensize.x - 12, footer_y - 8),
+                DrawStyle::new(PixelColor::Light, PixelColor::Dark, 1),
+            ),
+        )
+        .ok();
+        draw_thumbnail(app, gam, &preview.board, Point::new(20, box_top + 8), cell);
+
+        let mode = mode_label(app, preview.mode);
+        let mut tv = TextView::new(
+            gid,
+            TextBounds::GrowableFromTl(Point::new(20 + 8 * cell + 12, box_top + 8), 180),
+        );
+        tv.style = GlyphStyle::Small;
+        write!(
+            tv.text,
+            "{}\n{}\n{}\n{}",
+            s.saved_game,
+            mode,
+            fill(s.saved_move, &[&preview.move_count]),
+            fill(s.to_move, &[&s.player(preview.to_move)]),
+        )
+        .ok();
+        gam.post_textview(&mut tv).ok();
+    } else {
+        let mut tv = TextView::new(
+            gid,
+            TextBounds::GrowableFromTl(Point::new(16, footer_y - 30), text_width(app, 16, 0)),
+        );
+        tv.style = GlyphStyle::Small;
+        write!(tv.text, "{}", s.main_keys).ok();
+        gam.post_textview(&mut tv).ok();
+    }
+}
+
+/// Draw a miniature board with its top-left corner at `origin`
+///
+/// Cells under 6 px are filled edge to edge, leaving White's discs as a
+/// one-pixel ring.
+fn draw_thumbnail(app: &OthelloApp, gam: &Gam, board: &Board, origin: Point, cell: isize) {
+    let gid = app.gid;
+    let inset = if cell < 6 { 0 } else { 2 };
+    gam.draw_rectangle(
+        gid,
+        Rectangle::new_with_style(
+            origin,
+            Point::new(origin.x + 8 * cell, origin.y + 8 * cell),
+            DrawStyle::new(PixelColor::Light, PixelColor::Dark, 1),
+        ),
+    )
+    .ok();
+
+    for (player, fill) in [(Player::Black, PixelColor::Dark), (Player::White, PixelColor::Light)] {
+        for position in board.discs(player) {
+            let (row, col) = pos_to_rc(position);
+            let x = origin.x + col as isize * cell;
+            let y = origin.y + row as isize * cell;
+            gam.draw_rectangle(
+                gid,
+                Rectangle::new_with_style(
+                    Point::new(x + inset, y + inset),
+                    Point::new(x + cell - inset, y + cell - inset),
+                    DrawStyle::new(fill, PixelColor::Dark, 1),
+                ),
+            )
+            .ok();
+        }
+    }
+}
+
+/// Short name of a game mode, such as "vs CPU Hard"
+pub fn mode_label(app: &OthelloApp, mode: GameMode) -> String {
+    let s = app.text();
+    match mode {
+        GameMode::VsCpu(difficulty) => fill(s.mode_vs_cpu, &[&s.levels[difficulty as usize]]),
+        GameMode::TwoPlayer => String::from(s.mode_two_player),
+        GameMode::Adaptive => fill(s.mode_vs_cpu, &[&s.level_adaptive]),
+    }
+}
+
+/// Draw new game menu
+fn draw_new_game_menu(app: &OthelloApp, gam: &Gam, selected: usize) {
+    let s = app.text();
+    draw_header(app, gam, s.title_new_game, 0, 0);
+    draw_footer(app, gam);
+
+    let gid = app.gid;
+    let start_y = HEADER_HEIGHT + 40;
+    let option_height = 26;
     use core::fmt::Write;
-    write!(tv.text, "OTHELLO").ok();
+
+    // Blank entries only leave a gap, so modes are counted separately
+    let mut mode = 0;
+    for (i, option) in s.new_game_options.iter().enumerate() {
+        if option.is_empty() {
+            continue;
+        }
+        let mut tv = TextView::new(
+            gid,
+            TextBounds::GrowableFromTl(Point::new(24, start_y + i as isize * option_height), text_width(app, 24, 32)),
+        );
+        tv.style = if mode == selected { GlyphStyle::Bold } else { GlyphStyle::Regular };
+        write!(tv.text, "{} {}", if mode == selected { ">" } else { " " }, option).ok();
+        gam.post_textview(&mut tv).ok();
+        mode += 1;
+    }
+
+    // What the selected mode plays like
+    let details = match GameMode::ALL[selected.min(GameMode::ALL.len() - 1)] {
+        GameMode::VsCpu(difficulty) => {
+            let level = difficulty as usize;
+            let solver = if difficulty.use_endgame_solver() {
+                fill(s.level_solver, &[&difficulty.endgame_threshold()])
+            } else {
+                String::from(s.level_no_solver)
+            };
+            let book = if difficulty.use_opening_book() { s.yes } else { s.no };
+            let mut details = format!(
+                "{}\n\n{}\n{}\n{}\n{}\n{}",
+                s.level_descriptions[level],
+                fill(s.level_strength, &[&s.level_strengths[level]]),
+                fill(s.level_think, &[&s.level_think_times[level]]),
+                fill(s.level_depth, &[&difficulty.depth()]),
+                fill(s.level_book, &[&book]),
+                solver,
+            );
+            if difficulty.wld_threshold() > difficulty.endgame_threshold() {
+                details.push('\n');
+                details.push_str(&fill(s.level_wld, &[&difficulty.wld_threshold()]));
+            }
+            details
+        }
+        GameMode::TwoPlayer => String::from(s.two_player_description),
+        GameMode::Adaptive => {
+            let level = app.stats.adaptive_level;
+            format!(
+                "{}\n\n{}\n{}\n{}",
+                s.adaptive_description,
+                fill(s.adaptive_level, &[&(level.get() + 1), &(AdaptiveLevel::MAX + 1)]),
+                fill(s.level_depth, &[&level.depth()]),
+                fill(s.adaptive_record, &[&app.stats.adaptive_wins, &app.stats.adaptive_losses, &app.stats.adaptive_draws]),
+            )
+        }
+    };
+    let details_y = start_y + s.new_game_options.len() as isize * option_height + 16;
+    let mut tv = TextView::new(
+        gid,
+        TextBounds::GrowableFromTl(Point::new(24, details_y), text_width(app, 24, 12)),
+    );
+    tv.style = detail_style(app);
+    write!(tv.text, "{}", details).ok();
     gam.post_textview(&mut tv).ok();
 
-    // Instructions
     let mut tv = TextView::new(
         gid,
-        TextBounds::BoundingBox(Rectangle::new_coords(0, center_y + 20, app.screensize.x, center_y + 100)),
+        TextBounds::GrowableFromTl(Point::new(16, app.screensize.y - FOOTER_HEIGHT - 30), text_width(app, 16, 0)),
     );
-    tv.style = GlyphStyle::Regular;
-    write!(tv.text, "Press F1 for Menu").ok();
+    tv.style = GlyphStyle::Small;
+    write!(tv.text, "{}", s.new_game_keys).ok();
     gam.post_textview(&mut tv).ok();
 }
 
-/// Draw new game menu
-fn draw_new_game_menu(app: &OthelloApp, gam: &Gam) {
-    draw_header(app, gam, "NEW GAME", 0, 0);
+/// Draw the handicap step of a new game
+fn draw_handicap_menu(app: &OthelloApp, gam: &Gam, mode: GameMode) {
+    let s = app.text();
+    draw_header(app, gam, s.title_handicap, 0, 0);
     draw_footer(app, gam);
 
     let gid = app.gid;
     let start_y = HEADER_HEIGHT + 40;
 
-    let options = [
-        "1. Easy",
-        "2. Medium",
-        "3. Hard",
-        "4. Expert",
-        "",
-        "5. Two Players",
+    let (player, opponent) = match mode {
+        GameMode::VsCpu(_) | GameMode::Adaptive => (s.handicap_you_start, s.handicap_cpu_starts),
+        GameMode::TwoPlayer => (s.handicap_black_starts, s.handicap_white_starts),
+    };
+    let mut options = vec![
+        String::from(s.handicap_even),
+        fill(s.handicap_player, &[&player]),
+        fill(s.handicap_opponent, &[&opponent]),
     ];
+    if let GameMode::VsCpu(_) = mode {
+        options.push(String::from(s.handicap_gentle));
+    }
+    options.push(fill(s.handicap_clock, &[&clock_label(app, app.settings.game_clock)]));
 
     for (i, option) in options.iter().enumerate() {
         let mut tv = TextView::new(
             gid,
-            TextBounds::GrowableFromTl(Point::new(40, start_y + i as isize * 30), 256),
+            TextBounds::GrowableFromTl(Point::new(40, start_y + i as isize * 30), text_width(app, 40, 16)),
         );
         tv.style = GlyphStyle::Regular;
         use core::fmt::Write;
@@ -396,38 +771,143 @@ fn draw_new_game_menu(app: &OthelloApp, gam: &Gam) {
     }
 }
 
+/// Describe a game clock setting
+pub fn clock_label(app: &OthelloApp, clock: GameClock) -> String {
+    let s = app.text();
+    match clock.seconds() {
+        Some(secs) => fill(s.clock_minutes, &[&(secs / 60)]),
+        None => String::from(s.clock_off),
+    }
+}
+
 /// Draw settings menu
 fn draw_settings_menu(app: &OthelloApp, gam: &Gam) {
-    draw_header(app, gam, "SETTINGS", 0, 0);
+    let s = app.text();
+    draw_header(app, gam, s.title_settings, 0, 0);
     draw_footer(app, gam);
 
     let gid = app.gid;
     let start_y = HEADER_HEIGHT + 40;
 
     let check = |b: bool| if b { "[X]" } else { "[ ]" };
+    let undo_limit = match app.settings.undo_limit {
+        UndoLimit::Once => s.undo_once,
+        UndoLimit::Three => s.undo_three,
+        UndoLimit::Unlimited => s.undo_unlimited,
+    };
+    let text_size = match app.settings.text_size {
+        TextSize::Small => s.text_small,
+        TextSize::Regular => s.text_regular,
+        TextSize::Large => s.text_large,
+    };
+    let archive = match app.settings.archive {
+        ArchiveMode::Ask => s.archive_ask,
+        ArchiveMode::Always => s.archive_always,
+        ArchiveMode::Never => s.archive_never,
+    };
 
-    let options = [
-        format!("1. Show Coordinates  {}", check(app.settings.show_coordinates)),
-        format!("2. Show Valid Moves  {}", check(app.settings.show_valid_moves)),
-        format!("3. Allow Undo        {}", check(app.settings.allow_undo)),
-        format!("4. Vibration         {}", check(app.settings.vibration)),
+    // Reset shows how many moves there are to forget
+    let learned = fill(s.learned_moves, &[&app.learned_book_len()]);
+    let idle_demo = match app.settings.idle_demo.seconds() {
+        Some(secs) => fill(s.idle_minutes, &[&(secs / 60)]),
+        None => String::from(s.idle_off),
+    };
+    let values = [
+        check(app.settings.show_coordinates),
+        check(app.settings.show_valid_moves),
+        check(app.settings.allow_undo),
+        check(app.settings.vibration),
+        undo_limit,
+        check(app.settings.coach),
+        app.settings.language.name(),
+        text_size,
+        "",
+        archive,
+        check(app.settings.hot_seat),
+        check(app.settings.blunder_check),
+        check(app.settings.show_frontier),
+        check(app.settings.learn_book),
+        &learned,
+        &idle_demo,
     ];
 
-    for (i, option) in options.iter().enumerate() {
+    for (i, (label, value)) in s.settings_labels.iter().zip(values).enumerate() {
         let mut tv = TextView::new(
             gid,
-            TextBounds::GrowableFromTl(Point::new(40, start_y + i as isize * 30), 280),
+            TextBounds::GrowableFromTl(Point::new(40, start_y + i as isize * 28), text_width(app, 40, 16)),
         );
         tv.style = GlyphStyle::Regular;
         use core::fmt::Write;
-        write!(tv.text, "{}", option).ok();
+        write!(tv.text, "{}{}", label, value).ok();
+        gam.post_textview(&mut tv).ok();
+    }
+}
+
+/// Draw vibration as a whole and each haptic cue, on or off
+fn draw_vibration(app: &OthelloApp, gam: &Gam, selected: usize) {
+    let s = app.text();
+    draw_header(app, gam, s.title_vibration, 0, 0);
+    draw_footer(app, gam);
+
+    let gid = app.gid;
+    let start_y = HEADER_HEIGHT + 40;
+    use core::fmt::Write;
+
+    let cues = HapticCue::ALL.iter().map(|&cue| app.settings.cue_on(cue));
+    let values = core::iter::once(app.settings.vibration).chain(cues);
+    for (i, (name, on)) in s.vibration_items.iter().zip(values).enumerate() {
+        let mut tv = TextView::new(
+            gid,
+            TextBounds::GrowableFromTl(Point::new(40, start_y + i as isize * 30), text_width(app, 40, 16)),
+        );
+        tv.style = if i == selected { GlyphStyle::Bold } else { GlyphStyle::Regular };
+        write!(tv.text, "{} {} {}", if i == selected { ">" } else { " " }, if on { "[X]" } else { "[ ]" }, name).ok();
+        gam.post_textview(&mut tv).ok();
+    }
+
+    let mut tv = TextView::new(
+        gid,
+        TextBounds::GrowableFromTl(Point::new(16, app.screensize.y - FOOTER_HEIGHT - 30), text_width(app, 16, 0)),
+    );
+    tv.style = GlyphStyle::Small;
+    write!(tv.text, "{}", s.vibration_keys).ok();
+    gam.post_textview(&mut tv).ok();
+}
+
+/// Draw the status line choices
+fn draw_status_lines(app: &OthelloApp, gam: &Gam, selected: usize) {
+    let s = app.text();
+    draw_header(app, gam, s.title_status_lines, 0, 0);
+    draw_footer(app, gam);
+
+    let gid = app.gid;
+    let start_y = HEADER_HEIGHT + 40;
+    use core::fmt::Write;
+
+    for (i, (item, name)) in StatusItem::ALL.iter().zip(s.status_items).enumerate() {
+        let placement = app.settings.status_line(*item).map_or(0, |line| line + 1);
+        let mut tv = TextView::new(
+            gid,
+            TextBounds::GrowableFromTl(Point::new(40, start_y + i as isize * 30), text_width(app, 40, 16)),
+        );
+        tv.style = if i == selected { GlyphStyle::Bold } else { GlyphStyle::Regular };
+        write!(tv.text, "{} {}: {}", if i == selected { ">" } else { " " }, name, s.status_placements[placement]).ok();
         gam.post_textview(&mut tv).ok();
     }
+
+    let mut tv = TextView::new(
+        gid,
+        TextBounds::GrowableFromTl(Point::new(16, app.screensize.y - FOOTER_HEIGHT - 30), text_width(app, 16, 0)),
+    );
+    tv.style = GlyphStyle::Small;
+    write!(tv.text, "{}", s.status_lines_keys).ok();
+    gam.post_textview(&mut tv).ok();
 }
 
 /// Draw statistics
 fn draw_statistics(app: &OthelloApp, gam: &Gam) {
-    draw_header(app, gam, "STATISTICS", 0, 0);
+    let s = app.text();
+    draw_header(app, gam, s.title_statistics, 0, 0);
     draw_footer(app, gam);
 
     let gid = app.gid;
@@ -438,38 +918,208 @@ fn draw_statistics(app: &OthelloApp, gam: &Gam) {
     let mut y = start_y;
     let line_height = 22isize;
 
-    // Easy stats
-    draw_stats_line(gam, gid, y, "vs CPU Easy", true);
-    y += line_height;
-    draw_stats_line(gam, gid, y, &format!("  Won: {}  Lost: {}  Draw: {}", stats.easy_wins, stats.easy_losses, stats.easy_draws), false);
-    y += line_height * 2;
+    // One block per CPU level
+    let levels = [
+        (stats.easy_wins, stats.easy_losses, stats.easy_draws),
+        (stats.medium_wins, stats.medium_losses, stats.medium_draws),
+        (stats.hard_wins, stats.hard_losses, stats.hard_draws),
+        (stats.expert_wins, stats.expert_losses, stats.expert_draws),
+        (stats.grandmaster_wins, stats.grandmaster_losses, stats.grandmaster_draws),
+        (stats.adaptive_wins, stats.adaptive_losses, stats.adaptive_draws),
+    ];
+    for (heading, (won, lost, drawn)) in s.stats_levels.iter().zip(levels) {
+        draw_stats_line(app, gam, y, heading, true);
+        y += line_height;
+        draw_stats_line(app, gam, y, &fill(s.stats_record, &[&won, &lost, &drawn]), false);
+        y += line_height + 4;
+    }
 
-    // Medium stats
-    draw_stats_line(gam, gid, y, "vs CPU Medium", true);
+    // Two player stats
+    draw_stats_line(app, gam, y, &fill(s.stats_two_player, &[&stats.two_player_games]), true);
     y += line_height;
-    draw_stats_line(gam, gid, y, &format!("  Won: {}  Lost: {}  Draw: {}", stats.medium_wins, stats.medium_losses, stats.medium_draws), false);
-    y += line_height * 2;
-
-    // Hard stats
-    draw_stats_line(gam, gid, y, "vs CPU Hard", true);
+    draw_stats_line(app, gam, y, &fill(s.stats_resignations, &[&stats.resignations]), false);
     y += line_height;
-    draw_stats_line(gam, gid, y, &format!("  Won: {}  Lost: {}  Draw: {}", stats.hard_wins, stats.hard_losses, stats.hard_draws), false);
-    y += line_height * 2;
-
-    // Expert stats
-    draw_stats_line(gam, gid, y, "vs CPU Expert", true);
+    draw_stats_line(app, gam, y, &fill(s.stats_assisted, &[&stats.assisted_wins]), false);
     y += line_height;
-    draw_stats_line(gam, gid, y, &format!("  Won: {}  Lost: {}  Draw: {}", stats.expert_wins, stats.expert_losses, stats.expert_draws), false);
-    y += line_height * 2;
-
-    // Two player stats
-    draw_stats_line(gam, gid, y, &format!("Two Player Games: {}", stats.two_player_games), true);
-}
+    draw_stats_line(app, gam, y, &fill(s.stats_hint_free, &[&stats.hint_free_wins]), false);
+    y += line_height;
+    draw_stats_line(app, gam, y, &fill(s.stats_daily, &[&stats.daily_streak, &stats.daily_best_streak]), false);
 
-fn draw_stats_line(gam: &Gam, gid: Gid, y: isize, text: &str, bold: bool) {
     let mut tv = TextView::new(
         gid,
-        TextBounds::GrowableFromTl(Point::new(20, y), 300),
+        TextBounds::GrowableFromTl(Point::new(16, app.screensize.y - FOOTER_HEIGHT - 30), text_width(app, 16, 0)),
+    );
+    tv.style = GlyphStyle::Small;
+    use core::fmt::Write;
+    write!(tv.text, "{}", s.stats_records_keys).ok();
+    gam.post_textview(&mut tv).ok();
+}
+
+/// Draw the final-margin histogram for one CPU level
+///
+/// One bar per margin range, losses above wins, scaled to the fullest.
+fn draw_margins(app: &OthelloApp, gam: &Gam, level: usize) {
+    let s = app.text();
+    draw_header(app, gam, s.title_statistics, 0, 0);
+    draw_footer(app, gam);
+
+    let gid = app.gid;
+    let counts = &app.stats.margins[level];
+    let mut y = HEADER_HEIGHT + 20;
+    let line_height = 26isize;
+    use core::fmt::Write;
+
+    draw_stats_line(app, gam, y, &fill(s.stats_margins_title, &[&s.stats_levels[level]]), true);
+    y += line_height + 8;
+
+    let top = counts.iter().copied().max().unwrap_or(0).max(1) as isize;
+    let left = 170isize;
+    let width = app.screensize.x - left - 56;
+    for (label, &count) in s.stats_margin_buckets.iter().zip(counts) {
+        let mut tv = TextView::new(gid, TextBounds::GrowableFromTl(Point::new(20, y), 150));
+        tv.style = GlyphStyle::Small;
+        write!(tv.text, "{}", label).ok();
+        gam.post_textview(&mut tv).ok();
+
+        let length = width * count as isize / top;
+        if length > 0 {
+            gam.draw_rectangle(
+                gid,
+                Rectangle::new_with_style(
+                    Point::new(left, y + 3),
+                    Point::new(left + length, y + line_height - 9),
+                    DrawStyle::new(PixelColor::Dark, PixelColor::Dark, 1),
+                ),
+            )
+            .ok();
+        }
+
+        let mut tv = TextView::new(gid, TextBounds::GrowableFromTl(Point::new(left + length + 6, y), 48));
+        tv.style = GlyphStyle::Small;
+        write!(tv.text, "{}", count).ok();
+        gam.post_textview(&mut tv).ok();
+        y += line_height;
+    }
+
+    let mut tv = TextView::new(
+        gid,
+        TextBounds::GrowableFromTl(Point::new(16, app.screensize.y - FOOTER_HEIGHT - 30), text_width(app, 16, 0)),
+    );
+    tv.style = GlyphStyle::Small;
+    write!(tv.text, "{}", s.stats_margins_keys).ok();
+    gam.post_textview(&mut tv).ok();
+}
+
+/// Most openings listed as best, and again as worst
+const OPENINGS_SHOWN: usize = 3;
+
+/// Draw the named openings the player does best and worst with
+fn draw_openings(app: &OthelloApp, gam: &Gam) {
+    let s = app.text();
+    draw_header(app, gam, s.title_statistics, 0, 0);
+    draw_footer(app, gam);
+
+    let gid = app.gid;
+    let mut y = HEADER_HEIGHT + 20;
+    let line_height = 22isize;
+    use core::fmt::Write;
+
+    draw_stats_line(app, gam, y, s.stats_openings_title, true);
+    y += line_height * 2;
+
+    // Score in thousandths, a draw counting half a win
+    let mut played: Vec<(usize, u32, u32)> = app
+        .stats
+        .openings
+        .iter()
+        .enumerate()
+        .filter_map(|(index, &[won, lost, drawn])| {
+            let games = (won + lost + drawn) as u32;
+            (games > 0).then(|| (index, games, (2 * won as u32 + drawn as u32) * 500 / games))
+        })
+        .collect();
+    played.sort_by(|a, b| b.2.cmp(&a.2).then(b.1.cmp(&a.1)));
+
+    if played.is_empty() {
+        draw_stats_line(app, gam, y, s.stats_openings_none, false);
+    }
+
+    // Worst listed worst first, without repeating any of the best
+    let best: Vec<_> = played.iter().take(OPENINGS_SHOWN).collect();
+    let worst: Vec<_> = played.iter().skip(best.len()).rev().take(OPENINGS_SHOWN).collect();
+    for (heading, openings) in [(s.stats_openings_best, best), (s.stats_openings_worst, worst)] {
+        if openings.is_empty() {
+            continue;
+        }
+        draw_stats_line(app, gam, y, heading, true);
+        y += line_height;
+        for &(index, games, score) in openings {
+            let name = NAMED_OPENINGS[index].0;
+            draw_stats_line(app, gam, y, &fill(s.stats_opening_line, &[&name, &games, &((score + 5) / 10)]), false);
+            y += line_height;
+        }
+        y += line_height;
+    }
+
+    let mut tv = TextView::new(
+        gid,
+        TextBounds::GrowableFromTl(Point::new(16, app.screensize.y - FOOTER_HEIGHT - 30), text_width(app, 16, 0)),
+    );
+    tv.style = GlyphStyle::Small;
+    write!(tv.text, "{}", s.stats_openings_keys).ok();
+    gam.post_textview(&mut tv).ok();
+}
+
+/// Draw the time spent thinking, with each CPU level's share of the battery
+fn draw_thinking(app: &OthelloApp, gam: &Gam) {
+    let s = app.text();
+    draw_header(app, gam, s.title_statistics, 0, 0);
+    draw_footer(app, gam);
+
+    let gid = app.gid;
+    let stats = &app.stats;
+    let mut y = HEADER_HEIGHT + 20;
+    let line_height = 22isize;
+    use core::fmt::Write;
+
+    draw_stats_line(app, gam, y, s.stats_thinking_title, true);
+    y += line_height + 8;
+    draw_stats_line(app, gam, y, &fill(s.stats_player_time, &[&clock(stats.player_secs)]), false);
+    y += line_height + 8;
+
+    for (heading, thinking) in s.stats_levels.iter().zip(&stats.thinking) {
+        draw_stats_line(app, gam, y, heading, true);
+        y += line_height;
+        let line = match (thinking.ms_per_move(), thinking.charge_per_game()) {
+            (Some(ms), Some(charge)) => {
+                let per_move = format!("{}.{}", ms / 1000, ms % 1000 / 100);
+                let per_game = format!("{}.{:02}", charge / 100, charge % 100);
+                fill(s.stats_thinking_line, &[&per_move, &per_game])
+            }
+            _ => String::from(s.stats_thinking_none),
+        };
+        draw_stats_line(app, gam, y, &line, false);
+        y += line_height + 4;
+    }
+
+    let mut tv = TextView::new(gid, TextBounds::GrowableFromTl(Point::new(20, y), text_width(app, 20, 16)));
+    tv.style = GlyphStyle::Small;
+    write!(tv.text, "{}", fill(s.stats_thinking_note, &[&SEARCH_MA, &BATTERY_MAH])).ok();
+    gam.post_textview(&mut tv).ok();
+
+    let mut tv = TextView::new(
+        gid,
+        TextBounds::GrowableFromTl(Point::new(16, app.screensize.y - FOOTER_HEIGHT - 30), text_width(app, 16, 0)),
+    );
+    tv.style = GlyphStyle::Small;
+    write!(tv.text, "{}", s.stats_thinking_keys).ok();
+    gam.post_textview(&mut tv).ok();
+}
+
+fn draw_stats_line(app: &OthelloApp, gam: &Gam, y: isize, text: &str, bold: bool) {
+    let mut tv = TextView::new(
+        app.gid,
+        TextBounds::GrowableFromTl(Point::new(20, y), text_width(app, 20, 16)),
     );
     tv.style = if bold { GlyphStyle::Bold } else { GlyphStyle::Regular };
     use core::fmt::Write;
@@ -482,15 +1132,21 @@ fn draw_playing(
     app: &OthelloApp,
     gam: &Gam,
     game: &othello_core::GameState,
+    ply: &PlyInfo,
     mode: GameMode,
     player_color: Player,
     cursor_pos: (u8, u8),
     ai_thinking: bool,
     thinking_dots: u8,
     show_pass_notice: bool,
+    notice: Option<&str>,
+    meta: &GameMeta,
+    hidden: u64,
+    handover: bool,
 ) {
+    let s = app.text();
     let (black, white) = game.counts();
-    draw_header(app, gam, "OTHELLO", black, white);
+    draw_header(app, gam, s.title_app, black, white);
     draw_footer(app, gam);
 
     // Get last move position
@@ -500,72 +1156,265 @@ fn draw_playing(
         app,
         gam,
         game.board(),
-        Some(cursor_pos),
-        app.settings.show_valid_moves,
-        game.current_player(),
+        // The cursor shows where the previous player was looking
+        if handover { None } else { Some(cursor_pos) },
+        // Move dots would give the position away
+        if app.settings.show_valid_moves && hidden == 0 && !handover { ply.legal } else { 0 },
         last_move,
+        hidden,
     );
+    if !handover {
+        draw_frontier_marks(app, gam, ply.frontier, hidden);
+    }
+
+    // Scores are from the player's side, or Black's between two players
+    let side = mode.scoring_side(player_color);
+
+    // Whose turn it is, or what the game is waiting for
+    let current = game.current_player();
+    let disc = if current == Player::Black { "\u{25CF}" } else { "\u{25CB}" };
+    let turn = if ai_thinking {
+        let dots = ".".repeat((thinking_dots + 1) as usize);
+        format!("{}{}", s.cpu_thinking, dots)
+    } else if handover {
+        fill(s.hand_over, &[&s.player(current), &disc])
+    } else if show_pass_notice {
+        String::from(s.no_legal_moves)
+    } else if let Some(notice) = notice {
+        String::from(notice)
+    } else {
+        match mode {
+            GameMode::VsCpu(_) | GameMode::Adaptive => {
+                let template = if current == player_color { s.your_move } else { s.cpu_move };
+                fill(template, &[&disc])
+            }
+            GameMode::TwoPlayer => fill(s.side_move, &[&s.player(current), &disc]),
+        }
+    };
+
+    if side_panel(app) {
+        let show_coords = app.settings.show_coordinates || hidden != 0;
+        let left = board_origin(app, show_coords).x + cell_size(app, show_coords) * 8 + BOARD_MARGIN;
+        draw_side_panel(app, gam, game, ply, mode, player_color, side, &turn, meta, handover, left);
+        return;
+    }
+
+    if meta.timed() {
+        // The CPU has no clock, so against it the player's is shown throughout
+        let clock_side = match mode {
+            GameMode::VsCpu(_) | GameMode::Adaptive => player_color,
+            GameMode::TwoPlayer => current,
+        };
+        let show_coords = app.settings.show_coordinates;
+        let top = board_origin(app, show_coords).y + cell_size(app, show_coords) * 8 + 8;
+        draw_game_clock(app, gam, clock_side, meta.time_left(clock_side), Point::new(app.screensize.x / 2 - 60, top), true);
+    }
 
-    // Status area
-    let status_y = app.screensize.y - FOOTER_HEIGHT - 60;
+    // Status area, sized to the chosen text
+    let detail = detail_style(app);
+    let body = body_style(app);
+    let status_y = app.screensize.y - FOOTER_HEIGHT - row_height(detail) - row_height(body) - 19;
     let gid = app.gid;
 
-    // Mobility info
-    let black_moves = othello_core::count_moves(game.board(), Player::Black);
-    let white_moves = othello_core::count_moves(game.board(), Player::White);
+    let line = |index: usize| {
+        if handover {
+            return String::new();
+        }
+        StatusItem::ALL
+            .iter()
+            .filter(|&&item| app.settings.status_line(item) == Some(index))
+            .filter_map(|&item| status_item(app, item, game, ply, side))
+            .collect::<Vec<_>>()
+            .join("  ")
+    };
 
     let mut tv = TextView::new(
         gid,
-        TextBounds::GrowableFromTl(Point::new(16, status_y), 320),
+        TextBounds::GrowableFromTl(Point::new(16, status_y), text_width(app, 16, 0)),
     );
-    tv.style = GlyphStyle::Small;
+    tv.style = detail;
     use core::fmt::Write;
-
-    let last_str = if let Some(pos) = last_move {
-        if pos < 64 {
-            let alg = pos_to_algebraic(pos);
-            core::str::from_utf8(&alg).unwrap_or("--").to_string()
-        } else {
-            "Pass".to_string()
-        }
-    } else {
-        "--".to_string()
-    };
-    write!(tv.text, "\u{25CF} {} moves  \u{25CB} {} moves  Last: {}", black_moves, white_moves, last_str).ok();
+    write!(tv.text, "{}", line(0)).ok();
     gam.post_textview(&mut tv).ok();
 
     // Turn indicator
     let mut tv = TextView::new(
         gid,
-        TextBounds::GrowableFromTl(Point::new(16, status_y + 20), 320),
+        TextBounds::GrowableFromTl(Point::new(16, status_y + row_height(detail) + 1), text_width(app, 16, 0)),
     );
-    tv.style = GlyphStyle::Regular;
+    tv.style = body;
+    write!(tv.text, "{}", turn).ok();
+    let extra = line(1);
+    if !extra.is_empty() {
+        write!(tv.text, "  {}", extra).ok();
+    }
+    gam.post_textview(&mut tv).ok();
+}
 
-    if ai_thinking {
-        let dots = ".".repeat((thinking_dots + 1) as usize);
-        write!(tv.text, "CPU thinking{}", dots).ok();
-    } else if show_pass_notice {
-        write!(tv.text, "No legal moves! Pass to opponent").ok();
-    } else {
-        let current = game.current_player();
-        let disc = if current == Player::Black { "\u{25CF}" } else { "\u{25CB}" };
-        match mode {
-            GameMode::VsCpu(_) => {
-                if current == player_color {
-                    write!(tv.text, "Your move ({})", disc).ok();
-                } else {
-                    write!(tv.text, "CPU's move ({})", disc).ok();
-                }
-            }
-            GameMode::TwoPlayer => {
-                let color = if current == Player::Black { "Black" } else { "White" };
-                write!(tv.text, "{}'s move ({})", color, disc).ok();
-            }
+/// Draw the panel beside the board on a wide canvas, from `left` to the
+/// right edge: whose turn it is, the clocks, each chosen status item on a
+/// row of its own, then as much of the move list as fits, latest last
+fn draw_side_panel(
+    app: &OthelloApp,
+    gam: &Gam,
+    game: &othello_core::GameState,
+    ply: &PlyInfo,
+    mode: GameMode,
+    player_color: Player,
+    side: Player,
+    turn: &str,
+    meta: &GameMeta,
+    handover: bool,
+    left: isize,
+) {
+    let s = app.text();
+    let gid = app.gid;
+    let width = (app.screensize.x - left - BOARD_MARGIN) as u16;
+    let bottom = app.screensize.y - FOOTER_HEIGHT - 8;
+    let body = body_style(app);
+    let detail = detail_style(app);
+    let mut y = HEADER_HEIGHT + BOARD_MARGIN;
+    use core::fmt::Write;
+
+    // Two rows, as the turn can wrap in a narrow panel
+    let mut tv = TextView::new(gid, TextBounds::GrowableFromTl(Point::new(left, y), width));
+    tv.style = body;
+    write!(tv.text, "{}", turn).ok();
+    gam.post_textview(&mut tv).ok();
+    y += 2 * row_height(body) + 4;
+
+    // Both clocks between two players, only the player's against the CPU;
+    // only the running one flashes
+    if meta.timed() {
+        let sides: &[Player] = match mode {
+            GameMode::VsCpu(_) | GameMode::Adaptive => core::slice::from_ref(&player_color),
+            GameMode::TwoPlayer => &[Player::Black, Player::White],
+        };
+        for &clock_side in sides {
+            let running = clock_side == game.current_player();
+            draw_game_clock(app, gam, clock_side, meta.time_left(clock_side), Point::new(left, y), running);
+            y += 46;
+        }
+        y += 4;
+    }
+
+    if handover {
+        return;
+    }
+
+    // The move list below replaces the recent moves
+    for &item in StatusItem::ALL.iter().filter(|&&item| item != StatusItem::RecentMoves) {
+        if app.settings.status_line(item).is_none() {
+            continue;
         }
+        if let Some(text) = status_item(app, item, game, ply, side) {
+            let mut tv = TextView::new(gid, TextBounds::GrowableFromTl(Point::new(left, y), width));
+            tv.style = detail;
+            write!(tv.text, "{}", text).ok();
+            gam.post_textview(&mut tv).ok();
+            y += row_height(detail);
+        }
+    }
+    y += 8;
+
+    let mut tv = TextView::new(gid, TextBounds::GrowableFromTl(Point::new(left, y), width));
+    tv.style = GlyphStyle::Bold;
+    write!(tv.text, "{}", s.panel_moves).ok();
+    gam.post_textview(&mut tv).ok();
+    y += row_height(GlyphStyle::Bold);
+
+    let history = game.history();
+    let name = |index: usize| match history.get(index) {
+        Some(entry) if entry.is_pass() => String::from(s.pass),
+        Some(entry) => square(entry.pos),
+        None => String::new(),
+    };
+    let row = row_height(detail);
+    let rows = ((bottom - y) / row).max(0) as usize;
+    let pairs = history.len().div_ceil(2);
+    for (line, pair) in (pairs.saturating_sub(rows)..pairs).enumerate() {
+        let mut tv = TextView::new(gid, TextBounds::GrowableFromTl(Point::new(left, y + line as isize * row), width));
+        tv.style = detail;
+        write!(tv.text, "{:2}. {:<6}{}", pair + 1, name(2 * pair), name(2 * pair + 1)).ok();
+        gam.post_textview(&mut tv).ok();
     }
+}
+
+/// Draw a player's game clock in a box from `at`, flashing when a running
+/// clock is low
+fn draw_game_clock(app: &OthelloApp, gam: &Gam, player: Player, left: u16, at: Point, running: bool) {
+    let mut tv = TextView::new(
+        app.gid,
+        TextBounds::BoundingBox(Rectangle::new_coords(at.x, at.y, at.x + 120, at.y + 38)),
+    );
+    tv.style = GlyphStyle::Large;
+    tv.draw_border = true;
+    tv.invert = running && left < LOW_TIME_SECS && left % 2 == 0;
+    let disc = if player == Player::Black { "\u{25CF}" } else { "\u{25CB}" };
+    use core::fmt::Write;
+    write!(tv.text, "{} {}", disc, clock(left as u32)).ok();
     gam.post_textview(&mut tv).ok();
 }
 
+/// Text for one status item, if it has anything to show
+fn status_item(app: &OthelloApp, item: StatusItem, game: &othello_core::GameState, ply: &PlyInfo, side: Player) -> Option<String> {
+    let s = app.text();
+    let board = game.board();
+    match item {
+        StatusItem::Mobility => Some(fill(s.status_moves, &[&ply.mobility.0, &ply.mobility.1])),
+        StatusItem::LastMove => Some(fill(s.status_last, &[&ply.last_move])),
+        StatusItem::Evaluation => Some(fill(s.status_eval, &[&format!("{:+}", ply.eval)])),
+        StatusItem::Clock => {
+            let minutes = crate::feedback::local_time_ms()? / 60_000 % (24 * 60);
+            Some(format!("{:02}:{:02}", minutes / 60, minutes % 60))
+        }
+        StatusItem::DiscDiff => {
+            let diff = board.count(side) as i32 - board.count(side.opponent()) as i32;
+            Some(fill(s.status_diff, &[&format!("{:+}", diff)]))
+        }
+        StatusItem::RecentMoves => {
+            let history = game.history();
+            let moves: Vec<String> = history[history.len().saturating_sub(RECENT_MOVES)..]
+                .iter()
+                .map(|entry| {
+                    if entry.is_pass() {
+                        "--".to_string()
+                    } else {
+                        let alg = pos_to_algebraic(entry.pos);
+                        core::str::from_utf8(&alg).unwrap_or("??").to_string()
+                    }
+                })
+                .collect();
+            let moves = if moves.is_empty() { "--".to_string() } else { moves.join(" ") };
+            Some(fill(s.status_recent, &[&moves]))
+        }
+        StatusItem::Engine => {
+            let (report, ms) = app.last_search()?;
+            let reached = match report.method {
+                SearchMethod::Forced => return Some(String::from(s.engine_forced)),
+                SearchMethod::Book => return Some(String::from(s.engine_book)),
+                SearchMethod::Search => fill(s.engine_depth, &[&report.depth]),
+                SearchMethod::Outcome => fill(s.engine_wld, &[&report.depth]),
+                SearchMethod::Exact => fill(s.engine_exact, &[&report.depth]),
+            };
+            let secs = format!("{}.{}", ms / 1000, ms % 1000 / 100);
+            Some(fill(s.status_engine, &[&reached, &compact_count(report.nodes), &secs, &report.table_hit_percent()]))
+        }
+    }
+}
+
+/// A count cut to three figures or so, with k or M past four digits
+fn compact_count(n: u32) -> String {
+    match n {
+        0..=9_999 => n.to_string(),
+        10_000..=999_999 => format!("{}k", n / 1_000),
+        _ => format!("{}.{}M", n / 1_000_000, n % 1_000_000 / 100_000),
+    }
+}
+
+/// Moves listed by the recent moves status item
+const RECENT_MOVES: usize = 6;
+
 /// Draw game over state
 fn draw_game_over(
     app: &OthelloApp,
@@ -573,24 +1422,32 @@ fn draw_game_over(
     game: &othello_core::GameState,
     mode: GameMode,
     player_color: Player,
+    meta: &GameMeta,
+    review: &GameReview,
+    archived: bool,
 ) {
+    let s = app.text();
     let (black, white) = game.counts();
-    draw_header(app, gam, "GAME OVER", black, white);
+    draw_header(app, gam, s.title_game_over, black, white);
     draw_footer(app, gam);
 
-    draw_board(app, gam, game.board(), None, false, Player::Black, None);
+    draw_board(app, gam, game.board(), None, 0, None, 0);
+    draw_frontier(app, gam, game.board(), 0);
 
     // Result box
     let gid = app.gid;
     let center_x = app.screensize.x / 2;
     let box_y = app.screensize.y / 2 - 30;
+    // A handicap game gets a line saying which
+    let handicapped = meta.handicap != Handicap::Even;
+    let box_height = if handicapped { 120 } else { 100 };
 
     // Draw result box background
     gam.draw_rectangle(
         gid,
         Rectangle::new_with_style(
             Point::new(center_x - 100, box_y),
-            Point::new(center_x + 100, box_y + 80),
+            Point::new(center_x + 100, box_y + box_height),
             DrawStyle::new(PixelColor::Dark, PixelColor::Light, 2),
         ),
     )
@@ -598,24 +1455,30 @@ fn draw_game_over(
 
     // Result text
     let result_text = if let Some(result) = game.result() {
-        match mode {
-            GameMode::VsCpu(_) => {
-                match result.winner() {
-                    Some(winner) if winner == player_color => "YOU WIN!",
-                    Some(_) => "CPU WINS!",
-                    None => "DRAW!",
+        match result.resigned() {
+            Some(Player::Black) if meta.out_of_time(Player::Black) => s.black_out_of_time,
+            Some(Player::White) if meta.out_of_time(Player::White) => s.white_out_of_time,
+            Some(Player::Black) => s.black_resigned,
+            Some(Player::White) => s.white_resigned,
+            None => match mode {
+                GameMode::VsCpu(_) | GameMode::Adaptive => {
+                    match result.winner() {
+                        Some(winner) if winner == player_color => s.you_win,
+                        Some(_) => s.cpu_wins,
+                        None => s.draw,
+                    }
                 }
-            }
-            GameMode::TwoPlayer => {
-                match result.winner() {
-                    Some(Player::Black) => "BLACK WINS!",
-                    Some(Player::White) => "WHITE WINS!",
-                    None => "DRAW!",
+                GameMode::TwoPlayer => {
+                    match result.winner() {
+                        Some(Player::Black) => s.black_wins,
+                        Some(Player::White) => s.white_wins,
+                        None => s.draw,
+                    }
                 }
-            }
+            },
         }
     } else {
-        "GAME OVER"
+        s.title_game_over
     };
 
     let mut tv = TextView::new(
@@ -636,16 +1499,100 @@ fn draw_game_over(
     write!(tv.text, "\u{25CF} {}  -  \u{25CB} {}", black, white).ok();
     gam.post_textview(&mut tv).ok();
 
+    // Assistance used
+    let mut tv = TextView::new(
+        gid,
+        TextBounds::BoundingBox(Rectangle::new_coords(center_x - 90, box_y + 72, center_x + 90, box_y + 95)),
+    );
+    tv.style = GlyphStyle::Small;
+    write!(tv.text, "{}", fill(s.assistance, &[&meta.hints, &meta.undos])).ok();
+    if meta.peeks > 0 {
+        write!(tv.text, "{}", fill(s.peeks, &[&meta.peeks])).ok();
+    }
+    gam.post_textview(&mut tv).ok();
+
+    if handicapped {
+        let mut tv = TextView::new(
+            gid,
+            TextBounds::BoundingBox(Rectangle::new_coords(center_x - 90, box_y + 95, center_x + 90, box_y + 117)),
+        );
+        tv.style = GlyphStyle::Small;
+        write!(tv.text, "{}", meta.handicap.label(mode, s)).ok();
+        gam.post_textview(&mut tv).ok();
+    }
+
+    // Key moments, under the board
+    let show_coords = app.settings.show_coordinates;
+    let board_bottom = board_origin(app, show_coords).y + cell_size(app, show_coords) * 8;
+    let accuracy = |player| match review.accuracy(player) {
+        Some(percent) => format!("{}%", percent),
+        None => String::from("-"),
+    };
+    let mut tv = TextView::new(
+        gid,
+        TextBounds::GrowableFromTl(Point::new(16, board_bottom + 6), text_width(app, 16, 0)),
+    );
+    tv.style = GlyphStyle::Small;
+    write!(
+        tv.text,
+        "{}   {}",
+        fill(s.review_accuracy, &[&accuracy(Player::Black), &accuracy(Player::White)]),
+        fill(s.review_corners, &[&review.corners(Player::Black), &review.corners(Player::White)]),
+    )
+    .ok();
+    if let Some(swing) = review.swing() {
+        write!(tv.text, "\n{}", fill(s.review_swing, &[&swing.discs, &square(swing.pos), &(swing.index + 1)])).ok();
+    }
+    if !review.is_complete() {
+        let (done, total) = review.progress();
+        write!(tv.text, "\n{}", fill(s.reviewing, &[&done, &total])).ok();
+    } else if let Some(mistake) = crate::app::shown_mistake(review, mode, player_color) {
+        write!(
+            tv.text,
+            "\n{}",
+            fill(s.review_mistake, &[&(mistake.index + 1), &square(mistake.played), &square(mistake.best)]),
+        )
+        .ok();
+    } else {
+        write!(tv.text, "\n{}", s.review_no_mistake).ok();
+    }
+    let player_time = clock(meta.player_secs);
+    match mode {
+        GameMode::VsCpu(_) | GameMode::Adaptive => {
+            write!(tv.text, "\n{}", fill(s.game_over_time, &[&player_time, &clock(meta.cpu_ms / 1000)])).ok();
+        }
+        GameMode::TwoPlayer => {
+            write!(tv.text, "\n{}", fill(s.game_over_time_shared, &[&player_time])).ok();
+        }
+    }
+    write!(tv.text, "\n{}", if archived { s.game_over_archived } else { s.game_over_archive_key }).ok();
+    gam.post_textview(&mut tv).ok();
+
     // Instructions
     let mut tv = TextView::new(
         gid,
-        TextBounds::GrowableFromTl(Point::new(16, app.screensize.y - FOOTER_HEIGHT - 30), 320),
+        TextBounds::GrowableFromTl(Point::new(16, app.screensize.y - FOOTER_HEIGHT - 30), text_width(app, 16, 0)),
     );
     tv.style = GlyphStyle::Small;
-    write!(tv.text, "Enter: New Game   W: What If   N: Mode").ok();
+    write!(tv.text, "{}", s.game_over_keys).ok();
     gam.post_textview(&mut tv).ok();
 }
 
+/// A duration such as "2:05", or "1:02:05" from an hour up
+fn clock(secs: u32) -> String {
+    if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+    } else {
+        format!("{}:{:02}", secs / 60, secs % 60)
+    }
+}
+
+/// Name of a square, such as "D3"
+fn square(pos: Position) -> String {
+    let alg = pos_to_algebraic(pos);
+    String::from(core::str::from_utf8(&alg).unwrap_or("--"))
+}
+
 /// Draw What If mode
 fn draw_what_if(
     app: &OthelloApp,
@@ -655,21 +1602,30 @@ fn draw_what_if(
     view_index: usize,
     branched: bool,
     cursor_pos: (u8, u8),
+    graph: ReviewGraph,
+    bookmarks: usize,
+    notice: Option<&str>,
 ) {
-    let title = if branched { "WHAT IF (BRANCHED)" } else { "WHAT IF" };
+    let s = app.text();
+    let title = if branched { s.title_what_if_branched } else { s.title_what_if };
     let (black, white) = current_game.counts();
     draw_header(app, gam, title, black, white);
     draw_footer(app, gam);
 
-    draw_board(
-        app,
-        gam,
-        current_game.board(),
-        if branched { Some(cursor_pos) } else { None },
-        branched && app.settings.show_valid_moves,
-        current_game.current_player(),
-        None,
-    );
+    if graph == ReviewGraph::Off {
+        draw_board(
+            app,
+            gam,
+            current_game.board(),
+            if branched { Some(cursor_pos) } else { None },
+            if branched && app.settings.show_valid_moves { current_game.legal_moves_bitboard() } else { 0 },
+            None,
+            0,
+        );
+        draw_frontier(app, gam, current_game.board(), 0);
+    } else {
+        draw_review_graph(app, gam, base_game, view_index, graph);
+    }
 
     // Navigation info
     let gid = app.gid;
@@ -677,87 +1633,923 @@ fn draw_what_if(
 
     let mut tv = TextView::new(
         gid,
-        TextBounds::GrowableFromTl(Point::new(16, status_y), 320),
+        TextBounds::GrowableFromTl(Point::new(16, status_y), text_width(app, 16, 0)),
     );
     tv.style = GlyphStyle::Small;
     use core::fmt::Write;
-    write!(tv.text, "Move {}/{}  Empty: {}", view_index, base_game.move_count(), current_game.empty_count()).ok();
+    write!(tv.text, "{}", fill(s.what_if_position, &[&view_index, &base_game.move_count(), &current_game.empty_count(), &bookmarks])).ok();
+    // How the move that led here was judged
+    let annotation = view_index.checked_sub(1).and_then(|index| app.shown_review()?.annotation(index));
+    if let Some(annotation) = annotation.filter(|_| !branched) {
+        write!(tv.text, "  {}", annotation.symbol()).ok();
+    }
     gam.post_textview(&mut tv).ok();
 
     let mut tv = TextView::new(
         gid,
-        TextBounds::GrowableFromTl(Point::new(16, status_y + 18), 320),
+        TextBounds::GrowableFromTl(Point::new(16, status_y + 18), text_width(app, 16, 0)),
     );
     tv.style = GlyphStyle::Small;
-    if branched {
-        write!(tv.text, "Playing alternate timeline...").ok();
+    if let Some(notice) = notice {
+        write!(tv.text, "{}", notice).ok();
+    } else if branched {
+        write!(tv.text, "{}", s.what_if_branched).ok();
     } else {
-        write!(tv.text, "Left/Right: Navigate  Enter: Branch").ok();
+        write!(tv.text, "{}", s.what_if_keys).ok();
     }
     gam.post_textview(&mut tv).ok();
 }
 
-/// Draw move history
-fn draw_history(
+/// Draw both sides' mobility or frontier across the game where the board goes
+///
+/// Black is the thick line, White the thin one; the vertical line marks the
+/// position being viewed.
+fn draw_review_graph(app: &OthelloApp, gam: &Gam, game: &othello_core::GameState, view_index: usize, graph: ReviewGraph) {
+    let s = app.text();
+    let gid = app.gid;
+    let origin = board_origin(app, false);
+    let board_size = cell_size(app, false) * 8;
+    use core::fmt::Write;
+
+    let values: Vec<(u32, u32)> = game
+        .boards()
+        .map(|board| match graph {
+            ReviewGraph::Frontier => (count_frontier(&board, Player::Black), count_frontier(&board, Player::White)),
+            _ => (count_moves(&board, Player::Black), count_moves(&board, Player::White)),
+        })
+        .collect();
+    let top = values.iter().map(|&(b, w)| b.max(w)).max().unwrap_or(0).max(1) as isize;
+
+    let mut tv = TextView::new(gid, TextBounds::GrowableFromTl(origin, board_size as u16));
+    tv.style = GlyphStyle::Bold;
+    let name = if graph == ReviewGraph::Frontier { s.graph_frontier } else { s.graph_mobility };
+    write!(tv.text, "{}", name).ok();
+    gam.post_textview(&mut tv).ok();
+
+    let mut tv = TextView::new(gid, TextBounds::GrowableFromTl(Point::new(origin.x, origin.y + 22), board_size as u16));
+    tv.style = GlyphStyle::Small;
+    write!(tv.text, "{}", s.graph_legend).ok();
+    gam.post_textview(&mut tv).ok();
+
+    // Plot area, with room for the axis labels
+    let left = origin.x + 24;
+    let right = origin.x + board_size - 4;
+    let upper = origin.y + 48;
+    let lower = origin.y + board_size - 20;
+    gam.draw_rectangle(
+        gid,
+        Rectangle::new_with_style(
+            Point::new(left, upper),
+            Point::new(right, lower),
+            DrawStyle::new(PixelColor::Light, PixelColor::Dark, 1),
+        ),
+    )
+    .ok();
+
+    let last = values.len().saturating_sub(1).max(1) as isize;
+    let x_at = |index: usize| left + (right - left) * index as isize / last;
+    let y_at = |value: u32| lower - (lower - upper) * value as isize / top;
+
+    for (index, pair) in values.windows(2).enumerate() {
+        for (from, to, width) in [(pair[0].0, pair[1].0, 3), (pair[0].1, pair[1].1, 1)] {
+            gam.draw_line(
+                gid,
+                Line::new_with_style(
+                    Point::new(x_at(index), y_at(from)),
+                    Point::new(x_at(index + 1), y_at(to)),
+                    DrawStyle::new(PixelColor::Dark, PixelColor::Dark, width),
+                ),
+            )
+            .ok();
+        }
+    }
+
+    let marker = x_at(view_index.min(values.len().saturating_sub(1)));
+    gam.draw_line(
+        gid,
+        Line::new_with_style(
+            Point::new(marker, upper),
+            Point::new(marker, lower),
+            DrawStyle::new(PixelColor::Dark, PixelColor::Dark, 1),
+        ),
+    )
+    .ok();
+
+    // Axis labels: scale on the left, move numbers underneath
+    let labels = [
+        (Point::new(origin.x, upper - 2), top.to_string()),
+        (Point::new(origin.x, lower - 14), "0".to_string()),
+        (Point::new(left, lower + 2), "0".to_string()),
+        (Point::new(right - 20, lower + 2), game.move_count().to_string()),
+    ];
+    for (at, label) in labels {
+        let mut tv = TextView::new(gid, TextBounds::GrowableFromTl(at, 24));
+        tv.style = GlyphStyle::Small;
+        write!(tv.text, "{}", label).ok();
+        gam.post_textview(&mut tv).ok();
+    }
+}
+
+/// Draw a tutorial lesson
+fn draw_tutorial(
     app: &OthelloApp,
     gam: &Gam,
+    lesson: usize,
     game: &othello_core::GameState,
-    scroll_offset: usize,
+    cursor_pos: (u8, u8),
+    solved: bool,
+    message: Option<&str>,
 ) {
+    let s = app.text();
+    let current = &crate::tutorial::LESSONS[lesson];
     let (black, white) = game.counts();
-    draw_header(app, gam, "MOVE HISTORY", black, white);
+    let title = fill(s.title_lesson, &[&(lesson + 1), &crate::tutorial::LESSONS.len()]);
+    draw_header(app, gam, &title, black, white);
     draw_footer(app, gam);
 
     let gid = app.gid;
-    let start_y = HEADER_HEIGHT + 30;
-    let history = game.history();
+    use core::fmt::Write;
 
-    // Column headers
+    // Lesson name above the board
     let mut tv = TextView::new(
         gid,
-        TextBounds::GrowableFromTl(Point::new(20, start_y), 300),
+        TextBounds::GrowableFromTl(Point::new(16, HEADER_HEIGHT + 8), text_width(app, 16, 20)),
     );
     tv.style = GlyphStyle::Bold;
-    use core::fmt::Write;
-    write!(tv.text, " #   \u{25CF}        \u{25CB}").ok();
+    write!(tv.text, "{}", current.title).ok();
     gam.post_textview(&mut tv).ok();
 
-    // Move pairs
-    let mut line = 1;
-    let mut move_num = 1 + scroll_offset;
-    let mut i = scroll_offset * 2;
-
-    while i < history.len() && line < 15 {
-        let black_move = if i < history.len() {
-            let entry = &history[i];
-            if entry.is_pass() {
-                "--".to_string()
-            } else {
-                let alg = pos_to_algebraic(entry.pos);
-                core::str::from_utf8(&alg).unwrap_or("??").to_string()
+    let last_move = game.last_move().map(|m| m.pos);
+    draw_board(
+        app,
+        gam,
+        game.board(),
+        if solved { None } else { Some(cursor_pos) },
+        if solved { 0 } else { game.legal_moves_bitboard() },
+        last_move,
+        0,
+    );
+
+    // Instructions or feedback below the board
+    let show_coords = app.settings.show_coordinates;
+    let text_top = board_origin(app, show_coords).y + cell_size(app, show_coords) * 8 + 8;
+    let mut tv = TextView::new(
+        gid,
+        TextBounds::BoundingBox(Rectangle::new_coords(
+            16,
+            text_top,
+            app.screensize.x - 16,
+            app.screensize.y - FOOTER_HEIGHT - 4,
+        )),
+    );
+    tv.style = GlyphStyle::Small;
+    write!(tv.text, "{}", message.unwrap_or(current.prompt)).ok();
+    if solved {
+        let next = if lesson + 1 < crate::tutorial::LESSONS.len() { s.lesson_next } else { s.lesson_finish };
+        write!(tv.text, "\n\n{}", next).ok();
+    }
+    gam.post_textview(&mut tv).ok();
+}
+
+/// Draw the position editor
+fn draw_editor(
+    app: &OthelloApp,
+    gam: &Gam,
+    board: &Board,
+    to_move: Player,
+    cursor_pos: (u8, u8),
+    message: Option<&str>,
+) {
+    let s = app.text();
+    draw_header(app, gam, s.title_setup, board.count(Player::Black), board.count(Player::White));
+    draw_footer(app, gam);
+
+    let gid = app.gid;
+    use core::fmt::Write;
+
+    draw_board(app, gam, board, Some(cursor_pos), othello_core::legal_moves_bitboard(board, to_move), None, 0);
+
+    let show_coords = app.settings.show_coordinates;
+    let text_top = board_origin(app, show_coords).y + cell_size(app, show_coords) * 8 + 8;
+    let mut tv = TextView::new(
+        gid,
+        TextBounds::GrowableFromTl(Point::new(16, text_top), text_width(app, 16, 0)),
+    );
+    tv.style = GlyphStyle::Regular;
+    match message {
+        Some(message) => write!(tv.text, "{}", message).ok(),
+        None => write!(tv.text, "{}", fill(s.to_move, &[&s.player(to_move)])).ok(),
+    };
+    gam.post_textview(&mut tv).ok();
+
+    let mut tv = TextView::new(
+        gid,
+        TextBounds::GrowableFromTl(Point::new(16, text_top + 22), text_width(app, 16, 0)),
+    );
+    tv.style = GlyphStyle::Small;
+    write!(tv.text, "{}", s.editor_keys).ok();
+    gam.post_textview(&mut tv).ok();
+}
+
+/// Format a search score: a proven result, or the evaluation
+fn format_score(app: &OthelloApp, m: &MoveScore) -> String {
+    if m.is_won() {
+        String::from(app.text().score_win)
+    } else if m.is_lost() {
+        String::from(app.text().score_loss)
+    } else {
+        format!("{:+}", m.score)
+    }
+}
+
+/// Draw the analysis board
+fn draw_analysis(
+    app: &OthelloApp,
+    gam: &Gam,
+    board: &Board,
+    to_move: Player,
+    depth: u8,
+    result: Option<&(Analysis, Variation)>,
+    thinking: bool,
+) {
+    let s = app.text();
+    draw_header(app, gam, s.title_analysis, board.count(Player::Black), board.count(Player::White));
+    draw_footer(app, gam);
+
+    let gid = app.gid;
+    use core::fmt::Write;
+
+    // The best move gets the last-move marker
+    let best = result.and_then(|(analysis, _)| analysis.best()).map(|m| m.pos);
+    draw_board(app, gam, board, None, othello_core::legal_moves_bitboard(board, to_move), best, 0);
+
+    let show_coords = app.settings.show_coordinates;
+    let text_top = board_origin(app, show_coords).y + cell_size(app, show_coords) * 8 + 8;
+    let mut tv = TextView::new(
+        gid,
+        TextBounds::GrowableFromTl(Point::new(16, text_top), text_width(app, 16, 0)),
+    );
+    tv.style = GlyphStyle::Small;
+    write!(tv.text, "{}", fill(s.analysis_status, &[&s.player(to_move), &depth])).ok();
+    gam.post_textview(&mut tv).ok();
+
+    let mut tv = TextView::new(
+        gid,
+        TextBounds::BoundingBox(Rectangle::new_coords(
+            16,
+            text_top + 18,
+            app.screensize.x - 16,
+            app.screensize.y - FOOTER_HEIGHT - 4,
+        )),
+    );
+    tv.style = GlyphStyle::Small;
+    match result {
+        _ if thinking => {
+            write!(tv.text, "{}", s.searching).ok();
+        }
+        Some((analysis, line)) => {
+            for m in analysis.iter().take(4) {
+                let alg = pos_to_algebraic(m.pos);
+                write!(tv.text, "{} {}   ", core::str::from_utf8(&alg).unwrap_or("??"), format_score(app, m)).ok();
             }
-        } else {
-            "".to_string()
+            write!(tv.text, "\n{}", s.principal_variation).ok();
+            for &p in line.moves() {
+                if p == 255 {
+                    write!(tv.text, " --").ok();
+                } else {
+                    let alg = pos_to_algebraic(p);
+                    write!(tv.text, " {}", core::str::from_utf8(&alg).unwrap_or("??")).ok();
+                }
+            }
+        }
+        None => {
+            write!(tv.text, "{}", s.search_prompt).ok();
+        }
+    }
+    gam.post_textview(&mut tv).ok();
+}
+
+/// Draw the deep analysis progress: the position being searched, a bar and
+/// what is known so far
+fn draw_deep_analysis(
+    app: &OthelloApp,
+    gam: &Gam,
+    game: &othello_core::GameState,
+    review: &GameReview,
+    eval: Option<&MoveScore>,
+    elapsed_secs: u32,
+    running: bool,
+) {
+    let s = app.text();
+    let (done, total) = review.progress();
+    let shown = game.clone_at_move(done);
+    let (black, white) = shown.counts();
+    draw_header(app, gam, s.title_deep_analysis, black, white);
+    draw_footer(app, gam);
+
+    // The move being judged gets the last-move marker
+    let judged = game.history().get(done).filter(|entry| !entry.is_pass()).map(|entry| entry.pos);
+    draw_board(app, gam, shown.board(), None, 0, judged, 0);
+
+    let gid = app.gid;
+    let show_coords = app.settings.show_coordinates;
+    let text_top = board_origin(app, show_coords).y + cell_size(app, show_coords) * 8 + 8;
+    use core::fmt::Write;
+
+    // Progress bar
+    let bar_width = app.screensize.x - 32;
+    gam.draw_rectangle(
+        gid,
+        Rectangle::new_with_style(
+            Point::new(16, text_top),
+            Point::new(16 + bar_width, text_top + 8),
+            DrawStyle::new(PixelColor::Light, PixelColor::Dark, 1),
+        ),
+    )
+    .ok();
+    if done > 0 {
+        let filled = bar_width * done as isize / total.max(1) as isize;
+        gam.draw_rectangle(
+            gid,
+            Rectangle::new_with_style(
+                Point::new(16, text_top),
+                Point::new(16 + filled, text_top + 8),
+                DrawStyle::new(PixelColor::Dark, PixelColor::Dark, 1),
+            ),
+        )
+        .ok();
+    }
+
+    let accuracy = |player| match review.accuracy(player) {
+        Some(percent) => format!("{}%", percent),
+        None => String::from("-"),
+    };
+    let mut tv = TextView::new(
+        gid,
+        TextBounds::GrowableFromTl(Point::new(16, text_top + 14), text_width(app, 16, 0)),
+    );
+    tv.style = GlyphStyle::Small;
+    write!(tv.text, "{}   {}", fill(s.deep_progress, &[&done, &total]), fill(s.deep_elapsed, &[&clock(elapsed_secs)])).ok();
+    if let Some(eval) = eval {
+        write!(tv.text, "\n{}", fill(s.deep_eval, &[&format_score(app, eval)])).ok();
+    }
+    write!(tv.text, "\n{}", fill(s.review_accuracy, &[&accuracy(Player::Black), &accuracy(Player::White)])).ok();
+    if let Some(mistake) = review.biggest_mistake() {
+        write!(
+            tv.text,
+            "\n{}",
+            fill(s.review_mistake, &[&(mistake.index + 1), &square(mistake.played), &square(mistake.best)]),
+        )
+        .ok();
+    }
+    gam.post_textview(&mut tv).ok();
+
+    let mut tv = TextView::new(
+        gid,
+        TextBounds::GrowableFromTl(Point::new(16, app.screensize.y - FOOTER_HEIGHT - 30), text_width(app, 16, 0)),
+    );
+    tv.style = GlyphStyle::Small;
+    let keys = if review.is_complete() {
+        s.deep_done
+    } else if running {
+        s.deep_running_keys
+    } else {
+        s.deep_cancelled_keys
+    };
+    write!(tv.text, "{}", keys).ok();
+    gam.post_textview(&mut tv).ok();
+}
+
+/// Draw the notation entry screen
+fn draw_load_position(app: &OthelloApp, gam: &Gam, input: &str, status: Option<&str>, receiving: bool) {
+    let s = app.text();
+    draw_header(app, gam, s.title_load, 0, 0);
+    draw_footer(app, gam);
+
+    let gid = app.gid;
+    let start_y = HEADER_HEIGHT + 16;
+    use core::fmt::Write;
+
+    let mut tv = TextView::new(
+        gid,
+        TextBounds::BoundingBox(Rectangle::new_coords(16, start_y, app.screensize.x - 16, start_y + 100)),
+    );
+    tv.style = GlyphStyle::Small;
+    write!(tv.text, "{}", s.load_instructions).ok();
+    gam.post_textview(&mut tv).ok();
+
+    // Input line, wrapped
+    let input_y = start_y + 110;
+    gam.draw_rectangle(
+        gid,
+        Rectangle::new_with_style(
+            Point::new(12, input_y - 4),
+            Point::new(app.screensize.x - 12, input_y + 120),
+            DrawStyle::new(PixelColor::Light, PixelColor::Dark, 1),
+        ),
+    )
+    .ok();
+    let mut tv = TextView::new(
+        gid,
+        TextBounds::BoundingBox(Rectangle::new_coords(16, input_y, app.screensize.x - 16, input_y + 116)),
+    );
+    tv.style = GlyphStyle::Monospace;
+    write!(tv.text, "{}_", input).ok();
+    gam.post_textview(&mut tv).ok();
+
+    if let Some(status) = status.or(if receiving { Some(s.receiving) } else { None }) {
+        let mut tv = TextView::new(
+            gid,
+            TextBounds::GrowableFromTl(Point::new(16, input_y + 132), text_width(app, 16, 0)),
+        );
+        tv.style = GlyphStyle::Regular;
+        write!(tv.text, "{}", status).ok();
+        gam.post_textview(&mut tv).ok();
+    }
+}
+
+/// Draw the idle demo game
+fn draw_demo(app: &OthelloApp, gam: &Gam, game: &othello_core::GameState) {
+    let s = app.text();
+    let (black, white) = game.counts();
+    draw_header(app, gam, s.title_demo, black, white);
+    draw_footer(app, gam);
+
+    let last_move = game.last_move().map(|e| if e.is_pass() { 255 } else { e.pos });
+    draw_board(app, gam, game.board(), None, 0, last_move, 0);
+
+    let mut tv = TextView::new(
+        app.gid,
+        TextBounds::GrowableFromTl(Point::new(16, app.screensize.y - FOOTER_HEIGHT - 40), text_width(app, 16, 0)),
+    );
+    tv.style = GlyphStyle::Regular;
+    use core::fmt::Write;
+    write!(tv.text, "{}", s.demo_keys).ok();
+    gam.post_textview(&mut tv).ok();
+}
+
+/// Draw the backup and restore screen
+fn draw_backup(app: &OthelloApp, gam: &Gam, status: Option<&str>, receiving: bool) {
+    let s = app.text();
+    draw_header(app, gam, s.title_backup, 0, 0);
+    draw_footer(app, gam);
+
+    let gid = app.gid;
+    let start_y = HEADER_HEIGHT + 16;
+    use core::fmt::Write;
+
+    let mut tv = TextView::new(
+        gid,
+        TextBounds::BoundingBox(Rectangle::new_coords(16, start_y, app.screensize.x - 16, start_y + 240)),
+    );
+    tv.style = GlyphStyle::Regular;
+    write!(tv.text, "{}", s.backup_instructions).ok();
+    gam.post_textview(&mut tv).ok();
+
+    if let Some(status) = status.or(if receiving { Some(s.receiving) } else { None }) {
+        let mut tv = TextView::new(
+            gid,
+            TextBounds::GrowableFromTl(Point::new(16, start_y + 260), text_width(app, 16, 0)),
+        );
+        tv.style = GlyphStyle::Regular;
+        write!(tv.text, "{}", status).ok();
+        gam.post_textview(&mut tv).ok();
+    }
+}
+
+/// Draw the progress of reviewing the archived games
+fn draw_archive_review(app: &OthelloApp, gam: &Gam, finished: usize) {
+    let s = app.text();
+    draw_header(app, gam, s.title_archive_review, 0, 0);
+    draw_footer(app, gam);
+
+    let gid = app.gid;
+    let start_y = HEADER_HEIGHT + 16;
+    use core::fmt::Write;
+
+    let mut tv = TextView::new(
+        gid,
+        TextBounds::BoundingBox(Rectangle::new_coords(16, start_y, app.screensize.x - 16, start_y + 200)),
+    );
+    tv.style = GlyphStyle::Regular;
+    write!(tv.text, "{}", s.archive_review_about).ok();
+    gam.post_textview(&mut tv).ok();
+
+    let queue = app.review_queue();
+    let mut tv = TextView::new(
+        gid,
+        TextBounds::GrowableFromTl(Point::new(16, start_y + 220), text_width(app, 16, 0)),
+    );
+    tv.style = GlyphStyle::Regular;
+    if queue.is_empty() {
+        write!(tv.text, "{}", s.archive_review_done).ok();
+    } else {
+        write!(tv.text, "{}", fill(s.archive_review_left, &[&queue.len()])).ok();
+        if let Some((done, total)) = queue.progress() {
+            write!(tv.text, "\n{}", fill(s.archive_review_game, &[&done, &total])).ok();
+        }
+    }
+    write!(tv.text, "\n{}", fill(s.archive_review_finished, &[&finished])).ok();
+    gam.post_textview(&mut tv).ok();
+
+    let mut tv = TextView::new(
+        gid,
+        TextBounds::GrowableFromTl(Point::new(16, app.screensize.y - FOOTER_HEIGHT - 30), text_width(app, 16, 0)),
+    );
+    tv.style = GlyphStyle::Small;
+    write!(tv.text, "{}", s.archive_review_keys).ok();
+    gam.post_textview(&mut tv).ok();
+}
+
+/// Draw the puzzle pack list
+fn draw_puzzle_menu(
+    app: &OthelloApp,
+    gam: &Gam,
+    packs: &[String],
+    selected: usize,
+    status: Option<&str>,
+    busy: bool,
+    today: Option<u16>,
+) {
+    let s = app.text();
+    draw_header(app, gam, s.title_puzzles, 0, 0);
+    draw_footer(app, gam);
+
+    let gid = app.gid;
+    let start_y = HEADER_HEIGHT + 40;
+    use core::fmt::Write;
+
+    if packs.is_empty() {
+        let mut tv = TextView::new(
+            gid,
+            TextBounds::GrowableFromTl(Point::new(40, start_y), text_width(app, 40, 40)),
+        );
+        tv.style = GlyphStyle::Regular;
+        write!(tv.text, "{}", s.no_packs).ok();
+        gam.post_textview(&mut tv).ok();
+    }
+
+    for (i, name) in packs.iter().enumerate() {
+        let mut tv = TextView::new(
+            gid,
+            TextBounds::GrowableFromTl(Point::new(40, start_y + i as isize * 30), text_width(app, 40, 40)),
+        );
+        tv.style = if i == selected { GlyphStyle::Bold } else { GlyphStyle::Regular };
+        write!(tv.text, "{} {}", if i == selected { ">" } else { " " }, name).ok();
+        gam.post_textview(&mut tv).ok();
+    }
+
+    // Daily puzzle and streak
+    let status_y = app.screensize.y - FOOTER_HEIGHT - 40;
+    if let Some(day) = today {
+        let mut tv = TextView::new(
+            gid,
+            TextBounds::GrowableFromTl(Point::new(16, status_y - 24), text_width(app, 16, 0)),
+        );
+        tv.style = GlyphStyle::Regular;
+        let done = if app.stats.daily_last_played == day { s.daily_done } else { s.daily_ready };
+        write!(tv.text, "{}", fill(s.daily_status, &[&done, &app.stats.current_daily_streak(day)])).ok();
+        gam.post_textview(&mut tv).ok();
+    }
+
+    // Status and controls
+    let mut tv = TextView::new(
+        gid,
+        TextBounds::GrowableFromTl(Point::new(16, status_y), text_width(app, 16, 0)),
+    );
+    tv.style = GlyphStyle::Small;
+    if let Some(status) = status {
+        write!(tv.text, "{}", status).ok();
+    }
+    gam.post_textview(&mut tv).ok();
+
+    if !busy {
+        let mut tv = TextView::new(
+            gid,
+            TextBounds::GrowableFromTl(Point::new(16, status_y + 18), text_width(app, 16, 0)),
+        );
+        tv.style = GlyphStyle::Small;
+        write!(tv.text, "{}", s.puzzle_menu_keys).ok();
+        gam.post_textview(&mut tv).ok();
+    }
+}
+
+/// Pixels per cell of the archive list's thumbnails
+const ARCHIVE_THUMB_CELL: isize = 3;
+/// Left edge of the archive list's text, clear of the thumbnails
+const ARCHIVE_TEXT_X: isize = 16 + 8 * ARCHIVE_THUMB_CELL + 8;
+
+/// Draw the archive list
+fn draw_archive(
+    app: &OthelloApp,
+    gam: &Gam,
+    games: &[(String, ArchivedGame)],
+    selected: usize,
+    notice: Option<&str>,
+) {
+    let s = app.text();
+    draw_header(app, gam, s.title_archive, 0, 0);
+    draw_footer(app, gam);
+
+    let gid = app.gid;
+    let start_y = HEADER_HEIGHT + 16;
+    let keys_y = app.screensize.y - FOOTER_HEIGHT - 30;
+    // Two lines above the keys for the selected game's note
+    let note_y = keys_y - 2 * row_height(GlyphStyle::Small) - 4;
+    use core::fmt::Write;
+
+    if games.is_empty() {
+        let mut tv = TextView::new(
+            gid,
+            TextBounds::GrowableFromTl(Point::new(40, start_y + 24), text_width(app, 40, 40)),
+        );
+        tv.style = GlyphStyle::Regular;
+        write!(tv.text, "{}", s.archive_empty).ok();
+        gam.post_textview(&mut tv).ok();
+    }
+
+    // Two lines per game beside its final position, scrolled to keep the
+    // selection in view
+    let style = body_style(app);
+    let entry_height = (row_height(style) + row_height(GlyphStyle::Small)).max(8 * ARCHIVE_THUMB_CELL + 6);
+    let visible = ((note_y - start_y) / entry_height).max(1) as usize;
+    let first = selected.saturating_sub(visible - 1);
+
+    for (row, (i, (_, archived))) in games.iter().enumerate().skip(first).take(visible).enumerate() {
+        let y = start_y + row as isize * entry_height;
+        draw_thumbnail(app, gam, archived.game.board(), Point::new(16, y + 2), ARCHIVE_THUMB_CELL);
+
+        let mut tv = TextView::new(
+            gid,
+            TextBounds::GrowableFromTl(Point::new(ARCHIVE_TEXT_X, y), text_width(app, ARCHIVE_TEXT_X, 16)),
+        );
+        tv.style = match app.settings.text_size {
+            TextSize::Regular if i == selected => GlyphStyle::Bold,
+            _ => style,
         };
+        let mode = mode_label(app, archived.mode);
+        write!(tv.text, "{} {}: {}", if i == selected { ">" } else { " " }, mode, archive_result(app, archived)).ok();
+        gam.post_textview(&mut tv).ok();
 
-        let white_move = if i + 1 < history.len() {
-            let entry = &history[i + 1];
-            if entry.is_pass() {
-                "--".to_string()
-            } else {
-                let alg = pos_to_algebraic(entry.pos);
-                core::str::from_utf8(&alg).unwrap_or("??").to_string()
-            }
-        } else {
-            "".to_string()
+        let mut tv = TextView::new(
+            gid,
+            TextBounds::GrowableFromTl(Point::new(ARCHIVE_TEXT_X + 16, y + row_height(style)), text_width(app, ARCHIVE_TEXT_X + 16, 20)),
+        );
+        tv.style = GlyphStyle::Small;
+        let date = archived.date().unwrap_or_else(|| String::from(s.archive_no_date));
+        write!(tv.text, "{}   {}", date, fill(s.archive_moves, &[&archived.game.move_count()])).ok();
+        gam.post_textview(&mut tv).ok();
+    }
+
+    let note = games.get(selected).map_or("", |(_, archived)| archived.note.as_str());
+    let mut tv = TextView::new(
+        gid,
+        TextBounds::BoundingBox(Rectangle::new_coords(16, note_y, app.screensize.x - 16, keys_y - 4)),
+    );
+    tv.style = GlyphStyle::Small;
+    match notice {
+        Some(notice) => write!(tv.text, "{}", notice).ok(),
+        None if !note.is_empty() => write!(tv.text, "{}", fill(s.archive_note, &[&note])).ok(),
+        None => None,
+    };
+    gam.post_textview(&mut tv).ok();
+
+    let mut tv = TextView::new(
+        gid,
+        TextBounds::GrowableFromTl(Point::new(16, keys_y), text_width(app, 16, 0)),
+    );
+    tv.style = GlyphStyle::Small;
+    write!(tv.text, "{}", s.archive_keys).ok();
+    gam.post_textview(&mut tv).ok();
+}
+
+/// Draw the What If bookmark picker
+fn draw_bookmarks(app: &OthelloApp, gam: &Gam, bookmarks: &[Bookmark], selected: usize) {
+    let s = app.text();
+    draw_header(app, gam, s.title_bookmarks, 0, 0);
+    draw_footer(app, gam);
+
+    let gid = app.gid;
+    let start_y = HEADER_HEIGHT + 16;
+    let keys_y = app.screensize.y - FOOTER_HEIGHT - 30;
+    use core::fmt::Write;
+
+    if bookmarks.is_empty() {
+        let mut tv = TextView::new(
+            gid,
+            TextBounds::GrowableFromTl(Point::new(40, start_y + 24), text_width(app, 40, 40)),
+        );
+        tv.style = GlyphStyle::Regular;
+        write!(tv.text, "{}", s.bookmarks_empty).ok();
+        gam.post_textview(&mut tv).ok();
+    }
+
+    let style = body_style(app);
+    let visible = ((keys_y - start_y) / row_height(style)).max(1) as usize;
+    let first = selected.saturating_sub(visible - 1);
+
+    for (row, (i, bookmark)) in bookmarks.iter().enumerate().skip(first).take(visible).enumerate() {
+        let mut tv = TextView::new(
+            gid,
+            TextBounds::GrowableFromTl(Point::new(16, start_y + row as isize * row_height(style)), text_width(app, 16, 0)),
+        );
+        tv.style = match app.settings.text_size {
+            TextSize::Regular if i == selected => GlyphStyle::Bold,
+            _ => style,
         };
+        let last = bookmark.transcript.split_whitespace().last().unwrap_or(s.bookmark_start);
+        write!(tv.text, "{} {}", if i == selected { ">" } else { " " }, fill(s.bookmark_entry, &[&(i + 1), &bookmark.moves, &last])).ok();
+        if bookmark.branched {
+            write!(tv.text, "{}", s.bookmark_branch).ok();
+        }
+        gam.post_textview(&mut tv).ok();
+    }
+
+    let mut tv = TextView::new(
+        gid,
+        TextBounds::GrowableFromTl(Point::new(16, keys_y), text_width(app, 16, 0)),
+    );
+    tv.style = GlyphStyle::Small;
+    write!(tv.text, "{}", s.bookmarks_keys).ok();
+    gam.post_textview(&mut tv).ok();
+}
+
+/// Outcome of an archived game, such as "Won 40-24"
+fn archive_result(app: &OthelloApp, archived: &ArchivedGame) -> String {
+    let s = app.text();
+    let Some(result) = archived.game.result() else {
+        return String::new();
+    };
+    let (black, white) = result.counts();
+    let mut text = match (archived.mode, result.winner()) {
+        (_, None) => fill(s.archive_drawn, &[&black, &white]),
+        (GameMode::VsCpu(_) | GameMode::Adaptive, Some(winner)) if winner == archived.player_color => {
+            fill(s.archive_won, &[&black, &white])
+        }
+        (GameMode::VsCpu(_) | GameMode::Adaptive, Some(_)) => fill(s.archive_lost, &[&black, &white]),
+        (GameMode::TwoPlayer, Some(winner)) => fill(s.archive_side_won, &[&s.player(winner), &black, &white]),
+    };
+    if let Some(player) = result.resigned() {
+        text.push_str(if archived.meta.out_of_time(player) { s.archive_out_of_time } else { s.archive_resigned });
+    }
+    text
+}
+
+/// Draw a puzzle
+fn draw_puzzle(
+    app: &OthelloApp,
+    gam: &Gam,
+    puzzles: &[crate::puzzle::Puzzle],
+    index: usize,
+    game: &othello_core::GameState,
+    cursor_pos: (u8, u8),
+    cpu_thinking: bool,
+    outcome: Option<bool>,
+    message: Option<&str>,
+    daily: bool,
+) {
+    let s = app.text();
+    let puzzle = &puzzles[index];
+    let (black, white) = game.counts();
+    let title = if daily { String::from(s.title_daily) } else { fill(s.title_puzzle, &[&(index + 1), &puzzles.len()]) };
+    draw_header(app, gam, &title, black, white);
+    draw_footer(app, gam);
+
+    let gid = app.gid;
+    use core::fmt::Write;
 
+    // Objective above the board
+    let mut tv = TextView::new(
+        gid,
+        TextBounds::GrowableFromTl(Point::new(16, HEADER_HEIGHT + 8), text_width(app, 16, 20)),
+    );
+    tv.style = GlyphStyle::Bold;
+    let objective = match puzzle.objective {
+        Objective::FindMove(_) => String::from(s.objective_find),
+        Objective::Win => String::from(s.objective_win),
+        Objective::WinBy(margin) => fill(s.objective_win_by, &[&margin]),
+    };
+    write!(tv.text, "{}: {}", s.player(puzzle.to_move), objective).ok();
+    gam.post_textview(&mut tv).ok();
+
+    let finished = outcome.is_some();
+    draw_board(
+        app,
+        gam,
+        game.board(),
+        if finished { None } else { Some(cursor_pos) },
+        if !finished && app.settings.show_valid_moves { game.legal_moves_bitboard() } else { 0 },
+        game.last_move().map(|m| m.pos),
+        0,
+    );
+
+    let status_y = app.screensize.y - FOOTER_HEIGHT - 40;
+    let mut tv = TextView::new(
+        gid,
+        TextBounds::GrowableFromTl(Point::new(16, status_y), text_width(app, 16, 0)),
+    );
+    tv.style = GlyphStyle::Small;
+    if cpu_thinking {
+        write!(tv.text, "{}", s.cpu_defending).ok();
+    } else if let Some(message) = message {
+        write!(tv.text, "{}", message).ok();
+    }
+    gam.post_textview(&mut tv).ok();
+
+    if finished {
         let mut tv = TextView::new(
             gid,
-            TextBounds::GrowableFromTl(Point::new(20, start_y + line * 22), 300),
+            TextBounds::GrowableFromTl(Point::new(16, status_y + 18), text_width(app, 16, 0)),
         );
-        tv.style = GlyphStyle::Monospace;
-        write!(tv.text, "{:2}.  {}       {}", move_num, black_move, white_move).ok();
+        tv.style = GlyphStyle::Small;
+        if daily {
+            write!(tv.text, "{}", s.puzzle_daily_keys).ok();
+        } else {
+            write!(tv.text, "{}", s.puzzle_keys).ok();
+        }
+        gam.post_textview(&mut tv).ok();
+    }
+}
+
+/// Draw move history
+fn draw_history(
+    app: &OthelloApp,
+    gam: &Gam,
+    game: &othello_core::GameState,
+    scroll_offset: usize,
+) {
+    let s = app.text();
+    let (black, white) = game.counts();
+    draw_header(app, gam, s.title_history, black, white);
+    draw_footer(app, gam);
+
+    let gid = app.gid;
+    let start_y = HEADER_HEIGHT + 30;
+    let history = game.history();
+    let style = body_style(app);
+    let row = row_height(style);
+    let total_y = app.screensize.y - FOOTER_HEIGHT - 30;
+    // A reviewed game gives a line to the legend
+    let review = app.shown_review();
+    let legend_y = total_y - if review.is_some() { row } else { 0 };
+    let rows = (legend_y - start_y) / row;
+
+    // Column headers
+    let mut tv = TextView::new(
+        gid,
+        TextBounds::GrowableFromTl(Point::new(20, start_y), text_width(app, 20, 16)),
+    );
+    tv.style = match app.settings.text_size {
+        TextSize::Regular => GlyphStyle::Bold,
+        _ => style,
+    };
+    use core::fmt::Write;
+    write!(tv.text, " #  \u{25CF}        \u{25CB}        +/-").ok();
+    gam.post_textview(&mut tv).ok();
+
+    // Each move with the discs it flipped and any annotation, e.g. "D3(2)?!"
+    let cell = |index: usize| match history.get(index) {
+        Some(entry) if entry.is_pass() => s.pass.to_string(),
+        Some(entry) => {
+            let annotation = review.and_then(|r| r.annotation(index)).map_or("", Annotation::symbol);
+            format!("{}({}){}", square(entry.pos), entry.flipped.count_ones(), annotation)
+        }
+        None => String::new(),
+    };
+
+    // Black's disc lead, carried over the moves scrolled out of view
+    let start = game.start_board();
+    let mut lead = start.count(Player::Black) as i32 - start.count(Player::White) as i32;
+    let mut swing = |index: usize| {
+        if let Some(entry) = history.get(index).filter(|e| !e.is_pass()) {
+            let discs = 2 * entry.flipped.count_ones() as i32 + 1;
+            lead += if entry.player == Player::Black { discs } else { -discs };
+        }
+        lead
+    };
+    for index in 0..scroll_offset * 2 {
+        swing(index);
+    }
+
+    // Move pairs
+    let mut line = 1;
+    let mut move_num = 1 + scroll_offset;
+    let mut i = scroll_offset * 2;
+
+    while i < history.len() && line < rows {
+        swing(i);
+        let lead = swing(i + 1);
+
+        let mut tv = TextView::new(
+            gid,
+            TextBounds::GrowableFromTl(Point::new(20, start_y + line * row), text_width(app, 20, 16)),
+        );
+        // Regular text keeps the columns lined up in the monospace face
+        tv.style = match app.settings.text_size {
+            TextSize::Regular => GlyphStyle::Monospace,
+            _ => style,
+        };
+        write!(tv.text, "{:2}. {:<9}{:<9}{:+}", move_num, cell(i), cell(i + 1), lead).ok();
         gam.post_textview(&mut tv).ok();
 
         move_num += 1;
@@ -765,73 +2557,217 @@ fn draw_history(
         line += 1;
     }
 
+    if review.is_some() {
+        let mut tv = TextView::new(
+            gid,
+            TextBounds::GrowableFromTl(Point::new(20, legend_y), text_width(app, 20, 16)),
+        );
+        tv.style = detail_style(app);
+        write!(tv.text, "{}", s.history_legend).ok();
+        gam.post_textview(&mut tv).ok();
+    }
+
     // Total
     let mut tv = TextView::new(
         gid,
-        TextBounds::GrowableFromTl(Point::new(20, app.screensize.y - FOOTER_HEIGHT - 30), 300),
+        TextBounds::GrowableFromTl(Point::new(20, total_y), text_width(app, 20, 16)),
+    );
+    tv.style = detail_style(app);
+    write!(tv.text, "{}", fill(s.history_total, &[&history.len()])).ok();
+    gam.post_textview(&mut tv).ok();
+}
+
+/// Draw the position in words from a line on, a blank row between lines
+fn draw_position_text(app: &OthelloApp, gam: &Gam, lines: &[String], scroll_offset: usize) {
+    let s = app.text();
+    draw_header(app, gam, s.title_position_text, 0, 0);
+    draw_footer(app, gam);
+
+    let gid = app.gid;
+    let start_y = HEADER_HEIGHT + 16;
+    let keys_y = app.screensize.y - FOOTER_HEIGHT - 30;
+    let text = lines.get(scroll_offset..).unwrap_or_default().join("\n\n");
+    let mut tv = TextView::new(
+        gid,
+        TextBounds::BoundingBox(Rectangle::new_coords(16, start_y, app.screensize.x - 16, keys_y - 4)),
+    );
+    tv.style = body_style(app);
+    use core::fmt::Write;
+    write!(tv.text, "{}", text).ok();
+    gam.post_textview(&mut tv).ok();
+
+    let mut tv = TextView::new(
+        gid,
+        TextBounds::GrowableFromTl(Point::new(16, keys_y), text_width(app, 16, 0)),
     );
     tv.style = GlyphStyle::Small;
-    write!(tv.text, "Total: {} moves", history.len()).ok();
+    write!(tv.text, "{}", s.position_keys).ok();
     gam.post_textview(&mut tv).ok();
 }
 
-/// Draw menu overlay
-pub fn draw_menu(app: &OthelloApp, gam: &Gam) {
+/// Draw a notice in a small box under the header
+pub fn draw_toast(app: &OthelloApp, gam: &Gam, text: &str) {
+    let center_x = app.screensize.x / 2;
+    let half_width = (app.screensize.x / 2 - 8).min(140);
+    let top = HEADER_HEIGHT + 12;
+    let mut tv = TextView::new(
+        app.gid,
+        TextBounds::BoundingBox(Rectangle::new_coords(center_x - half_width, top, center_x + half_width, top + 32)),
+    );
+    tv.style = GlyphStyle::Bold;
+    tv.draw_border = true;
+    tv.border_width = 2;
+    tv.rounded_border = Some(6);
+    tv.margin = Point::new(10, 6);
+    use core::fmt::Write;
+    write!(tv.text, "{}", text).ok();
+    gam.post_textview(&mut tv).ok();
+}
+
+/// Draw the bar that says keys are going to the input line
+pub fn draw_command_line(app: &OthelloApp, gam: &Gam) {
+    let bottom = app.screensize.y - FOOTER_HEIGHT - 4;
+    let mut tv = TextView::new(
+        app.gid,
+        TextBounds::BoundingBox(Rectangle::new_coords(4, bottom - 28, app.screensize.x - 4, bottom)),
+    );
+    tv.style = GlyphStyle::Small;
+    tv.draw_border = true;
+    tv.border_width = 2;
+    tv.margin = Point::new(8, 6);
+    use core::fmt::Write;
+    write!(tv.text, "{}", app.text().command_hint).ok();
+    gam.post_textview(&mut tv).ok();
+}
+
+/// Size of a dialog's box
+fn dialog_size(app: &OthelloApp, dialog: &DialogKind) -> (isize, isize) {
+    match dialog {
+        DialogKind::Confirm { .. } => (240, 96),
+        DialogKind::Choice { options, .. } => (240, 60 + options.len() as isize * row_height(body_style(app))),
+        DialogKind::Prompt { .. } => (280, 96),
+    }
+}
+
+/// Draw a dialog overlay
+pub fn draw_dialog(app: &OthelloApp, gam: &Gam, dialog: &DialogKind) {
+    let s = app.text();
     let gid = app.gid;
-    let menu = &app.menu;
 
-    let menu_width = 200isize;
-    let item_height = 24isize;
-    let menu_height = (menu.items.len() as isize + 1) * item_height + 10;
-    let x = (app.screensize.x - menu_width) / 2;
-    let y = (app.screensize.y - menu_height) / 2;
+    let (box_width, box_height) = dialog_size(app, dialog);
+    let x = (app.screensize.x - box_width) / 2;
+    let y = (app.screensize.y - box_height) / 2;
 
     // Background
     gam.draw_rectangle(
         gid,
         Rectangle::new_with_style(
             Point::new(x, y),
-            Point::new(x + menu_width, y + menu_height),
-            DrawStyle::new(PixelColor::Dark, PixelColor::Light, 2),
+            Point::new(x + box_width, y + box_height),
+            DrawStyle::new(PixelColor::Light, PixelColor::Dark, 2),
         ),
     )
     .ok();
 
-    // Menu items
-    for (i, item) in menu.items.iter().enumerate() {
-        let item_y = y + 8 + i as isize * item_height;
-        let is_selected = i == menu.selected;
+    // Question or title
+    let title = match dialog {
+        DialogKind::Confirm { action, .. } => action.prompt(s),
+        DialogKind::Choice { action, .. } => String::from(action.title(s)),
+        DialogKind::Prompt { action, .. } => String::from(action.title(s)),
+    };
+    let title_bottom = match dialog {
+        DialogKind::Confirm { .. } => y + 40,
+        DialogKind::Choice { .. } | DialogKind::Prompt { .. } => y + 30,
+    };
+    let mut tv = TextView::new(
+        gid,
+        TextBounds::BoundingBox(Rectangle::new_coords(x + 8, y + 10, x + box_width - 8, title_bottom)),
+    );
+    tv.style = GlyphStyle::Bold;
+    use core::fmt::Write;
+    write!(tv.text, "{}", title).ok();
+    gam.post_textview(&mut tv).ok();
 
-        if is_selected {
-            gam.draw_rectangle(
+    let keys = match dialog {
+        DialogKind::Confirm { yes_selected, .. } => {
+            // Yes / No buttons
+            let button_width = 80isize;
+            let button_y = y + 48;
+            for (i, (label, selected)) in [(s.no, !*yes_selected), (s.yes, *yes_selected)].iter().enumerate() {
+                let bx = x + 30 + i as isize * (button_width + 20);
+
+                if *selected {
+                    gam.draw_rectangle(
+                        gid,
+                        Rectangle::new_with_style(
+                            Point::new(bx, button_y),
+                            Point::new(bx + button_width, button_y + 24),
+                            DrawStyle::new(PixelColor::Dark, PixelColor::Dark, 1),
+                        ),
+                    )
+                    .ok();
+                }
+
+                let mut tv = TextView::new(
+                    gid,
+                    TextBounds::BoundingBox(Rectangle::new_coords(bx, button_y + 2, bx + button_width, button_y + 22)),
+                );
+                tv.style = if *selected { GlyphStyle::Bold } else { GlyphStyle::Regular };
+                tv.invert = *selected;
+                write!(tv.text, "{}", label).ok();
+                gam.post_textview(&mut tv).ok();
+            }
+            s.confirm_keys
+        }
+        DialogKind::Choice { options, selected, .. } => {
+            let style = body_style(app);
+            let item_height = row_height(style);
+            for (i, option) in options.iter().enumerate() {
+                let item_y = title_bottom + 4 + i as isize * item_height;
+                let is_selected = i == *selected;
+                if is_selected {
+                    gam.draw_rectangle(
+                        gid,
+                        Rectangle::new_with_style(
+                            Point::new(x + 4, item_y),
+                            Point::new(x + box_width - 4, item_y + item_height - 2),
+                            DrawStyle::new(PixelColor::Dark, PixelColor::Dark, 1),
+                        ),
+                    )
+                    .ok();
+                }
+                let mut tv = TextView::new(
+                    gid,
+                    TextBounds::GrowableFromTl(Point::new(x + 12, item_y + 2), (box_width - 24) as u16),
+                );
+                tv.style = style;
+                tv.invert = is_selected;
+                write!(tv.text, "{}. {}", i + 1, option).ok();
+                gam.post_textview(&mut tv).ok();
+            }
+            s.choice_keys
+        }
+        DialogKind::Prompt { text, .. } => {
+            let mut tv = TextView::new(
                 gid,
-                Rectangle::new_with_style(
-                    Point::new(x + 4, item_y),
-                    Point::new(x + menu_width - 4, item_y + item_height - 2),
-                    DrawStyle::new(PixelColor::Dark, PixelColor::Dark, 1),
-                ),
-            )
-            .ok();
+                TextBounds::BoundingBox(Rectangle::new_coords(x + 8, title_bottom + 6, x + box_width - 8, title_bottom + 42)),
+            );
+            tv.style = GlyphStyle::Regular;
+            tv.draw_border = true;
+            tv.rounded_border = Some(2);
+            tv.margin = Point::new(6, 4);
+            write!(tv.text, "{}_", text).ok();
+            gam.post_textview(&mut tv).ok();
+            s.prompt_keys
         }
-
-        let mut tv = TextView::new(
-            gid,
-            TextBounds::GrowableFromTl(Point::new(x + 12, item_y + 4), (menu_width - 24) as u16),
-        );
-        tv.style = if is_selected { GlyphStyle::Bold } else { GlyphStyle::Regular };
-        tv.invert = is_selected;
-        use core::fmt::Write;
-        write!(tv.text, "{}", item.label()).ok();
-        gam.post_textview(&mut tv).ok();
-    }
+    };
 
     // Footer hint
     let mut tv = TextView::new(
         gid,
-        TextBounds::BoundingBox(Rectangle::new_coords(x, y + menu_height - item_height, x + menu_width, y + menu_height)),
+        TextBounds::BoundingBox(Rectangle::new_coords(x, y + box_height - 20, x + box_width, y + box_height)),
     );
     tv.style = GlyphStyle::Small;
-    use core::fmt::Write;
-    write!(tv.text, "F4 to close").ok();
+    write!(tv.text, "{}", keys).ok();
     gam.post_textview(&mut tv).ok();
 }